@@ -37,7 +37,10 @@ use fixnum::ops::{CheckedAdd, CheckedSub, RoundingDiv, RoundingMul, RoundingSqrt
 use fixnum::ArithmeticError;
 use static_assertions::_core::cmp::Ordering;
 
-use crate::{fixed, pow, Balance, Fixed, FixedInner, FIXED_PRECISION};
+use crate::{fixed, fixed_const, fixed_wrapper, pow, Balance, Fixed, FixedInner, FIXED_PRECISION};
+
+/// `ln(2)` rounded down to `FIXED_PRECISION` digits.
+const LN_2: Fixed = fixed_const!(0.693147180559945309);
 
 /// A convenient wrapper around `Fixed` type for safe math.
 ///
@@ -90,6 +93,80 @@ impl FixedWrapper {
         }
     }
 
+    /// Calculates `e` raised to the power of underlying Fixed number.
+    ///
+    /// The argument is reduced to `x = k * ln(2) + r`, where `0 <= r < ln(2)`,
+    /// so that `e^x = 2^k * e^r` and `e^r` is evaluated with Taylor series.
+    pub fn exp(self) -> Self {
+        let x = match self.inner {
+            Ok(x) => x.into_bits(),
+            Err(e) => return Err(e).into(),
+        };
+        if x < 0 {
+            return fixed_wrapper!(1) / Self::from(-x).exp();
+        }
+        let one = pow(10, FIXED_PRECISION);
+        let k = x / LN_2.into_bits();
+        let r = Self::from(x - k * LN_2.into_bits());
+        let mut term = fixed_wrapper!(1);
+        let mut sum = fixed_wrapper!(1);
+        for n in 1..FixedInner::from(FIXED_PRECISION * 2) {
+            term = term * r.clone() / Self::from(n * one);
+            if term == fixed_wrapper!(0) {
+                break;
+            }
+            sum = sum + term.clone();
+        }
+        // `2^k` can't be represented as Fixed for larger values anyway.
+        let power = if k < 127 {
+            (2 as FixedInner).pow(k as u32).checked_mul(one)
+        } else {
+            None
+        };
+        match power {
+            Some(power) => sum * Self::from(power),
+            None => Err(ArithmeticError::Overflow).into(),
+        }
+    }
+
+    /// Calculates natural logarithm of underlying Fixed number.
+    ///
+    /// The argument is reduced to `x = 2^k * y`, where `1 <= y < 2`, so that
+    /// `ln(x) = k * ln(2) + ln(y)` and `ln(y)` is evaluated with the series
+    /// `ln(y) = 2 * (z + z^3 / 3 + z^5 / 5 + ...)`, where `z = (y - 1) / (y + 1)`.
+    pub fn ln(self) -> Self {
+        let mut y = match self.inner {
+            Ok(x) => x.into_bits(),
+            Err(e) => return Err(e).into(),
+        };
+        if y <= 0 {
+            return Err(ArithmeticError::DomainViolation).into();
+        }
+        let one = pow(10, FIXED_PRECISION);
+        let mut k: FixedInner = 0;
+        while y >= 2 * one {
+            y /= 2;
+            k += 1;
+        }
+        while y < one {
+            y *= 2;
+            k -= 1;
+        }
+        let y = Self::from(y);
+        let z = (y.clone() - fixed_wrapper!(1)) / (y + fixed_wrapper!(1));
+        let z_squared = z.clone() * z.clone();
+        let mut term = z.clone();
+        let mut sum = z;
+        for n in (3..FixedInner::from(FIXED_PRECISION * 8)).step_by(2) {
+            term = term * z_squared.clone();
+            if term == fixed_wrapper!(0) {
+                break;
+            }
+            sum = sum + term.clone() / Self::from(n * one);
+        }
+        fixed_wrapper!(2) * sum + Self::from(k * LN_2.into_bits())
+    }
+
     pub fn to_fraction(&self) -> Result<f64, ArithmeticError> {
         self.inner.clone().map(Fixed::to_f64)
     }
@@ -280,6 +357,7 @@ impl_fixed_wrapper_for_type!(u128);
 
 #[cfg(test)]
 mod tests {
+    use crate::prelude::FixedWrapper;
     use crate::*;
     #[test]
     fn fixed_wrapper_sqrt_small_sanity_check() {
@@ -309,4 +387,47 @@ mod tests {
             fixed_wrapper!(1934799.981764110013554299)
         )
     }
+
+    #[test]
+    fn fixed_wrapper_exp_and_ln_sanity_check() {
+        let epsilon = fixed_wrapper!(0.000000000001);
+        let near = |lhs: FixedWrapper, rhs: FixedWrapper| {
+            let diff = lhs.clone() - rhs.clone();
+            assert!(
+                diff.clone() < epsilon.clone() && -diff < epsilon.clone(),
+                "{:?} != {:?}",
+                lhs,
+                rhs
+            );
+        };
+        // basic
+        assert_eq!(fixed_wrapper!(0).exp(), fixed_wrapper!(1));
+        assert_eq!(fixed_wrapper!(1).ln(), fixed_wrapper!(0));
+        near(
+            fixed_wrapper!(1).exp(),
+            fixed_wrapper!(2.718281828459045235),
+        );
+        near(fixed_wrapper!(2).ln(), fixed_wrapper!(0.693147180559945309));
+        near(
+            fixed_wrapper!(10).ln(),
+            fixed_wrapper!(2.302585092994045684),
+        );
+        near(
+            fixed_wrapper!(0.5).ln(),
+            -fixed_wrapper!(0.693147180559945309),
+        );
+        // negative argument
+        near(
+            (-fixed_wrapper!(1)).exp(),
+            fixed_wrapper!(0.367879441171442321),
+        );
+        // inverse of each other
+        near(fixed_wrapper!(12.345).exp().ln(), fixed_wrapper!(12.345));
+        near(fixed_wrapper!(12.345).ln().exp(), fixed_wrapper!(12.345));
+        // out of domain
+        assert!(fixed_wrapper!(0).ln().get().is_err());
+        assert!((-fixed_wrapper!(1)).ln().get().is_err());
+        // overflow
+        assert!(fixed_wrapper!(100).exp().get().is_err());
+    }
 }
@@ -29,6 +29,7 @@
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 use crate::traits::{IsRepresentation, PureOrWrapped};
+use crate::BasisPoints;
 use codec::{Decode, Encode};
 use core::fmt::Debug;
 use frame_support::dispatch::DispatchError;
@@ -96,6 +97,8 @@ pub struct DEXInfo<AssetId> {
     pub base_asset_id: AssetId,
     /// Determines if DEX can be managed by regular users.
     pub is_public: bool,
+    /// Default fee in basis points applied to the exchanges in DEX.
+    pub default_fee: BasisPoints,
 }
 
 //TODO: consider replacing base_asset_id with dex_id, and getting base asset from dex
@@ -420,6 +423,31 @@ impl Default for AssetName {
     }
 }
 
+/// Optional asset metadata which can be updated by the asset owner, unlike the precision.
+/// All the fields are UTF-8 encoded text, empty if not set.
+#[derive(Encode, Decode, Eq, PartialEq, Clone, Default, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct AssetExtendedInfo {
+    /// Description of the asset
+    #[cfg_attr(
+        feature = "std",
+        serde(with = "crate::utils::bytes_string_serialization")
+    )]
+    pub description: Vec<u8>,
+    /// URI of the asset icon or other content
+    #[cfg_attr(
+        feature = "std",
+        serde(with = "crate::utils::bytes_string_serialization")
+    )]
+    pub content_uri: Vec<u8>,
+    /// Website of the asset issuer
+    #[cfg_attr(
+        feature = "std",
+        serde(with = "crate::utils::bytes_string_serialization")
+    )]
+    pub website: Vec<u8>,
+}
+
 #[derive(Encode, Decode, Eq, PartialEq, PartialOrd, Ord, Debug, Copy, Clone, Hash)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum TechAssetId<AssetId> {
@@ -459,6 +487,7 @@ pub enum LiquiditySourceType {
     MockPool2,
     MockPool3,
     MockPool4,
+    OrderBook,
 }
 
 #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
@@ -28,19 +28,22 @@
 // STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use crate::prelude::{ManagementMode, SwapAmount, SwapOutcome};
-use crate::{Fixed, LiquiditySourceFilter, LiquiditySourceId};
+use crate::prelude::{FixedWrapper, ManagementMode, SwapAmount, SwapOutcome};
+use crate::{
+    fixed, Fixed, FixedInner, LiquiditySourceFilter, LiquiditySourceId, LiquiditySourceType,
+};
 use frame_support::dispatch::DispatchResult;
 use frame_support::pallet_prelude::MaybeSerializeDeserialize;
 use frame_support::sp_runtime::traits::BadOrigin;
 use frame_support::sp_runtime::DispatchError;
 use frame_support::weights::Weight;
-use frame_support::Parameter;
+use frame_support::{Parameter, RuntimeDebug};
 use frame_system::RawOrigin;
 //FIXME maybe try info or try from is better than From and Option.
 //use sp_std::convert::TryInto;
 use crate::primitives::Balance;
 use codec::{Decode, Encode};
+use fixnum::ArithmeticError;
 use sp_std::vec::Vec;
 
 /// Check on origin that it is a DEX owner.
@@ -89,6 +92,70 @@ impl<DEXId, AssetId> EnsureTradingPairExists<DEXId, AssetId, DispatchError> for
     }
 }
 
+/// Enable the liquidity source for the trading pair it provides liquidity for.
+pub trait EnableSourceForTradingPair<DEXId, AssetId, Error> {
+    fn enable_source_for_trading_pair(
+        dex_id: &DEXId,
+        base_asset_id: &AssetId,
+        target_asset_id: &AssetId,
+        source_type: LiquiditySourceType,
+    ) -> Result<(), Error>;
+}
+
+impl<DEXId, AssetId> EnableSourceForTradingPair<DEXId, AssetId, DispatchError> for () {
+    fn enable_source_for_trading_pair(
+        _dex_id: &DEXId,
+        _base_asset_id: &AssetId,
+        _target_asset_id: &AssetId,
+        _source_type: LiquiditySourceType,
+    ) -> Result<(), DispatchError> {
+        Ok(())
+    }
+}
+
+/// Check whether the asset is referenced by other pallets, e.g. traded in trading pairs or pools,
+/// the asset can't be destroyed while it's in use.
+pub trait IsAssetInUse<AssetId> {
+    fn is_asset_in_use(asset_id: &AssetId) -> bool;
+}
+
+impl<AssetId> IsAssetInUse<AssetId> for () {
+    fn is_asset_in_use(_asset_id: &AssetId) -> bool {
+        false
+    }
+}
+
+impl<AssetId, A, B> IsAssetInUse<AssetId> for (A, B)
+where
+    A: IsAssetInUse<AssetId>,
+    B: IsAssetInUse<AssetId>,
+{
+    fn is_asset_in_use(asset_id: &AssetId) -> bool {
+        A::is_asset_in_use(asset_id) || B::is_asset_in_use(asset_id)
+    }
+}
+
+/// Check that trading is not paused by the emergency pause.
+pub trait EnsureTradingNotPaused<DEXId, AssetId, Error> {
+    fn ensure_trading_not_paused(
+        dex_id: &DEXId,
+        source_type: LiquiditySourceType,
+        input_asset_id: &AssetId,
+        output_asset_id: &AssetId,
+    ) -> Result<(), Error>;
+}
+
+impl<DEXId, AssetId> EnsureTradingNotPaused<DEXId, AssetId, DispatchError> for () {
+    fn ensure_trading_not_paused(
+        _dex_id: &DEXId,
+        _source_type: LiquiditySourceType,
+        _input_asset_id: &AssetId,
+        _output_asset_id: &AssetId,
+    ) -> Result<(), DispatchError> {
+        Ok(())
+    }
+}
+
 /// Indicates that particular object can be used to perform exchanges.
 pub trait LiquiditySource<TargetId, AccountId, AssetId, Amount, Error> {
     /// Check if liquidity source provides an exchange from given input asset to output asset.
@@ -116,6 +183,22 @@ pub trait LiquiditySource<TargetId, AccountId, AssetId, Amount, Error> {
         output_asset_id: &AssetId,
         swap_amount: SwapAmount<Amount>,
     ) -> Result<SwapOutcome<Amount>, DispatchError>;
+
+    /// Account the fee of the exchanges between given assets is collected on, the fee is nominated
+    /// in the base asset. None returned if liquidity source does not keep the fee on a separate account.
+    fn fee_account(
+        _target_id: &TargetId,
+        _input_asset_id: &AssetId,
+        _output_asset_id: &AssetId,
+    ) -> Option<AccountId> {
+        None
+    }
+
+    /// Weight of `exchange` depending on the state of the source, e.g. the number of parts
+    /// a trade is split into, charged on top of the weight of a swap.
+    fn exchange_weight() -> Weight {
+        0
+    }
 }
 
 impl<DEXId, AccountId, AssetId> LiquiditySource<DEXId, AccountId, AssetId, Fixed, DispatchError>
@@ -182,6 +265,267 @@ impl<DEXId, AccountId, AssetId> LiquiditySource<DEXId, AccountId, AssetId, Balan
     }
 }
 
+/// Liquidity source identified by its `LiquiditySourceType` in a `LiquiditySourceRegistry`.
+pub trait TaggedLiquiditySource<DEXId, AccountId, AssetId>:
+    LiquiditySource<DEXId, AccountId, AssetId, Balance, DispatchError> + GetSwapCurve<DEXId, AssetId>
+{
+    const SOURCE_TYPE: LiquiditySourceType;
+}
+
+/// Set of liquidity sources dispatched by their `LiquiditySourceType`.
+///
+/// Implemented for tuples of `TaggedLiquiditySource`s, so a runtime can compose any number of
+/// sources. If several sources share a type, the first one of them is used.
+pub trait LiquiditySourceRegistry<DEXId, AccountId, AssetId> {
+    /// Types of the liquidity sources in the registry.
+    fn source_types() -> Vec<LiquiditySourceType>;
+
+    /// Same as `LiquiditySource::can_exchange`, `false` if there is no source of `source_type`.
+    fn can_exchange(
+        source_type: LiquiditySourceType,
+        dex_id: &DEXId,
+        input_asset_id: &AssetId,
+        output_asset_id: &AssetId,
+    ) -> bool;
+
+    /// Same as `LiquiditySource::quote`, `None` if there is no source of `source_type`.
+    fn quote(
+        source_type: LiquiditySourceType,
+        dex_id: &DEXId,
+        input_asset_id: &AssetId,
+        output_asset_id: &AssetId,
+        swap_amount: SwapAmount<Balance>,
+    ) -> Option<Result<SwapOutcome<Balance>, DispatchError>>;
+
+    /// Same as `LiquiditySource::exchange`, `None` if there is no source of `source_type`.
+    fn exchange(
+        source_type: LiquiditySourceType,
+        sender: &AccountId,
+        receiver: &AccountId,
+        dex_id: &DEXId,
+        input_asset_id: &AssetId,
+        output_asset_id: &AssetId,
+        swap_amount: SwapAmount<Balance>,
+    ) -> Option<Result<SwapOutcome<Balance>, DispatchError>>;
+
+    /// Same as `GetSwapCurve::swap_curve`, `None` if there is no source of `source_type`.
+    fn swap_curve(
+        source_type: LiquiditySourceType,
+        dex_id: &DEXId,
+        input_asset_id: &AssetId,
+        output_asset_id: &AssetId,
+    ) -> Option<SwapCurve>;
+
+    /// Same as `LiquiditySource::fee_account`, `None` if there is no source of `source_type`.
+    fn fee_account(
+        source_type: LiquiditySourceType,
+        dex_id: &DEXId,
+        input_asset_id: &AssetId,
+        output_asset_id: &AssetId,
+    ) -> Option<AccountId>;
+
+    /// Total of `LiquiditySource::exchange_weight` of the sources, i.e. the weight of exchanging
+    /// with each of them once.
+    fn exchange_weight() -> Weight;
+}
+
+impl<DEXId, AccountId, AssetId> LiquiditySourceRegistry<DEXId, AccountId, AssetId> for () {
+    fn source_types() -> Vec<LiquiditySourceType> {
+        Vec::new()
+    }
+
+    fn can_exchange(
+        _source_type: LiquiditySourceType,
+        _dex_id: &DEXId,
+        _input_asset_id: &AssetId,
+        _output_asset_id: &AssetId,
+    ) -> bool {
+        false
+    }
+
+    fn quote(
+        _source_type: LiquiditySourceType,
+        _dex_id: &DEXId,
+        _input_asset_id: &AssetId,
+        _output_asset_id: &AssetId,
+        _swap_amount: SwapAmount<Balance>,
+    ) -> Option<Result<SwapOutcome<Balance>, DispatchError>> {
+        None
+    }
+
+    fn exchange(
+        _source_type: LiquiditySourceType,
+        _sender: &AccountId,
+        _receiver: &AccountId,
+        _dex_id: &DEXId,
+        _input_asset_id: &AssetId,
+        _output_asset_id: &AssetId,
+        _swap_amount: SwapAmount<Balance>,
+    ) -> Option<Result<SwapOutcome<Balance>, DispatchError>> {
+        None
+    }
+
+    fn swap_curve(
+        _source_type: LiquiditySourceType,
+        _dex_id: &DEXId,
+        _input_asset_id: &AssetId,
+        _output_asset_id: &AssetId,
+    ) -> Option<SwapCurve> {
+        None
+    }
+
+    fn fee_account(
+        _source_type: LiquiditySourceType,
+        _dex_id: &DEXId,
+        _input_asset_id: &AssetId,
+        _output_asset_id: &AssetId,
+    ) -> Option<AccountId> {
+        None
+    }
+
+    fn exchange_weight() -> Weight {
+        0
+    }
+}
+
+macro_rules! impl_liquidity_source_registry {
+    ($($source:ident),+) => {
+        impl<DEXId, AccountId, AssetId, $($source),+> LiquiditySourceRegistry<DEXId, AccountId, AssetId>
+            for ($($source,)+)
+        where
+            $($source: TaggedLiquiditySource<DEXId, AccountId, AssetId>),+
+        {
+            fn source_types() -> Vec<LiquiditySourceType> {
+                let mut source_types = Vec::new();
+                $(
+                    if !source_types.contains(&$source::SOURCE_TYPE) {
+                        source_types.push($source::SOURCE_TYPE);
+                    }
+                )+
+                source_types
+            }
+
+            fn can_exchange(
+                source_type: LiquiditySourceType,
+                dex_id: &DEXId,
+                input_asset_id: &AssetId,
+                output_asset_id: &AssetId,
+            ) -> bool {
+                $(
+                    if source_type == $source::SOURCE_TYPE {
+                        return $source::can_exchange(
+                            dex_id,
+                            input_asset_id,
+                            output_asset_id,
+                        );
+                    }
+                )+
+                false
+            }
+
+            fn quote(
+                source_type: LiquiditySourceType,
+                dex_id: &DEXId,
+                input_asset_id: &AssetId,
+                output_asset_id: &AssetId,
+                swap_amount: SwapAmount<Balance>,
+            ) -> Option<Result<SwapOutcome<Balance>, DispatchError>> {
+                $(
+                    if source_type == $source::SOURCE_TYPE {
+                        return Some($source::quote(
+                            dex_id,
+                            input_asset_id,
+                            output_asset_id,
+                            swap_amount,
+                        ));
+                    }
+                )+
+                None
+            }
+
+            fn exchange(
+                source_type: LiquiditySourceType,
+                sender: &AccountId,
+                receiver: &AccountId,
+                dex_id: &DEXId,
+                input_asset_id: &AssetId,
+                output_asset_id: &AssetId,
+                swap_amount: SwapAmount<Balance>,
+            ) -> Option<Result<SwapOutcome<Balance>, DispatchError>> {
+                $(
+                    if source_type == $source::SOURCE_TYPE {
+                        return Some($source::exchange(
+                            sender,
+                            receiver,
+                            dex_id,
+                            input_asset_id,
+                            output_asset_id,
+                            swap_amount,
+                        ));
+                    }
+                )+
+                None
+            }
+
+            fn swap_curve(
+                source_type: LiquiditySourceType,
+                dex_id: &DEXId,
+                input_asset_id: &AssetId,
+                output_asset_id: &AssetId,
+            ) -> Option<SwapCurve> {
+                $(
+                    if source_type == $source::SOURCE_TYPE {
+                        return $source::swap_curve(
+                            dex_id,
+                            input_asset_id,
+                            output_asset_id,
+                        );
+                    }
+                )+
+                None
+            }
+
+            fn fee_account(
+                source_type: LiquiditySourceType,
+                dex_id: &DEXId,
+                input_asset_id: &AssetId,
+                output_asset_id: &AssetId,
+            ) -> Option<AccountId> {
+                $(
+                    if source_type == $source::SOURCE_TYPE {
+                        return $source::fee_account(
+                            dex_id,
+                            input_asset_id,
+                            output_asset_id,
+                        );
+                    }
+                )+
+                None
+            }
+
+            fn exchange_weight() -> Weight {
+                let mut weight: Weight = 0;
+                $(
+                    weight = weight.saturating_add($source::exchange_weight());
+                )+
+                weight
+            }
+        }
+    };
+}
+
+macro_rules! impl_liquidity_source_registry_for_tuples {
+    ($source:ident) => {
+        impl_liquidity_source_registry!($source);
+    };
+    ($source:ident, $($rest:ident),+) => {
+        impl_liquidity_source_registry!($source, $($rest),+);
+        impl_liquidity_source_registry_for_tuples!($($rest),+);
+    };
+}
+
+impl_liquidity_source_registry_for_tuples!(S1, S2, S3, S4, S5, S6, S7, S8, S9, S10, S11, S12);
+
 pub trait LiquidityRegistry<DEXId, AccountId, AssetId, LiquiditySourceIndex, Amount, Error>:
     LiquiditySource<LiquiditySourceId<DEXId, LiquiditySourceIndex>, AccountId, AssetId, Amount, Error>
 where
@@ -412,14 +756,162 @@ impl<AssetId> GetMarketInfo<AssetId> for () {
 }
 
 /// Trait for bounding liquidity proxy associated type representing secondary market.
-pub trait GetPoolReserves<AssetId> {
+pub trait GetPoolReserves<DEXId, AssetId> {
     /// Returns the amount of the `(base_asset, other_asset)` pair reserves in a liquidity pool
-    /// or the default value if such pair doesn't exist.
-    fn reserves(base_asset: &AssetId, other_asset: &AssetId) -> (Balance, Balance);
+    /// of the DEX or the default value if such pair doesn't exist.
+    fn reserves(dex_id: &DEXId, base_asset: &AssetId, other_asset: &AssetId) -> (Balance, Balance);
 }
 
-impl<AssetId> GetPoolReserves<AssetId> for () {
-    fn reserves(_base_asset: &AssetId, _other_asset: &AssetId) -> (Balance, Balance) {
+impl<DEXId, AssetId> GetPoolReserves<DEXId, AssetId> for () {
+    fn reserves(
+        _dex_id: &DEXId,
+        _base_asset: &AssetId,
+        _other_asset: &AssetId,
+    ) -> (Balance, Balance) {
         Default::default()
     }
 }
+
+/// Closed-form description of the amount of the input asset a liquidity source takes
+/// to provide a given amount of the output asset, fees included.
+#[derive(Clone, Copy, RuntimeDebug, PartialEq, Eq)]
+pub enum SwapCurve {
+    /// Constant product pool `x * y = k` which takes `fee` fraction of the input amount,
+    /// a fee taken from the output amount is expressed by shrinking `output_reserve` by that fraction.
+    ConstantProduct {
+        input_reserve: Balance,
+        output_reserve: Balance,
+        fee: Fixed,
+    },
+    /// Bonding curve with the price of the output asset growing by `slope` per unit bought,
+    /// i.e. buying `y` units costs `price * y + slope * y² / 2`.
+    Linear { price: Fixed, slope: Fixed },
+}
+
+/// Trait for liquidity sources able to describe their prices with a `SwapCurve`,
+/// used by liquidity proxy to split trades without sampling the sources.
+pub trait GetSwapCurve<SourceId, AssetId> {
+    /// Returns the curve of trading `input_asset_id` for `output_asset_id` in the source,
+    /// or `None` if the source can't describe it in a closed form.
+    fn swap_curve(
+        source: &SourceId,
+        input_asset_id: &AssetId,
+        output_asset_id: &AssetId,
+    ) -> Option<SwapCurve>;
+}
+
+impl<SourceId, AssetId> GetSwapCurve<SourceId, AssetId> for () {
+    fn swap_curve(
+        _source: &SourceId,
+        _input_asset_id: &AssetId,
+        _output_asset_id: &AssetId,
+    ) -> Option<SwapCurve> {
+        None
+    }
+}
+
+/// Shape of a bonding curve, i.e. the buy price of one unit of the main asset as a function
+/// of its issuance. The buy price is expected to be positive for any non-negative issuance.
+pub trait BondingCurve {
+    /// The price in terms of the collateral at which one can buy a unit of the main asset
+    /// when `issuance` units are in circulation.
+    fn buy_price(&self, issuance: FixedWrapper) -> FixedWrapper;
+
+    /// The price in terms of the collateral at which one can sell a unit of the main asset
+    /// when `issuance` units are in circulation, which is `sell_price_coefficient` of the buy price.
+    fn sell_price(
+        &self,
+        issuance: FixedWrapper,
+        sell_price_coefficient: FixedWrapper,
+    ) -> FixedWrapper {
+        sell_price_coefficient * self.buy_price(issuance)
+    }
+
+    /// The amount of collateral under the buy price curve: `∫ [buy_price(x) dx, x = from to to]`.
+    fn integral(&self, from: FixedWrapper, to: FixedWrapper) -> FixedWrapper;
+
+    /// The quantity `q` of the main asset matching the collateral `amount` at current `issuance`:
+    ///
+    /// - without `sell_price_coefficient` it is bought for `amount`,
+    ///   i.e. `integral(issuance, issuance + q) = amount`;
+    /// - with `sell_price_coefficient` it is sold for `amount`,
+    ///   i.e. `sell_price_coefficient * integral(issuance - q, issuance) = amount`.
+    ///
+    /// Default implementation bisects over `integral` rounding in favour of the pool,
+    /// curves with a closed-form inverse should override it.
+    fn inverse_integral(
+        &self,
+        issuance: FixedWrapper,
+        amount: FixedWrapper,
+        sell_price_coefficient: Option<FixedWrapper>,
+    ) -> FixedWrapper {
+        let (issuance_value, amount) = match (issuance.clone().get(), amount.get()) {
+            (Ok(issuance), Ok(amount)) => (issuance, amount),
+            (Err(e), _) | (_, Err(e)) => return Err(e).into(),
+        };
+        match sell_price_coefficient {
+            None => bisect_quantity(
+                |q| self.integral(issuance.clone(), issuance.clone() + q).get(),
+                amount,
+                None,
+                false,
+            ),
+            Some(coefficient) => bisect_quantity(
+                |q| {
+                    (coefficient.clone() * self.integral(issuance.clone() - q, issuance.clone()))
+                        .get()
+                },
+                amount,
+                Some(issuance_value),
+                true,
+            ),
+        }
+        .into()
+    }
+}
+
+/// Finds the quantity `q` at which non-decreasing `value(q)` reaches `amount`.
+///
+/// Returns the largest `q` with `value(q) <= amount` or, if `round_up` is set, the smallest `q`
+/// with `value(q) >= amount`. The search is bounded by `upper` if it's given.
+fn bisect_quantity<F>(
+    value: F,
+    amount: Fixed,
+    upper: Option<Fixed>,
+    round_up: bool,
+) -> Result<Fixed, ArithmeticError>
+where
+    F: Fn(Fixed) -> Result<Fixed, ArithmeticError>,
+{
+    let reaches = |q: FixedInner| value(Fixed::from_bits(q)).map(|v| v >= amount);
+    let mut lo: FixedInner = 0;
+    if reaches(lo)? {
+        return Ok(Fixed::from_bits(lo));
+    }
+    let mut hi = match upper {
+        Some(upper) if reaches(upper.into_bits())? => upper.into_bits(),
+        Some(_) => return Err(ArithmeticError::Overflow),
+        None => {
+            let mut hi = fixed!(1).into_bits();
+            while !reaches(hi)? {
+                lo = hi;
+                hi = hi.checked_mul(2).ok_or(ArithmeticError::Overflow)?;
+            }
+            hi
+        }
+    };
+    // Invariant: `value(lo) < amount <= value(hi)`.
+    while hi - lo > 1 {
+        let mid = lo + (hi - lo) / 2;
+        if reaches(mid)? {
+            hi = mid;
+        } else {
+            lo = mid;
+        }
+    }
+    if round_up || value(Fixed::from_bits(hi))? == amount {
+        Ok(Fixed::from_bits(hi))
+    } else {
+        Ok(Fixed::from_bits(lo))
+    }
+}
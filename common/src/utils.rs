@@ -144,8 +144,24 @@ pub mod string_serialization {
     }
 }
 
+/// (De)serializes bytes of UTF-8 text stored on chain as a string.
+pub mod bytes_string_serialization {
+    #[cfg(feature = "std")]
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    #[cfg(feature = "std")]
+    pub fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&String::from_utf8_lossy(bytes))
+    }
+
+    #[cfg(feature = "std")]
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        Ok(String::deserialize(deserializer)?.into_bytes())
+    }
+}
+
 /// Generalized filtration mechanism for listing liquidity sources.
-#[derive(Encode, Decode, Clone, RuntimeDebug)]
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
 pub struct LiquiditySourceFilter<DEXId: PartialEq + Copy, LiquiditySourceIndex: PartialEq + Copy> {
     /// DEX Id to which listing is limited.
     pub dex_id: DEXId,
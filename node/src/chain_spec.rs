@@ -6,7 +6,8 @@ use serde::{Deserialize, Serialize};
 use sp_core::{sr25519, Pair, Public};
 use sp_runtime::traits::{IdentifyAccount, Verify};
 
-use parachain_runtime::{TokensConfig, PermissionsConfig, AssetsConfig, DEXManagerConfig, TechnicalConfig, TradingPairConfig};
+use parachain_runtime::{TokensConfig, PermissionsConfig, AssetsConfig, DEXManagerConfig, TechnicalConfig, TradingPairConfig, BondingCurvePoolConfig, DEXAPIConfig, PoolXYKConfig};
+use common::LiquiditySourceType;
 
 /// Specialized `ChainSpec` for the normal parachain runtime.
 pub type ChainSpec = sc_service::GenericChainSpec<parachain_runtime::GenesisConfig, Extensions>;
@@ -153,5 +154,20 @@ fn testnet_genesis(
         trading_pair: TradingPairConfig {
             trading_pairs: vec![],
         },
+        bonding_curve_pool: BondingCurvePoolConfig {
+            reserves_account_id: Default::default(),
+            distribution_accounts: vec![],
+            // Without beneficiaries all the free reserves go to the VAL buy-back.
+            val_holders_coefficient: common::fixed!(1),
+            curves: vec![],
+        },
+        pool_xyk: PoolXYKConfig {},
+        dex_api: DEXAPIConfig {
+            source_types: vec![
+                LiquiditySourceType::XYKPool,
+                LiquiditySourceType::BondingCurvePool,
+                LiquiditySourceType::OrderBook,
+            ],
+        },
 	}
 }
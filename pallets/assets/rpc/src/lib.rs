@@ -91,6 +91,17 @@ pub trait AssetsAPI<
 
     #[rpc(name = "assets_getAssetInfo")]
     fn get_asset_info(&self, asset_id: AssetId, at: Option<BlockHash>) -> Result<OptionAssetInfo>;
+
+    #[rpc(name = "assets_isAssetFrozen")]
+    fn is_asset_frozen(&self, asset_id: AssetId, at: Option<BlockHash>) -> Result<bool>;
+
+    #[rpc(name = "assets_isAccountFrozen")]
+    fn is_account_frozen(
+        &self,
+        account_id: AccountId,
+        asset_id: AssetId,
+        at: Option<BlockHash>,
+    ) -> Result<bool>;
 }
 
 pub struct AssetsClient<C, B> {
@@ -250,4 +261,40 @@ where
             data: Some(format!("{:?}", e).into()),
         })
     }
+
+    fn is_asset_frozen(
+        &self,
+        asset_id: AssetId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<bool> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or(
+            // If the block hash is not supplied assume the best block.
+            self.client.info().best_hash,
+        ));
+        api.is_asset_frozen(&at, asset_id).map_err(|e| RpcError {
+            code: ErrorCode::ServerError(InvokeRPCError::RuntimeError.into()),
+            message: "Unable to check if Asset is frozen.".into(),
+            data: Some(format!("{:?}", e).into()),
+        })
+    }
+
+    fn is_account_frozen(
+        &self,
+        account_id: AccountId,
+        asset_id: AssetId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<bool> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or(
+            // If the block hash is not supplied assume the best block.
+            self.client.info().best_hash,
+        ));
+        api.is_account_frozen(&at, account_id, asset_id)
+            .map_err(|e| RpcError {
+                code: ErrorCode::ServerError(InvokeRPCError::RuntimeError.into()),
+                message: "Unable to check if Account is frozen.".into(),
+                data: Some(format!("{:?}", e).into()),
+            })
+    }
 }
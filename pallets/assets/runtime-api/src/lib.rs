@@ -35,6 +35,7 @@
 use codec::{Codec, Decode, Encode};
 #[cfg(feature = "std")]
 use common::utils::string_serialization;
+use common::AssetExtendedInfo;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 use sp_runtime::traits::{MaybeDisplay, MaybeFromStr};
@@ -110,9 +111,16 @@ pub struct AssetInfo<AssetId, AssetSymbol, AssetName, Precision> {
 
     #[cfg_attr(feature = "std", serde(with = "string_serialization"))]
     pub is_mintable: bool,
+
+    #[cfg_attr(
+        feature = "std",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub extended_info: Option<AssetExtendedInfo>,
 }
 
 sp_api::decl_runtime_apis! {
+    #[api_version(2)]
     pub trait AssetsAPI<AccountId, AssetId, Balance, AssetSymbol, AssetName, Precision> where
         AccountId: Codec,
         AssetId: Codec,
@@ -134,6 +142,10 @@ sp_api::decl_runtime_apis! {
         fn list_asset_infos() -> Vec<AssetInfo<AssetId, AssetSymbol, AssetName, Precision>>;
 
         fn get_asset_info(asset_id: AssetId) -> Option<AssetInfo<AssetId, AssetSymbol, AssetName, Precision>>;
+
+        fn is_asset_frozen(asset_id: AssetId) -> bool;
+
+        fn is_account_frozen(account_id: AccountId, asset_id: AssetId) -> bool;
     }
 }
 
@@ -165,6 +177,7 @@ mod tests {
             name: ConcrAssetName(b"SORA".to_vec()),
             precision: 18,
             is_mintable: true,
+            extended_info: None,
         };
 
         let json_str = r#"{"asset_id":"0x020003000400050006000700080009000a000b000c000d000e000f0001000200","symbol":"XOR","name":"SORA","precision":"18","is_mintable":"true"}"#;
@@ -178,4 +191,40 @@ mod tests {
         // should not panic
         serde_json::to_value(&asset_info).unwrap();
     }
+
+    #[test]
+    fn should_serialize_and_deserialize_asset_info_with_extended_info() {
+        type AssetInfoTy = AssetInfo<
+            ConcrAssetId<ConcrAssetIdUnderlying>,
+            ConcrAssetSymbol,
+            ConcrAssetName,
+            ConcrBalancePrecision,
+        >;
+        let asset_info = AssetInfoTy {
+            asset_id: ConcrAssetId {
+                code: [
+                    2, 0, 3, 0, 4, 0, 5, 0, 6, 0, 7, 0, 8, 0, 9, 0, 10, 0, 11, 0, 12, 0, 13, 0, 14,
+                    0, 15, 0, 1, 0, 2, 0,
+                ],
+                phantom: Default::default(),
+            },
+            symbol: ConcrAssetSymbol(b"XOR".to_vec()),
+            name: ConcrAssetName(b"SORA".to_vec()),
+            precision: 18,
+            is_mintable: true,
+            extended_info: Some(AssetExtendedInfo {
+                description: b"Native token".to_vec(),
+                content_uri: b"ipfs://icon".to_vec(),
+                website: b"https://sora.org".to_vec(),
+            }),
+        };
+
+        let json_str = r#"{"asset_id":"0x020003000400050006000700080009000a000b000c000d000e000f0001000200","symbol":"XOR","name":"SORA","precision":"18","is_mintable":"true","extended_info":{"description":"Native token","content_uri":"ipfs://icon","website":"https://sora.org"}}"#;
+
+        assert_eq!(serde_json::to_string(&asset_info).unwrap(), json_str);
+        assert_eq!(
+            serde_json::from_str::<AssetInfoTy>(json_str).unwrap(),
+            asset_info
+        );
+    }
 }
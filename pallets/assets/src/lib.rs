@@ -39,6 +39,7 @@
 //! ### Dispatchable Functions
 //!
 //! - `register` - registers new asset by a given ID.
+//! - `update_info` - updates symbol, name and extended info of the asset.
 
 // TODO: add info about weight
 
@@ -48,6 +49,7 @@
 #[macro_use]
 extern crate alloc;
 
+pub mod migrations;
 pub mod weights;
 
 mod benchmarking;
@@ -60,7 +62,10 @@ mod tests;
 
 use codec::{Decode, Encode};
 use common::prelude::Balance;
-use common::{hash, Amount, AssetName, AssetSymbol, BalancePrecision, DEFAULT_BALANCE_PRECISION};
+use common::{
+    hash, Amount, AssetExtendedInfo, AssetName, AssetSymbol, BalancePrecision, IsAssetInUse,
+    DEFAULT_BALANCE_PRECISION,
+};
 use frame_support::dispatch::{DispatchError, DispatchResult};
 use frame_support::sp_runtime::traits::{MaybeSerializeDeserialize, Member};
 use frame_support::traits::Get;
@@ -77,12 +82,28 @@ use traits::{
     MultiCurrency, MultiCurrencyExtended, MultiLockableCurrency, MultiReservableCurrency,
 };
 
+/// Storage layout version, used to run the migrations only once.
+#[derive(Debug, Encode, Decode, Clone, Copy, PartialEq, Eq)]
+pub enum StorageVersion {
+    /// Assets have no `AssetExtendedInfos`.
+    V1,
+    /// Every asset has `AssetExtendedInfos`.
+    V2,
+}
+
 pub trait WeightInfo {
     fn register() -> Weight;
     fn transfer() -> Weight;
     fn mint() -> Weight;
     fn burn() -> Weight;
     fn set_non_mintable() -> Weight;
+    fn update_info() -> Weight;
+    fn transfer_ownership() -> Weight;
+    fn destroy() -> Weight;
+    fn freeze_account() -> Weight;
+    fn thaw_account() -> Weight;
+    fn freeze_asset() -> Weight;
+    fn thaw_asset() -> Weight;
 }
 
 pub type AssetIdOf<T> = <T as Config>::AssetId;
@@ -92,9 +113,16 @@ type AccountIdOf<T> = <T as frame_system::Config>::AccountId;
 type CurrencyIdOf<T> =
     <<T as Config>::Currency as MultiCurrency<<T as frame_system::Config>::AccountId>>::CurrencyId;
 
+// Encoded assets and accounts whose balances have been changed while `track_balance_changes`
+// is running, the pallet types can't be used here since the environment is not generic.
+environmental::environmental!(tracked_balances: Vec<Vec<u8>>);
+
 const ASSET_SYMBOL_MAX_LENGTH: usize = 7;
 const ASSET_NAME_MAX_LENGTH: usize = 33;
 const MAX_ALLOWED_PRECISION: u8 = 18;
+const ASSET_DESCRIPTION_MAX_LENGTH: usize = 200;
+const ASSET_CONTENT_URI_MAX_LENGTH: usize = 200;
+const ASSET_WEBSITE_MAX_LENGTH: usize = 100;
 
 #[derive(Clone, Copy, Eq, PartialEq, Encode, Decode)]
 pub enum AssetRecordArg<T: Config> {
@@ -224,6 +252,10 @@ pub mod pallet {
             > + MultiReservableCurrency<Self::AccountId, CurrencyId = Self::AssetId, Balance = Balance>
             + MultiCurrencyExtended<Self::AccountId, Amount = Amount>;
 
+        /// Pallets referencing the assets, e.g. in trading pairs or pools, which keep them
+        /// from being destroyed.
+        type IsAssetInUse: IsAssetInUse<Self::AssetId>;
+
         /// Weight information for extrinsics in this pallet.
         type WeightInfo: WeightInfo;
     }
@@ -233,7 +265,11 @@ pub mod pallet {
     pub struct Pallet<T>(PhantomData<T>);
 
     #[pallet::hooks]
-    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_runtime_upgrade() -> Weight {
+            migrations::migrate_extended_infos::<T>()
+        }
+    }
 
     #[pallet::call]
     impl<T: Config> Pallet<T> {
@@ -334,6 +370,144 @@ pub mod pallet {
             Self::deposit_event(Event::AssetSetNonMintable(asset_id.clone()));
             Ok(().into())
         }
+
+        /// Update symbol, name and extended info of the given asset.
+        /// Precision and mintability can't be changed this way.
+        ///
+        /// - `origin`: caller Account, should correspond to Asset owner,
+        /// - `asset_id`: Id of updated Asset,
+        /// - `symbol`: new Asset symbol, left unchanged if `None`,
+        /// - `name`: new Asset name, left unchanged if `None`,
+        /// - `extended_info`: new Asset extended info, left unchanged if `None`.
+        #[pallet::weight(<T as Config>::WeightInfo::update_info())]
+        pub fn update_info(
+            origin: OriginFor<T>,
+            asset_id: T::AssetId,
+            symbol: Option<AssetSymbol>,
+            name: Option<AssetName>,
+            extended_info: Option<AssetExtendedInfo>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::update_info_from(&asset_id, &who, symbol, name, extended_info)?;
+            Self::deposit_event(Event::AssetInfoUpdated(asset_id));
+            Ok(().into())
+        }
+
+        /// Transfer the ownership of the given asset to another account, together with the
+        /// `TRANSFER`, `MINT` and `BURN` permissions scoped to the asset.
+        ///
+        /// - `origin`: caller Account, should correspond to Asset owner,
+        /// - `asset_id`: Id of the Asset,
+        /// - `new_owner`: the account to become the owner.
+        #[pallet::weight(<T as Config>::WeightInfo::transfer_ownership())]
+        pub fn transfer_ownership(
+            origin: OriginFor<T>,
+            asset_id: T::AssetId,
+            new_owner: T::AccountId,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::transfer_ownership_from(&asset_id, &who, &new_owner)?;
+            Self::deposit_event(Event::AssetOwnershipTransferred(asset_id, who, new_owner));
+            Ok(().into())
+        }
+
+        /// Remove the given asset from the registry. Possible only if its total issuance is zero
+        /// and it's not in use, e.g. by trading pairs or pools. The permissions in the scope of
+        /// the asset are revoked from every holder.
+        ///
+        /// - `origin`: caller Account, should correspond to Asset owner,
+        /// - `asset_id`: Id of destroyed Asset.
+        #[pallet::weight(<T as Config>::WeightInfo::destroy())]
+        pub fn destroy(origin: OriginFor<T>, asset_id: T::AssetId) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::destroy_from(&asset_id, &who)?;
+            Self::deposit_event(Event::AssetDestroyed(asset_id));
+            Ok(().into())
+        }
+
+        /// Forbid the given account to send, receive or burn the given asset.
+        ///
+        /// - `origin`: caller Account, should correspond to Asset owner,
+        /// - `asset_id`: Id of the Asset,
+        /// - `who`: the account to freeze.
+        #[pallet::weight(<T as Config>::WeightInfo::freeze_account())]
+        pub fn freeze_account(
+            origin: OriginFor<T>,
+            asset_id: T::AssetId,
+            who: T::AccountId,
+        ) -> DispatchResultWithPostInfo {
+            let owner = ensure_signed(origin)?;
+            Self::ensure_can_freeze(&asset_id, &owner)?;
+            FrozenAccounts::<T>::try_mutate(&asset_id, &who, |frozen| {
+                ensure!(!*frozen, Error::<T>::AccountIsFrozen);
+                *frozen = true;
+                Ok::<_, Error<T>>(())
+            })?;
+            Self::deposit_event(Event::AccountFrozen(asset_id, who));
+            Ok(().into())
+        }
+
+        /// Allow the previously frozen account to operate with the given asset again.
+        ///
+        /// - `origin`: caller Account, should correspond to Asset owner,
+        /// - `asset_id`: Id of the Asset,
+        /// - `who`: the account to thaw.
+        #[pallet::weight(<T as Config>::WeightInfo::thaw_account())]
+        pub fn thaw_account(
+            origin: OriginFor<T>,
+            asset_id: T::AssetId,
+            who: T::AccountId,
+        ) -> DispatchResultWithPostInfo {
+            let owner = ensure_signed(origin)?;
+            Self::ensure_can_freeze(&asset_id, &owner)?;
+            ensure!(
+                Self::is_account_frozen(&asset_id, &who),
+                Error::<T>::AccountIsNotFrozen
+            );
+            FrozenAccounts::<T>::remove(&asset_id, &who);
+            Self::deposit_event(Event::AccountThawed(asset_id, who));
+            Ok(().into())
+        }
+
+        /// Forbid all transfers, mints and burns of the given asset.
+        ///
+        /// - `origin`: caller Account, should correspond to Asset owner,
+        /// - `asset_id`: Id of the Asset.
+        #[pallet::weight(<T as Config>::WeightInfo::freeze_asset())]
+        pub fn freeze_asset(
+            origin: OriginFor<T>,
+            asset_id: T::AssetId,
+        ) -> DispatchResultWithPostInfo {
+            let owner = ensure_signed(origin)?;
+            Self::ensure_can_freeze(&asset_id, &owner)?;
+            FrozenAssets::<T>::try_mutate(&asset_id, |frozen| {
+                ensure!(!*frozen, Error::<T>::AssetIsFrozen);
+                *frozen = true;
+                Ok::<_, Error<T>>(())
+            })?;
+            Self::deposit_event(Event::AssetFrozen(asset_id));
+            Ok(().into())
+        }
+
+        /// Allow the previously frozen asset to be used again.
+        ///
+        /// - `origin`: caller Account, should correspond to Asset owner,
+        /// - `asset_id`: Id of the Asset.
+        #[pallet::weight(<T as Config>::WeightInfo::thaw_asset())]
+        pub fn thaw_asset(
+            origin: OriginFor<T>,
+            asset_id: T::AssetId,
+        ) -> DispatchResultWithPostInfo {
+            let owner = ensure_signed(origin)?;
+            Self::ensure_can_freeze(&asset_id, &owner)?;
+            ensure!(
+                Self::is_asset_frozen(&asset_id),
+                Error::<T>::AssetIsNotFrozen
+            );
+            FrozenAssets::<T>::remove(&asset_id);
+            Self::deposit_event(Event::AssetThawed(asset_id));
+            Ok(().into())
+        }
     }
 
     #[pallet::event]
@@ -350,6 +524,20 @@ pub mod pallet {
         Burn(AccountIdOf<T>, AssetIdOf<T>, Balance),
         /// Asset is set as non-mintable. [Target Asset Id]
         AssetSetNonMintable(AssetIdOf<T>),
+        /// Asset info has been updated. [Target Asset Id]
+        AssetInfoUpdated(AssetIdOf<T>),
+        /// Ownership of asset has been transferred. [Asset Id, Previous Owner, New Owner]
+        AssetOwnershipTransferred(AssetIdOf<T>, AccountIdOf<T>, AccountIdOf<T>),
+        /// Asset has been removed from the registry. [Asset Id]
+        AssetDestroyed(AssetIdOf<T>),
+        /// Account can no longer operate with the asset. [Asset Id, Frozen Account]
+        AccountFrozen(AssetIdOf<T>, AccountIdOf<T>),
+        /// Account can operate with the asset again. [Asset Id, Thawed Account]
+        AccountThawed(AssetIdOf<T>, AccountIdOf<T>),
+        /// Asset can no longer be transferred, minted or burned. [Asset Id]
+        AssetFrozen(AssetIdOf<T>),
+        /// Asset can be transferred, minted and burned again. [Asset Id]
+        AssetThawed(AssetIdOf<T>),
     }
 
     #[pallet::error]
@@ -372,6 +560,24 @@ pub mod pallet {
         InvalidAssetOwner,
         /// Increment account reference error.
         IncRefError,
+        /// Description is not valid. It must be UTF-8 text, length <= 200.
+        InvalidAssetDescription,
+        /// Content URI is not valid. It must be UTF-8 text, length <= 200.
+        InvalidAssetContentUri,
+        /// Website is not valid. It must be UTF-8 text, length <= 100.
+        InvalidAssetWebsite,
+        /// Asset can't be destroyed while some amount of it is issued.
+        AssetSupplyIsNotZero,
+        /// Account is frozen for the given asset.
+        AccountIsFrozen,
+        /// Account is not frozen for the given asset.
+        AccountIsNotFrozen,
+        /// Asset is frozen.
+        AssetIsFrozen,
+        /// Asset is not frozen.
+        AssetIsNotFrozen,
+        /// Asset can't be destroyed while it's in use, e.g. by trading pairs or pools.
+        AssetIsInUse,
     }
 
     /// Asset Id -> Owner Account Id
@@ -391,12 +597,47 @@ pub mod pallet {
         ValueQuery,
     >;
 
+    /// Asset Id -> Extended info, which can be updated by the owner
+    #[pallet::storage]
+    #[pallet::getter(fn asset_extended_info)]
+    pub type AssetExtendedInfos<T: Config> =
+        StorageMap<_, Twox64Concat, T::AssetId, AssetExtendedInfo>;
+
+    #[pallet::type_value]
+    pub(super) fn DefaultForPalletStorageVersion() -> StorageVersion {
+        StorageVersion::V1
+    }
+
+    /// Storage layout version, chains started before the versioning have `V1`.
+    #[pallet::storage]
+    #[pallet::getter(fn pallet_storage_version)]
+    pub(super) type PalletStorageVersion<T: Config> =
+        StorageValue<_, StorageVersion, ValueQuery, DefaultForPalletStorageVersion>;
+
     /// Asset Id -> AssetRecord<T>
     #[pallet::storage]
     #[pallet::getter(fn tuple_from_asset_id)]
     pub type AssetRecordAssetId<T: Config> =
         StorageMap<_, Twox64Concat, T::AssetId, AssetRecord<T>>;
 
+    /// Asset Id -> Whether the asset is frozen
+    #[pallet::storage]
+    #[pallet::getter(fn is_asset_frozen)]
+    pub type FrozenAssets<T: Config> = StorageMap<_, Twox64Concat, T::AssetId, bool, ValueQuery>;
+
+    /// Asset Id -> Account Id -> Whether the account is frozen for the asset
+    #[pallet::storage]
+    #[pallet::getter(fn is_account_frozen)]
+    pub type FrozenAccounts<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        T::AssetId,
+        Blake2_128Concat,
+        T::AccountId,
+        bool,
+        ValueQuery,
+    >;
+
     #[pallet::genesis_config]
     pub struct GenesisConfig<T: Config> {
         pub endowed_assets: Vec<(
@@ -435,7 +676,8 @@ pub mod pallet {
                     )
                     .expect("Failed to register asset.");
                 },
-            )
+            );
+            PalletStorageVersion::<T>::put(StorageVersion::V2);
         }
     }
 }
@@ -491,6 +733,7 @@ impl<T: Config> Pallet<T> {
         );
         ensure!(crate::is_name_valid(&name), Error::<T>::InvalidAssetName);
         AssetInfos::<T>::insert(asset_id, (symbol, name, precision, is_mintable));
+        AssetExtendedInfos::<T>::insert(asset_id, AssetExtendedInfo::default());
         ensure!(
             precision <= MAX_ALLOWED_PRECISION,
             Error::<T>::InvalidPrecision
@@ -624,16 +867,28 @@ impl<T: Config> Pallet<T> {
     ) -> DispatchResult {
         Self::ensure_asset_exists(asset_id)?;
         Self::check_permission_maybe_with_parameters(from, TRANSFER, asset_id)?;
-        T::Currency::transfer(asset_id.clone(), from, to, amount)
+        Self::ensure_not_frozen(asset_id, from)?;
+        Self::ensure_not_frozen(asset_id, to)?;
+        T::Currency::transfer(asset_id.clone(), from, to, amount)?;
+        Self::note_balance_change(asset_id, from);
+        Self::note_balance_change(asset_id, to);
+        Ok(())
     }
 
+    /// Transfers the funds without checking the `TRANSFER` permission, frozen funds still
+    /// can't be moved.
     pub fn force_transfer(
         asset_id: &T::AssetId,
         from: &T::AccountId,
         to: &T::AccountId,
         amount: Balance,
     ) -> DispatchResult {
-        T::Currency::transfer(asset_id.clone(), from, to, amount)
+        Self::ensure_not_frozen(asset_id, from)?;
+        Self::ensure_not_frozen(asset_id, to)?;
+        T::Currency::transfer(asset_id.clone(), from, to, amount)?;
+        Self::note_balance_change(asset_id, from);
+        Self::note_balance_change(asset_id, to);
+        Ok(())
     }
 
     pub fn mint_to(
@@ -646,7 +901,10 @@ impl<T: Config> Pallet<T> {
         Self::check_permission_maybe_with_parameters(issuer, MINT, asset_id)?;
         let (_, _, _, is_mintable) = AssetInfos::<T>::get(asset_id);
         ensure!(is_mintable, Error::<T>::AssetSupplyIsNotMintable);
-        T::Currency::deposit(asset_id.clone(), to, amount)
+        Self::ensure_not_frozen(asset_id, to)?;
+        T::Currency::deposit(asset_id.clone(), to, amount)?;
+        Self::note_balance_change(asset_id, to);
+        Ok(())
     }
 
     pub fn burn_from(
@@ -660,7 +918,10 @@ impl<T: Config> Pallet<T> {
         if issuer != to {
             Self::check_permission_maybe_with_parameters(issuer, BURN, asset_id)?;
         }
-        T::Currency::withdraw(*asset_id, to, amount)
+        Self::ensure_not_frozen(asset_id, to)?;
+        T::Currency::withdraw(*asset_id, to, amount)?;
+        Self::note_balance_change(asset_id, to);
+        Ok(())
     }
 
     pub fn update_balance(
@@ -674,7 +935,10 @@ impl<T: Config> Pallet<T> {
             let (_, _, _, is_mintable) = AssetInfos::<T>::get(asset_id);
             ensure!(is_mintable, Error::<T>::AssetSupplyIsNotMintable);
         }
-        T::Currency::update_balance(asset_id.clone(), who, by_amount)
+        Self::ensure_not_frozen(asset_id, who)?;
+        T::Currency::update_balance(asset_id.clone(), who, by_amount)?;
+        Self::note_balance_change(asset_id, who);
+        Ok(())
     }
 
     pub fn can_reserve(asset_id: T::AssetId, who: &T::AccountId, amount: Balance) -> bool {
@@ -687,9 +951,15 @@ impl<T: Config> Pallet<T> {
         amount: Balance,
     ) -> Result<(), DispatchError> {
         Self::ensure_asset_exists(&asset_id)?;
-        T::Currency::reserve(asset_id, who, amount)
+        Self::ensure_not_frozen(&asset_id, who)?;
+        T::Currency::reserve(asset_id, who, amount)?;
+        Self::note_balance_change(&asset_id, who);
+        Ok(())
     }
 
+    /// Returns the reserved funds to the free balance of the account. The funds don't leave
+    /// the account, so it's allowed for the frozen ones, e.g. to cancel their orders, and they
+    /// stay frozen.
     pub fn unreserve(
         asset_id: T::AssetId,
         who: &T::AccountId,
@@ -697,9 +967,32 @@ impl<T: Config> Pallet<T> {
     ) -> Result<Balance, DispatchError> {
         Self::ensure_asset_exists(&asset_id)?;
         let amount = T::Currency::unreserve(asset_id, who, amount);
+        Self::note_balance_change(&asset_id, who);
         Ok(amount)
     }
 
+    /// Runs `f` recording the assets and accounts whose balances are changed through this pallet.
+    ///
+    /// The tracking is kept in memory, so it's free for the transfers made outside of `f`.
+    pub fn track_balance_changes<R>(f: impl FnOnce() -> R) -> (R, Vec<(T::AssetId, T::AccountId)>) {
+        let mut tracked = Vec::new();
+        let result = tracked_balances::using(&mut tracked, f);
+        let tracked = tracked
+            .into_iter()
+            .filter_map(|entry| Decode::decode(&mut &entry[..]).ok())
+            .collect();
+        (result, tracked)
+    }
+
+    fn note_balance_change(asset_id: &T::AssetId, who: &T::AccountId) {
+        tracked_balances::with(|tracked| {
+            let entry = (asset_id, who).encode();
+            if !tracked.contains(&entry) {
+                tracked.push(entry);
+            }
+        });
+    }
+
     pub fn set_non_mintable_from(asset_id: &T::AssetId, who: &T::AccountId) -> DispatchResult {
         ensure!(
             Self::is_asset_owner(asset_id, who),
@@ -712,6 +1005,151 @@ impl<T: Config> Pallet<T> {
         })
     }
 
+    pub fn update_info_from(
+        asset_id: &T::AssetId,
+        who: &T::AccountId,
+        symbol: Option<AssetSymbol>,
+        name: Option<AssetName>,
+        extended_info: Option<AssetExtendedInfo>,
+    ) -> DispatchResult {
+        Self::ensure_asset_exists(asset_id)?;
+        ensure!(
+            Self::is_asset_owner(asset_id, who),
+            Error::<T>::InvalidAssetOwner
+        );
+        if let Some(symbol) = &symbol {
+            ensure!(is_symbol_valid(symbol), Error::<T>::InvalidAssetSymbol);
+        }
+        if let Some(name) = &name {
+            ensure!(is_name_valid(name), Error::<T>::InvalidAssetName);
+        }
+        if let Some(extended_info) = &extended_info {
+            ensure_extended_info_valid::<T>(extended_info)?;
+        }
+        if symbol.is_some() || name.is_some() {
+            AssetInfos::<T>::mutate(asset_id, |(old_symbol, old_name, _, _)| {
+                if let Some(symbol) = symbol {
+                    *old_symbol = symbol;
+                }
+                if let Some(name) = name {
+                    *old_name = name;
+                }
+            });
+        }
+        if let Some(extended_info) = extended_info {
+            AssetExtendedInfos::<T>::insert(asset_id, extended_info);
+        }
+        Ok(())
+    }
+
+    pub fn transfer_ownership_from(
+        asset_id: &T::AssetId,
+        who: &T::AccountId,
+        new_owner: &T::AccountId,
+    ) -> DispatchResult {
+        Self::ensure_asset_exists(asset_id)?;
+        ensure!(
+            Self::is_asset_owner(asset_id, who),
+            Error::<T>::InvalidAssetOwner
+        );
+        common::with_transaction(|| {
+            let scope = Scope::Limited(hash(asset_id));
+            for permission_id in &[TRANSFER, MINT, BURN] {
+                Permissions::<T>::transfer_permission(
+                    who.clone(),
+                    new_owner.clone(),
+                    *permission_id,
+                    scope,
+                )?;
+                Permissions::<T>::grant_permission_with_scope(
+                    new_owner.clone(),
+                    new_owner.clone(),
+                    *permission_id,
+                    scope,
+                )?;
+                if who != new_owner {
+                    Self::revoke_owned_permission(new_owner, who, *permission_id, scope)?;
+                }
+            }
+            frame_system::Pallet::<T>::inc_consumers(new_owner)
+                .map_err(|_| Error::<T>::IncRefError)?;
+            AssetOwners::<T>::insert(asset_id, new_owner.clone());
+            frame_system::Pallet::<T>::dec_consumers(who);
+            Ok(())
+        })
+    }
+
+    pub fn destroy_from(asset_id: &T::AssetId, who: &T::AccountId) -> DispatchResult {
+        Self::ensure_asset_exists(asset_id)?;
+        ensure!(
+            Self::is_asset_owner(asset_id, who),
+            Error::<T>::InvalidAssetOwner
+        );
+        ensure!(
+            T::Currency::total_issuance(asset_id.clone()).is_zero(),
+            Error::<T>::AssetSupplyIsNotZero
+        );
+        ensure!(
+            !T::IsAssetInUse::is_asset_in_use(asset_id),
+            Error::<T>::AssetIsInUse
+        );
+        common::with_transaction(|| {
+            let scope = Scope::Limited(hash(asset_id));
+            let permission_ids = [TRANSFER, MINT, BURN];
+            // The owner's own permissions might not be indexed by the scope.
+            for permission_id in &permission_ids {
+                Self::revoke_owned_permission(who, who, *permission_id, scope)?;
+            }
+            Permissions::<T>::remove_scope(scope, &permission_ids);
+            AssetOwners::<T>::remove(asset_id);
+            AssetInfos::<T>::remove(asset_id);
+            AssetExtendedInfos::<T>::remove(asset_id);
+            AssetRecordAssetId::<T>::remove(asset_id);
+            FrozenAssets::<T>::remove(asset_id);
+            FrozenAccounts::<T>::remove_prefix(asset_id);
+            frame_system::Pallet::<T>::dec_consumers(who);
+            Ok(())
+        })
+    }
+
+    fn ensure_can_freeze(asset_id: &T::AssetId, who: &T::AccountId) -> DispatchResult {
+        Self::ensure_asset_exists(asset_id)?;
+        ensure!(
+            Self::is_asset_owner(asset_id, who),
+            Error::<T>::InvalidAssetOwner
+        );
+        Ok(())
+    }
+
+    /// Checks that neither the asset nor the given account are frozen.
+    pub fn ensure_not_frozen(asset_id: &T::AssetId, who: &T::AccountId) -> DispatchResult {
+        ensure!(!Self::is_asset_frozen(asset_id), Error::<T>::AssetIsFrozen);
+        ensure!(
+            !Self::is_account_frozen(asset_id, who),
+            Error::<T>::AccountIsFrozen
+        );
+        Ok(())
+    }
+
+    /// Revokes the scoped permission from `holder`, if it is held.
+    fn revoke_owned_permission(
+        owner: &T::AccountId,
+        holder: &T::AccountId,
+        permission_id: u32,
+        scope: Scope,
+    ) -> DispatchResult {
+        match Permissions::<T>::revoke_permission_with_scope(
+            owner.clone(),
+            holder.clone(),
+            permission_id,
+            scope,
+        ) {
+            // The permission might have been revoked from the holder before.
+            Ok(()) | Err(permissions::Error::<T>::PermissionNotFound) => Ok(()),
+            Err(error) => Err(error.into()),
+        }
+    }
+
     pub fn list_registered_asset_ids() -> Vec<T::AssetId> {
         AssetInfos::<T>::iter().map(|(key, _)| key).collect()
     }
@@ -751,3 +1189,24 @@ pub fn is_name_valid(name: &AssetName) -> bool {
     name.0.len() <= ASSET_NAME_MAX_LENGTH
         && name.0.iter().all(|byte| allowed_graphemes.contains(&byte))
 }
+
+fn is_text_valid(text: &[u8], max_length: usize) -> bool {
+    text.len() <= max_length && sp_std::str::from_utf8(text).is_ok()
+}
+
+/// Checks that all the fields of extended info are UTF-8 text not exceeding length limits.
+pub fn ensure_extended_info_valid<T: Config>(info: &AssetExtendedInfo) -> DispatchResult {
+    ensure!(
+        is_text_valid(&info.description, ASSET_DESCRIPTION_MAX_LENGTH),
+        Error::<T>::InvalidAssetDescription
+    );
+    ensure!(
+        is_text_valid(&info.content_uri, ASSET_CONTENT_URI_MAX_LENGTH),
+        Error::<T>::InvalidAssetContentUri
+    );
+    ensure!(
+        is_text_valid(&info.website, ASSET_WEBSITE_MAX_LENGTH),
+        Error::<T>::InvalidAssetWebsite
+    );
+    Ok(())
+}
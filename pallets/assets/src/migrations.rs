@@ -0,0 +1,56 @@
+// This file is part of the SORA network and Polkaswap app.
+
+// Copyright (c) 2020, 2021, Polka Biome Ltd. All rights reserved.
+// SPDX-License-Identifier: BSD-4-Clause
+
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+
+// Redistributions of source code must retain the above copyright notice, this list
+// of conditions and the following disclaimer.
+// Redistributions in binary form must reproduce the above copyright notice, this
+// list of conditions and the following disclaimer in the documentation and/or other
+// materials provided with the distribution.
+//
+// All advertising materials mentioning features or use of this software must display
+// the following acknowledgement: This product includes software developed by Polka Biome
+// Ltd., SORA, and Polkaswap.
+//
+// Neither the name of the Polka Biome Ltd. nor the names of its contributors may be used
+// to endorse or promote products derived from this software without specific prior written permission.
+
+// THIS SOFTWARE IS PROVIDED BY Polka Biome Ltd. AS IS AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL Polka Biome Ltd. BE LIABLE FOR ANY
+// DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS;
+// OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT,
+// STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::{AssetExtendedInfos, AssetInfos, Config, PalletStorageVersion, StorageVersion};
+use common::AssetExtendedInfo;
+use frame_support::traits::Get;
+use frame_support::weights::Weight;
+use sp_std::vec::Vec;
+
+/// Creates empty extended info for the assets registered before `AssetExtendedInfos` has been
+/// introduced. Runs only for `StorageVersion::V1`, assets already having extended info are
+/// skipped.
+pub fn migrate_extended_infos<T: Config>() -> Weight {
+    if PalletStorageVersion::<T>::get() != StorageVersion::V1 {
+        return T::DbWeight::get().reads(1);
+    }
+    PalletStorageVersion::<T>::put(StorageVersion::V2);
+    let asset_ids: Vec<_> = AssetInfos::<T>::iter()
+        .map(|(asset_id, _)| asset_id)
+        .collect();
+    let mut writes = 0;
+    for asset_id in asset_ids.iter() {
+        if !AssetExtendedInfos::<T>::contains_key(asset_id) {
+            AssetExtendedInfos::<T>::insert(asset_id, AssetExtendedInfo::default());
+            writes += 1;
+        }
+    }
+    T::DbWeight::get().reads_writes(2 * asset_ids.len() as Weight + 1, writes + 1)
+}
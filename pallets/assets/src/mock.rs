@@ -124,6 +124,7 @@ impl crate::Config for Runtime {
     type AssetId = AssetId;
     type GetBaseAssetId = GetBaseAssetId;
     type Currency = currencies::Module<Runtime>;
+    type IsAssetInUse = ();
     type WeightInfo = ();
 }
 
@@ -30,11 +30,13 @@
 
 mod tests {
     use crate::mock::*;
-    use crate::Error;
+    use crate::{migrations, AssetExtendedInfos, Error, PalletStorageVersion, StorageVersion};
     use common::prelude::{AssetName, AssetSymbol, Balance};
-    use common::{AssetId32, DOT, VAL, XOR};
+    use common::AssetExtendedInfo;
+    use common::{hash, AssetId32, DOT, VAL, XOR};
     use frame_support::{assert_err, assert_noop, assert_ok};
     use hex_literal::hex;
+    use permissions::{Scope, BURN, MINT, TRANSFER};
     use sp_runtime::traits::Zero;
 
     #[test]
@@ -457,4 +459,575 @@ mod tests {
             );
         })
     }
+
+    #[test]
+    fn should_track_balance_changes() {
+        let mut ext = ExtBuilder::default().build();
+        ext.execute_with(|| {
+            assert_ok!(Assets::register_asset_id(
+                ALICE,
+                XOR,
+                AssetSymbol(b"XOR".to_vec()),
+                AssetName(b"SORA".to_vec()),
+                18,
+                Balance::from(10u32),
+                true,
+            ));
+            let (result, tracked) = Assets::track_balance_changes(|| {
+                Assets::transfer_from(&XOR, &ALICE, &BOB, Balance::from(4u32))?;
+                Assets::burn_from(&XOR, &ALICE, &ALICE, Balance::from(1u32))
+            });
+            assert_ok!(result);
+            assert_eq!(tracked, vec![(XOR, ALICE), (XOR, BOB)]);
+            // Nothing is tracked outside of `track_balance_changes`.
+            assert_ok!(Assets::mint_to(&XOR, &ALICE, &BOB, Balance::from(1u32)));
+            let (_, tracked) = Assets::track_balance_changes(|| ());
+            assert!(tracked.is_empty());
+        })
+    }
+
+    #[test]
+    fn should_update_asset_info() {
+        let mut ext = ExtBuilder::default().build();
+        ext.execute_with(|| {
+            System::set_block_number(1);
+            assert_ok!(Assets::register_asset_id(
+                ALICE,
+                XOR,
+                AssetSymbol(b"XRO".to_vec()),
+                AssetName(b"SROA".to_vec()),
+                18,
+                Balance::zero(),
+                true,
+            ));
+            assert_eq!(
+                Assets::asset_extended_info(&XOR),
+                Some(AssetExtendedInfo::default())
+            );
+            let extended_info = AssetExtendedInfo {
+                description: "Native token of SORA".as_bytes().to_vec(),
+                content_uri: b"ipfs://QmXoypizjW3WknFiJnKLwHCnL72vedxjQkDDP1mXWo6uco".to_vec(),
+                website: b"https://sora.org".to_vec(),
+            };
+            assert_noop!(
+                Assets::update_info(
+                    Origin::signed(BOB),
+                    XOR,
+                    Some(AssetSymbol(b"XOR".to_vec())),
+                    None,
+                    None,
+                ),
+                Error::<Runtime>::InvalidAssetOwner
+            );
+            assert_ok!(Assets::update_info(
+                Origin::signed(ALICE),
+                XOR,
+                Some(AssetSymbol(b"XOR".to_vec())),
+                None,
+                Some(extended_info.clone()),
+            ));
+            assert_eq!(
+                Assets::get_asset_info(&XOR),
+                (
+                    AssetSymbol(b"XOR".to_vec()),
+                    AssetName(b"SROA".to_vec()),
+                    18,
+                    true
+                )
+            );
+            assert_ok!(Assets::update_info(
+                Origin::signed(ALICE),
+                XOR,
+                None,
+                Some(AssetName(b"SORA".to_vec())),
+                None,
+            ));
+            assert_eq!(
+                Assets::get_asset_info(&XOR),
+                (
+                    AssetSymbol(b"XOR".to_vec()),
+                    AssetName(b"SORA".to_vec()),
+                    18,
+                    true
+                )
+            );
+            assert_eq!(Assets::asset_extended_info(&XOR), Some(extended_info));
+            assert!(System::events()
+                .iter()
+                .any(|record| record.event == crate::Event::AssetInfoUpdated(XOR).into()));
+        })
+    }
+
+    #[test]
+    fn should_not_update_asset_info_with_invalid_values() {
+        let mut ext = ExtBuilder::default().build();
+        ext.execute_with(|| {
+            assert_ok!(Assets::register_asset_id(
+                ALICE,
+                XOR,
+                AssetSymbol(b"XOR".to_vec()),
+                AssetName(b"SORA".to_vec()),
+                18,
+                Balance::zero(),
+                true,
+            ));
+            assert_noop!(
+                Assets::update_info(Origin::signed(ALICE), DOT, None, None, None),
+                Error::<Runtime>::AssetIdNotExists
+            );
+            assert_noop!(
+                Assets::update_info(
+                    Origin::signed(ALICE),
+                    XOR,
+                    Some(AssetSymbol(b"xor".to_vec())),
+                    None,
+                    None,
+                ),
+                Error::<Runtime>::InvalidAssetSymbol
+            );
+            assert_noop!(
+                Assets::update_info(
+                    Origin::signed(ALICE),
+                    XOR,
+                    None,
+                    Some(AssetName(b"SORA!".to_vec())),
+                    None,
+                ),
+                Error::<Runtime>::InvalidAssetName
+            );
+            let valid_info = AssetExtendedInfo {
+                description: vec![b'a'; 200],
+                content_uri: vec![b'a'; 200],
+                website: vec![b'a'; 100],
+            };
+            assert_noop!(
+                Assets::update_info(
+                    Origin::signed(ALICE),
+                    XOR,
+                    None,
+                    None,
+                    Some(AssetExtendedInfo {
+                        description: vec![b'a'; 201],
+                        ..valid_info.clone()
+                    }),
+                ),
+                Error::<Runtime>::InvalidAssetDescription
+            );
+            assert_noop!(
+                Assets::update_info(
+                    Origin::signed(ALICE),
+                    XOR,
+                    None,
+                    None,
+                    Some(AssetExtendedInfo {
+                        content_uri: vec![0xff, 0xfe],
+                        ..valid_info.clone()
+                    }),
+                ),
+                Error::<Runtime>::InvalidAssetContentUri
+            );
+            assert_noop!(
+                Assets::update_info(
+                    Origin::signed(ALICE),
+                    XOR,
+                    None,
+                    None,
+                    Some(AssetExtendedInfo {
+                        website: vec![b'a'; 101],
+                        ..valid_info.clone()
+                    }),
+                ),
+                Error::<Runtime>::InvalidAssetWebsite
+            );
+            assert_ok!(Assets::update_info(
+                Origin::signed(ALICE),
+                XOR,
+                None,
+                None,
+                Some(valid_info),
+            ));
+        })
+    }
+
+    #[test]
+    fn should_migrate_extended_infos() {
+        let mut ext = ExtBuilder::default().build();
+        ext.execute_with(|| {
+            assert_ok!(Assets::register_asset_id(
+                ALICE,
+                XOR,
+                AssetSymbol(b"XOR".to_vec()),
+                AssetName(b"SORA".to_vec()),
+                18,
+                Balance::zero(),
+                true,
+            ));
+            assert_ok!(Assets::register_asset_id(
+                ALICE,
+                DOT,
+                AssetSymbol(b"DOT".to_vec()),
+                AssetName(b"Polkadot".to_vec()),
+                10,
+                Balance::zero(),
+                true,
+            ));
+            let extended_info = AssetExtendedInfo {
+                description: b"Native token".to_vec(),
+                ..Default::default()
+            };
+            AssetExtendedInfos::<Runtime>::insert(&XOR, extended_info.clone());
+            // Imitate the asset registered before extended info has been introduced.
+            AssetExtendedInfos::<Runtime>::remove(&DOT);
+            PalletStorageVersion::<Runtime>::put(StorageVersion::V1);
+
+            migrations::migrate_extended_infos::<Runtime>();
+            assert_eq!(Assets::asset_extended_info(&XOR), Some(extended_info));
+            assert_eq!(
+                Assets::asset_extended_info(&DOT),
+                Some(AssetExtendedInfo::default())
+            );
+            assert_eq!(Assets::pallet_storage_version(), StorageVersion::V2);
+            // Running it again must not change anything.
+            AssetExtendedInfos::<Runtime>::remove(&DOT);
+            migrations::migrate_extended_infos::<Runtime>();
+            assert_eq!(Assets::asset_extended_info(&DOT), None);
+        })
+    }
+
+    #[test]
+    fn should_run_extended_infos_migration_once() {
+        let mut ext = ExtBuilder::default().build();
+        ext.execute_with(|| {
+            // Chains started before the versioning have no storage version stored.
+            assert_eq!(Assets::pallet_storage_version(), StorageVersion::V1);
+            assert_ok!(Assets::register_asset_id(
+                ALICE,
+                XOR,
+                AssetSymbol(b"XOR".to_vec()),
+                AssetName(b"SORA".to_vec()),
+                18,
+                Balance::zero(),
+                true,
+            ));
+            AssetExtendedInfos::<Runtime>::remove(&XOR);
+
+            migrations::migrate_extended_infos::<Runtime>();
+            assert_eq!(Assets::pallet_storage_version(), StorageVersion::V2);
+            assert_eq!(
+                Assets::asset_extended_info(&XOR),
+                Some(AssetExtendedInfo::default())
+            );
+
+            AssetExtendedInfos::<Runtime>::remove(&XOR);
+            migrations::migrate_extended_infos::<Runtime>();
+            assert_eq!(Assets::pallet_storage_version(), StorageVersion::V2);
+            assert_eq!(Assets::asset_extended_info(&XOR), None);
+        })
+    }
+
+    #[test]
+    fn should_track_balance_changes() {
+        let mut ext = ExtBuilder::default().build();
+        ext.execute_with(|| {
+            assert_ok!(Assets::register_asset_id(
+                ALICE,
+                XOR,
+                AssetSymbol(b"XOR".to_vec()),
+                AssetName(b"SORA".to_vec()),
+                18,
+                Balance::from(100u32),
+                true,
+            ));
+            let (result, tracked) = Assets::track_balance_changes(|| {
+                Assets::transfer_from(&XOR, &ALICE, &BOB, Balance::from(10u32))?;
+                Assets::transfer_from(&XOR, &ALICE, &BOB, Balance::from(10u32))
+            });
+            assert_ok!(result);
+            assert_eq!(tracked, vec![(XOR, ALICE), (XOR, BOB)]);
+
+            // Changes made outside of the closure are not tracked.
+            assert_ok!(Assets::transfer_from(
+                &XOR,
+                &ALICE,
+                &BOB,
+                Balance::from(10u32)
+            ));
+            let ((), tracked) = Assets::track_balance_changes(|| ());
+            assert!(tracked.is_empty());
+        })
+    }
+
+    #[test]
+    fn should_transfer_asset_ownership() {
+        let mut ext = ExtBuilder::default().build();
+        ext.execute_with(|| {
+            System::set_block_number(1);
+            assert_ok!(Assets::register_asset_id(
+                ALICE,
+                XOR,
+                AssetSymbol(b"XOR".to_vec()),
+                AssetName(b"SORA".to_vec()),
+                18,
+                Balance::zero(),
+                true,
+            ));
+            assert_noop!(
+                Assets::transfer_ownership(Origin::signed(BOB), XOR, BOB),
+                Error::<Runtime>::InvalidAssetOwner
+            );
+            assert_ok!(Assets::transfer_ownership(Origin::signed(ALICE), XOR, BOB));
+            assert!(System::events().iter().any(|record| record.event
+                == crate::Event::AssetOwnershipTransferred(XOR, ALICE, BOB).into()));
+            assert!(Assets::is_asset_owner(&XOR, &BOB));
+            assert!(!Assets::is_asset_owner(&XOR, &ALICE));
+            assert_noop!(
+                Assets::mint_to(&XOR, &ALICE, &ALICE, Balance::from(10u32)),
+                permissions::Error::<Runtime>::Forbidden
+            );
+            assert_ok!(Assets::mint_to(&XOR, &BOB, &BOB, Balance::from(10u32)));
+            assert_noop!(
+                Assets::set_non_mintable(Origin::signed(ALICE), XOR),
+                Error::<Runtime>::InvalidAssetOwner
+            );
+            assert_ok!(Assets::set_non_mintable(Origin::signed(BOB), XOR));
+        })
+    }
+
+    #[test]
+    fn should_destroy_asset_with_zero_supply() {
+        let mut ext = ExtBuilder::default().build();
+        ext.execute_with(|| {
+            System::set_block_number(1);
+            let consumers = System::consumers(&ALICE);
+            let bob_consumers = System::consumers(&BOB);
+            assert_ok!(Assets::register_asset_id(
+                ALICE,
+                XOR,
+                AssetSymbol(b"XOR".to_vec()),
+                AssetName(b"SORA".to_vec()),
+                18,
+                Balance::from(10u32),
+                true,
+            ));
+            assert_ok!(permissions::Pallet::<Runtime>::grant_permission_with_scope(
+                ALICE,
+                BOB,
+                MINT,
+                Scope::Limited(hash(&XOR))
+            ));
+            assert_noop!(
+                Assets::destroy(Origin::signed(BOB), XOR),
+                Error::<Runtime>::InvalidAssetOwner
+            );
+            assert_noop!(
+                Assets::destroy(Origin::signed(ALICE), XOR),
+                Error::<Runtime>::AssetSupplyIsNotZero
+            );
+            assert_ok!(Assets::burn_from(
+                &XOR,
+                &ALICE,
+                &ALICE,
+                Balance::from(10u32)
+            ));
+            assert_ok!(Assets::destroy(Origin::signed(ALICE), XOR));
+            assert!(System::events()
+                .iter()
+                .any(|record| record.event == crate::Event::AssetDestroyed(XOR).into()));
+            assert!(Assets::ensure_asset_exists(&XOR).is_err());
+            assert!(Assets::asset_extended_info(&XOR).is_none());
+            assert_eq!(System::consumers(&ALICE), consumers);
+            assert_eq!(System::consumers(&BOB), bob_consumers);
+            // Permissions granted to other accounts are revoked along with the owner's.
+            assert!(
+                permissions::Permissions::<Runtime>::get(&BOB, Scope::Limited(hash(&XOR)))
+                    .is_empty()
+            );
+            for permission_id in &[TRANSFER, MINT, BURN] {
+                assert!(permissions::Owners::<Runtime>::get(
+                    permission_id,
+                    Scope::Limited(hash(&XOR))
+                )
+                .is_empty());
+            }
+            assert_noop!(
+                Assets::destroy(Origin::signed(ALICE), XOR),
+                Error::<Runtime>::AssetIdNotExists
+            );
+        })
+    }
+
+    #[test]
+    fn should_freeze_and_thaw_account() {
+        let mut ext = ExtBuilder::default().build();
+        ext.execute_with(|| {
+            System::set_block_number(1);
+            assert_ok!(Assets::register_asset_id(
+                ALICE,
+                XOR,
+                AssetSymbol(b"XOR".to_vec()),
+                AssetName(b"SORA".to_vec()),
+                18,
+                Balance::from(10u32),
+                true,
+            ));
+            assert_noop!(
+                Assets::freeze_account(Origin::signed(BOB), XOR, ALICE),
+                Error::<Runtime>::InvalidAssetOwner
+            );
+            assert_ok!(Assets::freeze_account(Origin::signed(ALICE), XOR, ALICE));
+            assert!(System::events()
+                .iter()
+                .any(|record| record.event == crate::Event::AccountFrozen(XOR, ALICE).into()));
+            assert!(Assets::is_account_frozen(&XOR, &ALICE));
+            assert!(!Assets::is_account_frozen(&XOR, &BOB));
+            assert_noop!(
+                Assets::freeze_account(Origin::signed(ALICE), XOR, ALICE),
+                Error::<Runtime>::AccountIsFrozen
+            );
+            assert_noop!(
+                Assets::transfer_from(&XOR, &ALICE, &BOB, Balance::from(1u32)),
+                Error::<Runtime>::AccountIsFrozen
+            );
+            assert_noop!(
+                Assets::burn_from(&XOR, &ALICE, &ALICE, Balance::from(1u32)),
+                Error::<Runtime>::AccountIsFrozen
+            );
+            assert_noop!(
+                Assets::update_balance(&XOR, &ALICE, 1),
+                Error::<Runtime>::AccountIsFrozen
+            );
+
+            assert_ok!(Assets::thaw_account(Origin::signed(ALICE), XOR, ALICE));
+            assert!(System::events()
+                .iter()
+                .any(|record| record.event == crate::Event::AccountThawed(XOR, ALICE).into()));
+            assert_noop!(
+                Assets::thaw_account(Origin::signed(ALICE), XOR, ALICE),
+                Error::<Runtime>::AccountIsNotFrozen
+            );
+            assert_ok!(Assets::freeze_account(Origin::signed(ALICE), XOR, BOB));
+            assert_noop!(
+                Assets::transfer_from(&XOR, &ALICE, &BOB, Balance::from(1u32)),
+                Error::<Runtime>::AccountIsFrozen
+            );
+            assert_ok!(Assets::transfer_from(
+                &XOR,
+                &ALICE,
+                &ALICE,
+                Balance::from(1u32)
+            ));
+        })
+    }
+
+    #[test]
+    fn should_not_move_funds_of_frozen_account() {
+        let mut ext = ExtBuilder::default().build();
+        ext.execute_with(|| {
+            assert_ok!(Assets::register_asset_id(
+                ALICE,
+                XOR,
+                AssetSymbol(b"XOR".to_vec()),
+                AssetName(b"SORA".to_vec()),
+                18,
+                Balance::from(10u32),
+                true,
+            ));
+            assert_ok!(Assets::reserve(XOR, &ALICE, Balance::from(2u32)));
+            assert_ok!(Assets::freeze_account(Origin::signed(ALICE), XOR, ALICE));
+            assert_noop!(
+                Assets::force_transfer(&XOR, &ALICE, &BOB, Balance::from(1u32)),
+                Error::<Runtime>::AccountIsFrozen
+            );
+            assert_noop!(
+                Assets::force_transfer(&XOR, &BOB, &ALICE, Balance::from(0u32)),
+                Error::<Runtime>::AccountIsFrozen
+            );
+            assert_noop!(
+                Assets::reserve(XOR, &ALICE, Balance::from(1u32)),
+                Error::<Runtime>::AccountIsFrozen
+            );
+            // Unreserved funds stay in the account and remain frozen.
+            assert_ok!(Assets::unreserve(XOR, &ALICE, Balance::from(2u32)));
+            assert_eq!(
+                Assets::free_balance(&XOR, &ALICE).unwrap(),
+                Balance::from(10u32)
+            );
+            assert_noop!(
+                Assets::transfer_from(&XOR, &ALICE, &BOB, Balance::from(2u32)),
+                Error::<Runtime>::AccountIsFrozen
+            );
+
+            assert_ok!(Assets::freeze_asset(Origin::signed(ALICE), XOR));
+            assert_ok!(Assets::thaw_account(Origin::signed(ALICE), XOR, ALICE));
+            assert_noop!(
+                Assets::force_transfer(&XOR, &ALICE, &BOB, Balance::from(1u32)),
+                Error::<Runtime>::AssetIsFrozen
+            );
+            assert_noop!(
+                Assets::reserve(XOR, &ALICE, Balance::from(1u32)),
+                Error::<Runtime>::AssetIsFrozen
+            );
+            assert_ok!(Assets::thaw_asset(Origin::signed(ALICE), XOR));
+            assert_ok!(Assets::force_transfer(
+                &XOR,
+                &ALICE,
+                &BOB,
+                Balance::from(1u32)
+            ));
+            assert_ok!(Assets::reserve(XOR, &ALICE, Balance::from(1u32)));
+        })
+    }
+
+    #[test]
+    fn should_freeze_and_thaw_asset() {
+        let mut ext = ExtBuilder::default().build();
+        ext.execute_with(|| {
+            System::set_block_number(1);
+            assert_ok!(Assets::register_asset_id(
+                ALICE,
+                XOR,
+                AssetSymbol(b"XOR".to_vec()),
+                AssetName(b"SORA".to_vec()),
+                18,
+                Balance::from(10u32),
+                true,
+            ));
+            assert_noop!(
+                Assets::freeze_asset(Origin::signed(BOB), XOR),
+                Error::<Runtime>::InvalidAssetOwner
+            );
+            assert_noop!(
+                Assets::thaw_asset(Origin::signed(ALICE), XOR),
+                Error::<Runtime>::AssetIsNotFrozen
+            );
+            assert_ok!(Assets::freeze_asset(Origin::signed(ALICE), XOR));
+            assert!(System::events()
+                .iter()
+                .any(|record| record.event == crate::Event::AssetFrozen(XOR).into()));
+            assert!(Assets::is_asset_frozen(&XOR));
+            assert_noop!(
+                Assets::transfer_from(&XOR, &ALICE, &BOB, Balance::from(1u32)),
+                Error::<Runtime>::AssetIsFrozen
+            );
+            assert_noop!(
+                Assets::mint_to(&XOR, &ALICE, &BOB, Balance::from(1u32)),
+                Error::<Runtime>::AssetIsFrozen
+            );
+            assert_noop!(
+                Assets::burn_from(&XOR, &ALICE, &ALICE, Balance::from(1u32)),
+                Error::<Runtime>::AssetIsFrozen
+            );
+
+            assert_ok!(Assets::thaw_asset(Origin::signed(ALICE), XOR));
+            assert!(System::events()
+                .iter()
+                .any(|record| record.event == crate::Event::AssetThawed(XOR).into()));
+            assert_ok!(Assets::transfer_from(
+                &XOR,
+                &ALICE,
+                &BOB,
+                Balance::from(1u32)
+            ));
+        })
+    }
 }
@@ -59,6 +59,41 @@ impl<T: frame_system::Config> crate::WeightInfo for WeightInfo<T> {
             .saturating_add(T::DbWeight::get().reads(2 as Weight))
             .saturating_add(T::DbWeight::get().writes(1 as Weight))
     }
+    fn update_info() -> Weight {
+        (200_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    fn transfer_ownership() -> Weight {
+        (600_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(11 as Weight))
+            .saturating_add(T::DbWeight::get().writes(12 as Weight))
+    }
+    fn destroy() -> Weight {
+        (400_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(14 as Weight))
+            .saturating_add(T::DbWeight::get().writes(12 as Weight))
+    }
+    fn freeze_account() -> Weight {
+        (150_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn thaw_account() -> Weight {
+        (150_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn freeze_asset() -> Weight {
+        (150_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn thaw_asset() -> Weight {
+        (150_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
 }
 
 impl crate::WeightInfo for () {
@@ -77,4 +112,25 @@ impl crate::WeightInfo for () {
     fn set_non_mintable() -> Weight {
         EXTRINSIC_FIXED_WEIGHT
     }
+    fn update_info() -> Weight {
+        EXTRINSIC_FIXED_WEIGHT
+    }
+    fn transfer_ownership() -> Weight {
+        EXTRINSIC_FIXED_WEIGHT
+    }
+    fn destroy() -> Weight {
+        EXTRINSIC_FIXED_WEIGHT
+    }
+    fn freeze_account() -> Weight {
+        EXTRINSIC_FIXED_WEIGHT
+    }
+    fn thaw_account() -> Weight {
+        EXTRINSIC_FIXED_WEIGHT
+    }
+    fn freeze_asset() -> Weight {
+        EXTRINSIC_FIXED_WEIGHT
+    }
+    fn thaw_asset() -> Weight {
+        EXTRINSIC_FIXED_WEIGHT
+    }
 }
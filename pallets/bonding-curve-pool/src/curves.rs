@@ -0,0 +1,338 @@
+// This file is part of the SORA network and Polkaswap app.
+
+// Copyright (c) 2020, 2021, Polka Biome Ltd. All rights reserved.
+// SPDX-License-Identifier: BSD-4-Clause
+
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+
+// Redistributions of source code must retain the above copyright notice, this list
+// of conditions and the following disclaimer.
+// Redistributions in binary form must reproduce the above copyright notice, this
+// list of conditions and the following disclaimer in the documentation and/or other
+// materials provided with the distribution.
+//
+// All advertising materials mentioning features or use of this software must display
+// the following acknowledgement: This product includes software developed by Polka Biome
+// Ltd., SORA, and Polkaswap.
+//
+// Neither the name of the Polka Biome Ltd. nor the names of its contributors may be used
+// to endorse or promote products derived from this software without specific prior written permission.
+
+// THIS SOFTWARE IS PROVIDED BY Polka Biome Ltd. AS IS AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL Polka Biome Ltd. BE LIABLE FOR ANY
+// DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS;
+// OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT,
+// STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+//! Bonding curve shapes which can be selected for the pool.
+
+use codec::{Decode, Encode};
+use common::prelude::{Fixed, FixedWrapper};
+use common::{balance, fixed, fixed_wrapper, BondingCurve};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+/// Linear curve, the price goes up by `PC_R` for every `PC_S * PC_R` assets.
+///
+/// `P_B(Q) = Q / (PC_S * PC_R) + P_I`
+///
+/// where
+/// `P_B(Q)`: buy price for one asset
+/// `P_I`: initial asset price
+/// `PC_R`: price change rate
+/// `PC_S`: price change step
+/// `Q`: asset issuance (quantity)
+#[derive(Debug, Encode, Decode, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct LinearCurve {
+    pub initial_price: Fixed,
+    pub price_change_step: Fixed,
+    pub price_change_rate: Fixed,
+}
+
+#[allow(non_snake_case)]
+impl BondingCurve for LinearCurve {
+    fn buy_price(&self, issuance: FixedWrapper) -> FixedWrapper {
+        let PC_S = FixedWrapper::from(self.price_change_step);
+        let PC_R = self.price_change_rate;
+        issuance / (PC_S * PC_R) + self.initial_price
+    }
+
+    /// ```nocompile
+    /// P_M(Q, Q') = ∫ [P_B(x) dx, x = Q to Q']
+    ///            = x² / (2 * PC_S * PC_R) + P_I * x, x = Q to Q'
+    ///            = (Q' / (2 * PC_S * PC_R) + P_I) * Q' -
+    ///              (Q  / (2 * PC_S * PC_R) + P_I) * Q;
+    /// ```
+    ///
+    /// [Formula calculation](https://www.wolframalpha.com/input/?i=p+%3D+q+%2F+(s+*+r)+%2B+i+integrate+for+q&assumption="i"+->+"Variable")
+    fn integral(&self, from: FixedWrapper, to: FixedWrapper) -> FixedWrapper {
+        let P_I = self.initial_price;
+        let PC_S = FixedWrapper::from(self.price_change_step);
+        let PC_R = self.price_change_rate;
+        let two_times_PC_S_times_PC_R = balance!(2) * PC_S * PC_R;
+        let to = (to.clone() / two_times_PC_S_times_PC_R.clone() + P_I) * to;
+        let from = (from.clone() / two_times_PC_S_times_PC_R + P_I) * from;
+        to - from
+    }
+
+    /// Using derived formula for `integral`
+    /// ```nocompile
+    /// q_BM = √(Q² + 2 * Q * PC_S * PC_R * P_I + PC_S * PC_R *(PC_S * PC_R * P_I²
+    ///         + 2 * P_M(Q, Q'))) - Q - PC_S * PC_R * P_I
+    ///
+    /// q_SM = Q + PC_S * PC_R * P_I - (PC_S * PC_R * √(((Q * P_Sc) / (PC_S * PC_R)
+    ///          + P_I * P_Sc)² - (2 * P_Sc * P_M(Q, Q')) / (PC_S * PC_R))) / P_Sc
+    /// ```
+    /// where
+    /// `P_Sc: sell price coefficient (%)`
+    /// `q_BM`: quantity of assets to be bought, when P_M(Q, Q') tokens are spent
+    /// `q_SM`: quantity of assets to be sold, when P_M(Q, Q') tokens are received
+    ///
+    /// [Wolfram Alpha (buy)](https://www.wolframalpha.com/input/?i=y+%3D+%28%28a%2Bx%29+%2F+%282+*+b+*+c%29+%2B+d%29+*+%28a%2Bx%29+-+%28+a+%2F+%282+*+b+*+c%29+%2B+d%29+*+a+solve+for+x)
+    /// [Wolfram Alpha (sell)](https://www.wolframalpha.com/input/?i=y+%3D+%28%28a++%2F+%282+*+b+*+c%29+%2B+d%29+*+a+-+%28%28a+-+x%29+%2F+%282+*+b+*+c%29+%2B+d%29+*+%28a+-+x%29%29+*+k+solve+for+x)
+    fn inverse_integral(
+        &self,
+        issuance: FixedWrapper,
+        amount: FixedWrapper,
+        sell_price_coefficient: Option<FixedWrapper>,
+    ) -> FixedWrapper {
+        let Q = issuance;
+        let P_I = FixedWrapper::from(self.initial_price);
+        let PC_S = FixedWrapper::from(self.price_change_step);
+        let PC_R = FixedWrapper::from(self.price_change_rate);
+
+        let PC_S_times_PC_R = PC_S.clone() * PC_R.clone();
+        let PC_S_times_PC_R_times_P_I = PC_S_times_PC_R.clone() * P_I.clone();
+        let PC_S_times_PC_R_times_P_I_squared = PC_S_times_PC_R_times_P_I.clone() * P_I.clone();
+
+        match sell_price_coefficient {
+            None => {
+                let Q_squared = Q.clone() * Q.clone();
+                let inner_term_a = balance!(2) * Q.clone() * PC_S_times_PC_R_times_P_I.clone();
+                let inner_term_b =
+                    PC_S * PC_R * (PC_S_times_PC_R_times_P_I_squared + balance!(2) * amount);
+                let under_sqrt = Q_squared + inner_term_a + inner_term_b;
+                under_sqrt.sqrt_accurate() - Q - PC_S_times_PC_R_times_P_I
+            }
+            Some(P_Sc) => {
+                let inner_term_a =
+                    ((Q.clone() * P_Sc.clone()) / PC_S_times_PC_R.clone()) + (P_I * P_Sc.clone());
+                let inner_term_b = (balance!(2) * P_Sc.clone() * amount) / PC_S_times_PC_R.clone();
+                let under_sqrt = inner_term_a.clone() * inner_term_a - inner_term_b;
+                (Q + PC_S_times_PC_R_times_P_I)
+                    - ((PC_S_times_PC_R * under_sqrt.sqrt_accurate()) / P_Sc)
+            }
+        }
+    }
+}
+
+/// Exponential curve, the price grows `e` times for every `S` assets.
+///
+/// `P_B(Q) = P_I * e^(Q / S)`
+///
+/// where
+/// `P_I`: initial asset price
+/// `S`: scale
+/// `Q`: asset issuance (quantity)
+#[derive(Debug, Encode, Decode, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ExponentialCurve {
+    pub initial_price: Fixed,
+    pub scale: Fixed,
+}
+
+#[allow(non_snake_case)]
+impl BondingCurve for ExponentialCurve {
+    fn buy_price(&self, issuance: FixedWrapper) -> FixedWrapper {
+        (issuance / self.scale).exp() * self.initial_price
+    }
+
+    /// `P_M(Q, Q') = P_I * S * (e^(Q' / S) - e^(Q / S))`
+    fn integral(&self, from: FixedWrapper, to: FixedWrapper) -> FixedWrapper {
+        let P_I_times_S = FixedWrapper::from(self.initial_price) * self.scale;
+        P_I_times_S * ((to / self.scale).exp() - (from / self.scale).exp())
+    }
+
+    /// ```nocompile
+    /// q_BM = S * ln(e^(Q / S) + P_M(Q, Q') / (P_I * S)) - Q
+    /// q_SM = Q - S * ln(e^(Q / S) - P_M(Q, Q') / (P_Sc * P_I * S))
+    /// ```
+    fn inverse_integral(
+        &self,
+        issuance: FixedWrapper,
+        amount: FixedWrapper,
+        sell_price_coefficient: Option<FixedWrapper>,
+    ) -> FixedWrapper {
+        let S = FixedWrapper::from(self.scale);
+        let P_I_times_S = FixedWrapper::from(self.initial_price) * self.scale;
+        let e_Q = (issuance.clone() / S.clone()).exp();
+        match sell_price_coefficient {
+            None => S * (e_Q + amount / P_I_times_S).ln() - issuance,
+            Some(P_Sc) => issuance - S * (e_Q - amount / (P_Sc * P_I_times_S)).ln(),
+        }
+    }
+}
+
+/// Power-law curve.
+///
+/// `P_B(Q) = P_I + a * (Q / S)^n`
+///
+/// where
+/// `P_I`: initial asset price
+/// `a`: price coefficient
+/// `S`: scale
+/// `n`: exponent
+/// `Q`: asset issuance (quantity)
+#[derive(Debug, Encode, Decode, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct PowerLawCurve {
+    pub initial_price: Fixed,
+    pub coefficient: Fixed,
+    pub scale: Fixed,
+    pub exponent: u32,
+}
+
+#[allow(non_snake_case)]
+impl BondingCurve for PowerLawCurve {
+    fn buy_price(&self, issuance: FixedWrapper) -> FixedWrapper {
+        (issuance / self.scale).pow(self.exponent) * self.coefficient + self.initial_price
+    }
+
+    /// `P_M(Q, Q') = P_I * (Q' - Q) + a * S / (n + 1) * ((Q' / S)^(n + 1) - (Q / S)^(n + 1))`
+    fn integral(&self, from: FixedWrapper, to: FixedWrapper) -> FixedWrapper {
+        let n_plus_one = self.exponent + 1;
+        let linear_part = (to.clone() - from.clone()) * self.initial_price;
+        let a_times_S = FixedWrapper::from(self.coefficient) * self.scale;
+        let power_part = (to / self.scale).pow(n_plus_one) - (from / self.scale).pow(n_plus_one);
+        linear_part + a_times_S * power_part / (balance!(1) * u128::from(n_plus_one))
+    }
+}
+
+/// Sigmoid curve, the price grows from `P_I` to `P_I + P_A` with the steepest growth
+/// at `Q_M` assets.
+///
+/// `P_B(Q) = P_I + P_A / (1 + e^(-(Q - Q_M) / S))`
+///
+/// where
+/// `P_I`: initial asset price
+/// `P_A`: price amplitude
+/// `Q_M`: issuance at the midpoint of the curve
+/// `S`: scale
+/// `Q`: asset issuance (quantity)
+#[derive(Debug, Encode, Decode, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct SigmoidCurve {
+    pub initial_price: Fixed,
+    pub price_amplitude: Fixed,
+    pub midpoint: Fixed,
+    pub scale: Fixed,
+}
+
+impl SigmoidCurve {
+    /// Numerically stable `ln(1 + e^x)`.
+    fn softplus(x: FixedWrapper) -> FixedWrapper {
+        match x.clone().get() {
+            Ok(value) if value > fixed!(0) => x.clone() + (fixed_wrapper!(1) + (-x).exp()).ln(),
+            _ => (fixed_wrapper!(1) + x.exp()).ln(),
+        }
+    }
+}
+
+#[allow(non_snake_case)]
+impl BondingCurve for SigmoidCurve {
+    fn buy_price(&self, issuance: FixedWrapper) -> FixedWrapper {
+        let exponent = (FixedWrapper::from(self.midpoint) - issuance) / self.scale;
+        FixedWrapper::from(self.price_amplitude) / (fixed_wrapper!(1) + exponent.exp())
+            + self.initial_price
+    }
+
+    /// ```nocompile
+    /// P_M(Q, Q') = P_I * (Q' - Q) + P_A * S * (ln(1 + e^((Q' - Q_M) / S)) - ln(1 + e^((Q - Q_M) / S)))
+    /// ```
+    fn integral(&self, from: FixedWrapper, to: FixedWrapper) -> FixedWrapper {
+        let linear_part = (to.clone() - from.clone()) * self.initial_price;
+        let P_A_times_S = FixedWrapper::from(self.price_amplitude) * self.scale;
+        let to = Self::softplus((to - self.midpoint) / self.scale);
+        let from = Self::softplus((from - self.midpoint) / self.scale);
+        linear_part + P_A_times_S * (to - from)
+    }
+}
+
+/// Bonding curve shape selected for a pool.
+#[derive(Debug, Encode, Decode, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum CurveShape {
+    Linear(LinearCurve),
+    Exponential(ExponentialCurve),
+    PowerLaw(PowerLawCurve),
+    Sigmoid(SigmoidCurve),
+}
+
+impl CurveShape {
+    /// Checks that the price of the curve is positive and grows with the issuance.
+    pub fn is_valid(&self) -> bool {
+        let positive = |value: Fixed| value > fixed!(0);
+        match self {
+            CurveShape::Linear(curve) => {
+                positive(curve.initial_price)
+                    && positive(curve.price_change_step)
+                    && positive(curve.price_change_rate)
+            }
+            CurveShape::Exponential(curve) => {
+                positive(curve.initial_price) && positive(curve.scale)
+            }
+            CurveShape::PowerLaw(curve) => {
+                positive(curve.initial_price)
+                    && positive(curve.coefficient)
+                    && positive(curve.scale)
+                    && curve.exponent > 0
+            }
+            CurveShape::Sigmoid(curve) => {
+                positive(curve.initial_price)
+                    && positive(curve.price_amplitude)
+                    && positive(curve.scale)
+            }
+        }
+    }
+
+    fn as_curve(&self) -> &dyn BondingCurve {
+        match self {
+            CurveShape::Linear(curve) => curve,
+            CurveShape::Exponential(curve) => curve,
+            CurveShape::PowerLaw(curve) => curve,
+            CurveShape::Sigmoid(curve) => curve,
+        }
+    }
+}
+
+impl BondingCurve for CurveShape {
+    fn buy_price(&self, issuance: FixedWrapper) -> FixedWrapper {
+        self.as_curve().buy_price(issuance)
+    }
+
+    fn sell_price(
+        &self,
+        issuance: FixedWrapper,
+        sell_price_coefficient: FixedWrapper,
+    ) -> FixedWrapper {
+        self.as_curve().sell_price(issuance, sell_price_coefficient)
+    }
+
+    fn integral(&self, from: FixedWrapper, to: FixedWrapper) -> FixedWrapper {
+        self.as_curve().integral(from, to)
+    }
+
+    fn inverse_integral(
+        &self,
+        issuance: FixedWrapper,
+        amount: FixedWrapper,
+        sell_price_coefficient: Option<FixedWrapper>,
+    ) -> FixedWrapper {
+        self.as_curve()
+            .inverse_integral(issuance, amount, sell_price_coefficient)
+    }
+}
@@ -36,25 +36,48 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+mod curves;
+pub use curves::*;
+
+pub mod migrations;
+pub mod weights;
+
+pub trait WeightInfo {
+    fn set_distribution_accounts(n: u32) -> Weight;
+    fn update_distribution_account() -> Weight;
+    fn set_buy_back_filter() -> Weight;
+    fn retry_buy_backs(n: u32) -> Weight;
+    fn set_curve() -> Weight;
+}
+
 use codec::{Decode, Encode};
-use common::prelude::{Balance, Fixed, FixedWrapper, SwapAmount, SwapOutcome};
-use common::{balance, fixed, DEXId, LiquiditySource, USDT, VAL};
+use common::prelude::{Balance, Fixed, FixedWrapper, SwapAmount, SwapOutcome, SwapVariant};
+use common::{
+    fixed, fixed_wrapper, BondingCurve, DEXId, GetSwapCurve, LiquiditySource,
+    LiquiditySourceFilter, LiquiditySourceType, SwapCurve, TaggedLiquiditySource, USDT, VAL,
+};
 use core::convert::TryInto;
+use frame_support::dispatch::DispatchResult;
 use frame_support::traits::Get;
+use frame_support::weights::Weight;
 use frame_support::{ensure, fail};
+use liquidity_proxy::LiquidityProxyTrait;
 use permissions::{Scope, BURN, MINT, TRANSFER};
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 use sp_arithmetic::traits::Zero;
 use sp_runtime::DispatchError;
+use sp_std::collections::btree_set::BTreeSet;
+use sp_std::vec::Vec;
 
 type Assets<T> = assets::Pallet<T>;
 type Technical<T> = technical::Pallet<T>;
 
 pub const TECH_ACCOUNT_PREFIX: &[u8] = b"bonding-curve-pool";
 pub const TECH_ACCOUNT_RESERVES: &[u8] = b"reserves";
+pub const MAX_DISTRIBUTION_ACCOUNT_LABEL_LENGTH: usize = 32;
 
-#[derive(Debug, Encode, Decode, Clone)]
+#[derive(Debug, Encode, Decode, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum DistributionAccount<AccountId, TechAccountId> {
     Account(AccountId),
@@ -92,6 +115,36 @@ impl<DistributionAccount> DistributionAccountData<DistributionAccount> {
     }
 }
 
+/// Beneficiary of the free reserves distribution.
+#[derive(Debug, Encode, Decode, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct DistributionAccountEntry<DistributionAccount> {
+    /// Name of the beneficiary, unique within the distribution list.
+    pub label: Vec<u8>,
+    pub account: DistributionAccount,
+    /// Share of the free reserves (converted to the main asset) sent to the `account`.
+    pub coefficient: Fixed,
+}
+
+impl<DistributionAccount> DistributionAccountEntry<DistributionAccount> {
+    pub fn new(label: Vec<u8>, account: DistributionAccount, coefficient: Fixed) -> Self {
+        DistributionAccountEntry {
+            label,
+            account,
+            coefficient,
+        }
+    }
+}
+
+pub type DistributionAccountEntryOf<T> = DistributionAccountEntry<
+    DistributionAccount<
+        <T as frame_system::Config>::AccountId,
+        <T as technical::Config>::TechAccountId,
+    >,
+>;
+
+/// Distribution accounts layout used before `DistributionAccountsList`,
+/// it's only kept to migrate the storage.
 #[derive(Debug, Encode, Decode, Clone)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct DistributionAccounts<DistributionAccountData> {
@@ -117,29 +170,6 @@ impl<AccountId, TechAccountId>
             &self.projects,
         ]
     }
-
-    pub fn xor_distribution_accounts_as_array(
-        &self,
-    ) -> [&DistributionAccount<AccountId, TechAccountId>; 5] {
-        [
-            &self.xor_allocation.account,
-            &self.sora_citizens.account,
-            &self.stores_and_shops.account,
-            &self.parliament_and_development.account,
-            &self.projects.account,
-        ]
-    }
-
-    pub fn accounts(&self) -> [&DistributionAccount<AccountId, TechAccountId>; 6] {
-        [
-            &self.xor_allocation.account,
-            &self.sora_citizens.account,
-            &self.stores_and_shops.account,
-            &self.parliament_and_development.account,
-            &self.projects.account,
-            &self.val_holders.account,
-        ]
-    }
 }
 
 impl<DistributionAccountData: Default> Default for DistributionAccounts<DistributionAccountData> {
@@ -155,6 +185,15 @@ impl<DistributionAccountData: Default> Default for DistributionAccounts<Distribu
     }
 }
 
+/// Storage layout version, used to run the migrations only once.
+#[derive(Debug, Encode, Decode, Clone, Copy, PartialEq, Eq)]
+pub enum StorageVersion {
+    /// Distribution accounts are kept in the fixed `DistributionAccounts` struct.
+    V1,
+    /// Distribution accounts are kept in `DistributionAccountsList`.
+    V2,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SwapKind {
     Buy,
@@ -165,7 +204,7 @@ pub enum SwapKind {
 /// `in_asset_id` to reserves and mint `output_amount` of `out_asset_id`.
 ///
 /// If there's enough reserves in the pool, this function will also distribute some free amount
-/// to accounts specified in `DistributionAccounts` struct and buy-back and burn some amount
+/// to accounts specified in `DistributionAccountsList` and buy-back and burn some amount
 /// of VAL asset.
 ///
 /// Note: all fees are going to reserves.
@@ -263,25 +302,31 @@ impl<T: Config> BuyMainAsset<T> {
             let reserves_acc = &self.reserves_account_id;
             let in_asset = &self.in_asset_id;
             let out_asset = &self.out_asset_id;
-            let swapped_xor_amount = T::DEXApi::exchange(
-                reserves_acc,
-                reserves_acc,
-                &DEXId::Polkaswap.into(),
-                in_asset,
-                out_asset,
-                SwapAmount::with_desired_input(free_amount, Balance::zero()),
-            )?
-            .amount;
+            let swapped_xor_amount = match common::with_transaction(|| {
+                T::LiquidityProxy::exchange(
+                    reserves_acc,
+                    reserves_acc,
+                    in_asset,
+                    out_asset,
+                    SwapAmount::with_desired_input(free_amount, Balance::zero()),
+                    Pallet::<T>::buy_back_filter(),
+                )
+            }) {
+                Ok(outcome) => outcome.amount,
+                Err(_) => {
+                    // The free reserves stay in the pool and are distributed by the next buy.
+                    Pallet::<T>::deposit_event(Event::DistributionSkipped(*in_asset, free_amount));
+                    return Ok(());
+                }
+            };
             Technical::<T>::burn(out_asset, reserves_tech_acc, swapped_xor_amount)?;
             Technical::<T>::mint(out_asset, reserves_tech_acc, swapped_xor_amount)?;
 
-            let distribution_accounts: DistributionAccounts<
-                DistributionAccountData<DistributionAccount<T::AccountId, T::TechAccountId>>,
-            > = DistributionAccountsEntry::<T>::get();
-            for (account, coefficient) in distribution_accounts
-                .xor_distribution_as_array()
-                .iter()
-                .map(|x| (&x.account, x.coefficient))
+            for DistributionAccountEntry {
+                account,
+                coefficient,
+                ..
+            } in Pallet::<T>::distribution_accounts()
             {
                 let amount = FixedWrapper::from(swapped_xor_amount) * coefficient;
                 let amount = amount
@@ -291,29 +336,25 @@ impl<T: Config> BuyMainAsset<T> {
                     DistributionAccount::Account(account) => {
                         let reserves_acc =
                             Technical::<T>::tech_account_id_to_account_id(reserves_tech_acc)?;
-                        Assets::<T>::transfer_from(out_asset, &reserves_acc, account, amount)?;
+                        Assets::<T>::transfer_from(out_asset, &reserves_acc, &account, amount)?;
                     }
                     DistributionAccount::TechAccount(account) => {
-                        Technical::<T>::transfer(out_asset, reserves_tech_acc, account, amount)?;
+                        Technical::<T>::transfer(out_asset, reserves_tech_acc, &account, amount)?;
                     }
                 }
             }
 
-            let desired_amount_in = FixedWrapper::from(swapped_xor_amount)
-                * distribution_accounts.val_holders.coefficient;
+            let desired_amount_in =
+                FixedWrapper::from(swapped_xor_amount) * Pallet::<T>::val_holders_coefficient();
             let desired_amount_in = desired_amount_in
                 .try_into_balance()
                 .map_err(|_| Error::<T>::CalculatePriceFailed)?;
-            let val_amount = T::DEXApi::exchange(
-                reserves_acc,
-                reserves_acc,
-                &DEXId::Polkaswap.into(),
-                out_asset,
-                &VAL.into(),
-                SwapAmount::with_desired_input(desired_amount_in, Balance::zero()),
-            )?
-            .amount;
-            Technical::<T>::burn(&VAL.into(), reserves_tech_acc, val_amount)?;
+            if desired_amount_in != Balance::zero()
+                && Pallet::<T>::buy_back_and_burn(out_asset, desired_amount_in).is_err()
+            {
+                // The main asset stays in the reserves until the buy-back succeeds.
+                Pallet::<T>::queue_buy_back(out_asset, desired_amount_in);
+            }
             Ok(())
         })
     }
@@ -345,25 +386,32 @@ impl<T: Config> BuyMainAsset<T> {
 
 #[allow(non_snake_case)]
 impl<T: Config> Pallet<T> {
-    /// Calculates and returns the current buy price for one main asset.
+    /// Returns the bonding curve selected for the pool of the given main asset.
     ///
-    /// For every `PC_S` assets the price goes up by `PC_R`.
+    /// Pools without a selected curve use the linear curve with parameters from
+    /// `InitialPrice`, `PriceChangeStep` and `PriceChangeRate`.
+    pub fn curve(main_asset_id: &T::AssetId) -> CurveShape {
+        Curves::<T>::get(main_asset_id).unwrap_or_else(|| {
+            CurveShape::Linear(LinearCurve {
+                initial_price: Self::initial_price(),
+                price_change_step: Self::price_change_step(),
+                price_change_rate: Self::price_change_rate(),
+            })
+        })
+    }
+
+    /// Calculates and returns the current buy price for one main asset.
     ///
-    /// `P_BM1(Q) = Q / (PC_S * PC_R) + P_I`
+    /// `P_BM1(Q) = P_B(Q)`
     ///
     /// where
     /// `P_BM1(Q)`: buy price for one asset
-    /// `P_I`: initial asset price
-    /// `PC_R`: price change rate
-    /// `PC_S`: price change step
+    /// `P_B(Q)`: buy price of the pool curve (see `curve`)
     /// `Q`: asset issuance (quantity)
     pub fn buy_price_for_one_main_asset(out_asset_id: &T::AssetId) -> Result<Fixed, DispatchError> {
         let total_issuance = Assets::<T>::total_issuance(out_asset_id)?;
         let Q = FixedWrapper::from(total_issuance);
-        let P_I = Self::initial_price();
-        let PC_S = Self::price_change_step();
-        let PC_R: FixedWrapper = Self::price_change_rate().into();
-        let price = Q / (PC_S * PC_R) + P_I;
+        let price = Self::curve(out_asset_id).buy_price(Q);
         price
             .get()
             .map_err(|_| Error::<T>::CalculatePriceFailed.into())
@@ -376,9 +424,6 @@ impl<T: Config> Pallet<T> {
     ///
     /// ```nocompile
     /// P_M(Q, Q') = ∫ [P_B(x) dx, x = Q to Q']
-    ///            = x² / (2 * PC_S * PC_R) + P_I * x, x = Q to Q'
-    ///            = (Q' / (2 * PC_S * PC_R) + P_I) * Q' -
-    ///              (Q  / (2 * PC_S * PC_R) + P_I) * Q;
     ///
     /// P_BM(Q, q) = P_M(Q, Q+q);
     /// P_SM(Q, q) = P_M(Q-q, Q) * P_Sc
@@ -386,15 +431,10 @@ impl<T: Config> Pallet<T> {
     /// where
     /// `Q`: current asset issuance (quantity)
     /// `Q'`: new asset issuance (quantity)
-    /// `P_I`: initial asset price
-    /// `PC_R`: price change rate
-    /// `PC_S`: price change step
     /// `P_Sc: sell price coefficient (%)`
     /// `P_M(Q, Q')`: helper function to calculate price for `q` assets, where `q = |Q' - Q|`
     /// `P_BM(Q, q)`: price for `q` assets to buy
     /// `P_SM(Q, q)`: price for `q` assets to sell
-    ///
-    /// [Formula calculation](https://www.wolframalpha.com/input/?i=p+%3D+q+%2F+(s+*+r)+%2B+i+integrate+for+q&assumption="i"+->+"Variable")
     pub fn price_for_main_asset(
         main_asset_id: &T::AssetId,
         quantity: Balance,
@@ -402,21 +442,13 @@ impl<T: Config> Pallet<T> {
     ) -> Result<Fixed, DispatchError> {
         let total_issuance = Assets::<T>::total_issuance(&main_asset_id)?;
         let Q: FixedWrapper = total_issuance.into();
-        let P_I = Self::initial_price();
-        let PC_S: FixedWrapper = Self::price_change_step().into();
-        let PC_R = Self::price_change_rate();
-        let Q_prime = if kind == SwapKind::Buy {
-            Q.clone() + quantity
-        } else {
-            Q.clone() - quantity
-        };
-        let two_times_PC_S_times_PC_R = balance!(2) * PC_S * PC_R;
-        let to = (Q_prime.clone() / two_times_PC_S_times_PC_R.clone() + P_I) * Q_prime;
-        let from = (Q.clone() / two_times_PC_S_times_PC_R + P_I) * Q;
+        let curve = Self::curve(main_asset_id);
         let price: FixedWrapper = if kind == SwapKind::Buy {
-            to - from
+            let Q_prime = Q.clone() + quantity;
+            curve.integral(Q, Q_prime)
         } else {
-            Self::sell_price_coefficient() * (from - to)
+            let Q_prime = Q.clone() - quantity;
+            Self::sell_price_coefficient() * curve.integral(Q_prime, Q)
         };
         price
             .get()
@@ -425,31 +457,9 @@ impl<T: Config> Pallet<T> {
 
     /// Calculates and returns the current buy/sell price for target asset.
     ///
-    ///
-    /// Using derived formula for `price_for_base_asset`
-    /// ```nocompile
-    /// P_M(Q, Q')  = | (Q' / (2 * PC_S * PC_R) + P_I) * Q' -
-    ///                 (Q  / (2 * PC_S * PC_R) + P_I) * Q |
-    ///
-    /// q_BM = √(Q² + 2 * Q * PC_S * PC_R * P_I + PC_S * PC_R *(PC_S * PC_R * P_I²
-    ///         + 2 * P_TB(Q, Q'))) - Q - PC_S * PC_R * P_I
-    ///
-    /// q_SM = Q + PC_S * PC_R * P_I - (PC_S * PC_R * √(((Q * P_Sc) / (PC_S * PC_R)
-    ///          + P_I * P_Sc)² - (2 * P_Sc * P_M(Q, Q')) / (PC_S * PC_R))) / P_Sc
-    /// ```
-    /// where
-    /// `Q`: current token issuance (quantity)
-    /// `Q'`: new token issuance (quantity)
-    /// `P_I`: initial asset price
-    /// `PC_R`: price change rate
-    /// `PC_S`: price change step
-    /// `P_M(Q, Q')`: helper function to calculate price for `q` assets, where `q = |Q' - Q|`
-    /// `P_Sc: sell price coefficient (%)`
-    /// `q_BM`: price for `q` assets to be bought, when P_M(Q, Q') tokens are spend
-    /// `q_SM`: price for `q` assets to be sold, when P_M(Q, Q') tokens are spend
-    ///
-    /// [Wolfram Alpha (buy)](https://www.wolframalpha.com/input/?i=y+%3D+%28%28a%2Bx%29+%2F+%282+*+b+*+c%29+%2B+d%29+*+%28a%2Bx%29+-+%28+a+%2F+%282+*+b+*+c%29+%2B+d%29+*+a+solve+for+x)
-    /// [Wolfram Alpha (sell)](https://www.wolframalpha.com/input/?i=y+%3D+%28%28a++%2F+%282+*+b+*+c%29+%2B+d%29+*+a+-+%28%28a+-+x%29+%2F+%282+*+b+*+c%29+%2B+d%29+*+%28a+-+x%29%29+*+k+solve+for+x)
+    /// It's the quantity `q` of main asset for which `P_BM(Q, q)` or `P_SM(Q, q)`
+    /// (see `price_for_main_asset`) is equal to the given amount of target asset,
+    /// the pool curve provides its closed-form inverse or falls back to a numerical one.
     pub fn price_for_collateral_asset(
         main_asset_id: &T::AssetId,
         quantity: Balance,
@@ -457,31 +467,14 @@ impl<T: Config> Pallet<T> {
     ) -> Result<Fixed, DispatchError> {
         let total_issuance = Assets::<T>::total_issuance(&main_asset_id)?;
         let Q = FixedWrapper::from(total_issuance);
-        let P_I = FixedWrapper::from(Self::initial_price());
-        let PC_S = FixedWrapper::from(Self::price_change_step());
-        let PC_R = FixedWrapper::from(Self::price_change_rate());
         let OUT_PRICE = FixedWrapper::from(quantity);
-
-        let PC_S_times_PC_R = PC_S.clone() * PC_R.clone();
-        let PC_S_times_PC_R_times_P_I = PC_S_times_PC_R.clone() * P_I.clone();
-        let PC_S_times_PC_R_times_P_I_squared = PC_S_times_PC_R_times_P_I.clone() * P_I.clone();
-
-        let price: FixedWrapper = if kind == SwapKind::Buy {
-            let Q_squared = Q.clone() * Q.clone();
-            let inner_term_a = balance!(2) * Q.clone() * PC_S_times_PC_R_times_P_I.clone();
-            let inner_term_b =
-                PC_S * PC_R * (PC_S_times_PC_R_times_P_I_squared + balance!(2) * OUT_PRICE);
-            let under_sqrt = Q_squared + inner_term_a + inner_term_b;
-            under_sqrt.sqrt_accurate() - Q - PC_S_times_PC_R_times_P_I
+        let sell_price_coefficient = if kind == SwapKind::Buy {
+            None
         } else {
-            let P_Sc = FixedWrapper::from(Self::sell_price_coefficient());
-            let inner_term_a =
-                ((Q.clone() * P_Sc.clone()) / PC_S_times_PC_R.clone()) + (P_I * P_Sc.clone());
-            let inner_term_b = (balance!(2) * P_Sc.clone() * OUT_PRICE) / PC_S_times_PC_R.clone();
-            let under_sqrt = inner_term_a.clone() * inner_term_a - inner_term_b;
-            (Q + PC_S_times_PC_R_times_P_I)
-                - ((PC_S_times_PC_R * under_sqrt.sqrt_accurate()) / P_Sc)
+            Some(FixedWrapper::from(Self::sell_price_coefficient()))
         };
+        let price =
+            Self::curve(main_asset_id).inverse_integral(Q, OUT_PRICE, sell_price_coefficient);
         price
             .get()
             .map_err(|_| Error::<T>::CalculatePriceFailed.into())
@@ -494,9 +487,10 @@ impl<T: Config> Pallet<T> {
     /// where
     /// `P_Sc: sell price coefficient (%)`
     pub fn sell_price_for_one_main_asset(in_asset_id: &T::AssetId) -> Result<Fixed, DispatchError> {
-        let P_B = Self::buy_price_for_one_main_asset(in_asset_id)?;
+        let total_issuance = Assets::<T>::total_issuance(in_asset_id)?;
+        let Q = FixedWrapper::from(total_issuance);
         let P_Sc = FixedWrapper::from(Self::sell_price_coefficient());
-        let price = P_Sc * P_B;
+        let price = Self::curve(in_asset_id).sell_price(Q, P_Sc);
         price
             .get()
             .map_err(|_| Error::<T>::CalculatePriceFailed.into())
@@ -651,12 +645,109 @@ impl<T: Config> Pallet<T> {
         })
     }
 
-    pub fn set_distribution_accounts(
-        distribution_accounts: DistributionAccounts<
-            DistributionAccountData<DistributionAccount<T::AccountId, T::TechAccountId>>,
-        >,
-    ) {
-        DistributionAccountsEntry::<T>::set(distribution_accounts);
+    /// Checks that labels of the distribution accounts are unique and that the coefficients
+    /// together with `val_holders_coefficient` add up to one.
+    pub fn ensure_distribution_accounts_valid(
+        distribution_accounts: &[DistributionAccountEntryOf<T>],
+        val_holders_coefficient: Fixed,
+    ) -> DispatchResult {
+        ensure!(
+            distribution_accounts.len() <= T::MaxDistributionAccounts::get() as usize,
+            Error::<T>::TooManyDistributionAccounts
+        );
+        let mut labels = BTreeSet::new();
+        let mut coefficients_sum = FixedWrapper::from(val_holders_coefficient);
+        ensure!(
+            val_holders_coefficient >= fixed!(0),
+            Error::<T>::InvalidDistributionCoefficients
+        );
+        for entry in distribution_accounts {
+            ensure!(
+                !entry.label.is_empty()
+                    && entry.label.len() <= MAX_DISTRIBUTION_ACCOUNT_LABEL_LENGTH
+                    && labels.insert(&entry.label),
+                Error::<T>::InvalidDistributionAccountLabel
+            );
+            ensure!(
+                entry.coefficient >= fixed!(0),
+                Error::<T>::InvalidDistributionCoefficients
+            );
+            coefficients_sum = coefficients_sum + entry.coefficient;
+        }
+        ensure!(
+            coefficients_sum == fixed_wrapper!(1),
+            Error::<T>::InvalidDistributionCoefficients
+        );
+        Ok(())
+    }
+
+    /// Exchanges `main_asset_amount` of the main asset held in the reserves for VAL
+    /// using the buy-back filter and burns the VAL bought.
+    ///
+    /// Returns the amount of VAL burned, nothing is changed if the exchange fails.
+    pub fn buy_back_and_burn(
+        main_asset_id: &T::AssetId,
+        main_asset_amount: Balance,
+    ) -> Result<Balance, DispatchError> {
+        common::with_transaction(|| {
+            let reserves_tech_account_id = Self::reserves_account_id();
+            let reserves_account_id =
+                Technical::<T>::tech_account_id_to_account_id(&reserves_tech_account_id)?;
+            let val_asset_id = VAL.into();
+            let val_amount = T::LiquidityProxy::exchange(
+                &reserves_account_id,
+                &reserves_account_id,
+                main_asset_id,
+                &val_asset_id,
+                SwapAmount::with_desired_input(main_asset_amount, Balance::zero()),
+                Self::buy_back_filter(),
+            )?
+            .amount;
+            ensure!(
+                val_amount != Balance::zero(),
+                Error::<T>::CalculatePriceFailed
+            );
+            Technical::<T>::burn(&val_asset_id, &reserves_tech_account_id, val_amount)?;
+            let price = (FixedWrapper::from(main_asset_amount) / FixedWrapper::from(val_amount))
+                .get()
+                .map_err(|_| Error::<T>::CalculatePriceFailed)?;
+            Self::deposit_event(Event::BuyBackBurned(
+                *main_asset_id,
+                main_asset_amount,
+                val_amount,
+                price,
+            ));
+            Ok(val_amount)
+        })
+    }
+
+    /// Adds `main_asset_amount` to the buy-back retried in `on_initialize`.
+    fn queue_buy_back(main_asset_id: &T::AssetId, main_asset_amount: Balance) {
+        PendingBuyBacks::<T>::mutate(main_asset_id, |amount| {
+            *amount = amount.saturating_add(main_asset_amount)
+        });
+        Self::deposit_event(Event::BuyBackQueued(*main_asset_id, main_asset_amount));
+    }
+
+    /// Retries up to `limit` queued buy-backs, the failed ones stay in the queue.
+    ///
+    /// Returns the weight of the retries, it's known before any buy-back is performed.
+    /// Each retry routes the exchange for VAL through the liquidity proxy.
+    fn retry_buy_backs(limit: u32) -> Weight {
+        let pending_buy_backs = PendingBuyBacks::<T>::iter()
+            .take(limit as usize)
+            .collect::<Vec<_>>();
+        let retries = pending_buy_backs.len() as u32;
+        let weight = <T as Config>::WeightInfo::retry_buy_backs(retries).saturating_add(
+            T::LiquidityProxy::exchange_weight(SwapVariant::WithDesiredInput)
+                .saturating_mul(retries as Weight),
+        );
+        for (main_asset_id, main_asset_amount) in pending_buy_backs {
+            if Self::buy_back_and_burn(&main_asset_id, main_asset_amount).is_ok() {
+                PendingBuyBacks::<T>::remove(&main_asset_id);
+            }
+        }
+        weight
     }
 }
 
@@ -695,6 +786,10 @@ impl<T: Config> LiquiditySource<T::DEXId, T::AccountId, T::AssetId, Balance, Dis
                         Self::price_for_main_asset(input_asset_id, base_amount_in, SwapKind::Sell)?;
                     let fee = FixedWrapper::from(amount) * Self::fee();
                     let fee = fee.get().map_err(|_| Error::<T>::CalculatePriceFailed)?;
+                    // Fee is deducted from the output the same way it's done by `exchange`.
+                    let amount = (FixedWrapper::from(amount) - fee)
+                        .get()
+                        .map_err(|_| Error::<T>::CalculatePriceFailed)?;
                     SwapOutcome::new(amount, fee)
                 }
                 SwapAmount::WithDesiredOutput {
@@ -718,12 +813,15 @@ impl<T: Config> LiquiditySource<T::DEXId, T::AccountId, T::AssetId, Balance, Dis
                     ..
                 } => {
                     let amount = Self::price_for_collateral_asset(
-                        input_asset_id,
+                        output_asset_id,
                         target_amount_in,
                         SwapKind::Buy,
                     )?;
                     let fee = FixedWrapper::from(amount) * Self::fee();
                     let fee = fee.get().map_err(|_| Error::<T>::CalculatePriceFailed)?;
+                    let amount = (FixedWrapper::from(amount) - fee)
+                        .get()
+                        .map_err(|_| Error::<T>::CalculatePriceFailed)?;
                     SwapOutcome::new(amount, fee)
                 }
                 SwapAmount::WithDesiredOutput {
@@ -785,11 +883,47 @@ impl<T: Config> LiquiditySource<T::DEXId, T::AccountId, T::AssetId, Balance, Dis
         }
     }
 }
+impl<T: Config> GetSwapCurve<T::DEXId, T::AssetId> for Pallet<T> {
+    /// Only buying the main asset along the linear curve has a closed form,
+    /// the fee is taken from the bought amount the same way `quote` does for the desired input.
+    fn swap_curve(
+        dex_id: &T::DEXId,
+        input_asset_id: &T::AssetId,
+        output_asset_id: &T::AssetId,
+    ) -> Option<SwapCurve> {
+        if output_asset_id != &T::GetBaseAssetId::get()
+            || !Self::can_exchange(dex_id, input_asset_id, output_asset_id)
+        {
+            return None;
+        }
+        let curve = match Self::curve(output_asset_id) {
+            CurveShape::Linear(curve) => curve,
+            _ => return None,
+        };
+        let price = Self::buy_price_for_one_main_asset(output_asset_id).ok()?;
+        let slope = fixed_wrapper!(1)
+            / (FixedWrapper::from(curve.price_change_step) * curve.price_change_rate);
+        let output_share = fixed_wrapper!(1) - Self::fee();
+        Some(SwapCurve::Linear {
+            price: (FixedWrapper::from(price) / output_share.clone())
+                .get()
+                .ok()?,
+            slope: (slope / (output_share.clone() * output_share)).get().ok()?,
+        })
+    }
+}
+
+impl<T: Config> TaggedLiquiditySource<T::DEXId, T::AccountId, T::AssetId> for Pallet<T> {
+    const SOURCE_TYPE: LiquiditySourceType = LiquiditySourceType::BondingCurvePool;
+}
+
 pub use pallet::*;
 
 #[frame_support::pallet]
 pub mod pallet {
     use super::*;
+    use assets::AssetIdOf;
+    use common::FilterMode;
     use frame_support::pallet_prelude::*;
     use frame_system::pallet_prelude::*;
 
@@ -797,13 +931,14 @@ pub mod pallet {
     pub trait Config:
         frame_system::Config + common::Config + assets::Config + technical::Config
     {
-        type DEXApi: LiquiditySource<
-            Self::DEXId,
-            Self::AccountId,
-            Self::AssetId,
-            Balance,
-            DispatchError,
-        >;
+        type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+        type LiquidityProxy: LiquidityProxyTrait<Self::DEXId, Self::AccountId, Self::AssetId>;
+        /// Maximum number of accounts the free reserves are distributed to.
+        type MaxDistributionAccounts: Get<u32>;
+        /// Maximum number of queued buy-backs retried in one block.
+        type MaxBuyBackRetriesPerBlock: Get<u32>;
+        /// Weight information for extrinsics in this pallet.
+        type WeightInfo: WeightInfo;
     }
 
     #[pallet::pallet]
@@ -811,10 +946,134 @@ pub mod pallet {
     pub struct Pallet<T>(PhantomData<T>);
 
     #[pallet::hooks]
-    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(_block_number: T::BlockNumber) -> Weight {
+            Self::retry_buy_backs(T::MaxBuyBackRetriesPerBlock::get())
+        }
+
+        fn on_runtime_upgrade() -> Weight {
+            migrations::migrate_distribution_accounts::<T>()
+        }
+    }
 
     #[pallet::call]
-    impl<T: Config> Pallet<T> {}
+    impl<T: Config> Pallet<T> {
+        /// Replace the list of accounts the free reserves are distributed to.
+        ///
+        /// - `distribution_accounts`: beneficiaries with their shares of the free reserves.
+        /// - `val_holders_coefficient`: share of the free reserves used to buy back and burn VAL.
+        ///
+        /// All the coefficients must add up to one.
+        #[pallet::weight(<T as Config>::WeightInfo::set_distribution_accounts(distribution_accounts.len() as u32))]
+        pub fn set_distribution_accounts(
+            origin: OriginFor<T>,
+            distribution_accounts: Vec<DistributionAccountEntryOf<T>>,
+            val_holders_coefficient: Fixed,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            Self::ensure_distribution_accounts_valid(
+                &distribution_accounts,
+                val_holders_coefficient,
+            )?;
+            DistributionAccountsList::<T>::put(&distribution_accounts);
+            ValHoldersCoefficient::<T>::put(val_holders_coefficient);
+            Self::deposit_event(Event::DistributionAccountsSet(
+                distribution_accounts,
+                val_holders_coefficient,
+            ));
+            Ok(().into())
+        }
+
+        /// Change the account of the distribution list entry with the given `label`
+        /// keeping its coefficient.
+        #[pallet::weight(<T as Config>::WeightInfo::update_distribution_account())]
+        pub fn update_distribution_account(
+            origin: OriginFor<T>,
+            label: Vec<u8>,
+            account: DistributionAccount<T::AccountId, T::TechAccountId>,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            DistributionAccountsList::<T>::try_mutate(|distribution_accounts| {
+                let entry = distribution_accounts
+                    .iter_mut()
+                    .find(|entry| entry.label == label)
+                    .ok_or(Error::<T>::UnknownDistributionAccount)?;
+                entry.account = account.clone();
+                Ok::<_, Error<T>>(())
+            })?;
+            Self::deposit_event(Event::DistributionAccountUpdated(label, account));
+            Ok(().into())
+        }
+
+        /// Set liquidity sources used to exchange the free reserves and to buy back VAL.
+        ///
+        /// - `dex_id`: DEX the exchanges are performed on,
+        /// - `selected_source_types`: list of selected LiquiditySource types, selection effect is determined by filter_mode,
+        /// - `filter_mode`: indicate either to allow or forbid selected types only, or disable filtering.
+        ///
+        /// The filter must exclude the bonding curve pool itself.
+        #[pallet::weight(<T as Config>::WeightInfo::set_buy_back_filter())]
+        pub fn set_buy_back_filter(
+            origin: OriginFor<T>,
+            dex_id: T::DEXId,
+            selected_source_types: Vec<LiquiditySourceType>,
+            filter_mode: FilterMode,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            let filter =
+                LiquiditySourceFilter::with_mode(dex_id, filter_mode, selected_source_types);
+            ensure!(
+                !filter.matches_index(LiquiditySourceType::BondingCurvePool),
+                Error::<T>::InvalidBuyBackFilter
+            );
+            BuyBackFilter::<T>::put(&filter);
+            Self::deposit_event(Event::BuyBackFilterSet(filter));
+            Ok(().into())
+        }
+
+        /// Select the bonding curve for the pool of the given main asset.
+        ///
+        /// - `main_asset_id`: main asset of the pool,
+        /// - `curve`: curve shape with its parameters, the price must be positive and grow
+        ///   with the issuance.
+        #[pallet::weight(<T as Config>::WeightInfo::set_curve())]
+        pub fn set_curve(
+            origin: OriginFor<T>,
+            main_asset_id: T::AssetId,
+            curve: CurveShape,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            ensure!(curve.is_valid(), Error::<T>::InvalidCurveParameters);
+            Curves::<T>::insert(main_asset_id, curve.clone());
+            Self::deposit_event(Event::CurveSet(main_asset_id, curve));
+            Ok(().into())
+        }
+    }
+
+    #[pallet::event]
+    #[pallet::metadata(AssetIdOf<T> = "AssetId")]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// VAL has been bought back with the main asset from the reserves and burned.
+        /// [Main Asset Id, Main Asset Amount, VAL Burned, VAL Price in Main Asset]
+        BuyBackBurned(AssetIdOf<T>, Balance, Balance, Fixed),
+        /// Buy-back has failed and is queued for retry.
+        /// [Main Asset Id, Main Asset Amount]
+        BuyBackQueued(AssetIdOf<T>, Balance),
+        /// Free reserves couldn't be exchanged for the main asset, the distribution is skipped.
+        /// [Collateral Asset Id, Free Reserves Amount]
+        DistributionSkipped(AssetIdOf<T>, Balance),
+        /// Bonding curve has been selected for the pool. [Main Asset Id, Curve]
+        CurveSet(AssetIdOf<T>, CurveShape),
+        /// List of the distribution accounts has been replaced.
+        /// [Distribution Accounts, VAL Holders Coefficient]
+        DistributionAccountsSet(Vec<DistributionAccountEntryOf<T>>, Fixed),
+        /// Account of the distribution list entry has been changed. [Label, Account]
+        DistributionAccountUpdated(Vec<u8>, DistributionAccount<T::AccountId, T::TechAccountId>),
+        /// Liquidity sources used to exchange the free reserves and to buy back VAL have been
+        /// changed. [Filter]
+        BuyBackFilterSet(LiquiditySourceFilter<T::DEXId, LiquiditySourceType>),
+    }
 
     #[pallet::error]
     pub enum Error<T> {
@@ -826,6 +1085,18 @@ pub mod pallet {
         NotEnoughReserves,
         /// Liquidity source can't exchange assets with the given IDs on the given DEXId.
         CantExchange,
+        /// Distribution accounts number exceeds `MaxDistributionAccounts`.
+        TooManyDistributionAccounts,
+        /// Distribution account label is empty, too long or not unique.
+        InvalidDistributionAccountLabel,
+        /// Distribution coefficients are negative or don't add up to one.
+        InvalidDistributionCoefficients,
+        /// Distribution account with the given label doesn't exist.
+        UnknownDistributionAccount,
+        /// Buy-back filter allows to exchange on the bonding curve pool itself.
+        InvalidBuyBackFilter,
+        /// Curve price is not positive or doesn't grow with the issuance.
+        InvalidCurveParameters,
     }
 
     #[pallet::storage]
@@ -881,22 +1152,78 @@ pub mod pallet {
     pub(super) type SellPriceCoefficient<T: Config> =
         StorageValue<_, Fixed, ValueQuery, DefaultForSellPriceCoefficient>;
 
+    /// Bonding curves selected for pools, keyed by the main asset.
+    #[pallet::storage]
+    pub(super) type Curves<T: Config> =
+        StorageMap<_, Twox64Concat, T::AssetId, CurveShape, OptionQuery>;
+
+    /// Accounts the free reserves are distributed to.
+    ///
+    /// Every write goes through `ensure_distribution_accounts_valid`, so the list holds at most
+    /// `MaxDistributionAccounts` entries with labels up to `MAX_DISTRIBUTION_ACCOUNT_LABEL_LENGTH`.
     #[pallet::storage]
     #[pallet::getter(fn distribution_accounts)]
+    pub(super) type DistributionAccountsList<T: Config> =
+        StorageValue<_, Vec<DistributionAccountEntryOf<T>>, ValueQuery>;
+
+    /// Share of the free reserves used to buy back and burn VAL.
+    #[pallet::storage]
+    #[pallet::getter(fn val_holders_coefficient)]
+    pub(super) type ValHoldersCoefficient<T: Config> = StorageValue<_, Fixed, ValueQuery>;
+
+    #[pallet::type_value]
+    pub(super) fn DefaultForBuyBackFilter<T: Config>(
+    ) -> LiquiditySourceFilter<T::DEXId, LiquiditySourceType> {
+        LiquiditySourceFilter::with_forbidden(
+            DEXId::Polkaswap.into(),
+            sp_std::vec![LiquiditySourceType::BondingCurvePool],
+        )
+    }
+
+    /// Liquidity sources used to exchange the free reserves and to buy back VAL.
+    #[pallet::storage]
+    #[pallet::getter(fn buy_back_filter)]
+    pub(super) type BuyBackFilter<T: Config> = StorageValue<
+        _,
+        LiquiditySourceFilter<T::DEXId, LiquiditySourceType>,
+        ValueQuery,
+        DefaultForBuyBackFilter<T>,
+    >;
+
+    /// Amounts of the main asset held in the reserves waiting for the VAL buy-back to be retried.
+    #[pallet::storage]
+    #[pallet::getter(fn pending_buy_back)]
+    pub(super) type PendingBuyBacks<T: Config> =
+        StorageMap<_, Twox64Concat, T::AssetId, Balance, ValueQuery>;
+
+    /// Distribution accounts in the layout used before `DistributionAccountsList`,
+    /// it's emptied by `migrations::migrate_distribution_accounts`.
+    #[pallet::storage]
     pub(super) type DistributionAccountsEntry<T: Config> = StorageValue<
         _,
         DistributionAccounts<
             DistributionAccountData<DistributionAccount<T::AccountId, T::TechAccountId>>,
         >,
-        ValueQuery,
+        OptionQuery,
     >;
 
+    #[pallet::type_value]
+    pub(super) fn DefaultForPalletStorageVersion() -> StorageVersion {
+        StorageVersion::V1
+    }
+
+    /// Storage layout version, chains started before the versioning have `V1`.
+    #[pallet::storage]
+    #[pallet::getter(fn pallet_storage_version)]
+    pub(super) type PalletStorageVersion<T: Config> =
+        StorageValue<_, StorageVersion, ValueQuery, DefaultForPalletStorageVersion>;
+
     #[pallet::genesis_config]
     pub struct GenesisConfig<T: Config> {
         pub reserves_account_id: T::TechAccountId,
-        pub distribution_accounts: DistributionAccounts<
-            DistributionAccountData<DistributionAccount<T::AccountId, T::TechAccountId>>,
-        >,
+        pub distribution_accounts: Vec<DistributionAccountEntryOf<T>>,
+        pub val_holders_coefficient: Fixed,
+        pub curves: Vec<(T::AssetId, CurveShape)>,
     }
 
     #[cfg(feature = "std")]
@@ -905,6 +1232,9 @@ pub mod pallet {
             Self {
                 reserves_account_id: Default::default(),
                 distribution_accounts: Default::default(),
+                // Coefficients must add up to one, so by default the VAL buy-back gets it all.
+                val_holders_coefficient: fixed!(1),
+                curves: Default::default(),
             }
         }
     }
@@ -913,7 +1243,18 @@ pub mod pallet {
     impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
         fn build(&self) {
             ReservesAcc::<T>::put(&self.reserves_account_id);
-            DistributionAccountsEntry::<T>::put(&self.distribution_accounts);
+            Pallet::<T>::ensure_distribution_accounts_valid(
+                &self.distribution_accounts,
+                self.val_holders_coefficient,
+            )
+            .expect("invalid distribution accounts");
+            DistributionAccountsList::<T>::put(&self.distribution_accounts);
+            ValHoldersCoefficient::<T>::put(&self.val_holders_coefficient);
+            for (main_asset_id, curve) in &self.curves {
+                assert!(curve.is_valid(), "invalid bonding curve parameters");
+                Curves::<T>::insert(main_asset_id, curve);
+            }
+            PalletStorageVersion::<T>::put(StorageVersion::V2);
         }
     }
 }
@@ -0,0 +1,77 @@
+// This file is part of the SORA network and Polkaswap app.
+
+// Copyright (c) 2020, 2021, Polka Biome Ltd. All rights reserved.
+// SPDX-License-Identifier: BSD-4-Clause
+
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+
+// Redistributions of source code must retain the above copyright notice, this list
+// of conditions and the following disclaimer.
+// Redistributions in binary form must reproduce the above copyright notice, this
+// list of conditions and the following disclaimer in the documentation and/or other
+// materials provided with the distribution.
+//
+// All advertising materials mentioning features or use of this software must display
+// the following acknowledgement: This product includes software developed by Polka Biome
+// Ltd., SORA, and Polkaswap.
+//
+// Neither the name of the Polka Biome Ltd. nor the names of its contributors may be used
+// to endorse or promote products derived from this software without specific prior written permission.
+
+// THIS SOFTWARE IS PROVIDED BY Polka Biome Ltd. AS IS AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL Polka Biome Ltd. BE LIABLE FOR ANY
+// DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS;
+// OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT,
+// STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+use crate::{
+    Config, DistributionAccountEntry, DistributionAccountsEntry, DistributionAccountsList,
+    PalletStorageVersion, StorageVersion, ValHoldersCoefficient,
+};
+use frame_support::traits::Get;
+use frame_support::weights::Weight;
+use sp_std::vec::Vec;
+
+/// Labels given to the fields of the fixed `DistributionAccounts` struct, in the order of
+/// `DistributionAccounts::xor_distribution_as_array`.
+pub const LEGACY_DISTRIBUTION_ACCOUNT_LABELS: [&[u8]; 5] = [
+    b"xor_allocation",
+    b"sora_citizens",
+    b"stores_and_shops",
+    b"parliament_and_development",
+    b"projects",
+];
+
+/// Moves the fixed `DistributionAccounts` struct into `DistributionAccountsList`, the share
+/// of `val_holders` goes to `ValHoldersCoefficient`. Runs only for `StorageVersion::V1`.
+pub fn migrate_distribution_accounts<T: Config>() -> Weight {
+    if PalletStorageVersion::<T>::get() != StorageVersion::V1 {
+        return T::DbWeight::get().reads(1);
+    }
+    PalletStorageVersion::<T>::put(StorageVersion::V2);
+    let weight = T::DbWeight::get().reads_writes(1, 1);
+    let migration_weight = match DistributionAccountsEntry::<T>::take() {
+        Some(distribution_accounts) => {
+            let entries: Vec<_> = distribution_accounts
+                .xor_distribution_as_array()
+                .iter()
+                .zip(LEGACY_DISTRIBUTION_ACCOUNT_LABELS.iter())
+                .map(|(data, label)| {
+                    DistributionAccountEntry::new(
+                        label.to_vec(),
+                        data.account.clone(),
+                        data.coefficient,
+                    )
+                })
+                .collect();
+            DistributionAccountsList::<T>::put(entries);
+            ValHoldersCoefficient::<T>::put(distribution_accounts.val_holders.coefficient);
+            T::DbWeight::get().reads_writes(1, 3)
+        }
+        None => T::DbWeight::get().reads(1),
+    };
+    weight.saturating_add(migration_weight)
+}
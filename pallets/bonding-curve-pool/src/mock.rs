@@ -30,16 +30,18 @@
 
 use crate::{self as bonding_curve_pool, Config};
 use common::mock::ExistentialDeposits;
-use common::prelude::{Balance, FixedWrapper, SwapAmount, SwapOutcome};
+use common::prelude::{Balance, FixedWrapper, SwapAmount, SwapOutcome, SwapVariant};
+use common::weights::constants::EXTRINSIC_FIXED_WEIGHT;
 use common::{
-    self, balance, Amount, AssetId32, AssetName, AssetSymbol, LiquiditySource, TechPurpose, USDT,
-    VAL, XOR,
+    self, balance, Amount, AssetId32, AssetName, AssetSymbol, BasisPoints, LiquiditySourceFilter,
+    LiquiditySourceType, TechPurpose, USDT, VAL, XOR,
 };
 use currencies::BasicCurrencyAdapter;
 use frame_support::traits::GenesisBuild;
 use frame_support::weights::Weight;
 use frame_support::{construct_runtime, parameter_types};
 use frame_system;
+use liquidity_proxy::LiquidityProxyTrait;
 use orml_traits::MultiCurrency;
 use sp_core::crypto::AccountId32;
 use sp_core::H256;
@@ -73,6 +75,7 @@ parameter_types! {
     pub const CreationFee: u128 = 0;
     pub const TransactionByteFee: u128 = 1;
     pub const GetBaseAssetId: AssetId = XOR;
+    pub const MaxDexFee: BasisPoints = 100;
 }
 
 construct_runtime! {
@@ -82,10 +85,10 @@ construct_runtime! {
         UncheckedExtrinsic = UncheckedExtrinsic,
     {
         System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
-        DexManager: dex_manager::{Pallet, Call, Config<T>, Storage},
+        DexManager: dex_manager::{Pallet, Call, Config<T>, Storage, Event<T>},
         TradingPair: trading_pair::{Pallet, Call, Config<T>, Storage, Event<T>},
         MockLiquiditySource: mock_liquidity_source::<Instance1>::{Pallet, Call, Config<T>, Storage},
-        BondingCurvePool: bonding_curve_pool::{Pallet, Call, Config<T>, Storage},
+        BondingCurvePool: bonding_curve_pool::{Pallet, Call, Config<T>, Storage, Event<T>},
         Tokens: tokens::{Pallet, Call, Config<T>, Storage, Event<T>},
         Currencies: currencies::{Pallet, Call, Storage, Event<T>},
         Assets: assets::{Pallet, Call, Config<T>, Storage, Event<T>},
@@ -136,7 +139,11 @@ impl cumulus_pallet_parachain_system::Config for Runtime {
     type XcmpMessageHandlers = ();
 }
 
-impl dex_manager::Config for Runtime {}
+impl dex_manager::Config for Runtime {
+    type Event = Event;
+    type MaxFee = MaxDexFee;
+    type WeightInfo = ();
+}
 
 impl trading_pair::Config for Runtime {
     type Event = Event;
@@ -150,8 +157,17 @@ impl mock_liquidity_source::Config<mock_liquidity_source::Instance1> for Runtime
     type EnsureTradingPairExists = ();
 }
 
+parameter_types! {
+    pub const MaxDistributionAccounts: u32 = 8;
+    pub const MaxBuyBackRetriesPerBlock: u32 = 4;
+}
+
 impl Config for Runtime {
-    type DEXApi = MockDEXApi;
+    type Event = Event;
+    type LiquidityProxy = MockLiquidityProxy;
+    type MaxDistributionAccounts = MaxDistributionAccounts;
+    type MaxBuyBackRetriesPerBlock = MaxBuyBackRetriesPerBlock;
+    type WeightInfo = ();
 }
 
 impl tokens::Config for Runtime {
@@ -185,6 +201,7 @@ impl assets::Config for Runtime {
     type AssetId = AssetId;
     type GetBaseAssetId = GetBaseAssetId;
     type Currency = currencies::Module<Runtime>;
+    type IsAssetInUse = ();
     type WeightInfo = ();
 }
 
@@ -212,9 +229,9 @@ impl pallet_balances::Config for Runtime {
     type MaxLocks = ();
 }
 
-pub struct MockDEXApi;
+pub struct MockLiquidityProxy;
 
-impl MockDEXApi {
+impl MockLiquidityProxy {
     pub fn init() -> Result<(), DispatchError> {
         let mock_liquidity_source_tech_account_id =
             TechAccountId::Pure(DEXId::Polkaswap.into(), TechPurpose::FeeCollector);
@@ -227,47 +244,66 @@ impl MockDEXApi {
         Currencies::deposit(USDT, &account_id, balance!(1000000))?;
         Ok(())
     }
-}
 
-impl<DEXId> LiquiditySource<DEXId, AccountId, AssetId, Balance, DispatchError> for MockDEXApi {
-    fn can_exchange(
-        _target_id: &DEXId,
-        _input_asset_id: &AssetId,
-        _output_asset_id: &AssetId,
-    ) -> bool {
-        unimplemented!()
+    /// Exchanges only the desired input at the fixed prices with the 0.3% fee.
+    fn outcome(
+        input_asset_id: &AssetId,
+        output_asset_id: &AssetId,
+        swap_amount: SwapAmount<Balance>,
+        filter: &LiquiditySourceFilter<DEXId, LiquiditySourceType>,
+    ) -> Result<SwapOutcome<Balance>, DispatchError> {
+        if !filter.matches_index(LiquiditySourceType::MockPool) {
+            return Err(DispatchError::Other("Mock pool is filtered out."));
+        }
+        let prices: HashMap<_, _> = vec![((USDT, XOR), balance!(0.01)), ((XOR, VAL), balance!(2))]
+            .into_iter()
+            .collect();
+        let price = prices
+            .get(&(*input_asset_id, *output_asset_id))
+            .ok_or(DispatchError::Other("Unknown pair."))?;
+        match swap_amount {
+            SwapAmount::WithDesiredInput {
+                desired_amount_in, ..
+            } => {
+                let amount_out = FixedWrapper::from(desired_amount_in) * *price;
+                let fee = amount_out.clone() * balance!(0.003);
+                let amount_out = amount_out - fee.clone();
+                Ok(SwapOutcome::new(
+                    amount_out.into_balance(),
+                    fee.into_balance(),
+                ))
+            }
+            _ => Err(DispatchError::Other("Bad swap amount.")),
+        }
     }
+}
 
+impl LiquidityProxyTrait<DEXId, AccountId, AssetId> for MockLiquidityProxy {
     fn quote(
-        _target_id: &DEXId,
-        _input_asset_id: &AssetId,
-        _output_asset_id: &AssetId,
-        _swap_amount: SwapAmount<Balance>,
+        input_asset_id: &AssetId,
+        output_asset_id: &AssetId,
+        amount: SwapAmount<Balance>,
+        filter: LiquiditySourceFilter<DEXId, LiquiditySourceType>,
     ) -> Result<SwapOutcome<Balance>, DispatchError> {
-        unimplemented!()
+        Self::outcome(input_asset_id, output_asset_id, amount, &filter)
     }
 
     fn exchange(
         sender: &AccountId,
         receiver: &AccountId,
-        _target_id: &DEXId,
         input_asset_id: &AssetId,
         output_asset_id: &AssetId,
         swap_amount: SwapAmount<Balance>,
+        filter: LiquiditySourceFilter<DEXId, LiquiditySourceType>,
     ) -> Result<SwapOutcome<Balance>, DispatchError> {
-        let prices: HashMap<_, _> = vec![((USDT, XOR), balance!(0.01)), ((XOR, VAL), balance!(2))]
-            .into_iter()
-            .collect();
+        let SwapOutcome {
+            amount: amount_out,
+            fee,
+        } = Self::outcome(input_asset_id, output_asset_id, swap_amount, &filter)?;
         match swap_amount {
             SwapAmount::WithDesiredInput {
                 desired_amount_in, ..
             } => {
-                let amount_out = FixedWrapper::from(desired_amount_in)
-                    * prices[&(*input_asset_id, *output_asset_id)];
-                let fee = amount_out.clone() * balance!(0.003);
-                let amount_out = amount_out - fee.clone();
-                let amount_out = amount_out.into_balance();
-                let fee = fee.into_balance();
                 let reserves_account_id =
                     &Technical::tech_account_id_to_account_id(&ReservesAccount::get())?;
                 assert_ne!(desired_amount_in, 0);
@@ -286,6 +322,10 @@ impl<DEXId> LiquiditySource<DEXId, AccountId, AssetId, Balance, DispatchError> f
             _ => Err(DispatchError::Other("Bad swap amount.")),
         }
     }
+
+    fn exchange_weight(_amount: SwapVariant) -> Weight {
+        EXTRINSIC_FIXED_WEIGHT
+    }
 }
 
 pub struct ExtBuilder {
@@ -32,12 +32,16 @@
 mod tests {
     use core::convert::TryInto;
     use hex_literal::hex;
-    use crate::{DistributionAccount, DistributionAccountData, DistributionAccounts, Error, SwapKind, mock::*};
-    use common::{self, AssetName, AssetSymbol, DEXId, LiquiditySource, TechPurpose, USDT, VAL, XOR, balance, fixed, fixed_wrapper, fixnum::FixedPoint};
-    use common::prelude::{Balance, Fixed, SwapAmount, SwapOutcome, FixedWrapper};
+    use crate::{CurveShape, StorageVersion, DistributionAccount, DistributionAccountData, DistributionAccounts, Error, migrations, ExponentialCurve, LinearCurve, PowerLawCurve, SigmoidCurve, SwapKind, mock::*};
+    use common::{self, AssetName, AssetSymbol, DEXId, FilterMode, LiquiditySource, LiquiditySourceFilter, LiquiditySourceType, TechPurpose, USDT, VAL, XOR, balance, fixed, fixed_wrapper, fixnum::FixedPoint};
+    use common::prelude::{Balance, Fixed, SwapAmount, SwapOutcome, SwapVariant, FixedWrapper};
     use common::prelude::fixnum::ops::One;
-    use frame_support::assert_err;
+    use frame_support::{assert_err, assert_noop, assert_ok};
     use frame_support::storage::{with_transaction, TransactionOutcome};
+    use frame_support::traits::{GenesisBuild, Get, OnInitialize};
+    use frame_support::weights::Weight;
+    use liquidity_proxy::LiquidityProxyTrait;
+    use orml_traits::MultiCurrency;
     use sp_arithmetic::traits::{Zero};
     use sp_runtime::DispatchError;
 
@@ -147,14 +151,22 @@ mod tests {
         });
     }
 
+    type DistributionAccountEntry = crate::DistributionAccountEntryOf<Runtime>;
+
+    fn tech_distribution_account(label: &[u8], coefficient: FixedWrapper) -> DistributionAccountEntry {
+        DistributionAccountEntry::new(
+            label.to_vec(),
+            DistributionAccount::TechAccount(TechAccountId::Pure(
+                DEXId::Polkaswap,
+                TechPurpose::Identifier(label.to_vec()),
+            )),
+            coefficient.get().unwrap(),
+        )
+    }
+
     fn bonding_curve_pool_init(
         initial_reserves: Vec<(AssetId, Balance)>,
-    ) -> Result<
-        DistributionAccounts<DistributionAccountData<DistributionAccount<
-            <Runtime as frame_system::Config>::AccountId,
-            <Runtime as technical::Config>::TechAccountId>>>,
-        DispatchError,
-    > {
+    ) -> Result<Vec<DistributionAccountEntry>, DispatchError> {
         let bonding_curve_tech_account_id = TechAccountId::Pure(
             DEXId::Polkaswap,
             TechPurpose::Identifier(b"bonding_curve_tech_account_id".to_vec()),
@@ -184,62 +196,31 @@ mod tests {
                 + val_holders_buy_back_coefficient.clone()).get().unwrap()
         );
 
-        let xor_allocation = DistributionAccountData::new(
-            DistributionAccount::TechAccount(TechAccountId::Pure(
-                DEXId::Polkaswap,
-                TechPurpose::Identifier(b"xor_allocation".to_vec()),
-            )),
-            val_holders_xor_alloc_coeff.get().unwrap(),
-        );
-        let sora_citizens = DistributionAccountData::new(
-            DistributionAccount::TechAccount(TechAccountId::Pure(
-                DEXId::Polkaswap,
-                TechPurpose::Identifier(b"sora_citizens".to_vec()),
-            )),
-            projects_sora_citizens_coeff.get().unwrap(),
-        );
-        let stores_and_shops = DistributionAccountData::new(
-            DistributionAccount::TechAccount(TechAccountId::Pure(
-                DEXId::Polkaswap,
-                TechPurpose::Identifier(b"stores_and_shops".to_vec()),
-            )),
-            projects_stores_and_shops_coeff.get().unwrap(),
-        );
-        let parliament_and_development = DistributionAccountData::new(
-            DistributionAccount::Account(hex!("881b87c9f83664b95bd13e2bb40675bfa186287da93becc0b22683334d411e4e").into()),
-            projects_parliament_and_development_coeff.get().unwrap(),
-        );
-        let projects = DistributionAccountData::new(
-            DistributionAccount::TechAccount(TechAccountId::Pure(
-                DEXId::Polkaswap,
-                TechPurpose::Identifier(b"projects".to_vec()),
-            )),
-            projects_other_coeff.get().unwrap(),
-        );
-        let val_holders = DistributionAccountData::new(
-            DistributionAccount::TechAccount(TechAccountId::Pure(
-                DEXId::Polkaswap,
-                TechPurpose::Identifier(b"val_holders".to_vec()),
-            )),
-            val_holders_buy_back_coefficient.get().unwrap(),
-        );
-        let accounts = DistributionAccounts::<_> {
-            xor_allocation,
-            sora_citizens,
-            stores_and_shops,
-            parliament_and_development,
-            projects,
-            val_holders,
-        };
-        for account in &accounts.xor_distribution_accounts_as_array() {
-            match account {
+        let accounts = vec![
+            tech_distribution_account(b"xor_allocation", val_holders_xor_alloc_coeff),
+            tech_distribution_account(b"sora_citizens", projects_sora_citizens_coeff),
+            tech_distribution_account(b"stores_and_shops", projects_stores_and_shops_coeff),
+            DistributionAccountEntry::new(
+                b"parliament_and_development".to_vec(),
+                DistributionAccount::Account(hex!("881b87c9f83664b95bd13e2bb40675bfa186287da93becc0b22683334d411e4e").into()),
+                projects_parliament_and_development_coeff.get().unwrap(),
+            ),
+            tech_distribution_account(b"projects", projects_other_coeff),
+        ];
+        for entry in &accounts {
+            match &entry.account {
                 DistributionAccount::Account(_) => continue,
                 DistributionAccount::TechAccount(account) => {
                     Technical::register_tech_account_id(account.clone())?;
                 }
             }
         }
-        BondingCurvePool::set_distribution_accounts(accounts.clone());
+        BondingCurvePool::set_distribution_accounts(
+            Origin::root(),
+            accounts.clone(),
+            val_holders_buy_back_coefficient.get().unwrap(),
+        )
+        .map_err(|e| e.error)?;
         Ok(accounts)
     }
 
@@ -261,9 +242,9 @@ mod tests {
         ])
         .build();
         ext.execute_with(|| {
-            MockDEXApi::init().unwrap();
+            MockLiquidityProxy::init().unwrap();
             let distribution_accounts = bonding_curve_pool_init(Vec::new()).unwrap();
-            let distribution_accounts_array = distribution_accounts.xor_distribution_accounts_as_array();
+            let distribution_accounts_array = distribution_accounts.iter().map(|entry| &entry.account).collect::<Vec<_>>();
             let alice = &alice();
             assert_eq!(
                 BondingCurvePool::exchange(
@@ -329,7 +310,7 @@ mod tests {
         ])
         .build();
         ext.execute_with(|| {
-            MockDEXApi::init().unwrap();
+            MockLiquidityProxy::init().unwrap();
             let total_issuance = Assets::total_issuance(&XOR).unwrap();
             let reserve_amount_expected =
                 BondingCurvePool::price_for_main_asset(&XOR, total_issuance, SwapKind::Sell)
@@ -340,7 +321,7 @@ mod tests {
             let pool_usd_amount = pool_usd_amount.into_balance();
             let distribution_accounts =
                 bonding_curve_pool_init(vec![(USDT, pool_usd_amount)]).unwrap();
-            let distribution_accounts_array = distribution_accounts.xor_distribution_accounts_as_array();
+            let distribution_accounts_array = distribution_accounts.iter().map(|entry| &entry.account).collect::<Vec<_>>();
             let alice = &alice();
             assert_eq!(
                 BondingCurvePool::exchange(
@@ -417,7 +398,7 @@ mod tests {
         ])
         .build();
         ext.execute_with(|| {
-            MockDEXApi::init().unwrap();
+            MockLiquidityProxy::init().unwrap();
             let total_issuance = Assets::total_issuance(&XOR).unwrap();
             let reserve_amount_expected =
                 BondingCurvePool::price_for_main_asset(&XOR, total_issuance, SwapKind::Sell)
@@ -425,7 +406,7 @@ mod tests {
             let reserve_amount_expected = reserve_amount_expected.into_bits().try_into().unwrap();
             let distribution_accounts =
                 bonding_curve_pool_init(vec![(USDT, reserve_amount_expected)]).unwrap();
-            let distribution_accounts_array = distribution_accounts.xor_distribution_accounts_as_array();
+            let distribution_accounts_array = distribution_accounts.iter().map(|entry| &entry.account).collect::<Vec<_>>();
             let alice = &alice();
             assert_eq!(
                 BondingCurvePool::exchange(
@@ -493,7 +474,7 @@ mod tests {
         ])
         .build();
         ext.execute_with(|| {
-            MockDEXApi::init().unwrap();
+            MockLiquidityProxy::init().unwrap();
             let _ = bonding_curve_pool_init(vec![]).unwrap();
             let alice = &alice();
             assert_err!(
@@ -528,7 +509,7 @@ mod tests {
         ])
         .build();
         ext.execute_with(|| {
-            MockDEXApi::init().unwrap();
+            MockLiquidityProxy::init().unwrap();
             let alice = &alice();
             let _ = bonding_curve_pool_init(Vec::new()).unwrap();
             let amount = balance!(100);
@@ -611,4 +592,557 @@ mod tests {
             assert_eq!(whole_outcome, cumulative_outcome);
         });
     }
+
+    // The tests below check every curve from `curves()` on a fixed grid of issuances
+    // and amounts, they are not randomized and don't cover other curve parameters.
+    fn curves() -> Vec<CurveShape> {
+        vec![
+            CurveShape::Linear(LinearCurve {
+                initial_price: fixed!(99.3),
+                price_change_step: fixed!(5000),
+                price_change_rate: fixed!(100),
+            }),
+            CurveShape::Exponential(ExponentialCurve {
+                initial_price: fixed!(100),
+                scale: fixed!(10000),
+            }),
+            CurveShape::PowerLaw(PowerLawCurve {
+                initial_price: fixed!(100),
+                coefficient: fixed!(10),
+                scale: fixed!(100),
+                exponent: 2,
+            }),
+            CurveShape::Sigmoid(SigmoidCurve {
+                initial_price: fixed!(50),
+                price_amplitude: fixed!(100),
+                midpoint: fixed!(50),
+                scale: fixed!(20),
+            }),
+        ]
+    }
+
+    fn ext_with_usdt() -> sp_io::TestExternalities {
+        ExtBuilder::new(vec![
+            (alice(), USDT, balance!(100000), AssetSymbol(b"USDT".to_vec()), AssetName(b"Tether USD".to_vec()), 18, 0, true),
+            (alice(), XOR, 0, AssetSymbol(b"XOR".to_vec()), AssetName(b"SORA".to_vec()), 18, 0, true),
+            (alice(), VAL, 0, AssetSymbol(b"VAL".to_vec()), AssetName(b"SORA Validator Token".to_vec()), 18, 0, true),
+        ])
+        .build()
+    }
+
+    fn quote_and_exchange(
+        input_asset_id: AssetId,
+        output_asset_id: AssetId,
+        amount: Balance,
+    ) -> (SwapOutcome<Balance>, SwapOutcome<Balance>) {
+        let alice = &alice();
+        let swap_amount = SwapAmount::with_desired_input(amount, Balance::zero());
+        let quote_outcome = BondingCurvePool::quote(
+            &DEXId::Polkaswap.into(),
+            &input_asset_id,
+            &output_asset_id,
+            swap_amount,
+        )
+        .expect("failed to quote");
+        let exchange_outcome = with_transaction(|| {
+            let outcome = BondingCurvePool::exchange(
+                alice,
+                alice,
+                &DEXId::Polkaswap.into(),
+                &input_asset_id,
+                &output_asset_id,
+                swap_amount,
+            )
+            .expect("failed to exchange");
+            TransactionOutcome::Rollback(outcome)
+        });
+        (quote_outcome, exchange_outcome)
+    }
+
+    #[test]
+    fn inverse_integral_should_match_integral_for_every_curve() {
+        let mut ext = ExtBuilder::default().build();
+        ext.execute_with(|| {
+            let sell_price_coefficient = fixed_wrapper!(0.8);
+            for curve in curves() {
+                for issuance in (0u128..1000).step_by(97) {
+                    let issuance = FixedWrapper::from(issuance * balance!(1));
+                    for quantity in (1u128..100).step_by(13) {
+                        let quantity = quantity * balance!(1);
+                        let cost = curve.integral(issuance.clone(), issuance.clone() + quantity);
+                        let bought = curve.inverse_integral(issuance.clone(), cost, None);
+                        let error = FixedWrapper::from(quantity) - bought;
+                        assert!(error.clone() < fixed_wrapper!(0.000001) && -error < fixed_wrapper!(0.000001), "{:?}", curve);
+                    }
+                    for quantity in (1u128..100).step_by(13) {
+                        let quantity = quantity * balance!(1);
+                        if FixedWrapper::from(quantity) > issuance {
+                            continue;
+                        }
+                        let payout = sell_price_coefficient.clone()
+                            * curve.integral(issuance.clone() - quantity, issuance.clone());
+                        let sold = curve.inverse_integral(issuance.clone(), payout, Some(sell_price_coefficient.clone()));
+                        let error = FixedWrapper::from(quantity) - sold;
+                        assert!(error.clone() < fixed_wrapper!(0.000001) && -error < fixed_wrapper!(0.000001), "{:?}", curve);
+                    }
+                }
+            }
+        });
+    }
+
+    #[test]
+    fn quote_should_match_exchange_for_every_curve() {
+        let mut ext = ext_with_usdt();
+        ext.execute_with(|| {
+            MockLiquidityProxy::init().unwrap();
+            let _ = bonding_curve_pool_init(Vec::new()).unwrap();
+            for curve in curves() {
+                with_transaction(|| {
+                    assert_ok!(BondingCurvePool::set_curve(Origin::root(), XOR, curve.clone()));
+                    for amount in &[balance!(0.001), balance!(1), balance!(100), balance!(1234.5678)] {
+                        let (quote_outcome, exchange_outcome) = quote_and_exchange(USDT, XOR, *amount);
+                        assert_eq!(quote_outcome, exchange_outcome, "{:?}", curve);
+                    }
+                    BondingCurvePool::exchange(
+                        &alice(),
+                        &alice(),
+                        &DEXId::Polkaswap.into(),
+                        &USDT,
+                        &XOR,
+                        SwapAmount::with_desired_input(balance!(10000), Balance::zero()),
+                    )
+                    .unwrap();
+                    let xor_balance = Assets::total_balance(&XOR, &alice()).unwrap();
+                    for amount in &[balance!(0.001), xor_balance / 10, xor_balance / 2] {
+                        let (quote_outcome, exchange_outcome) = quote_and_exchange(XOR, USDT, *amount);
+                        assert_eq!(quote_outcome, exchange_outcome, "{:?}", curve);
+                    }
+                    TransactionOutcome::Rollback(())
+                });
+            }
+        });
+    }
+
+    #[test]
+    fn round_trip_should_not_create_value_for_every_curve() {
+        let mut ext = ext_with_usdt();
+        ext.execute_with(|| {
+            MockLiquidityProxy::init().unwrap();
+            let _ = bonding_curve_pool_init(Vec::new()).unwrap();
+            let alice = &alice();
+            for curve in curves() {
+                for amount in &[balance!(0.001), balance!(1), balance!(100), balance!(10000)] {
+                    with_transaction(|| {
+                        assert_ok!(BondingCurvePool::set_curve(Origin::root(), XOR, curve.clone()));
+                        let bought = BondingCurvePool::exchange(
+                            alice,
+                            alice,
+                            &DEXId::Polkaswap.into(),
+                            &USDT,
+                            &XOR,
+                            SwapAmount::with_desired_input(*amount, Balance::zero()),
+                        )
+                        .unwrap()
+                        .amount;
+                        let sold = BondingCurvePool::exchange(
+                            alice,
+                            alice,
+                            &DEXId::Polkaswap.into(),
+                            &XOR,
+                            &USDT,
+                            SwapAmount::with_desired_input(bought, Balance::zero()),
+                        )
+                        .unwrap()
+                        .amount;
+                        assert!(sold <= *amount, "{:?}: {} > {}", curve, sold, amount);
+                        assert_eq!(Assets::total_balance(&XOR, alice).unwrap(), Balance::zero());
+                        TransactionOutcome::Rollback(())
+                    });
+                }
+            }
+        });
+    }
+
+    #[test]
+    fn should_set_curve() {
+        let mut ext = ExtBuilder::default().build();
+        ext.execute_with(|| {
+            System::set_block_number(1);
+            let curve = curves()[1].clone();
+            assert_noop!(
+                BondingCurvePool::set_curve(Origin::signed(alice()), XOR, curve.clone()),
+                DispatchError::BadOrigin
+            );
+            let invalid_curves = vec![
+                CurveShape::Linear(LinearCurve {
+                    initial_price: fixed!(99.3),
+                    price_change_step: fixed!(0),
+                    price_change_rate: fixed!(100),
+                }),
+                CurveShape::Exponential(ExponentialCurve {
+                    initial_price: fixed!(100),
+                    scale: fixed!(-10000),
+                }),
+                CurveShape::PowerLaw(PowerLawCurve {
+                    initial_price: fixed!(100),
+                    coefficient: fixed!(10),
+                    scale: fixed!(100),
+                    exponent: 0,
+                }),
+                CurveShape::Sigmoid(SigmoidCurve {
+                    initial_price: fixed!(0),
+                    price_amplitude: fixed!(100),
+                    midpoint: fixed!(50),
+                    scale: fixed!(20),
+                }),
+            ];
+            for invalid_curve in invalid_curves {
+                assert_noop!(
+                    BondingCurvePool::set_curve(Origin::root(), XOR, invalid_curve),
+                    Error::<Runtime>::InvalidCurveParameters
+                );
+            }
+            assert_ok!(BondingCurvePool::set_curve(Origin::root(), XOR, curve.clone()));
+            assert_eq!(BondingCurvePool::curve(&XOR), curve);
+            let expected_event: Event = crate::Event::CurveSet(XOR, curve).into();
+            assert!(System::events().iter().any(|record| record.event == expected_event));
+        });
+    }
+
+    #[test]
+    fn should_set_distribution_accounts() {
+        let mut ext = ExtBuilder::default().build();
+        ext.execute_with(|| {
+            System::set_block_number(1);
+            let accounts = vec![
+                tech_distribution_account(b"xor_allocation", fixed_wrapper!(0.6)),
+                tech_distribution_account(b"projects", fixed_wrapper!(0.3)),
+            ];
+            assert_ok!(BondingCurvePool::set_distribution_accounts(Origin::root(), accounts.clone(), fixed!(0.1)));
+            assert_eq!(BondingCurvePool::distribution_accounts(), accounts);
+            assert_eq!(BondingCurvePool::val_holders_coefficient(), fixed!(0.1));
+            let expected_event: Event = crate::Event::DistributionAccountsSet(accounts.clone(), fixed!(0.1)).into();
+            assert!(System::events().iter().any(|record| record.event == expected_event));
+            assert_noop!(
+                BondingCurvePool::set_distribution_accounts(Origin::signed(alice()), accounts.clone(), fixed!(0.1)),
+                DispatchError::BadOrigin
+            );
+            assert_noop!(
+                BondingCurvePool::set_distribution_accounts(Origin::root(), accounts.clone(), fixed!(0.2)),
+                Error::<Runtime>::InvalidDistributionCoefficients
+            );
+            assert_noop!(
+                BondingCurvePool::set_distribution_accounts(
+                    Origin::root(),
+                    vec![
+                        tech_distribution_account(b"projects", fixed_wrapper!(0.6)),
+                        tech_distribution_account(b"projects", fixed_wrapper!(0.3)),
+                    ],
+                    fixed!(0.1),
+                ),
+                Error::<Runtime>::InvalidDistributionAccountLabel
+            );
+            assert_noop!(
+                BondingCurvePool::set_distribution_accounts(
+                    Origin::root(),
+                    vec![
+                        tech_distribution_account(b"xor_allocation", fixed_wrapper!(1.1)),
+                        tech_distribution_account(b"projects", fixed_wrapper!(0) - fixed_wrapper!(0.2)),
+                    ],
+                    fixed!(0.1),
+                ),
+                Error::<Runtime>::InvalidDistributionCoefficients
+            );
+            let too_many_accounts = (0u8..9)
+                .map(|i| tech_distribution_account(&[b'a', i], fixed_wrapper!(0)))
+                .collect();
+            assert_noop!(
+                BondingCurvePool::set_distribution_accounts(Origin::root(), too_many_accounts, fixed!(1)),
+                Error::<Runtime>::TooManyDistributionAccounts
+            );
+        });
+    }
+
+    #[test]
+    fn should_update_distribution_account() {
+        let mut ext = ExtBuilder::default().build();
+        ext.execute_with(|| {
+            System::set_block_number(1);
+            let accounts = vec![
+                tech_distribution_account(b"xor_allocation", fixed_wrapper!(0.6)),
+                tech_distribution_account(b"projects", fixed_wrapper!(0.3)),
+            ];
+            assert_ok!(BondingCurvePool::set_distribution_accounts(Origin::root(), accounts.clone(), fixed!(0.1)));
+            assert_ok!(BondingCurvePool::update_distribution_account(
+                Origin::root(),
+                b"projects".to_vec(),
+                DistributionAccount::Account(alice()),
+            ));
+            let updated = BondingCurvePool::distribution_accounts();
+            assert_eq!(updated[0], accounts[0]);
+            assert_eq!(updated[1].account, DistributionAccount::Account(alice()));
+            assert_eq!(updated[1].coefficient, fixed!(0.3));
+            let expected_event: Event =
+                crate::Event::DistributionAccountUpdated(b"projects".to_vec(), DistributionAccount::Account(alice())).into();
+            assert!(System::events().iter().any(|record| record.event == expected_event));
+            assert_noop!(
+                BondingCurvePool::update_distribution_account(
+                    Origin::root(),
+                    b"unknown".to_vec(),
+                    DistributionAccount::Account(alice()),
+                ),
+                Error::<Runtime>::UnknownDistributionAccount
+            );
+        });
+    }
+
+    #[test]
+    fn should_build_default_genesis() {
+        let mut t = frame_system::GenesisConfig::default().build_storage::<Runtime>().unwrap();
+        crate::GenesisConfig::<Runtime>::default().assimilate_storage(&mut t).unwrap();
+        let mut ext: sp_io::TestExternalities = t.into();
+        ext.execute_with(|| {
+            assert!(BondingCurvePool::distribution_accounts().is_empty());
+            assert_eq!(BondingCurvePool::val_holders_coefficient(), fixed!(1));
+        });
+    }
+
+    #[test]
+    fn should_migrate_distribution_accounts() {
+        let mut ext = ExtBuilder::default().build();
+        ext.execute_with(|| {
+            let data = |label: &[u8], coefficient: Fixed| {
+                DistributionAccountData::new(
+                    DistributionAccount::TechAccount(TechAccountId::Pure(
+                        DEXId::Polkaswap,
+                        TechPurpose::Identifier(label.to_vec()),
+                    )),
+                    coefficient,
+                )
+            };
+            crate::DistributionAccountsEntry::<Runtime>::put(DistributionAccounts {
+                xor_allocation: data(b"xor_allocation", fixed!(0.45)),
+                sora_citizens: data(b"sora_citizens", fixed!(0.005)),
+                stores_and_shops: data(b"stores_and_shops", fixed!(0.02)),
+                parliament_and_development: data(b"parliament_and_development", fixed!(0.025)),
+                projects: data(b"projects", fixed!(0.45)),
+                val_holders: data(b"val_holders", fixed!(0.05)),
+            });
+            migrations::migrate_distribution_accounts::<Runtime>();
+            assert_eq!(
+                BondingCurvePool::distribution_accounts(),
+                vec![
+                    tech_distribution_account(b"xor_allocation", fixed_wrapper!(0.45)),
+                    tech_distribution_account(b"sora_citizens", fixed_wrapper!(0.005)),
+                    tech_distribution_account(b"stores_and_shops", fixed_wrapper!(0.02)),
+                    tech_distribution_account(b"parliament_and_development", fixed_wrapper!(0.025)),
+                    tech_distribution_account(b"projects", fixed_wrapper!(0.45)),
+                ]
+            );
+            assert_eq!(BondingCurvePool::val_holders_coefficient(), fixed!(0.05));
+            assert!(crate::DistributionAccountsEntry::<Runtime>::get().is_none());
+            assert_eq!(BondingCurvePool::pallet_storage_version(), StorageVersion::V2);
+            // Running it again must not change anything.
+            crate::DistributionAccountsEntry::<Runtime>::put(DistributionAccounts::default());
+            migrations::migrate_distribution_accounts::<Runtime>();
+            assert_eq!(BondingCurvePool::distribution_accounts().len(), 5);
+            assert!(crate::DistributionAccountsEntry::<Runtime>::get().is_some());
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid distribution accounts")]
+    fn should_not_build_genesis_with_invalid_distribution_accounts() {
+        let config = crate::GenesisConfig::<Runtime> {
+            reserves_account_id: Default::default(),
+            distribution_accounts: vec![tech_distribution_account(b"projects", fixed_wrapper!(0.5))],
+            val_holders_coefficient: fixed!(0.1),
+            curves: vec![],
+        };
+        let _ = frame_support::traits::GenesisBuild::<Runtime>::build_storage(&config);
+    }
+
+    fn ext_with_full_reserves() -> sp_io::TestExternalities {
+        ExtBuilder::new(vec![
+            (alice(), USDT, balance!(10000), AssetSymbol(b"USDT".to_vec()), AssetName(b"Tether USD".to_vec()), 18, 0, true),
+            (alice(), XOR, balance!(10), AssetSymbol(b"XOR".to_vec()), AssetName(b"SORA".to_vec()), 18, 0, true),
+            (alice(), VAL, 0, AssetSymbol(b"VAL".to_vec()), AssetName(b"SORA Validator Token".to_vec()), 18, 0, true),
+        ])
+        .build()
+    }
+
+    fn init_with_full_reserves() {
+        System::set_block_number(1);
+        MockLiquidityProxy::init().unwrap();
+        let total_issuance = Assets::total_issuance(&XOR).unwrap();
+        let reserve_amount_expected =
+            BondingCurvePool::price_for_main_asset(&XOR, total_issuance, SwapKind::Sell).unwrap();
+        let reserve_amount_expected = reserve_amount_expected.into_bits().try_into().unwrap();
+        bonding_curve_pool_init(vec![(USDT, reserve_amount_expected)]).unwrap();
+    }
+
+    fn buy_back_burned_event(main_asset_amount: Balance, val_amount: Balance) -> Event {
+        let price = (FixedWrapper::from(main_asset_amount) / FixedWrapper::from(val_amount)).get().unwrap();
+        crate::Event::BuyBackBurned(XOR, main_asset_amount, val_amount, price).into()
+    }
+
+    #[test]
+    fn should_buy_back_and_burn_val() {
+        let mut ext = ext_with_full_reserves();
+        ext.execute_with(|| {
+            init_with_full_reserves();
+            let val_issuance = Assets::total_issuance(&VAL).unwrap();
+            let alice = &alice();
+            assert_ok!(BondingCurvePool::exchange(
+                alice,
+                alice,
+                &DEXId::Polkaswap.into(),
+                &USDT,
+                &XOR,
+                SwapAmount::with_desired_output(balance!(1), Balance::max_value()),
+            ));
+            let main_asset_amount = balance!(0.0099004114937);
+            let val_amount = balance!(0.0197414205184378);
+            assert_eq!(Assets::total_issuance(&VAL).unwrap(), val_issuance - val_amount);
+            assert_eq!(BondingCurvePool::pending_buy_back(&XOR), 0);
+            let expected_event = buy_back_burned_event(main_asset_amount, val_amount);
+            assert!(System::events().iter().any(|record| record.event == expected_event));
+        });
+    }
+
+    #[test]
+    fn should_queue_failed_buy_back_and_retry_it() {
+        let mut ext = ext_with_full_reserves();
+        ext.execute_with(|| {
+            init_with_full_reserves();
+            // Take all VAL out of the DEX so the buy-back fails.
+            let dex_account_id = Technical::tech_account_id_to_account_id(&ReservesAccount::get()).unwrap();
+            let val_liquidity = Assets::free_balance(&VAL, &dex_account_id).unwrap();
+            Currencies::withdraw(VAL, &dex_account_id, val_liquidity).unwrap();
+            let val_issuance = Assets::total_issuance(&VAL).unwrap();
+            let alice = &alice();
+            assert_ok!(BondingCurvePool::exchange(
+                alice,
+                alice,
+                &DEXId::Polkaswap.into(),
+                &USDT,
+                &XOR,
+                SwapAmount::with_desired_output(balance!(1), Balance::max_value()),
+            ));
+            let main_asset_amount = balance!(0.0099004114937);
+            assert_eq!(BondingCurvePool::pending_buy_back(&XOR), main_asset_amount);
+            assert_eq!(Assets::total_issuance(&VAL).unwrap(), val_issuance);
+            let expected_event: Event = crate::Event::BuyBackQueued(XOR, main_asset_amount).into();
+            assert!(System::events().iter().any(|record| record.event == expected_event));
+
+            // Nothing changes while the DEX still has no VAL.
+            BondingCurvePool::on_initialize(2);
+            assert_eq!(BondingCurvePool::pending_buy_back(&XOR), main_asset_amount);
+
+            Currencies::deposit(VAL, &dex_account_id, val_liquidity).unwrap();
+            System::set_block_number(3);
+            BondingCurvePool::on_initialize(3);
+            let val_amount = balance!(0.0197414205184378);
+            assert_eq!(BondingCurvePool::pending_buy_back(&XOR), 0);
+            assert_eq!(Assets::total_issuance(&VAL).unwrap(), val_issuance + val_liquidity - val_amount);
+            let expected_event = buy_back_burned_event(main_asset_amount, val_amount);
+            assert!(System::events().iter().any(|record| record.event == expected_event));
+        });
+    }
+
+    #[test]
+    fn should_set_buy_back_filter() {
+        let mut ext = ExtBuilder::default().build();
+        ext.execute_with(|| {
+            System::set_block_number(1);
+            assert_eq!(
+                BondingCurvePool::buy_back_filter(),
+                LiquiditySourceFilter::with_forbidden(DEXId::Polkaswap, vec![LiquiditySourceType::BondingCurvePool])
+            );
+            assert_ok!(BondingCurvePool::set_buy_back_filter(
+                Origin::root(),
+                DEXId::Polkaswap,
+                vec![LiquiditySourceType::XYKPool],
+                FilterMode::AllowSelected,
+            ));
+            let filter = LiquiditySourceFilter::with_allowed(DEXId::Polkaswap, vec![LiquiditySourceType::XYKPool]);
+            assert_eq!(BondingCurvePool::buy_back_filter(), filter);
+            let expected_event: Event = crate::Event::BuyBackFilterSet(filter).into();
+            assert!(System::events().iter().any(|record| record.event == expected_event));
+            assert_noop!(
+                BondingCurvePool::set_buy_back_filter(
+                    Origin::signed(alice()),
+                    DEXId::Polkaswap,
+                    vec![LiquiditySourceType::XYKPool],
+                    FilterMode::AllowSelected,
+                ),
+                DispatchError::BadOrigin
+            );
+            assert_noop!(
+                BondingCurvePool::set_buy_back_filter(Origin::root(), DEXId::Polkaswap, Vec::new(), FilterMode::Disabled),
+                Error::<Runtime>::InvalidBuyBackFilter
+            );
+            assert_noop!(
+                BondingCurvePool::set_buy_back_filter(
+                    Origin::root(),
+                    DEXId::Polkaswap,
+                    vec![LiquiditySourceType::BondingCurvePool],
+                    FilterMode::AllowSelected,
+                ),
+                Error::<Runtime>::InvalidBuyBackFilter
+            );
+        });
+    }
+
+    #[test]
+    fn should_skip_distribution_when_filter_excludes_every_source() {
+        let mut ext = ext_with_full_reserves();
+        ext.execute_with(|| {
+            init_with_full_reserves();
+            assert_ok!(BondingCurvePool::set_buy_back_filter(
+                Origin::root(),
+                DEXId::Polkaswap,
+                vec![LiquiditySourceType::XYKPool],
+                FilterMode::AllowSelected,
+            ));
+            let val_issuance = Assets::total_issuance(&VAL).unwrap();
+            let alice = &alice();
+            let xor_balance = Assets::total_balance(&XOR, alice).unwrap();
+            // The free reserves can't be exchanged for the main asset either,
+            // so the distribution is skipped but the user still buys the main asset.
+            assert_ok!(BondingCurvePool::exchange(
+                alice,
+                alice,
+                &DEXId::Polkaswap.into(),
+                &USDT,
+                &XOR,
+                SwapAmount::with_desired_output(balance!(1), Balance::max_value()),
+            ));
+            assert_eq!(BondingCurvePool::pending_buy_back(&XOR), 0);
+            assert_eq!(Assets::total_issuance(&VAL).unwrap(), val_issuance);
+            assert!(Assets::total_balance(&XOR, alice).unwrap() > xor_balance);
+        });
+    }
+
+    #[test]
+    fn should_retry_limited_number_of_buy_backs_per_block() {
+        let mut ext = ext_with_full_reserves();
+        ext.execute_with(|| {
+            init_with_full_reserves();
+            // The mock proxy can't exchange these assets for VAL, so the buy-backs keep failing.
+            let assets = [USDT, VAL, AssetId::from_bytes([7u8; 32]), AssetId::from_bytes([8u8; 32]), AssetId::from_bytes([9u8; 32])];
+            for asset_id in &assets {
+                crate::PendingBuyBacks::<Runtime>::insert(asset_id, balance!(1));
+            }
+            let weight = BondingCurvePool::on_initialize(2);
+            let retries = MaxBuyBackRetriesPerBlock::get();
+            // Every retry is charged for routing the exchange through the liquidity proxy.
+            assert_eq!(
+                weight,
+                <() as crate::WeightInfo>::retry_buy_backs(retries)
+                    + MockLiquidityProxy::exchange_weight(SwapVariant::WithDesiredInput) * retries as Weight
+            );
+            assert_eq!(crate::PendingBuyBacks::<Runtime>::iter().count(), assets.len());
+        });
+    }
 }
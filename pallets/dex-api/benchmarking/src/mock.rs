@@ -32,7 +32,10 @@
 
 use crate::{Config, *};
 use common::mock::ExistentialDeposits;
-use common::{fixed_from_basis_points, hash, Amount, AssetId32, DEXInfo, Fixed};
+use common::{
+    fixed_from_basis_points, hash, Amount, AssetId32, BasisPoints, DEXInfo, Fixed,
+    LiquiditySourceType,
+};
 use currencies::BasicCurrencyAdapter;
 
 use frame_support::traits::GenesisBuild;
@@ -75,6 +78,7 @@ parameter_types! {
     pub const GetBurnUpdateFrequency: BlockNumber = 14400;
     pub GetIncentiveAssetId: AssetId = common::PSWAP.into();
     pub GetParliamentAccountId: AccountId = AccountId32::from([8; 32]);
+    pub const MaxDexFee: BasisPoints = 100;
 }
 
 construct_runtime! {
@@ -88,7 +92,7 @@ construct_runtime! {
         Currencies: currencies::{Module, Call, Storage, Event<T>},
         Assets: assets::{Module, Call, Config<T>, Storage, Event<T>},
         Balances: pallet_balances::{Module, Call, Storage, Event<T>},
-        DexManager: dex_manager::{Module, Call, Config<T>, Storage},
+        DexManager: dex_manager::{Module, Call, Config<T>, Storage, Event<T>},
         Technical: technical::{Module, Call, Config<T>, Storage, Event<T>},
         Permissions: permissions::{Module, Call, Config<T>, Storage, Event<T>},
         DexApi: dex_api::{Module, Call, Config, Storage, Event<T>},
@@ -150,6 +154,7 @@ impl assets::Config for Runtime {
     type AssetId = AssetId;
     type GetBaseAssetId = GetBaseAssetId;
     type Currency = currencies::Module<Runtime>;
+    type IsAssetInUse = ();
     type WeightInfo = ();
 }
 
@@ -168,7 +173,11 @@ impl pallet_balances::Config for Runtime {
     type MaxLocks = ();
 }
 
-impl dex_manager::Config for Runtime {}
+impl dex_manager::Config for Runtime {
+    type Event = Event;
+    type MaxFee = MaxDexFee;
+    type WeightInfo = ();
+}
 
 impl technical::Config for Runtime {
     type Event = Event;
@@ -187,13 +196,7 @@ impl permissions::Config for Runtime {
 
 impl dex_api::Config for Runtime {
     type Event = Event;
-    type MockLiquiditySource = ();
-    type MockLiquiditySource2 = ();
-    type MockLiquiditySource3 = ();
-    type MockLiquiditySource4 = ();
-    type XYKPool = pool_xyk::Module<Runtime>;
-    type BondingCurvePool = ();
-    type MulticollateralBondingCurvePool = ();
+    type LiquiditySources = (pool_xyk::Module<Runtime>,);
     type WeightInfo = ();
 }
 
@@ -213,6 +216,7 @@ impl pool_xyk::Config for Runtime {
     type PolySwapAction =
         pool_xyk::PolySwapAction<AssetId, TechAssetId, Balance, AccountId, TechAccountId>;
     type EnsureDEXManager = dex_manager::Pallet<Runtime>;
+    type EnsureTradingNotPaused = ();
     type WeightInfo = ();
 }
 
@@ -254,6 +258,7 @@ impl Default for ExtBuilder {
                 DEXInfo {
                     base_asset_id: GetBaseAssetId::get(),
                     is_public: true,
+                    default_fee: 30,
                 },
             )],
             initial_permission_owners: vec![
@@ -295,6 +300,14 @@ impl ExtBuilder {
         .assimilate_storage(&mut t)
         .unwrap();
 
+        <dex_api::GenesisConfig as GenesisBuild<Runtime>>::assimilate_storage(
+            &dex_api::GenesisConfig {
+                source_types: vec![LiquiditySourceType::XYKPool],
+            },
+            &mut t,
+        )
+        .unwrap();
+
         t.into()
     }
 }
@@ -68,7 +68,11 @@ pub trait DEXAPI<BlockHash, AssetId, DEXId, Balance, LiquiditySourceType, SwapVa
     ) -> Result<bool>;
 
     #[rpc(name = "dexApi_listSupportedSources")]
-    fn list_supported_sources(&self, at: Option<BlockHash>) -> Result<Vec<LiquiditySourceType>>;
+    fn list_supported_sources(
+        &self,
+        dex_id: DEXId,
+        at: Option<BlockHash>,
+    ) -> Result<Vec<LiquiditySourceType>>;
 }
 
 pub struct DEX<C, B> {
@@ -167,6 +171,7 @@ where
 
     fn list_supported_sources(
         &self,
+        dex_id: DEXId,
         at: Option<<Block as BlockT>::Hash>,
     ) -> Result<Vec<LiquiditySourceType>> {
         let api = self.client.runtime_api();
@@ -174,10 +179,11 @@ where
             // If the block hash is not supplied assume the best block.
             self.client.info().best_hash,
         ));
-        api.list_supported_sources(&at).map_err(|e| RpcError {
-            code: ErrorCode::ServerError(InvokeRPCError::RuntimeError.into()),
-            message: "Unable to query supported liquidity source types.".into(),
-            data: Some(format!("{:?}", e).into()),
-        })
+        api.list_supported_sources(&at, dex_id)
+            .map_err(|e| RpcError {
+                code: ErrorCode::ServerError(InvokeRPCError::RuntimeError.into()),
+                message: "Unable to query supported liquidity source types.".into(),
+                data: Some(format!("{:?}", e).into()),
+            })
     }
 }
@@ -92,6 +92,6 @@ sp_api::decl_runtime_apis! {
             output_asset_id: AssetId,
         ) -> bool;
 
-        fn list_supported_sources() -> Vec<LiquiditySourceType>;
+        fn list_supported_sources(dex_id: DEXId) -> Vec<LiquiditySourceType>;
     }
 }
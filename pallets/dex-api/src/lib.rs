@@ -30,14 +30,22 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use common::prelude::{Balance, SwapAmount, SwapOutcome, SwapVariant};
+use codec::{Decode, Encode};
+use common::prelude::{
+    Balance, EnsureDEXManager, ManagementMode, SwapAmount, SwapOutcome, SwapVariant,
+};
 use common::{
-    LiquidityRegistry, LiquiditySource, LiquiditySourceFilter, LiquiditySourceId,
-    LiquiditySourceType,
+    hash, EnsureTradingNotPaused, GetSwapCurve, LiquidityRegistry, LiquiditySource,
+    LiquiditySourceFilter, LiquiditySourceId, LiquiditySourceRegistry, LiquiditySourceType,
+    SwapCurve, TradingPair,
 };
+use frame_support::dispatch::DispatchResult;
 use frame_support::sp_runtime::DispatchError;
 use frame_support::weights::Weight;
-use frame_system::ensure_signed;
+use frame_support::{ensure, RuntimeDebug};
+use frame_system::pallet_prelude::OriginFor;
+use frame_system::{ensure_root, ensure_signed};
+use permissions::{Scope, PAUSE_TRADING};
 use sp_std::vec::Vec;
 
 pub mod weights;
@@ -50,10 +58,36 @@ mod tests;
 
 pub trait WeightInfo {
     fn swap() -> Weight;
+    fn enable_liquidity_source() -> Weight;
+    fn disable_liquidity_source() -> Weight;
+    fn pause_trading() -> Weight;
+    fn resume_trading() -> Weight;
 }
 
 type DEXManager<T> = dex_manager::Pallet<T>;
 
+/// Scope of the emergency trading pause.
+#[derive(Encode, Decode, Eq, PartialEq, Clone, Copy, RuntimeDebug)]
+pub enum PauseScope<DEXId, AssetId> {
+    /// Trading of the pair with the liquidity source type in the DEX.
+    Pool(DEXId, TradingPair<AssetId>, LiquiditySourceType),
+    /// Trading with the liquidity source type in all DEXes.
+    SourceType(LiquiditySourceType),
+    /// All trading in the DEX.
+    DEX(DEXId),
+}
+
+impl<DEXId, AssetId> PauseScope<DEXId, AssetId> {
+    fn dex_id(&self) -> Option<&DEXId> {
+        match self {
+            PauseScope::Pool(dex_id, ..) | PauseScope::DEX(dex_id) => Some(dex_id),
+            PauseScope::SourceType(_) => None,
+        }
+    }
+}
+
+pub type PauseScopeOf<T> = PauseScope<<T as common::Config>::DEXId, <T as assets::Config>::AssetId>;
+
 impl<T: Config>
     LiquiditySource<
         LiquiditySourceId<T::DEXId, LiquiditySourceType>,
@@ -68,25 +102,15 @@ impl<T: Config>
         input_asset_id: &T::AssetId,
         output_asset_id: &T::AssetId,
     ) -> bool {
-        use LiquiditySourceType::*;
-        macro_rules! can_exchange {
-            ($source_type:ident) => {
-                T::$source_type::can_exchange(
-                    &liquidity_source_id.dex_id,
-                    input_asset_id,
-                    output_asset_id,
-                )
-            };
-        }
-        match liquidity_source_id.liquidity_source_index {
-            XYKPool => can_exchange!(XYKPool),
-            BondingCurvePool => can_exchange!(BondingCurvePool),
-            MulticollateralBondingCurvePool => can_exchange!(MulticollateralBondingCurvePool),
-            MockPool => can_exchange!(MockLiquiditySource),
-            MockPool2 => can_exchange!(MockLiquiditySource2),
-            MockPool3 => can_exchange!(MockLiquiditySource3),
-            MockPool4 => can_exchange!(MockLiquiditySource4),
+        if Self::ensure_can_trade(liquidity_source_id, input_asset_id, output_asset_id).is_err() {
+            return false;
         }
+        T::LiquiditySources::can_exchange(
+            liquidity_source_id.liquidity_source_index,
+            &liquidity_source_id.dex_id,
+            input_asset_id,
+            output_asset_id,
+        )
     }
 
     fn quote(
@@ -95,26 +119,14 @@ impl<T: Config>
         output_asset_id: &T::AssetId,
         swap_amount: SwapAmount<Balance>,
     ) -> Result<SwapOutcome<Balance>, DispatchError> {
-        use LiquiditySourceType::*;
-        macro_rules! quote {
-            ($source_type:ident) => {
-                T::$source_type::quote(
-                    &liquidity_source_id.dex_id,
-                    input_asset_id,
-                    output_asset_id,
-                    swap_amount,
-                )
-            };
-        }
-        match liquidity_source_id.liquidity_source_index {
-            LiquiditySourceType::XYKPool => quote!(XYKPool),
-            BondingCurvePool => quote!(BondingCurvePool),
-            MulticollateralBondingCurvePool => quote!(MulticollateralBondingCurvePool),
-            MockPool => quote!(MockLiquiditySource),
-            MockPool2 => quote!(MockLiquiditySource2),
-            MockPool3 => quote!(MockLiquiditySource3),
-            MockPool4 => quote!(MockLiquiditySource4),
-        }
+        T::LiquiditySources::quote(
+            liquidity_source_id.liquidity_source_index,
+            &liquidity_source_id.dex_id,
+            input_asset_id,
+            output_asset_id,
+            swap_amount,
+        )
+        .unwrap_or_else(|| Err(Error::<T>::UnknownLiquiditySourceType.into()))
     }
 
     fn exchange(
@@ -125,36 +137,212 @@ impl<T: Config>
         output_asset_id: &T::AssetId,
         swap_amount: SwapAmount<Balance>,
     ) -> Result<SwapOutcome<Balance>, DispatchError> {
-        use LiquiditySourceType::*;
-        macro_rules! exchange {
-            ($source_type:ident) => {
-                T::$source_type::exchange(
-                    sender,
-                    receiver,
-                    &liquidity_source_id.dex_id,
-                    input_asset_id,
-                    output_asset_id,
-                    swap_amount,
-                )
-            };
-        }
-        match liquidity_source_id.liquidity_source_index {
-            XYKPool => exchange!(XYKPool),
-            BondingCurvePool => exchange!(BondingCurvePool),
-            MulticollateralBondingCurvePool => exchange!(MulticollateralBondingCurvePool),
-            MockPool => exchange!(MockLiquiditySource),
-            MockPool2 => exchange!(MockLiquiditySource2),
-            MockPool3 => exchange!(MockLiquiditySource3),
-            MockPool4 => exchange!(MockLiquiditySource4),
-        }
+        Self::ensure_can_trade(liquidity_source_id, input_asset_id, output_asset_id)?;
+        let outcome = T::LiquiditySources::exchange(
+            liquidity_source_id.liquidity_source_index,
+            sender,
+            receiver,
+            &liquidity_source_id.dex_id,
+            input_asset_id,
+            output_asset_id,
+            swap_amount,
+        )
+        .unwrap_or_else(|| Err(Error::<T>::UnknownLiquiditySourceType.into()))?;
+        let (input_amount, output_amount) = match swap_amount {
+            SwapAmount::WithDesiredInput {
+                desired_amount_in, ..
+            } => (desired_amount_in, outcome.amount),
+            SwapAmount::WithDesiredOutput {
+                desired_amount_out, ..
+            } => (outcome.amount, desired_amount_out),
+        };
+        trading_pair::Pallet::<T>::record_trade(
+            &liquidity_source_id.dex_id,
+            liquidity_source_id.liquidity_source_index,
+            input_asset_id,
+            output_asset_id,
+            input_amount,
+            output_amount,
+        );
+        Ok(outcome)
+    }
+
+    fn fee_account(
+        liquidity_source_id: &LiquiditySourceId<T::DEXId, LiquiditySourceType>,
+        input_asset_id: &T::AssetId,
+        output_asset_id: &T::AssetId,
+    ) -> Option<T::AccountId> {
+        T::LiquiditySources::fee_account(
+            liquidity_source_id.liquidity_source_index,
+            &liquidity_source_id.dex_id,
+            input_asset_id,
+            output_asset_id,
+        )
+    }
+
+    fn exchange_weight() -> Weight {
+        T::LiquiditySources::exchange_weight()
+            .saturating_add(trading_pair::Pallet::<T>::record_trade_weight())
+    }
+}
+
+impl<T: Config> GetSwapCurve<LiquiditySourceId<T::DEXId, LiquiditySourceType>, T::AssetId>
+    for Pallet<T>
+{
+    fn swap_curve(
+        liquidity_source_id: &LiquiditySourceId<T::DEXId, LiquiditySourceType>,
+        input_asset_id: &T::AssetId,
+        output_asset_id: &T::AssetId,
+    ) -> Option<SwapCurve> {
+        T::LiquiditySources::swap_curve(
+            liquidity_source_id.liquidity_source_index,
+            &liquidity_source_id.dex_id,
+            input_asset_id,
+            output_asset_id,
+        )
+    }
+}
+
+impl<T: Config> EnsureTradingNotPaused<T::DEXId, T::AssetId, DispatchError> for Pallet<T> {
+    fn ensure_trading_not_paused(
+        dex_id: &T::DEXId,
+        source_type: LiquiditySourceType,
+        input_asset_id: &T::AssetId,
+        output_asset_id: &T::AssetId,
+    ) -> Result<(), DispatchError> {
+        let paused = Self::is_paused(PauseScope::DEX(*dex_id))
+            || Self::is_paused(PauseScope::SourceType(source_type))
+            || Self::is_paused(PauseScope::Pool(
+                *dex_id,
+                TradingPair {
+                    base_asset_id: *input_asset_id,
+                    target_asset_id: *output_asset_id,
+                },
+                source_type,
+            ))
+            || Self::is_paused(PauseScope::Pool(
+                *dex_id,
+                TradingPair {
+                    base_asset_id: *output_asset_id,
+                    target_asset_id: *input_asset_id,
+                },
+                source_type,
+            ));
+        ensure!(!paused, Error::<T>::TradingIsPaused);
+        Ok(())
     }
 }
 
 impl<T: Config> Pallet<T> {
-    /// List liquidity source types which are enabled on chain, this applies to all DEX'es.
-    /// Used in aggregation pallets, such as liquidity-proxy.
-    pub fn get_supported_types() -> Vec<LiquiditySourceType> {
-        EnabledSourceTypes::<T>::get()
+    /// List liquidity source types which are enabled in the DEX, the ones enabled by default
+    /// unless the DEX has its own setting. Used in aggregation pallets, such as liquidity-proxy.
+    pub fn get_supported_types(dex_id: &T::DEXId) -> Vec<LiquiditySourceType> {
+        DEXEnabledSourceTypes::<T>::get(dex_id).unwrap_or_else(EnabledSourceTypes::<T>::get)
+    }
+
+    /// Ensures the liquidity source type is enabled in the DEX and for the trading pairs the
+    /// exchange goes through, and trading in the pool is not paused.
+    fn ensure_can_trade(
+        liquidity_source_id: &LiquiditySourceId<T::DEXId, LiquiditySourceType>,
+        input_asset_id: &T::AssetId,
+        output_asset_id: &T::AssetId,
+    ) -> DispatchResult {
+        ensure!(
+            Self::get_supported_types(&liquidity_source_id.dex_id)
+                .contains(&liquidity_source_id.liquidity_source_index),
+            Error::<T>::LiquiditySourceDisabled
+        );
+        Self::ensure_enabled_for_trading_pairs(
+            liquidity_source_id,
+            input_asset_id,
+            output_asset_id,
+        )?;
+        Self::ensure_trading_not_paused(
+            &liquidity_source_id.dex_id,
+            liquidity_source_id.liquidity_source_index,
+            input_asset_id,
+            output_asset_id,
+        )
+    }
+
+    /// Ensures the liquidity source type is enabled for the trading pair of the exchanged assets
+    /// or, if they don't form a registered trading pair, for the trading pairs of the DEX base
+    /// asset with each of them. Assets of unregistered trading pairs can't be exchanged.
+    fn ensure_enabled_for_trading_pairs(
+        liquidity_source_id: &LiquiditySourceId<T::DEXId, LiquiditySourceType>,
+        input_asset_id: &T::AssetId,
+        output_asset_id: &T::AssetId,
+    ) -> DispatchResult {
+        let dex_id = &liquidity_source_id.dex_id;
+        let source_type = &liquidity_source_id.liquidity_source_index;
+        let enabled_sources = |&asset_id_a: &T::AssetId, &asset_id_b: &T::AssetId| {
+            trading_pair::Pallet::<T>::enabled_sources(
+                dex_id,
+                &TradingPair {
+                    base_asset_id: asset_id_a,
+                    target_asset_id: asset_id_b,
+                },
+            )
+            .or_else(|| {
+                trading_pair::Pallet::<T>::enabled_sources(
+                    dex_id,
+                    &TradingPair {
+                        base_asset_id: asset_id_b,
+                        target_asset_id: asset_id_a,
+                    },
+                )
+            })
+        };
+        let is_enabled = match enabled_sources(input_asset_id, output_asset_id) {
+            Some(sources) => sources.contains(source_type),
+            None => {
+                let base_asset_id = DEXManager::<T>::get_dex_info(dex_id)?.base_asset_id;
+                [*input_asset_id, *output_asset_id]
+                    .iter()
+                    .filter(|asset_id| **asset_id != base_asset_id)
+                    .all(|asset_id| {
+                        enabled_sources(&base_asset_id, asset_id)
+                            .map(|sources| sources.contains(source_type))
+                            .unwrap_or(false)
+                    })
+            }
+        };
+        ensure!(
+            is_enabled,
+            Error::<T>::LiquiditySourceDisabledForTradingPair
+        );
+        Ok(())
+    }
+
+    /// Ensures the origin is either root or a manager of the DEX.
+    fn ensure_can_manage_sources(origin: OriginFor<T>, dex_id: &T::DEXId) -> DispatchResult {
+        if ensure_root(origin.clone()).is_ok() {
+            DEXManager::<T>::ensure_dex_exists(dex_id)
+        } else {
+            DEXManager::<T>::ensure_can_manage(dex_id, origin, ManagementMode::Private)?;
+            Ok(())
+        }
+    }
+
+    /// Ensures the origin is either root or an account permitted to pause trading in the scope.
+    fn ensure_can_pause(origin: OriginFor<T>, scope: &PauseScopeOf<T>) -> DispatchResult {
+        if let Some(dex_id) = scope.dex_id() {
+            DEXManager::<T>::ensure_dex_exists(dex_id)?;
+        }
+        if ensure_root(origin.clone()).is_ok() {
+            return Ok(());
+        }
+        let who = ensure_signed(origin)?;
+        let permission_scope = match scope.dex_id() {
+            Some(dex_id) => Scope::Limited(hash(dex_id)),
+            None => Scope::Unlimited,
+        };
+        permissions::Pallet::<T>::check_permission_with_scope(
+            who,
+            PAUSE_TRADING,
+            &permission_scope,
+        )?;
+        Ok(())
     }
 }
 
@@ -173,7 +361,7 @@ impl<T: Config>
         output_asset_id: &T::AssetId,
         filter: LiquiditySourceFilter<T::DEXId, LiquiditySourceType>,
     ) -> Result<Vec<LiquiditySourceId<T::DEXId, LiquiditySourceType>>, DispatchError> {
-        let supported_types = Self::get_supported_types();
+        let supported_types = Self::get_supported_types(&filter.dex_id);
         DEXManager::<T>::ensure_dex_exists(&filter.dex_id)?;
         Ok(supported_types
             .iter()
@@ -211,55 +399,9 @@ pub mod pallet {
         frame_system::Config + common::Config + dex_manager::Config + trading_pair::Config
     {
         type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
-        type MockLiquiditySource: LiquiditySource<
-            Self::DEXId,
-            Self::AccountId,
-            Self::AssetId,
-            Balance,
-            DispatchError,
-        >;
-        type MockLiquiditySource2: LiquiditySource<
-            Self::DEXId,
-            Self::AccountId,
-            Self::AssetId,
-            Balance,
-            DispatchError,
-        >;
-        type MockLiquiditySource3: LiquiditySource<
-            Self::DEXId,
-            Self::AccountId,
-            Self::AssetId,
-            Balance,
-            DispatchError,
-        >;
-        type MockLiquiditySource4: LiquiditySource<
-            Self::DEXId,
-            Self::AccountId,
-            Self::AssetId,
-            Balance,
-            DispatchError,
-        >;
-        type BondingCurvePool: LiquiditySource<
-            Self::DEXId,
-            Self::AccountId,
-            Self::AssetId,
-            Balance,
-            DispatchError,
-        >;
-        type MulticollateralBondingCurvePool: LiquiditySource<
-            Self::DEXId,
-            Self::AccountId,
-            Self::AssetId,
-            Balance,
-            DispatchError,
-        >;
-        type XYKPool: LiquiditySource<
-            Self::DEXId,
-            Self::AccountId,
-            Self::AssetId,
-            Balance,
-            DispatchError,
-        >;
+        /// Liquidity sources the exchanges are performed with, e.g. a tuple of the liquidity
+        /// source pallets, see `LiquiditySourceRegistry`.
+        type LiquiditySources: LiquiditySourceRegistry<Self::DEXId, Self::AccountId, Self::AssetId>;
         /// Weight information for extrinsics in this pallet.
         type WeightInfo: WeightInfo;
     }
@@ -284,7 +426,11 @@ pub mod pallet {
         ///            determined by `swap_variant` parameter.
         /// - `swap_variant`: Either 'WithDesiredInput' or 'WithDesiredOutput', indicates amounts purpose.
         /// - `receiver`: Optional value, indicates AccountId for swap receiver. If not set, default is `sender`.
-        #[pallet::weight(<T as Config>::WeightInfo::swap())]
+        #[pallet::weight(
+            <T as Config>::WeightInfo::swap()
+                .saturating_add(T::LiquiditySources::exchange_weight())
+                .saturating_add(trading_pair::Pallet::<T>::record_trade_weight())
+        )]
         pub fn swap(
             origin: OriginFor<T>,
             dex_id: T::DEXId,
@@ -326,10 +472,99 @@ pub mod pallet {
             ));
             Ok(().into())
         }
+
+        /// Enable the liquidity source type in the DEX, the DEX stops following the default
+        /// set of enabled source types.
+        ///
+        /// - `origin`: the root account or a manager of the DEX,
+        /// - `dex_id`: ID of the exchange,
+        /// - `source_type`: type of the liquidity source to enable.
+        #[pallet::weight(<T as Config>::WeightInfo::enable_liquidity_source())]
+        pub fn enable_liquidity_source(
+            origin: OriginFor<T>,
+            dex_id: T::DEXId,
+            source_type: LiquiditySourceType,
+        ) -> DispatchResultWithPostInfo {
+            Self::ensure_can_manage_sources(origin, &dex_id)?;
+            ensure!(
+                T::LiquiditySources::source_types().contains(&source_type),
+                Error::<T>::UnknownLiquiditySourceType
+            );
+            let mut source_types = Self::get_supported_types(&dex_id);
+            ensure!(
+                !source_types.contains(&source_type),
+                Error::<T>::LiquiditySourceAlreadyEnabled
+            );
+            source_types.push(source_type);
+            DEXEnabledSourceTypes::<T>::insert(dex_id, source_types);
+            Self::deposit_event(Event::LiquiditySourceEnabled(dex_id, source_type));
+            Ok(().into())
+        }
+
+        /// Disable the liquidity source type in the DEX, the DEX stops following the default
+        /// set of enabled source types.
+        ///
+        /// - `origin`: the root account or a manager of the DEX,
+        /// - `dex_id`: ID of the exchange,
+        /// - `source_type`: type of the liquidity source to disable.
+        #[pallet::weight(<T as Config>::WeightInfo::disable_liquidity_source())]
+        pub fn disable_liquidity_source(
+            origin: OriginFor<T>,
+            dex_id: T::DEXId,
+            source_type: LiquiditySourceType,
+        ) -> DispatchResultWithPostInfo {
+            Self::ensure_can_manage_sources(origin, &dex_id)?;
+            let mut source_types = Self::get_supported_types(&dex_id);
+            ensure!(
+                source_types.contains(&source_type),
+                Error::<T>::LiquiditySourceAlreadyDisabled
+            );
+            source_types.retain(|enabled| *enabled != source_type);
+            DEXEnabledSourceTypes::<T>::insert(dex_id, source_types);
+            Self::deposit_event(Event::LiquiditySourceDisabled(dex_id, source_type));
+            Ok(().into())
+        }
+
+        /// Pause trading in the scope. Liquidity can still be withdrawn from the paused pools.
+        ///
+        /// - `origin`: the root account or an account with the `PAUSE_TRADING` permission,
+        /// - `scope`: a pool of the DEX, a liquidity source type or a whole DEX to pause.
+        #[pallet::weight(<T as Config>::WeightInfo::pause_trading())]
+        pub fn pause_trading(
+            origin: OriginFor<T>,
+            scope: PauseScopeOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            Self::ensure_can_pause(origin, &scope)?;
+            ensure!(!Self::is_paused(scope), Error::<T>::TradingAlreadyPaused);
+            PausedScopes::<T>::insert(scope, true);
+            Self::deposit_event(Event::TradingPaused(scope));
+            Ok(().into())
+        }
+
+        /// Resume trading in the previously paused scope.
+        ///
+        /// - `origin`: the root account or an account with the `PAUSE_TRADING` permission,
+        /// - `scope`: the paused scope.
+        #[pallet::weight(<T as Config>::WeightInfo::resume_trading())]
+        pub fn resume_trading(
+            origin: OriginFor<T>,
+            scope: PauseScopeOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            Self::ensure_can_pause(origin, &scope)?;
+            ensure!(Self::is_paused(scope), Error::<T>::TradingNotPaused);
+            PausedScopes::<T>::remove(scope);
+            Self::deposit_event(Event::TradingResumed(scope));
+            Ok(().into())
+        }
     }
 
     #[pallet::event]
-    #[pallet::metadata(AccountIdOf<T> = "AccountId", AssetIdOf<T> = "AssetId", DexIdOf<T> = "DEXId")]
+    #[pallet::metadata(
+        AccountIdOf<T> = "AccountId",
+        AssetIdOf<T> = "AssetId",
+        DexIdOf<T> = "DEXId",
+        PauseScopeOf<T> = "PauseScope"
+    )]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
         /// Exchange of tokens has been performed
@@ -345,11 +580,51 @@ pub mod pallet {
             Balance,
             Balance,
         ),
+        /// Liquidity source type has been enabled in the DEX [DEX Id, LiquiditySourceType]
+        LiquiditySourceEnabled(DexIdOf<T>, LiquiditySourceType),
+        /// Liquidity source type has been disabled in the DEX [DEX Id, LiquiditySourceType]
+        LiquiditySourceDisabled(DexIdOf<T>, LiquiditySourceType),
+        /// Trading has been paused in the scope [Scope]
+        TradingPaused(PauseScopeOf<T>),
+        /// Trading has been resumed in the scope [Scope]
+        TradingResumed(PauseScopeOf<T>),
     }
 
+    #[pallet::error]
+    pub enum Error<T> {
+        /// No liquidity source of the given type is configured in the runtime.
+        UnknownLiquiditySourceType,
+        /// The liquidity source type is already enabled in the DEX.
+        LiquiditySourceAlreadyEnabled,
+        /// The liquidity source type is already disabled in the DEX.
+        LiquiditySourceAlreadyDisabled,
+        /// The liquidity source type is disabled in the DEX.
+        LiquiditySourceDisabled,
+        /// The liquidity source type is not enabled for the trading pair.
+        LiquiditySourceDisabledForTradingPair,
+        /// Trading is paused by the emergency pause.
+        TradingIsPaused,
+        /// Trading is already paused in the scope.
+        TradingAlreadyPaused,
+        /// Trading is not paused in the scope.
+        TradingNotPaused,
+    }
+
+    /// Liquidity source types enabled in the DEXes without their own setting.
     #[pallet::storage]
     pub type EnabledSourceTypes<T: Config> = StorageValue<_, Vec<LiquiditySourceType>, ValueQuery>;
 
+    /// Liquidity source types enabled in the DEX, overrides `EnabledSourceTypes`.
+    #[pallet::storage]
+    pub type DEXEnabledSourceTypes<T: Config> =
+        StorageMap<_, Twox64Concat, T::DEXId, Vec<LiquiditySourceType>>;
+
+    /// Scopes where trading is paused by the emergency pause.
+    #[pallet::storage]
+    #[pallet::getter(fn is_paused)]
+    pub type PausedScopes<T: Config> =
+        StorageMap<_, Blake2_128Concat, PauseScopeOf<T>, bool, ValueQuery>;
+
     #[pallet::genesis_config]
     pub struct GenesisConfig {
         pub source_types: Vec<LiquiditySourceType>,
@@ -32,15 +32,16 @@ use crate::{self as dex_api, Config};
 use common::mock::ExistentialDeposits;
 use common::prelude::Balance;
 use common::{
-    balance, fixed, fixed_from_basis_points, hash, Amount, AssetId32, DEXInfo, Fixed,
+    balance, fixed, fixed_from_basis_points, hash, Amount, AssetId32, BasisPoints, DEXInfo, Fixed,
     LiquiditySourceType, DOT, KSM, XOR,
 };
 use currencies::BasicCurrencyAdapter;
 use frame_support::traits::GenesisBuild;
+use frame_support::weights::constants::RocksDbWeight;
 use frame_support::weights::Weight;
 use frame_support::{construct_runtime, parameter_types};
 use hex_literal::hex;
-use permissions::{Scope, INIT_DEX, MANAGE_DEX};
+use permissions::{Scope, INIT_DEX, MANAGE_DEX, PAUSE_TRADING};
 use sp_core::crypto::AccountId32;
 use sp_core::H256;
 use sp_runtime::testing::Header;
@@ -86,6 +87,7 @@ parameter_types! {
     pub const GetBurnUpdateFrequency: BlockNumber = 14400;
     pub GetIncentiveAssetId: AssetId = common::PSWAP.into();
     pub GetParliamentAccountId: AccountId = AccountId32::from([8; 32]);
+    pub const MaxDexFee: BasisPoints = 100;
 }
 
 construct_runtime! {
@@ -106,8 +108,8 @@ construct_runtime! {
         MockLiquiditySource3: mock_liquidity_source::<Instance3>::{Pallet, Call, Config<T>, Storage},
         MockLiquiditySource4: mock_liquidity_source::<Instance4>::{Pallet, Call, Config<T>, Storage},
         Technical: technical::{Pallet, Call, Storage, Event<T>},
-        DexManager: dex_manager::{Pallet, Call, Storage},
-        TradingPair: trading_pair::{Pallet, Call, Storage, Event<T>},
+        DexManager: dex_manager::{Pallet, Call, Storage, Event<T>},
+        TradingPair: trading_pair::{Pallet, Call, Config<T>, Storage, Event<T>},
         PoolXyk: pool_xyk::{Pallet, Call, Storage, Event<T>},
         //PswapDistribution: pswap_distribution::{Pallet, Call, Storage, Event<T>},
 
@@ -134,7 +136,7 @@ impl frame_system::Config for Runtime {
     type Header = Header;
     type Event = Event;
     type BlockHashCount = BlockHashCount;
-    type DbWeight = ();
+    type DbWeight = RocksDbWeight;
     type Version = ();
     type AccountData = pallet_balances::AccountData<Balance>;
     type OnNewAccount = ();
@@ -156,17 +158,13 @@ impl cumulus_pallet_parachain_system::Config for Runtime {
 
 impl Config for Runtime {
     type Event = Event;
-    type MockLiquiditySource =
-        mock_liquidity_source::Module<Runtime, mock_liquidity_source::Instance1>;
-    type MockLiquiditySource2 =
-        mock_liquidity_source::Module<Runtime, mock_liquidity_source::Instance2>;
-    type MockLiquiditySource3 =
-        mock_liquidity_source::Module<Runtime, mock_liquidity_source::Instance3>;
-    type MockLiquiditySource4 =
-        mock_liquidity_source::Module<Runtime, mock_liquidity_source::Instance4>;
-    type BondingCurvePool = ();
-    type MulticollateralBondingCurvePool = ();
-    type XYKPool = pool_xyk::Module<Runtime>;
+    type LiquiditySources = (
+        pool_xyk::Module<Runtime>,
+        mock_liquidity_source::Module<Runtime, mock_liquidity_source::Instance1>,
+        mock_liquidity_source::Module<Runtime, mock_liquidity_source::Instance2>,
+        mock_liquidity_source::Module<Runtime, mock_liquidity_source::Instance3>,
+        mock_liquidity_source::Module<Runtime, mock_liquidity_source::Instance4>,
+    );
     type WeightInfo = ();
 }
 
@@ -200,6 +198,7 @@ impl assets::Config for Runtime {
     type AssetId = AssetId;
     type GetBaseAssetId = GetBaseAssetId;
     type Currency = currencies::Module<Runtime>;
+    type IsAssetInUse = ();
     type WeightInfo = ();
 }
 
@@ -253,7 +252,11 @@ impl technical::Config for Runtime {
     type WeightInfo = ();
 }
 
-impl dex_manager::Config for Runtime {}
+impl dex_manager::Config for Runtime {
+    type Event = Event;
+    type MaxFee = MaxDexFee;
+    type WeightInfo = ();
+}
 
 impl trading_pair::Config for Runtime {
     type Event = Event;
@@ -271,6 +274,7 @@ impl pool_xyk::Config for Runtime {
     type PolySwapAction =
         pool_xyk::PolySwapAction<AssetId, TechAssetId, Balance, AccountId, TechAccountId>;
     type EnsureDEXManager = dex_manager::Pallet<Runtime>;
+    type EnsureTradingNotPaused = dex_api::Pallet<Runtime>;
     type WeightInfo = ();
 }
 
@@ -300,10 +304,17 @@ pub struct ExtBuilder {
     initial_permission_owners: Vec<(u32, Scope, Vec<AccountId>)>,
     initial_permissions: Vec<(AccountId, Scope, Vec<u32>)>,
     source_types: Vec<LiquiditySourceType>,
+    enabled_sources: Vec<(DEXId, AssetId, AssetId, LiquiditySourceType)>,
 }
 
 impl Default for ExtBuilder {
     fn default() -> Self {
+        let mock_sources = [
+            LiquiditySourceType::MockPool,
+            LiquiditySourceType::MockPool2,
+            LiquiditySourceType::MockPool3,
+            LiquiditySourceType::MockPool4,
+        ];
         Self {
             endowed_accounts: vec![
                 (alice(), XOR, balance!(1000000000000000000)),
@@ -335,6 +346,7 @@ impl Default for ExtBuilder {
                     DEXInfo {
                         base_asset_id: GetBaseAssetId::get(),
                         is_public: true,
+                        default_fee: 30,
                     },
                 ),
                 (
@@ -342,6 +354,7 @@ impl Default for ExtBuilder {
                     DEXInfo {
                         base_asset_id: GetBaseAssetId::get(),
                         is_public: true,
+                        default_fee: 30,
                     },
                 ),
             ],
@@ -349,10 +362,19 @@ impl Default for ExtBuilder {
                 (INIT_DEX, Scope::Unlimited, vec![alice()]),
                 (MANAGE_DEX, Scope::Limited(hash(&DEX_A_ID)), vec![alice()]),
                 (MANAGE_DEX, Scope::Limited(hash(&DEX_B_ID)), vec![alice()]),
+                (
+                    PAUSE_TRADING,
+                    Scope::Limited(hash(&DEX_A_ID)),
+                    vec![alice()],
+                ),
             ],
             initial_permissions: vec![
                 (alice(), Scope::Unlimited, vec![INIT_DEX]),
-                (alice(), Scope::Limited(hash(&DEX_A_ID)), vec![MANAGE_DEX]),
+                (
+                    alice(),
+                    Scope::Limited(hash(&DEX_A_ID)),
+                    vec![MANAGE_DEX, PAUSE_TRADING],
+                ),
                 (alice(), Scope::Limited(hash(&DEX_B_ID)), vec![MANAGE_DEX]),
             ],
             source_types: vec![
@@ -361,6 +383,16 @@ impl Default for ExtBuilder {
                 LiquiditySourceType::MockPool3,
                 LiquiditySourceType::MockPool4,
             ],
+            enabled_sources: mock_sources
+                .iter()
+                .flat_map(|source_type| {
+                    vec![
+                        (DEX_A_ID, XOR, DOT, *source_type),
+                        (DEX_A_ID, XOR, KSM, *source_type),
+                        (DEX_B_ID, XOR, DOT, *source_type),
+                    ]
+                })
+                .collect(),
         }
     }
 }
@@ -426,6 +458,36 @@ impl ExtBuilder {
         )
         .unwrap();
 
-        t.into()
+        let enabled_sources = self.enabled_sources;
+        let mut trading_pairs: Vec<(DEXId, common::TradingPair<AssetId>)> = Vec::new();
+        for (dex_id, base_asset_id, target_asset_id, _) in &enabled_sources {
+            let pair = (
+                *dex_id,
+                common::TradingPair {
+                    base_asset_id: *base_asset_id,
+                    target_asset_id: *target_asset_id,
+                },
+            );
+            if !trading_pairs.contains(&pair) {
+                trading_pairs.push(pair);
+            }
+        }
+        trading_pair::GenesisConfig::<Runtime> { trading_pairs }
+            .assimilate_storage(&mut t)
+            .unwrap();
+
+        let mut ext: sp_io::TestExternalities = t.into();
+        ext.execute_with(|| {
+            for (dex_id, base_asset_id, target_asset_id, source_type) in enabled_sources {
+                trading_pair::Pallet::<Runtime>::enable_source_for_trading_pair(
+                    &dex_id,
+                    &base_asset_id,
+                    &target_asset_id,
+                    source_type,
+                )
+                .unwrap();
+            }
+        });
+        ext
     }
 }
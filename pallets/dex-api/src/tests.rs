@@ -29,12 +29,13 @@
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 use crate::mock::*;
-use crate::Module;
+use crate::{Error, Module, PauseScope};
 use common::prelude::SwapAmount;
 use common::{
     balance, LiquidityRegistry, LiquiditySource, LiquiditySourceFilter, LiquiditySourceId,
-    LiquiditySourceType, DOT, XOR,
+    LiquiditySourceRegistry, LiquiditySourceType, TradingPair, DOT, KSM, XOR,
 };
+use frame_support::{assert_noop, assert_ok};
 
 type DexApi = Module<Runtime>;
 
@@ -137,3 +138,333 @@ fn test_different_reserves_should_pass() {
         );
     })
 }
+
+#[test]
+fn test_unknown_source_type_should_fail() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        let source_id = LiquiditySourceId::new(DEX_A_ID, LiquiditySourceType::BondingCurvePool);
+        assert!(!DexApi::can_exchange(&source_id, &XOR, &DOT));
+        assert_eq!(
+            DexApi::quote(
+                &source_id,
+                &XOR,
+                &DOT,
+                SwapAmount::with_desired_input(balance!(100), 0),
+            ),
+            Err(Error::<Runtime>::UnknownLiquiditySourceType.into())
+        );
+    })
+}
+
+#[test]
+fn test_disable_liquidity_source_should_pass() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(DexApi::disable_liquidity_source(
+            Origin::signed(alice()),
+            DEX_A_ID,
+            LiquiditySourceType::MockPool2,
+        ));
+        assert_eq!(
+            DexApi::get_supported_types(&DEX_A_ID),
+            vec![
+                LiquiditySourceType::MockPool,
+                LiquiditySourceType::MockPool3,
+                LiquiditySourceType::MockPool4,
+            ]
+        );
+        assert!(System::events().iter().any(|record| record.event
+            == crate::Event::LiquiditySourceDisabled(DEX_A_ID, LiquiditySourceType::MockPool2)
+                .into()));
+        let list =
+            DexApi::list_liquidity_sources(&XOR, &DOT, LiquiditySourceFilter::empty(DEX_A_ID))
+                .expect("Failed to list available sources.");
+        assert!(!list.contains(&LiquiditySourceId::new(
+            DEX_A_ID,
+            LiquiditySourceType::MockPool2
+        )));
+        // Other DEXes keep the default setting.
+        assert!(DexApi::get_supported_types(&DEX_B_ID).contains(&LiquiditySourceType::MockPool2));
+        assert_noop!(
+            DexApi::disable_liquidity_source(
+                Origin::root(),
+                DEX_A_ID,
+                LiquiditySourceType::MockPool2,
+            ),
+            Error::<Runtime>::LiquiditySourceAlreadyDisabled
+        );
+        assert_ok!(DexApi::enable_liquidity_source(
+            Origin::root(),
+            DEX_A_ID,
+            LiquiditySourceType::MockPool2,
+        ));
+        assert!(DexApi::get_supported_types(&DEX_A_ID).contains(&LiquiditySourceType::MockPool2));
+    })
+}
+
+#[test]
+fn test_exchange_should_fail_with_disabled_source() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        let source_id = LiquiditySourceId::new(DEX_A_ID, LiquiditySourceType::MockPool2);
+        assert!(DexApi::can_exchange(&source_id, &XOR, &DOT));
+        assert_ok!(DexApi::disable_liquidity_source(
+            Origin::signed(alice()),
+            DEX_A_ID,
+            LiquiditySourceType::MockPool2,
+        ));
+        assert!(!DexApi::can_exchange(&source_id, &XOR, &DOT));
+        assert_noop!(
+            DexApi::exchange(
+                &alice(),
+                &alice(),
+                &source_id,
+                &XOR,
+                &DOT,
+                SwapAmount::with_desired_input(balance!(100), 0),
+            ),
+            Error::<Runtime>::LiquiditySourceDisabled
+        );
+        // Other DEXes keep trading through the source.
+        assert!(DexApi::can_exchange(
+            &LiquiditySourceId::new(DEX_B_ID, LiquiditySourceType::MockPool2),
+            &XOR,
+            &DOT
+        ));
+    })
+}
+
+#[test]
+fn test_exchange_should_fail_with_source_disabled_for_trading_pair() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        let source_id = LiquiditySourceId::new(DEX_A_ID, LiquiditySourceType::MockPool2);
+        assert!(DexApi::can_exchange(&source_id, &XOR, &DOT));
+        assert!(DexApi::can_exchange(&source_id, &KSM, &DOT));
+        assert_ok!(
+            trading_pair::Pallet::<Runtime>::disable_source_for_trading_pair(
+                Origin::signed(alice()),
+                DEX_A_ID,
+                XOR,
+                DOT,
+                LiquiditySourceType::MockPool2,
+            )
+        );
+        assert!(!DexApi::can_exchange(&source_id, &DOT, &XOR));
+        // Assets without a trading pair of their own are exchanged through the pairs with
+        // the base asset, all of which have to enable the source.
+        assert!(!DexApi::can_exchange(&source_id, &KSM, &DOT));
+        assert!(DexApi::can_exchange(&source_id, &XOR, &KSM));
+        assert_noop!(
+            DexApi::exchange(
+                &alice(),
+                &alice(),
+                &source_id,
+                &XOR,
+                &DOT,
+                SwapAmount::with_desired_input(balance!(100), 0),
+            ),
+            Error::<Runtime>::LiquiditySourceDisabledForTradingPair
+        );
+        // Other DEXes keep trading the pair through the source.
+        assert!(DexApi::can_exchange(
+            &LiquiditySourceId::new(DEX_B_ID, LiquiditySourceType::MockPool2),
+            &XOR,
+            &DOT
+        ));
+    })
+}
+
+#[test]
+fn test_exchange_should_fail_with_deregistered_trading_pair() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        let source_types = [
+            LiquiditySourceType::MockPool,
+            LiquiditySourceType::MockPool2,
+            LiquiditySourceType::MockPool3,
+            LiquiditySourceType::MockPool4,
+        ];
+        for source_type in source_types.iter() {
+            assert_ok!(
+                trading_pair::Pallet::<Runtime>::disable_source_for_trading_pair(
+                    Origin::signed(alice()),
+                    DEX_A_ID,
+                    XOR,
+                    DOT,
+                    *source_type,
+                )
+            );
+        }
+        assert_ok!(trading_pair::Pallet::<Runtime>::deregister(
+            Origin::signed(alice()),
+            DEX_A_ID,
+            XOR,
+            DOT,
+        ));
+        for source_type in source_types.iter() {
+            let source_id = LiquiditySourceId::new(DEX_A_ID, *source_type);
+            assert!(!DexApi::can_exchange(&source_id, &XOR, &DOT));
+            assert_noop!(
+                DexApi::exchange(
+                    &alice(),
+                    &alice(),
+                    &source_id,
+                    &DOT,
+                    &XOR,
+                    SwapAmount::with_desired_input(balance!(100), 0),
+                ),
+                Error::<Runtime>::LiquiditySourceDisabledForTradingPair
+            );
+        }
+        // Sources of the pair of the remaining asset aren't affected.
+        assert!(DexApi::can_exchange(
+            &LiquiditySourceId::new(DEX_A_ID, LiquiditySourceType::MockPool),
+            &XOR,
+            &KSM
+        ));
+    })
+}
+
+#[test]
+fn test_enable_liquidity_source_should_fail() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        assert_noop!(
+            DexApi::enable_liquidity_source(
+                Origin::signed(bob()),
+                DEX_A_ID,
+                LiquiditySourceType::XYKPool,
+            ),
+            permissions::Error::<Runtime>::Forbidden
+        );
+        assert_noop!(
+            DexApi::enable_liquidity_source(
+                Origin::signed(alice()),
+                DEX_A_ID,
+                LiquiditySourceType::BondingCurvePool,
+            ),
+            Error::<Runtime>::UnknownLiquiditySourceType
+        );
+        assert_noop!(
+            DexApi::enable_liquidity_source(
+                Origin::signed(alice()),
+                DEX_A_ID,
+                LiquiditySourceType::MockPool,
+            ),
+            Error::<Runtime>::LiquiditySourceAlreadyEnabled
+        );
+        assert_ok!(DexApi::enable_liquidity_source(
+            Origin::signed(alice()),
+            DEX_A_ID,
+            LiquiditySourceType::XYKPool,
+        ));
+    })
+}
+
+#[test]
+fn test_pause_trading_should_pass() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        System::set_block_number(1);
+        let source_id = LiquiditySourceId::new(DEX_A_ID, LiquiditySourceType::MockPool);
+        let scope = PauseScope::Pool(
+            DEX_A_ID,
+            TradingPair {
+                base_asset_id: XOR,
+                target_asset_id: DOT,
+            },
+            LiquiditySourceType::MockPool,
+        );
+        assert_ok!(DexApi::pause_trading(Origin::signed(alice()), scope));
+        assert!(System::events()
+            .iter()
+            .any(|record| record.event == crate::Event::TradingPaused(scope).into()));
+        assert!(!DexApi::can_exchange(&source_id, &XOR, &DOT));
+        assert!(!DexApi::can_exchange(&source_id, &DOT, &XOR));
+        assert!(DexApi::can_exchange(
+            &LiquiditySourceId::new(DEX_A_ID, LiquiditySourceType::MockPool2),
+            &XOR,
+            &DOT
+        ));
+        assert_noop!(
+            DexApi::exchange(
+                &alice(),
+                &alice(),
+                &source_id,
+                &XOR,
+                &DOT,
+                SwapAmount::with_desired_input(balance!(100), 0),
+            ),
+            Error::<Runtime>::TradingIsPaused
+        );
+        assert_ok!(DexApi::resume_trading(Origin::signed(alice()), scope));
+        assert!(DexApi::can_exchange(&source_id, &XOR, &DOT));
+
+        assert_ok!(DexApi::pause_trading(
+            Origin::root(),
+            PauseScope::DEX(DEX_A_ID)
+        ));
+        let list =
+            DexApi::list_liquidity_sources(&XOR, &DOT, LiquiditySourceFilter::empty(DEX_A_ID))
+                .expect("Failed to list available sources.");
+        assert!(list.is_empty());
+        assert!(DexApi::can_exchange(
+            &LiquiditySourceId::new(DEX_B_ID, LiquiditySourceType::MockPool),
+            &XOR,
+            &DOT
+        ));
+    })
+}
+
+#[test]
+fn test_pause_trading_should_fail() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        assert_noop!(
+            DexApi::pause_trading(Origin::signed(bob()), PauseScope::DEX(DEX_A_ID)),
+            permissions::Error::<Runtime>::Forbidden
+        );
+        assert_noop!(
+            DexApi::pause_trading(Origin::signed(alice()), PauseScope::DEX(DEX_B_ID)),
+            permissions::Error::<Runtime>::Forbidden
+        );
+        assert_noop!(
+            DexApi::pause_trading(
+                Origin::signed(alice()),
+                PauseScope::SourceType(LiquiditySourceType::MockPool)
+            ),
+            permissions::Error::<Runtime>::Forbidden
+        );
+        assert_noop!(
+            DexApi::resume_trading(Origin::root(), PauseScope::DEX(DEX_A_ID)),
+            Error::<Runtime>::TradingNotPaused
+        );
+        assert_ok!(DexApi::pause_trading(
+            Origin::root(),
+            PauseScope::SourceType(LiquiditySourceType::MockPool)
+        ));
+        assert_noop!(
+            DexApi::pause_trading(
+                Origin::root(),
+                PauseScope::SourceType(LiquiditySourceType::MockPool)
+            ),
+            Error::<Runtime>::TradingAlreadyPaused
+        );
+    })
+}
+
+#[test]
+fn test_exchange_weight_should_include_pair_stats_update() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        let record_trade_weight = trading_pair::Pallet::<Runtime>::record_trade_weight();
+        assert!(record_trade_weight > 0);
+        assert_eq!(
+            <DexApi as LiquiditySource<_, _, _, _, _>>::exchange_weight(),
+            <Runtime as crate::Config>::LiquiditySources::exchange_weight() + record_trade_weight
+        );
+    })
+}
@@ -38,13 +38,45 @@ pub struct WeightInfo<T>(PhantomData<T>);
 impl<T: frame_system::Config> crate::WeightInfo for WeightInfo<T> {
     fn swap() -> Weight {
         (2_920_955_000 as Weight)
-            .saturating_add(T::DbWeight::get().reads(15 as Weight))
+            .saturating_add(T::DbWeight::get().reads(21 as Weight))
             .saturating_add(T::DbWeight::get().writes(6 as Weight))
     }
+    fn enable_liquidity_source() -> Weight {
+        (30_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn disable_liquidity_source() -> Weight {
+        (30_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn pause_trading() -> Weight {
+        (25_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn resume_trading() -> Weight {
+        (25_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
 }
 
 impl crate::WeightInfo for () {
     fn swap() -> Weight {
         EXTRINSIC_FIXED_WEIGHT
     }
+    fn enable_liquidity_source() -> Weight {
+        EXTRINSIC_FIXED_WEIGHT
+    }
+    fn disable_liquidity_source() -> Weight {
+        EXTRINSIC_FIXED_WEIGHT
+    }
+    fn pause_trading() -> Weight {
+        EXTRINSIC_FIXED_WEIGHT
+    }
+    fn resume_trading() -> Weight {
+        EXTRINSIC_FIXED_WEIGHT
+    }
 }
@@ -31,15 +31,20 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use assets::AssetIdOf;
+use codec::{Decode, Encode};
 use common::prelude::EnsureDEXManager;
-use common::{hash, ManagementMode};
+use common::{hash, in_basis_points_range, BasisPoints, ManagementMode};
 use frame_support::dispatch::DispatchResult;
 use frame_support::ensure;
 use frame_support::sp_runtime::DispatchError;
+use frame_support::weights::Weight;
 use frame_system::RawOrigin;
-use permissions::{Scope, MANAGE_DEX};
+use permissions::{Scope, INIT_DEX, MANAGE_DEX};
 use sp_std::vec::Vec;
 
+pub mod migrations;
+pub mod weights;
+
 #[cfg(test)]
 mod mock;
 
@@ -48,6 +53,22 @@ mod tests;
 
 type DEXInfo<T> = common::prelude::DEXInfo<AssetIdOf<T>>;
 
+/// Storage layout version, used to run the migrations only once.
+#[derive(Debug, Encode, Decode, Clone, Copy, PartialEq, Eq)]
+pub enum StorageVersion {
+    /// `DEXInfo` has no fee.
+    V1,
+    /// `DEXInfo` has `default_fee`.
+    V2,
+}
+
+pub trait WeightInfo {
+    fn initialize_dex() -> Weight;
+    fn set_public() -> Weight;
+    fn set_default_fee() -> Weight;
+    fn transfer_ownership() -> Weight;
+}
+
 impl<T: Config> EnsureDEXManager<T::DEXId, T::AccountId, DispatchError> for Pallet<T> {
     fn ensure_can_manage<OuterOrigin>(
         dex_id: &T::DEXId,
@@ -88,6 +109,33 @@ impl<T: Config> Pallet<T> {
         DEXInfos::<T>::iter().map(|(k, _)| k).collect()
     }
 
+    /// Ensures the fee is within the basis points range and doesn't exceed `MaxFee`.
+    fn ensure_fee_valid(fee: BasisPoints) -> DispatchResult {
+        ensure!(
+            in_basis_points_range(fee) && fee <= T::MaxFee::get(),
+            Error::<T>::InvalidFeeValue
+        );
+        Ok(())
+    }
+
+    /// Ensures the origin is either root or a direct manager of the DEX.
+    fn ensure_can_change_settings<OuterOrigin>(
+        dex_id: &T::DEXId,
+        origin: OuterOrigin,
+    ) -> DispatchResult
+    where
+        OuterOrigin: Into<Result<RawOrigin<T::AccountId>, OuterOrigin>>,
+    {
+        match origin.into() {
+            Ok(RawOrigin::Root) => Self::ensure_dex_exists(dex_id),
+            Ok(RawOrigin::Signed(who)) => {
+                Self::ensure_dex_exists(dex_id)?;
+                Self::ensure_direct_manager(dex_id, &who)
+            }
+            _ => Err(Error::<T>::InvalidAccountId.into()),
+        }
+    }
+
     fn ensure_direct_manager(dex_id: &T::DEXId, who: &T::AccountId) -> DispatchResult {
         permissions::Pallet::<T>::check_permission_with_scope(
             who.clone(),
@@ -103,21 +151,178 @@ pub use pallet::*;
 #[frame_support::pallet]
 pub mod pallet {
     use super::*;
+    use common::{AccountIdOf, DexIdOf};
     use frame_support::pallet_prelude::*;
     use frame_system::pallet_prelude::*;
 
     #[pallet::config]
-    pub trait Config: frame_system::Config + common::Config + assets::Config {}
+    pub trait Config: frame_system::Config + common::Config + assets::Config {
+        type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+        /// Maximum default fee of a DEX in basis points.
+        #[pallet::constant]
+        type MaxFee: Get<BasisPoints>;
+        /// Weight information for extrinsics in this pallet.
+        type WeightInfo: WeightInfo;
+    }
 
     #[pallet::pallet]
     #[pallet::generate_store(pub(super) trait Store)]
     pub struct Pallet<T>(PhantomData<T>);
 
     #[pallet::hooks]
-    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_runtime_upgrade() -> Weight {
+            migrations::migrate_dex_infos::<T>()
+        }
+    }
 
     #[pallet::call]
-    impl<T: Config> Pallet<T> {}
+    impl<T: Config> Pallet<T> {
+        /// Register a new DEX, the caller becomes its manager.
+        ///
+        /// - `origin`: an account with the `INIT_DEX` permission,
+        /// - `dex_id`: ID of the new exchange,
+        /// - `base_asset_id`: ID of the base asset of the exchange,
+        /// - `default_fee`: default fee of the exchange in basis points, at most `MaxFee`,
+        /// - `is_public`: determines if the exchange can be managed by regular users.
+        #[pallet::weight(<T as Config>::WeightInfo::initialize_dex())]
+        pub fn initialize_dex(
+            origin: OriginFor<T>,
+            dex_id: T::DEXId,
+            base_asset_id: T::AssetId,
+            default_fee: BasisPoints,
+            is_public: bool,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            permissions::Pallet::<T>::check_permission(who.clone(), INIT_DEX)?;
+            ensure!(
+                !DEXInfos::<T>::contains_key(&dex_id),
+                Error::<T>::DEXIdAlreadyExists
+            );
+            Self::ensure_fee_valid(default_fee)?;
+            assets::Pallet::<T>::ensure_asset_exists(&base_asset_id)?;
+            common::with_transaction(|| {
+                permissions::Pallet::<T>::assign_permission(
+                    who.clone(),
+                    &who,
+                    MANAGE_DEX,
+                    Scope::Limited(hash(&dex_id)),
+                )?;
+                DEXInfos::<T>::insert(
+                    dex_id,
+                    DEXInfo::<T> {
+                        base_asset_id,
+                        is_public,
+                        default_fee,
+                    },
+                );
+                Self::deposit_event(Event::DEXInitialized(dex_id, who));
+                Ok(().into())
+            })
+        }
+
+        /// Change whether the DEX can be managed by regular users.
+        ///
+        /// - `origin`: the root account or a manager of the DEX,
+        /// - `dex_id`: ID of the exchange,
+        /// - `is_public`: new value of the flag.
+        #[pallet::weight(<T as Config>::WeightInfo::set_public())]
+        pub fn set_public(
+            origin: OriginFor<T>,
+            dex_id: T::DEXId,
+            is_public: bool,
+        ) -> DispatchResultWithPostInfo {
+            Self::ensure_can_change_settings(&dex_id, origin)?;
+            DEXInfos::<T>::mutate(&dex_id, |dex_info| {
+                if let Some(dex_info) = dex_info {
+                    dex_info.is_public = is_public;
+                }
+            });
+            Self::deposit_event(Event::DEXPublicityChanged(dex_id, is_public));
+            Ok(().into())
+        }
+
+        /// Change the default fee of the DEX, the fee charged by its XYK pools.
+        ///
+        /// - `origin`: the root account or a manager of the DEX,
+        /// - `dex_id`: ID of the exchange,
+        /// - `default_fee`: new default fee in basis points, at most `MaxFee`.
+        #[pallet::weight(<T as Config>::WeightInfo::set_default_fee())]
+        pub fn set_default_fee(
+            origin: OriginFor<T>,
+            dex_id: T::DEXId,
+            default_fee: BasisPoints,
+        ) -> DispatchResultWithPostInfo {
+            Self::ensure_can_change_settings(&dex_id, origin)?;
+            Self::ensure_fee_valid(default_fee)?;
+            DEXInfos::<T>::mutate(&dex_id, |dex_info| {
+                if let Some(dex_info) = dex_info {
+                    dex_info.default_fee = default_fee;
+                }
+            });
+            Self::deposit_event(Event::DEXDefaultFeeChanged(dex_id, default_fee));
+            Ok(().into())
+        }
+
+        /// Transfer the ownership of the `MANAGE_DEX` permission of the DEX to another account,
+        /// the previous owner stops managing the DEX.
+        ///
+        /// - `origin`: the owner of the `MANAGE_DEX` permission of the DEX,
+        /// - `dex_id`: ID of the exchange,
+        /// - `new_owner`: the account to become the owner.
+        #[pallet::weight(<T as Config>::WeightInfo::transfer_ownership())]
+        pub fn transfer_ownership(
+            origin: OriginFor<T>,
+            dex_id: T::DEXId,
+            new_owner: T::AccountId,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            Self::ensure_dex_exists(&dex_id)?;
+            let scope = Scope::Limited(hash(&dex_id));
+            common::with_transaction(|| {
+                permissions::Pallet::<T>::transfer_permission(
+                    who.clone(),
+                    new_owner.clone(),
+                    MANAGE_DEX,
+                    scope,
+                )?;
+                permissions::Pallet::<T>::grant_permission_with_scope(
+                    new_owner.clone(),
+                    new_owner.clone(),
+                    MANAGE_DEX,
+                    scope,
+                )?;
+                if who != new_owner {
+                    match permissions::Pallet::<T>::revoke_permission_with_scope(
+                        new_owner.clone(),
+                        who.clone(),
+                        MANAGE_DEX,
+                        scope,
+                    ) {
+                        // The previous owner might not hold the permission themselves.
+                        Ok(()) | Err(permissions::Error::<T>::PermissionNotFound) => {}
+                        Err(error) => return Err(error.into()),
+                    }
+                }
+                Self::deposit_event(Event::DEXOwnershipTransferred(dex_id, who, new_owner));
+                Ok(().into())
+            })
+        }
+    }
+
+    #[pallet::event]
+    #[pallet::metadata(AccountIdOf<T> = "AccountId", DexIdOf<T> = "DEXId")]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// New DEX has been registered. [DEX Id, Owner Account]
+        DEXInitialized(DexIdOf<T>, AccountIdOf<T>),
+        /// DEX management by regular users has been allowed or forbidden. [DEX Id, Is Public]
+        DEXPublicityChanged(DexIdOf<T>, bool),
+        /// Default fee of DEX has been changed. [DEX Id, Default Fee]
+        DEXDefaultFeeChanged(DexIdOf<T>, BasisPoints),
+        /// Ownership of DEX has been transferred. [DEX Id, Previous Owner, New Owner]
+        DEXOwnershipTransferred(DexIdOf<T>, AccountIdOf<T>, AccountIdOf<T>),
+    }
 
     #[pallet::error]
     pub enum Error<T> {
@@ -125,7 +330,7 @@ pub mod pallet {
         DEXIdAlreadyExists,
         /// DEX with given Id is not registered.
         DEXDoesNotExist,
-        /// Numeric value provided as fee is not valid, e.g. out of basis-point range.
+        /// Numeric value provided as fee is not valid, e.g. out of basis-point range or above `MaxFee`.
         InvalidFeeValue,
         /// Account with given Id is not registered.
         InvalidAccountId,
@@ -135,6 +340,17 @@ pub mod pallet {
     #[pallet::getter(fn dex_id)]
     pub type DEXInfos<T: Config> = StorageMap<_, Twox64Concat, T::DEXId, DEXInfo<T>>;
 
+    #[pallet::type_value]
+    pub(super) fn DefaultForPalletStorageVersion() -> StorageVersion {
+        StorageVersion::V1
+    }
+
+    /// Storage layout version, chains started before the versioning have `V1`.
+    #[pallet::storage]
+    #[pallet::getter(fn pallet_storage_version)]
+    pub(super) type PalletStorageVersion<T: Config> =
+        StorageValue<_, StorageVersion, ValueQuery, DefaultForPalletStorageVersion>;
+
     #[pallet::genesis_config]
     pub struct GenesisConfig<T: Config> {
         pub dex_list: Vec<(T::DEXId, DEXInfo<T>)>,
@@ -154,7 +370,8 @@ pub mod pallet {
         fn build(&self) {
             self.dex_list.iter().for_each(|(dex_id, dex_info)| {
                 DEXInfos::<T>::insert(dex_id.clone(), dex_info);
-            })
+            });
+            PalletStorageVersion::<T>::put(StorageVersion::V2);
         }
     }
 }
@@ -31,7 +31,7 @@
 use crate::{self as dex_manager, Config};
 use common::mock::ExistentialDeposits;
 use common::prelude::Balance;
-use common::{self, fixed_from_basis_points, AssetId32, DEXInfo, Fixed, DOT, XOR};
+use common::{self, fixed_from_basis_points, AssetId32, BasisPoints, DEXInfo, Fixed, DOT, XOR};
 use currencies::BasicCurrencyAdapter;
 use frame_support::traits::GenesisBuild;
 use frame_support::weights::Weight;
@@ -67,6 +67,7 @@ parameter_types! {
     pub const CreationFee: u128 = 0;
     pub const TransactionByteFee: u128 = 1;
     pub GetFee: Fixed = fixed_from_basis_points(30u16);
+    pub const MaxDexFee: BasisPoints = 100;
 }
 
 construct_runtime! {
@@ -76,7 +77,7 @@ construct_runtime! {
         UncheckedExtrinsic = UncheckedExtrinsic,
     {
         System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
-        DexManager: dex_manager::{Pallet, Call, Config<T>, Storage},
+        DexManager: dex_manager::{Pallet, Call, Config<T>, Storage, Event<T>},
         Tokens: tokens::{Pallet, Call, Config<T>, Storage, Event<T>},
         Permissions: permissions::{Pallet, Call, Config<T>, Storage, Event<T>},
         Currencies: currencies::{Pallet, Call, Storage, Event<T>},
@@ -123,7 +124,11 @@ impl cumulus_pallet_parachain_system::Config for Runtime {
     type DownwardMessageHandlers = ();
 }
 
-impl Config for Runtime {}
+impl Config for Runtime {
+    type Event = Event;
+    type MaxFee = MaxDexFee;
+    type WeightInfo = ();
+}
 
 impl tokens::Config for Runtime {
     type Event = Event;
@@ -155,6 +160,7 @@ impl assets::Config for Runtime {
     type AssetId = AssetId;
     type GetBaseAssetId = GetBaseAssetId;
     type Currency = currencies::Module<Runtime>;
+    type IsAssetInUse = ();
     type WeightInfo = ();
 }
 
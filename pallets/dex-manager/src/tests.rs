@@ -29,11 +29,12 @@
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 use crate::mock::*;
-use crate::{Error, Module};
+use crate::{migrations, Error, Module, StorageVersion};
 use common::prelude::DEXInfo;
-use common::{hash, EnsureDEXManager, ManagementMode, VAL, XOR};
+use common::{hash, AssetName, AssetSymbol, EnsureDEXManager, ManagementMode, VAL, XOR};
+use frame_support::traits::Get;
 use frame_support::{assert_noop, assert_ok};
-use permissions::{Scope, MANAGE_DEX};
+use permissions::{Scope, INIT_DEX, MANAGE_DEX};
 
 type DEXModule = Module<Runtime>;
 
@@ -46,6 +47,7 @@ fn test_initialize_dex_should_pass() {
                 DEXInfo {
                     base_asset_id: XOR,
                     is_public: true,
+                    default_fee: 30,
                 },
             ),
             (
@@ -53,6 +55,7 @@ fn test_initialize_dex_should_pass() {
                 DEXInfo {
                     base_asset_id: VAL,
                     is_public: false,
+                    default_fee: 30,
                 },
             ),
         ],
@@ -67,6 +70,7 @@ fn test_initialize_dex_should_pass() {
             Some(DEXInfo {
                 base_asset_id: XOR,
                 is_public: true,
+                default_fee: 30,
             })
         );
         assert_eq!(
@@ -74,6 +78,7 @@ fn test_initialize_dex_should_pass() {
             Some(DEXInfo {
                 base_asset_id: VAL,
                 is_public: false,
+                default_fee: 30,
             })
         );
     })
@@ -87,6 +92,7 @@ fn test_share_manage_dex_permission_should_pass() {
             DEXInfo {
                 base_asset_id: XOR,
                 is_public: false,
+                default_fee: 30,
             },
         )],
         initial_permission_owners: vec![(MANAGE_DEX, Scope::Limited(hash(&DEX_A_ID)), vec![BOB])],
@@ -126,6 +132,7 @@ fn test_own_multiple_dexes_should_pass() {
                 DEXInfo {
                     base_asset_id: XOR,
                     is_public: true,
+                    default_fee: 30,
                 },
             ),
             (
@@ -133,6 +140,7 @@ fn test_own_multiple_dexes_should_pass() {
                 DEXInfo {
                     base_asset_id: XOR,
                     is_public: true,
+                    default_fee: 30,
                 },
             ),
         ],
@@ -165,6 +173,7 @@ fn test_can_manage_on_private_dex_should_pass() {
             DEXInfo {
                 base_asset_id: XOR,
                 is_public: false,
+                default_fee: 30,
             },
         )],
         initial_permission_owners: vec![(MANAGE_DEX, Scope::Limited(hash(&DEX_A_ID)), vec![ALICE])],
@@ -207,6 +216,7 @@ fn test_can_manage_on_public_dex_should_pass() {
             DEXInfo {
                 base_asset_id: XOR,
                 is_public: true,
+                default_fee: 30,
             },
         )],
         initial_permission_owners: vec![(MANAGE_DEX, Scope::Limited(hash(&DEX_A_ID)), vec![ALICE])],
@@ -249,6 +259,7 @@ fn test_ensure_dex_exists_should_pass() {
             DEXInfo {
                 base_asset_id: XOR,
                 is_public: true,
+                default_fee: 30,
             },
         )],
         initial_permission_owners: vec![(MANAGE_DEX, Scope::Limited(hash(&DEX_A_ID)), vec![ALICE])],
@@ -285,6 +296,7 @@ fn test_list_dex_ids_should_pass() {
                 DEXInfo {
                     base_asset_id: XOR,
                     is_public: true,
+                    default_fee: 30,
                 },
             ),
             (
@@ -292,6 +304,7 @@ fn test_list_dex_ids_should_pass() {
                 DEXInfo {
                     base_asset_id: XOR,
                     is_public: true,
+                    default_fee: 30,
                 },
             ),
         ],
@@ -333,3 +346,242 @@ fn test_queries_for_nonexistant_dex_should_fail() {
         );
     })
 }
+
+#[test]
+fn test_initialize_dex_with_extrinsic_should_pass() {
+    let mut ext = ExtBuilder {
+        initial_permission_owners: vec![(INIT_DEX, Scope::Unlimited, vec![ALICE])],
+        initial_permissions: vec![(ALICE, Scope::Unlimited, vec![INIT_DEX])],
+        ..Default::default()
+    }
+    .build();
+    ext.execute_with(|| {
+        System::set_block_number(1);
+        assets::Pallet::<Runtime>::register_asset_id(
+            ALICE,
+            XOR,
+            AssetSymbol(b"XOR".to_vec()),
+            AssetName(b"SORA".to_vec()),
+            18,
+            0,
+            true,
+        )
+        .expect("Failed to register asset.");
+        assert_noop!(
+            DEXModule::initialize_dex(Origin::signed(BOB), DEX_A_ID, XOR, 30, false),
+            permissions::Error::<Runtime>::Forbidden
+        );
+        assert_noop!(
+            DEXModule::initialize_dex(Origin::signed(ALICE), DEX_A_ID, XOR, 10_001, false),
+            Error::<Runtime>::InvalidFeeValue
+        );
+        assert_noop!(
+            DEXModule::initialize_dex(Origin::signed(ALICE), DEX_A_ID, VAL, 30, false),
+            assets::Error::<Runtime>::AssetIdNotExists
+        );
+        assert_ok!(DEXModule::initialize_dex(
+            Origin::signed(ALICE),
+            DEX_A_ID,
+            XOR,
+            30,
+            false
+        ));
+        assert_eq!(
+            DEXModule::dex_id(DEX_A_ID),
+            Some(DEXInfo {
+                base_asset_id: XOR,
+                is_public: false,
+                default_fee: 30,
+            })
+        );
+        assert!(System::events()
+            .iter()
+            .any(|record| record.event == crate::Event::DEXInitialized(DEX_A_ID, ALICE).into()));
+        assert_ok!(DEXModule::ensure_can_manage(
+            &DEX_A_ID,
+            Origin::signed(ALICE),
+            ManagementMode::Private
+        ));
+        assert_noop!(
+            DEXModule::initialize_dex(Origin::signed(ALICE), DEX_A_ID, XOR, 30, false),
+            Error::<Runtime>::DEXIdAlreadyExists
+        );
+    })
+}
+
+#[test]
+fn test_change_dex_settings_should_pass() {
+    let mut ext = ExtBuilder {
+        initial_dex_list: vec![(
+            DEX_A_ID,
+            DEXInfo {
+                base_asset_id: XOR,
+                is_public: false,
+                default_fee: 30,
+            },
+        )],
+        initial_permission_owners: vec![(MANAGE_DEX, Scope::Limited(hash(&DEX_A_ID)), vec![ALICE])],
+        initial_permissions: vec![(ALICE, Scope::Limited(hash(&DEX_A_ID)), vec![MANAGE_DEX])],
+        ..Default::default()
+    }
+    .build();
+    ext.execute_with(|| {
+        System::set_block_number(1);
+        assert_noop!(
+            DEXModule::set_public(Origin::signed(BOB), DEX_A_ID, true),
+            permissions::Error::<Runtime>::Forbidden
+        );
+        assert_noop!(
+            DEXModule::set_public(Origin::signed(ALICE), DEX_B_ID, true),
+            Error::<Runtime>::DEXDoesNotExist
+        );
+        assert_ok!(DEXModule::set_public(Origin::signed(ALICE), DEX_A_ID, true));
+        assert!(
+            System::events()
+                .iter()
+                .any(|record| record.event
+                    == crate::Event::DEXPublicityChanged(DEX_A_ID, true).into())
+        );
+        assert_noop!(
+            DEXModule::set_default_fee(Origin::signed(ALICE), DEX_A_ID, 10_001),
+            Error::<Runtime>::InvalidFeeValue
+        );
+        assert_ok!(DEXModule::set_default_fee(Origin::root(), DEX_A_ID, 20));
+        assert!(System::events()
+            .iter()
+            .any(|record| record.event == crate::Event::DEXDefaultFeeChanged(DEX_A_ID, 20).into()));
+        assert_eq!(
+            DEXModule::dex_id(DEX_A_ID),
+            Some(DEXInfo {
+                base_asset_id: XOR,
+                is_public: true,
+                default_fee: 20,
+            })
+        );
+    })
+}
+
+#[test]
+fn test_dex_fee_should_not_exceed_max_fee() {
+    let mut ext = ExtBuilder {
+        initial_permission_owners: vec![(INIT_DEX, Scope::Unlimited, vec![ALICE])],
+        initial_permissions: vec![(ALICE, Scope::Unlimited, vec![INIT_DEX])],
+        ..Default::default()
+    }
+    .build();
+    ext.execute_with(|| {
+        assets::Pallet::<Runtime>::register_asset_id(
+            ALICE,
+            XOR,
+            AssetSymbol(b"XOR".to_vec()),
+            AssetName(b"SORA".to_vec()),
+            18,
+            0,
+            true,
+        )
+        .expect("Failed to register asset.");
+        let max_fee = MaxDexFee::get();
+        assert_noop!(
+            DEXModule::initialize_dex(Origin::signed(ALICE), DEX_A_ID, XOR, max_fee + 1, false),
+            Error::<Runtime>::InvalidFeeValue
+        );
+        assert_ok!(DEXModule::initialize_dex(
+            Origin::signed(ALICE),
+            DEX_A_ID,
+            XOR,
+            max_fee,
+            false
+        ));
+        assert_noop!(
+            DEXModule::set_default_fee(Origin::root(), DEX_A_ID, max_fee + 1),
+            Error::<Runtime>::InvalidFeeValue
+        );
+        assert_ok!(DEXModule::set_default_fee(Origin::root(), DEX_A_ID, 0));
+        assert_ok!(DEXModule::set_default_fee(
+            Origin::root(),
+            DEX_A_ID,
+            max_fee
+        ));
+        assert_eq!(
+            DEXModule::get_dex_info(&DEX_A_ID)
+                .expect("Failed to get DEX info.")
+                .default_fee,
+            max_fee
+        );
+    })
+}
+
+#[test]
+fn test_transfer_ownership_should_pass() {
+    let mut ext = ExtBuilder {
+        initial_dex_list: vec![(
+            DEX_A_ID,
+            DEXInfo {
+                base_asset_id: XOR,
+                is_public: false,
+                default_fee: 30,
+            },
+        )],
+        initial_permission_owners: vec![(MANAGE_DEX, Scope::Limited(hash(&DEX_A_ID)), vec![ALICE])],
+        initial_permissions: vec![(ALICE, Scope::Limited(hash(&DEX_A_ID)), vec![MANAGE_DEX])],
+        ..Default::default()
+    }
+    .build();
+    ext.execute_with(|| {
+        System::set_block_number(1);
+        assert_noop!(
+            DEXModule::transfer_ownership(Origin::signed(BOB), DEX_A_ID, BOB),
+            permissions::Error::<Runtime>::PermissionNotOwned
+        );
+        assert_ok!(DEXModule::transfer_ownership(
+            Origin::signed(ALICE),
+            DEX_A_ID,
+            BOB
+        ));
+        assert!(System::events().iter().any(|record| record.event
+            == crate::Event::DEXOwnershipTransferred(DEX_A_ID, ALICE, BOB).into()));
+        assert_ok!(DEXModule::ensure_can_manage(
+            &DEX_A_ID,
+            Origin::signed(BOB),
+            ManagementMode::Private
+        ));
+        assert_noop!(
+            DEXModule::ensure_can_manage(&DEX_A_ID, Origin::signed(ALICE), ManagementMode::Private),
+            permissions::Error::<Runtime>::Forbidden
+        );
+        assert_noop!(
+            DEXModule::transfer_ownership(Origin::signed(ALICE), DEX_A_ID, ALICE),
+            permissions::Error::<Runtime>::PermissionNotOwned
+        );
+    })
+}
+
+#[test]
+fn test_migrate_dex_infos_should_pass() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        assert_eq!(DEXModule::pallet_storage_version(), StorageVersion::V2);
+        crate::PalletStorageVersion::<Runtime>::put(StorageVersion::V1);
+        frame_support::storage::unhashed::put(
+            &crate::DEXInfos::<Runtime>::hashed_key_for(DEX_B_ID),
+            &migrations::DEXInfoV1 {
+                base_asset_id: VAL,
+                is_public: true,
+            },
+        );
+        migrations::migrate_dex_infos::<Runtime>();
+        assert_eq!(
+            DEXModule::dex_id(DEX_B_ID),
+            Some(DEXInfo {
+                base_asset_id: VAL,
+                is_public: true,
+                default_fee: migrations::LEGACY_DEFAULT_FEE,
+            })
+        );
+        assert_eq!(DEXModule::pallet_storage_version(), StorageVersion::V2);
+        // Running it again must not change anything.
+        assert_ok!(DEXModule::set_default_fee(Origin::root(), DEX_B_ID, 20));
+        migrations::migrate_dex_infos::<Runtime>();
+        assert_eq!(DEXModule::dex_id(DEX_B_ID).unwrap().default_fee, 20);
+    })
+}
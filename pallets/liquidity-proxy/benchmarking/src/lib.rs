@@ -40,8 +40,8 @@ use liquidity_proxy::*;
 use codec::Decode;
 use common::prelude::{Balance, SwapAmount};
 use common::{
-    balance, AssetName, AssetSymbol, DEXId, FilterMode, LiquiditySourceType, DOT, PSWAP, USDT, VAL,
-    XOR,
+    balance, AssetName, AssetSymbol, DEXId, FilterMode, LiquiditySourceId, LiquiditySourceType,
+    DOT, PSWAP, USDT, VAL, XOR,
 };
 use frame_benchmarking::{benchmarks, Zero};
 use frame_support::traits::Get;
@@ -50,7 +50,6 @@ use hex_literal::hex;
 use sp_std::prelude::*;
 
 use assets::Pallet as Assets;
-use multicollateral_bonding_curve_pool::Pallet as MBCPool;
 use permissions::Pallet as Permissions;
 use pool_xyk::Pallet as XYKPool;
 use trading_pair::Pallet as TradingPair;
@@ -61,10 +60,7 @@ pub const DEX: DEXId = DEXId::Polkaswap;
 mod mock;
 
 pub struct Module<T: Config>(liquidity_proxy::Module<T>);
-pub trait Config:
-    liquidity_proxy::Config + pool_xyk::Config + multicollateral_bonding_curve_pool::Config
-{
-}
+pub trait Config: liquidity_proxy::Config + pool_xyk::Config + bonding_curve_pool::Config {}
 
 // Support Functions
 fn alice<T: Config>() -> T::AccountId {
@@ -197,17 +193,22 @@ fn setup_benchmark<T: Config>() -> Result<(), &'static str> {
         balance!(0),
     )?;
 
-    MBCPool::<T>::initialize_pool(owner_origin.clone(), USDT.into())?;
-    MBCPool::<T>::initialize_pool(owner_origin.clone(), VAL.into())?;
-
     Ok(())
 }
 
+// Liquidity sources a trade is split between in the split benchmarks
+fn split_sources<T: Config>() -> Vec<LiquiditySourceId<T::DEXId, LiquiditySourceType>> {
+    vec![
+        LiquiditySourceId::new(DEX.into(), LiquiditySourceType::XYKPool),
+        LiquiditySourceId::new(DEX.into(), LiquiditySourceType::BondingCurvePool),
+    ]
+}
+
 benchmarks! {
     swap_exact_input_primary_only {
         setup_benchmark::<T>()?;
         let caller = alice::<T>();
-        let from_asset: T::AssetId = VAL.into();
+        let from_asset: T::AssetId = USDT.into();
         let to_asset: T::AssetId = XOR.into();
         let initial_from_balance = Assets::<T>::free_balance(&from_asset, &caller).unwrap();
     }: swap(
@@ -216,7 +217,7 @@ benchmarks! {
         from_asset.clone(),
         to_asset.clone(),
         SwapAmount::with_desired_input(balance!(100), 0),
-        [LiquiditySourceType::MulticollateralBondingCurvePool].into(),
+        [LiquiditySourceType::BondingCurvePool].into(),
         FilterMode::AllowSelected
     )
     verify {
@@ -230,7 +231,7 @@ benchmarks! {
     // swap_exact_output_primary_only {
     //     setup_benchmark::<T>()?;
     //     let caller = alice::<T>();
-    //     let from_asset: T::AssetId = VAL.into();
+    //     let from_asset: T::AssetId = USDT.into();
     //     let to_asset: T::AssetId = XOR.into();
     //     let initial_to_balance = Assets::<T>::free_balance(&to_asset, &caller).unwrap();
     // }: swap(
@@ -239,7 +240,7 @@ benchmarks! {
     //     from_asset.clone(),
     //     to_asset.clone(),
     //     SwapAmount::with_desired_output(balance!(100), balance!(10000000)),
-    //     [LiquiditySourceType::MulticollateralBondingCurvePool].into(),
+    //     [LiquiditySourceType::BondingCurvePool].into(),
     //     FilterMode::AllowSelected
     // )
     // verify {
@@ -336,6 +337,51 @@ benchmarks! {
             Into::<u128>::into(initial_to_balance) + balance!(1)
         );
     }
+
+    split_sampling {
+        setup_benchmark::<T>()?;
+        let from_asset: T::AssetId = USDT.into();
+        let to_asset: T::AssetId = XOR.into();
+        let amount = SwapAmount::with_desired_input(balance!(100), 0);
+    }: {
+        liquidity_proxy::Pallet::<T>::sampling_split(
+            split_sources::<T>(),
+            &from_asset,
+            &to_asset,
+            amount,
+        )?;
+    }
+    verify {}
+
+    split_curves {
+        setup_benchmark::<T>()?;
+        let from_asset: T::AssetId = USDT.into();
+        let to_asset: T::AssetId = XOR.into();
+        let amount = SwapAmount::with_desired_input(balance!(100), 0);
+    }: {
+        liquidity_proxy::Pallet::<T>::curve_split(
+            split_sources::<T>(),
+            &from_asset,
+            &to_asset,
+            amount,
+        )?;
+    }
+    verify {
+        // Output quality compared with the sampling-based split of the same trade.
+        let outcome = liquidity_proxy::Pallet::<T>::curve_split(
+            split_sources::<T>(),
+            &from_asset,
+            &to_asset,
+            amount,
+        )?;
+        let sampled = liquidity_proxy::Pallet::<T>::sampling_split(
+            split_sources::<T>(),
+            &from_asset,
+            &to_asset,
+            amount,
+        )?;
+        assert!(outcome.amount + balance!(0.000001) >= sampled.amount);
+    }
 }
 
 #[cfg(test)]
@@ -353,6 +399,51 @@ mod tests {
             assert_ok!(test_benchmark_swap_exact_output_secondary_only::<Runtime>());
             assert_ok!(test_benchmark_swap_exact_input_multiple::<Runtime>());
             assert_ok!(test_benchmark_swap_exact_output_multiple::<Runtime>());
+            assert_ok!(test_benchmark_split_sampling::<Runtime>());
+            assert_ok!(test_benchmark_split_curves::<Runtime>());
+        });
+    }
+
+    #[test]
+    fn test_curve_split_should_not_be_worse_than_sampling() {
+        ExtBuilder::default().build().execute_with(|| {
+            setup_benchmark::<Runtime>().unwrap();
+            let from_asset = USDT.into();
+            let to_asset = XOR.into();
+
+            let amount = SwapAmount::with_desired_input(balance!(100), 0);
+            let split = liquidity_proxy::Pallet::<Runtime>::curve_split(
+                split_sources::<Runtime>(),
+                &from_asset,
+                &to_asset,
+                amount,
+            )
+            .unwrap();
+            let sampled = liquidity_proxy::Pallet::<Runtime>::sampling_split(
+                split_sources::<Runtime>(),
+                &from_asset,
+                &to_asset,
+                amount,
+            )
+            .unwrap();
+            assert!(split.amount + balance!(0.000001) >= sampled.amount);
+
+            let amount = SwapAmount::with_desired_output(balance!(10), balance!(10000));
+            let split = liquidity_proxy::Pallet::<Runtime>::curve_split(
+                split_sources::<Runtime>(),
+                &from_asset,
+                &to_asset,
+                amount,
+            )
+            .unwrap();
+            let sampled = liquidity_proxy::Pallet::<Runtime>::sampling_split(
+                split_sources::<Runtime>(),
+                &from_asset,
+                &to_asset,
+                amount,
+            )
+            .unwrap();
+            assert!(split.amount <= sampled.amount + balance!(0.000001));
         });
     }
 }
@@ -34,16 +34,14 @@ use crate::{Config, *};
 use common::mock::ExistentialDeposits;
 use common::prelude::Balance;
 use common::{
-    fixed_from_basis_points, hash, Amount, AssetId32, BalancePrecision, DEXInfo, Fixed,
-    FromGenericPair, LiquiditySourceType, TechPurpose,
+    fixed_from_basis_points, hash, Amount, AssetId32, BalancePrecision, BasisPoints, DEXInfo,
+    Fixed, FromGenericPair, LiquiditySourceType,
 };
 use currencies::BasicCurrencyAdapter;
 
 use frame_support::traits::GenesisBuild;
+use frame_support::weights::Weight;
 use frame_support::{construct_runtime, parameter_types};
-use multicollateral_bonding_curve_pool::{
-    DistributionAccount, DistributionAccountData, DistributionAccounts,
-};
 use permissions::{Scope, BURN, MANAGE_DEX, MINT, TRANSFER};
 use sp_core::H256;
 use sp_runtime::testing::Header;
@@ -82,14 +80,18 @@ parameter_types! {
     };
     pub const BlockHashCount: u64 = 250;
     pub const GetNumSamples: usize = 40;
+    pub const GetMaxRouteHops: usize = 3;
+    pub const GetMaxRoutingPairs: usize = 64;
+    pub const GetMaxCandidatePaths: usize = 8;
+    pub const GetMaxSplitRoutes: usize = 3;
+    pub const MaxBatchSwapLegs: u32 = 16;
+    pub const CommitRevealWindow: BlockNumber = 10;
+    pub const DcaExecutionWeightBudget: Weight = 1_000_000_000_000;
+    pub const MaxDcaOrdersPerBlock: u32 = 16;
     pub const GetBaseAssetId: AssetId = XOR;
     pub const ExistentialDeposit: u128 = 0;
     pub GetFee: Fixed = fixed_from_basis_points(0u16);
-    pub GetPswapDistributionAccountId: AccountId = AccountId32::from([3; 32]);
-    pub const GetDefaultSubscriptionFrequency: BlockNumber = 10;
-    pub const GetBurnUpdateFrequency: BlockNumber = 10;
-    pub GetIncentiveAssetId: AssetId = common::PSWAP.into();
-    pub GetParliamentAccountId: AccountId = AccountId32::from([8; 32]);
+    pub const MaxDexFee: BasisPoints = 100;
 }
 
 construct_runtime! {
@@ -104,14 +106,13 @@ construct_runtime! {
         Currencies: currencies::{Module, Call, Storage, Event<T>},
         Assets: assets::{Module, Call, Config<T>, Storage, Event<T>},
         Balances: pallet_balances::{Module, Call, Storage, Event<T>},
-        DexManager: dex_manager::{Module, Call, Config<T>, Storage},
+        DexManager: dex_manager::{Module, Call, Config<T>, Storage, Event<T>},
         Technical: technical::{Module, Call, Config<T>, Storage, Event<T>},
         Permissions: permissions::{Module, Call, Config<T>, Storage, Event<T>},
         DexApi: dex_api::{Module, Call, Config, Storage, Event<T>},
         TradingPair: trading_pair::{Module, Call, Config<T>, Storage, Event<T>},
         PoolXyk: pool_xyk::{Module, Call, Storage, Event<T>},
-        MBCPool: multicollateral_bonding_curve_pool::{Module, Call, Storage, Event<T>},
-        PswapDistribution: pswap_distribution::{Module, Call, Config<T>, Storage, Event<T>},
+        BondingCurvePool: bonding_curve_pool::{Module, Call, Config<T>, Storage, Event<T>},
     }
 }
 
@@ -144,10 +145,19 @@ impl liquidity_proxy::Config for Runtime {
     type Event = Event;
     type LiquidityRegistry = dex_api::Module<Runtime>;
     type GetNumSamples = GetNumSamples;
+    type GetMaxRouteHops = GetMaxRouteHops;
+    type GetMaxRoutingPairs = GetMaxRoutingPairs;
+    type GetMaxCandidatePaths = GetMaxCandidatePaths;
+    type GetMaxSplitRoutes = GetMaxSplitRoutes;
+    type MaxBatchSwapLegs = MaxBatchSwapLegs;
     type GetTechnicalAccountId = GetLiquidityProxyAccountId;
     type WeightInfo = ();
     type PrimaryMarket = ();
     type SecondaryMarket = ();
+    type SwapCurves = dex_api::Module<Runtime>;
+    type DcaExecutionWeightBudget = DcaExecutionWeightBudget;
+    type MaxDcaOrdersPerBlock = MaxDcaOrdersPerBlock;
+    type CommitRevealWindow = CommitRevealWindow;
 }
 
 impl tokens::Config for Runtime {
@@ -176,6 +186,7 @@ impl assets::Config for Runtime {
     type AssetId = AssetId;
     type GetBaseAssetId = GetBaseAssetId;
     type Currency = currencies::Module<Runtime>;
+    type IsAssetInUse = ();
     type WeightInfo = ();
 }
 
@@ -194,7 +205,11 @@ impl pallet_balances::Config for Runtime {
     type MaxLocks = ();
 }
 
-impl dex_manager::Config for Runtime {}
+impl dex_manager::Config for Runtime {
+    type Event = Event;
+    type MaxFee = MaxDexFee;
+    type WeightInfo = ();
+}
 
 impl mock_liquidity_source::Config<mock_liquidity_source::Instance1> for Runtime {
     type GetFee = GetFee;
@@ -237,13 +252,10 @@ impl permissions::Config for Runtime {
 
 impl dex_api::Config for Runtime {
     type Event = Event;
-    type MockLiquiditySource = ();
-    type MockLiquiditySource2 = ();
-    type MockLiquiditySource3 = ();
-    type MockLiquiditySource4 = ();
-    type XYKPool = pool_xyk::Module<Runtime>;
-    type BondingCurvePool = ();
-    type MulticollateralBondingCurvePool = multicollateral_bonding_curve_pool::Module<Runtime>;
+    type LiquiditySources = (
+        pool_xyk::Module<Runtime>,
+        bonding_curve_pool::Module<Runtime>,
+    );
     type WeightInfo = ();
 }
 
@@ -263,131 +275,35 @@ impl pool_xyk::Config for Runtime {
     type PolySwapAction =
         pool_xyk::PolySwapAction<AssetId, TechAssetId, Balance, AccountId, TechAccountId>;
     type EnsureDEXManager = dex_manager::Pallet<Runtime>;
+    type EnsureTradingNotPaused = ();
     type WeightInfo = ();
 }
 
-fn bonding_curve_distribution_accounts() -> DistributionAccounts<
-    DistributionAccountData<
-        DistributionAccount<
-            <Runtime as frame_system::Config>::AccountId,
-            <Runtime as technical::Config>::TechAccountId,
-        >,
-    >,
-> {
-    use common::fixed_wrapper;
-    let val_holders_coefficient = fixed_wrapper!(0.5);
-    let val_holders_xor_alloc_coeff = fixed_wrapper!(0.9) * val_holders_coefficient.clone();
-    let val_holders_buy_back_coefficient =
-        val_holders_coefficient.clone() * (fixed_wrapper!(1) - fixed_wrapper!(0.9));
-    let projects_coefficient = fixed_wrapper!(1) - val_holders_coefficient;
-    let projects_sora_citizens_coeff = projects_coefficient.clone() * fixed_wrapper!(0.01);
-    let projects_stores_and_shops_coeff = projects_coefficient.clone() * fixed_wrapper!(0.04);
-    let projects_parliament_and_development_coeff =
-        projects_coefficient.clone() * fixed_wrapper!(0.05);
-    let projects_other_coeff = projects_coefficient.clone() * fixed_wrapper!(0.9);
-
-    let xor_allocation = DistributionAccountData::new(
-        DistributionAccount::TechAccount(TechAccountId::Pure(
-            0u32,
-            TechPurpose::Identifier(b"xor_allocation".to_vec()),
-        )),
-        val_holders_xor_alloc_coeff.get().unwrap(),
-    );
-    let sora_citizens = DistributionAccountData::new(
-        DistributionAccount::TechAccount(TechAccountId::Pure(
-            0u32,
-            TechPurpose::Identifier(b"sora_citizens".to_vec()),
-        )),
-        projects_sora_citizens_coeff.get().unwrap(),
-    );
-    let stores_and_shops = DistributionAccountData::new(
-        DistributionAccount::TechAccount(TechAccountId::Pure(
-            0u32,
-            TechPurpose::Identifier(b"stores_and_shops".to_vec()),
-        )),
-        projects_stores_and_shops_coeff.get().unwrap(),
-    );
-    let parliament_and_development = DistributionAccountData::new(
-        DistributionAccount::Account(
-            hex!("881b87c9f83664b95bd13e2bb40675bfa186287da93becc0b22683334d411e4e").into(),
-        ),
-        projects_parliament_and_development_coeff.get().unwrap(),
-    );
-    let projects = DistributionAccountData::new(
-        DistributionAccount::TechAccount(TechAccountId::Pure(
-            0u32,
-            TechPurpose::Identifier(b"projects".to_vec()),
-        )),
-        projects_other_coeff.get().unwrap(),
-    );
-    let val_holders = DistributionAccountData::new(
-        DistributionAccount::TechAccount(TechAccountId::Pure(
-            0u32,
-            TechPurpose::Identifier(b"val_holders".to_vec()),
-        )),
-        val_holders_buy_back_coefficient.get().unwrap(),
-    );
-    DistributionAccounts::<_> {
-        xor_allocation,
-        sora_citizens,
-        stores_and_shops,
-        parliament_and_development,
-        projects,
-        val_holders,
-    }
-}
-
 parameter_types! {
-    pub GetMbcReservesTechAccountId: TechAccountId = {
-        let tech_account_id = TechAccountId::from_generic_pair(
-            multicollateral_bonding_curve_pool::TECH_ACCOUNT_PREFIX.to_vec(),
-            multicollateral_bonding_curve_pool::TECH_ACCOUNT_RESERVES.to_vec(),
-        );
-        tech_account_id
-    };
-    pub GetMbcReservesAccountId: AccountId = {
-        let tech_account_id = GetMbcReservesTechAccountId::get();
-        let account_id =
-            technical::Pallet::<Runtime>::tech_account_id_to_account_id(&tech_account_id)
-                .expect("Failed to get ordinary account id for technical account id.");
-        account_id
-    };
-    pub GetMbcRewardsTechAccountId: TechAccountId = {
+    pub GetBondingCurveReservesTechAccountId: TechAccountId = {
         let tech_account_id = TechAccountId::from_generic_pair(
-            multicollateral_bonding_curve_pool::TECH_ACCOUNT_PREFIX.to_vec(),
-            multicollateral_bonding_curve_pool::TECH_ACCOUNT_REWARDS.to_vec(),
+            bonding_curve_pool::TECH_ACCOUNT_PREFIX.to_vec(),
+            bonding_curve_pool::TECH_ACCOUNT_RESERVES.to_vec(),
         );
         tech_account_id
     };
-    pub GetMbcRewardsAccountId: AccountId = {
-        let tech_account_id = GetMbcRewardsTechAccountId::get();
+    pub GetBondingCurveReservesAccountId: AccountId = {
+        let tech_account_id = GetBondingCurveReservesTechAccountId::get();
         let account_id =
             technical::Pallet::<Runtime>::tech_account_id_to_account_id(&tech_account_id)
                 .expect("Failed to get ordinary account id for technical account id.");
         account_id
     };
+    pub const MaxDistributionAccounts: u32 = 8;
+    pub const MaxBuyBackRetriesPerBlock: u32 = 4;
 }
 
-impl multicollateral_bonding_curve_pool::Config for Runtime {
-    type Event = Event;
-    type LiquidityProxy = liquidity_proxy::Module<Runtime>;
-    type EnsureDEXManager = dex_manager::Pallet<Runtime>;
-    type EnsureTradingPairExists = trading_pair::Pallet<Runtime>;
-    type WeightInfo = ();
-}
-
-impl pswap_distribution::Config for Runtime {
+impl bonding_curve_pool::Config for Runtime {
     type Event = Event;
-    type GetIncentiveAssetId = GetIncentiveAssetId;
     type LiquidityProxy = liquidity_proxy::Module<Runtime>;
-    type CompatBalance = Balance;
-    type GetDefaultSubscriptionFrequency = GetDefaultSubscriptionFrequency;
-    type GetBurnUpdateFrequency = GetBurnUpdateFrequency;
-    type GetTechnicalAccountId = GetPswapDistributionAccountId;
-    type EnsureDEXManager = ();
-    type OnPswapBurnedAggregator = ();
+    type MaxDistributionAccounts = MaxDistributionAccounts;
+    type MaxBuyBackRetriesPerBlock = MaxBuyBackRetriesPerBlock;
     type WeightInfo = ();
-    type GetParliamentAccountId = GetParliamentAccountId;
 }
 
 impl Config for Runtime {}
@@ -417,6 +333,7 @@ impl Default for ExtBuilder {
                 DEXInfo {
                     base_asset_id: GetBaseAssetId::get(),
                     is_public: true,
+                    default_fee: 30,
                 },
             )],
             initial_permission_owners: vec![
@@ -429,23 +346,19 @@ impl Default for ExtBuilder {
                 (alice(), Scope::Unlimited, vec![MINT, BURN]),
                 (alice(), Scope::Limited(hash(&0_u32)), vec![MANAGE_DEX]),
                 (
-                    GetMbcReservesAccountId::get(),
+                    GetBondingCurveReservesAccountId::get(),
                     Scope::Unlimited,
                     vec![MINT, BURN],
                 ),
             ],
             source_types: vec![
-                LiquiditySourceType::MulticollateralBondingCurvePool,
+                LiquiditySourceType::BondingCurvePool,
                 LiquiditySourceType::XYKPool,
             ],
             tech_accounts: vec![
                 (
-                    GetMbcReservesAccountId::get(),
-                    GetMbcReservesTechAccountId::get(),
-                ),
-                (
-                    GetMbcRewardsAccountId::get(),
-                    GetMbcRewardsTechAccountId::get(),
+                    GetBondingCurveReservesAccountId::get(),
+                    GetBondingCurveReservesTechAccountId::get(),
                 ),
                 (
                     GetLiquidityProxyAccountId::get(),
@@ -509,35 +422,10 @@ impl ExtBuilder {
             .build_storage::<Runtime>()
             .unwrap();
 
-        let accounts = bonding_curve_distribution_accounts();
-        let mut tech_accounts = self.tech_accounts.clone();
-        for account in &accounts.accounts() {
-            match account {
-                DistributionAccount::Account(_) => continue,
-                DistributionAccount::TechAccount(account_id) => {
-                    tech_accounts.push((
-                        Technical::tech_account_id_to_account_id(&account_id).unwrap(),
-                        account_id.clone(),
-                    ));
-                }
-            }
-        }
-
         pallet_balances::GenesisConfig::<Runtime> {
             balances: vec![
                 (alice(), 0),
-                (
-                    if let DistributionAccount::TechAccount(account_id) =
-                        &accounts.val_holders.account
-                    {
-                        Technical::tech_account_id_to_account_id(account_id).unwrap()
-                    } else {
-                        panic!("not a tech account")
-                    },
-                    0,
-                ),
-                (GetMbcReservesAccountId::get(), 0),
-                (GetMbcRewardsAccountId::get(), 0),
+                (GetBondingCurveReservesAccountId::get(), 0),
                 (GetLiquidityProxyAccountId::get(), 0),
             ],
         }
@@ -564,7 +452,7 @@ impl ExtBuilder {
         .unwrap();
 
         technical::GenesisConfig::<Runtime> {
-            account_ids_to_tech_account_ids: tech_accounts,
+            account_ids_to_tech_account_ids: self.tech_accounts,
         }
         .assimilate_storage(&mut t)
         .unwrap();
@@ -577,12 +465,9 @@ impl ExtBuilder {
         )
         .unwrap();
 
-        multicollateral_bonding_curve_pool::GenesisConfig::<Runtime> {
-            distribution_accounts: accounts,
-            reserves_account_id: GetMbcReservesTechAccountId::get(),
-            reference_asset_id: USDT.into(),
-            incentives_account_id: GetMbcRewardsAccountId::get(),
-            initial_collateral_assets: Default::default(),
+        bonding_curve_pool::GenesisConfig::<Runtime> {
+            reserves_account_id: GetBondingCurveReservesTechAccountId::get(),
+            ..Default::default()
         }
         .assimilate_storage(&mut t)
         .unwrap();
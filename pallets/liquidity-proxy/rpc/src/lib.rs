@@ -40,7 +40,9 @@ use std::sync::Arc;
 
 // Custom imports
 pub use liquidity_proxy_runtime_api::LiquidityProxyAPI as LiquidityProxyRuntimeAPI;
-use liquidity_proxy_runtime_api::SwapOutcomeInfo;
+use liquidity_proxy_runtime_api::{
+    QuoteBreakdownInfo, RouteInfo, SwapOutcomeInfo, SwapSimulationInfo,
+};
 
 #[rpc]
 pub trait LiquidityProxyAPI<
@@ -51,6 +53,7 @@ pub trait LiquidityProxyAPI<
     SwapVariant,
     LiquiditySourceType,
     FilterMode,
+    AccountId,
     OutputTy,
 >
 {
@@ -84,6 +87,46 @@ pub trait LiquidityProxyAPI<
         output_asset_id: AssetId,
         at: Option<BlockHash>,
     ) -> Result<Vec<LiquiditySourceType>>;
+
+    #[rpc(name = "liquidityProxy_findRoutes")]
+    fn find_routes(
+        &self,
+        dex_id: DEXId,
+        input_asset_id: AssetId,
+        output_asset_id: AssetId,
+        amount: BalanceWrapper,
+        swap_variant: SwapVariant,
+        selected_source_types: Vec<LiquiditySourceType>,
+        filter_mode: FilterMode,
+        at: Option<BlockHash>,
+    ) -> Result<Vec<RouteInfo<AssetId, Balance>>>;
+
+    #[rpc(name = "liquidityProxy_quoteBreakdown")]
+    fn quote_breakdown(
+        &self,
+        dex_id: DEXId,
+        input_asset_id: AssetId,
+        output_asset_id: AssetId,
+        amount: BalanceWrapper,
+        swap_variant: SwapVariant,
+        selected_source_types: Vec<LiquiditySourceType>,
+        filter_mode: FilterMode,
+        at: Option<BlockHash>,
+    ) -> Result<Option<QuoteBreakdownInfo<AssetId, LiquiditySourceType, Balance>>>;
+
+    #[rpc(name = "liquidityProxy_simulateSwap")]
+    fn simulate_swap(
+        &self,
+        account_id: AccountId,
+        dex_id: DEXId,
+        input_asset_id: AssetId,
+        output_asset_id: AssetId,
+        amount: BalanceWrapper,
+        swap_variant: SwapVariant,
+        selected_source_types: Vec<LiquiditySourceType>,
+        filter_mode: FilterMode,
+        at: Option<BlockHash>,
+    ) -> Result<SwapSimulationInfo<AccountId, AssetId, Balance>>;
 }
 
 pub struct LiquidityProxyClient<C, B> {
@@ -101,7 +144,17 @@ impl<C, B> LiquidityProxyClient<C, B> {
     }
 }
 
-impl<C, Block, DEXId, AssetId, Balance, SwapVariant, LiquiditySourceType, FilterMode>
+impl<
+        C,
+        Block,
+        DEXId,
+        AssetId,
+        Balance,
+        SwapVariant,
+        LiquiditySourceType,
+        FilterMode,
+        AccountId,
+    >
     LiquidityProxyAPI<
         <Block as BlockT>::Hash,
         DEXId,
@@ -110,6 +163,7 @@ impl<C, Block, DEXId, AssetId, Balance, SwapVariant, LiquiditySourceType, Filter
         SwapVariant,
         LiquiditySourceType,
         FilterMode,
+        AccountId,
         Option<SwapOutcomeInfo<Balance>>,
     > for LiquidityProxyClient<C, Block>
 where
@@ -124,6 +178,7 @@ where
         SwapVariant,
         LiquiditySourceType,
         FilterMode,
+        AccountId,
     >,
     DEXId: Codec,
     AssetId: Codec,
@@ -131,6 +186,7 @@ where
     SwapVariant: Codec,
     LiquiditySourceType: Codec,
     FilterMode: Codec,
+    AccountId: Codec,
 {
     fn quote(
         &self,
@@ -204,4 +260,104 @@ where
                 data: Some(format!("{:?}", e).into()),
             })
     }
+
+    fn find_routes(
+        &self,
+        dex_id: DEXId,
+        input_asset_id: AssetId,
+        output_asset_id: AssetId,
+        amount: BalanceWrapper,
+        swap_variant: SwapVariant,
+        selected_source_types: Vec<LiquiditySourceType>,
+        filter_mode: FilterMode,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Vec<RouteInfo<AssetId, Balance>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or(
+            // If the block hash is not supplied assume the best block.
+            self.client.info().best_hash,
+        ));
+        api.find_routes(
+            &at,
+            dex_id,
+            input_asset_id,
+            output_asset_id,
+            amount,
+            swap_variant,
+            selected_source_types,
+            filter_mode,
+        )
+        .map_err(|e| RpcError {
+            code: ErrorCode::ServerError(InvokeRPCError::RuntimeError.into()),
+            message: "Unable to find routes.".into(),
+            data: Some(format!("{:?}", e).into()),
+        })
+    }
+    fn quote_breakdown(
+        &self,
+        dex_id: DEXId,
+        input_asset_id: AssetId,
+        output_asset_id: AssetId,
+        amount: BalanceWrapper,
+        swap_variant: SwapVariant,
+        selected_source_types: Vec<LiquiditySourceType>,
+        filter_mode: FilterMode,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Option<QuoteBreakdownInfo<AssetId, LiquiditySourceType, Balance>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or(
+            // If the block hash is not supplied assume the best block.
+            self.client.info().best_hash,
+        ));
+        api.quote_breakdown(
+            &at,
+            dex_id,
+            input_asset_id,
+            output_asset_id,
+            amount,
+            swap_variant,
+            selected_source_types,
+            filter_mode,
+        )
+        .map_err(|e| RpcError {
+            code: ErrorCode::ServerError(InvokeRPCError::RuntimeError.into()),
+            message: "Unable to get quote breakdown.".into(),
+            data: Some(format!("{:?}", e).into()),
+        })
+    }
+
+    fn simulate_swap(
+        &self,
+        account_id: AccountId,
+        dex_id: DEXId,
+        input_asset_id: AssetId,
+        output_asset_id: AssetId,
+        amount: BalanceWrapper,
+        swap_variant: SwapVariant,
+        selected_source_types: Vec<LiquiditySourceType>,
+        filter_mode: FilterMode,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<SwapSimulationInfo<AccountId, AssetId, Balance>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or(
+            // If the block hash is not supplied assume the best block.
+            self.client.info().best_hash,
+        ));
+        api.simulate_swap(
+            &at,
+            account_id,
+            dex_id,
+            input_asset_id,
+            output_asset_id,
+            amount,
+            swap_variant,
+            selected_source_types,
+            filter_mode,
+        )
+        .map_err(|e| RpcError {
+            code: ErrorCode::ServerError(InvokeRPCError::RuntimeError.into()),
+            message: "Unable to simulate swap.".into(),
+            data: Some(format!("{:?}", e).into()),
+        })
+    }
 }
@@ -39,6 +39,7 @@ use common::BalanceWrapper;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 use sp_runtime::traits::{MaybeDisplay, MaybeFromStr};
+use sp_runtime::DispatchError;
 use sp_std::prelude::*;
 
 #[derive(Eq, PartialEq, Encode, Decode, Default)]
@@ -68,14 +69,260 @@ pub struct SwapOutcomeInfo<Balance> {
     pub fee: Balance,
 }
 
+/// Path of a trade together with its quote.
+#[derive(Eq, PartialEq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+pub struct RouteInfo<AssetId, Balance> {
+    /// Assets the trade goes through, from the input asset to the output one.
+    pub path: Vec<AssetId>,
+    #[cfg_attr(
+        feature = "std",
+        serde(
+            bound(
+                serialize = "Balance: std::fmt::Display",
+                deserialize = "Balance: std::str::FromStr"
+            ),
+            with = "string_serialization"
+        )
+    )]
+    pub amount: Balance,
+    #[cfg_attr(
+        feature = "std",
+        serde(
+            bound(
+                serialize = "Balance: std::fmt::Display",
+                deserialize = "Balance: std::str::FromStr"
+            ),
+            with = "string_serialization"
+        )
+    )]
+    pub fee: Balance,
+}
+
+/// Part of an individual swap performed by a single liquidity source.
+#[derive(Eq, PartialEq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+pub struct SourceBreakdownInfo<LiquiditySourceType, Balance> {
+    pub source_type: LiquiditySourceType,
+    /// Share of the individual swap amount, as a fixed-point number with 18 decimals.
+    #[cfg_attr(
+        feature = "std",
+        serde(
+            bound(
+                serialize = "Balance: std::fmt::Display",
+                deserialize = "Balance: std::str::FromStr"
+            ),
+            with = "string_serialization"
+        )
+    )]
+    pub share: Balance,
+    #[cfg_attr(
+        feature = "std",
+        serde(
+            bound(
+                serialize = "Balance: std::fmt::Display",
+                deserialize = "Balance: std::str::FromStr"
+            ),
+            with = "string_serialization"
+        )
+    )]
+    pub input_amount: Balance,
+    #[cfg_attr(
+        feature = "std",
+        serde(
+            bound(
+                serialize = "Balance: std::fmt::Display",
+                deserialize = "Balance: std::str::FromStr"
+            ),
+            with = "string_serialization"
+        )
+    )]
+    pub output_amount: Balance,
+}
+
+/// Detailed outcome of an individual swap.
+#[derive(Eq, PartialEq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+pub struct SwapBreakdownInfo<AssetId, LiquiditySourceType, Balance> {
+    pub input_asset_id: AssetId,
+    pub output_asset_id: AssetId,
+    #[cfg_attr(
+        feature = "std",
+        serde(
+            bound(
+                serialize = "Balance: std::fmt::Display",
+                deserialize = "Balance: std::str::FromStr"
+            ),
+            with = "string_serialization"
+        )
+    )]
+    pub input_amount: Balance,
+    #[cfg_attr(
+        feature = "std",
+        serde(
+            bound(
+                serialize = "Balance: std::fmt::Display",
+                deserialize = "Balance: std::str::FromStr"
+            ),
+            with = "string_serialization"
+        )
+    )]
+    pub output_amount: Balance,
+    pub sources: Vec<SourceBreakdownInfo<LiquiditySourceType, Balance>>,
+    /// Price before the swap, as a fixed-point number with 18 decimals.
+    #[cfg_attr(
+        feature = "std",
+        serde(
+            bound(
+                serialize = "Balance: std::fmt::Display",
+                deserialize = "Balance: std::str::FromStr"
+            ),
+            with = "string_serialization"
+        )
+    )]
+    pub spot_price: Balance,
+    /// Price the swap is performed at, as a fixed-point number with 18 decimals.
+    #[cfg_attr(
+        feature = "std",
+        serde(
+            bound(
+                serialize = "Balance: std::fmt::Display",
+                deserialize = "Balance: std::str::FromStr"
+            ),
+            with = "string_serialization"
+        )
+    )]
+    pub effective_price: Balance,
+    /// Price impact in basis points.
+    pub price_impact: u16,
+    /// Fee nominated in XOR.
+    #[cfg_attr(
+        feature = "std",
+        serde(
+            bound(
+                serialize = "Balance: std::fmt::Display",
+                deserialize = "Balance: std::str::FromStr"
+            ),
+            with = "string_serialization"
+        )
+    )]
+    pub fee: Balance,
+    /// Fee nominated in the input asset.
+    #[cfg_attr(
+        feature = "std",
+        serde(
+            bound(
+                serialize = "Balance: std::fmt::Display",
+                deserialize = "Balance: std::str::FromStr"
+            ),
+            with = "string_serialization"
+        )
+    )]
+    pub fee_in_input_asset: Balance,
+}
+
+/// Detailed outcome of a part of a trade performed along a single path.
+#[derive(Eq, PartialEq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+pub struct RouteBreakdownInfo<AssetId, LiquiditySourceType, Balance> {
+    pub path: Vec<AssetId>,
+    /// Share of the trade amount, as a fixed-point number with 18 decimals.
+    #[cfg_attr(
+        feature = "std",
+        serde(
+            bound(
+                serialize = "Balance: std::fmt::Display",
+                deserialize = "Balance: std::str::FromStr"
+            ),
+            with = "string_serialization"
+        )
+    )]
+    pub share: Balance,
+    pub swaps: Vec<SwapBreakdownInfo<AssetId, LiquiditySourceType, Balance>>,
+}
+
+/// Detailed outcome of a trade.
+#[derive(Eq, PartialEq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+pub struct QuoteBreakdownInfo<AssetId, LiquiditySourceType, Balance> {
+    #[cfg_attr(
+        feature = "std",
+        serde(
+            bound(
+                serialize = "Balance: std::fmt::Display",
+                deserialize = "Balance: std::str::FromStr"
+            ),
+            with = "string_serialization"
+        )
+    )]
+    pub amount: Balance,
+    #[cfg_attr(
+        feature = "std",
+        serde(
+            bound(
+                serialize = "Balance: std::fmt::Display",
+                deserialize = "Balance: std::str::FromStr"
+            ),
+            with = "string_serialization"
+        )
+    )]
+    pub fee: Balance,
+    pub routes: Vec<RouteBreakdownInfo<AssetId, LiquiditySourceType, Balance>>,
+}
+
+/// Change of an account balance caused by a simulated swap.
+#[derive(Eq, PartialEq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+pub struct BalanceChangeInfo<AccountId, AssetId, Balance> {
+    pub account_id: AccountId,
+    pub asset_id: AssetId,
+    #[cfg_attr(
+        feature = "std",
+        serde(
+            bound(
+                serialize = "Balance: std::fmt::Display",
+                deserialize = "Balance: std::str::FromStr"
+            ),
+            with = "string_serialization"
+        )
+    )]
+    pub balance_before: Balance,
+    #[cfg_attr(
+        feature = "std",
+        serde(
+            bound(
+                serialize = "Balance: std::fmt::Display",
+                deserialize = "Balance: std::str::FromStr"
+            ),
+            with = "string_serialization"
+        )
+    )]
+    pub balance_after: Balance,
+}
+
+/// Effects of a swap executed against the current state without being committed.
+#[derive(Eq, PartialEq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+pub struct SwapSimulationInfo<AccountId, AssetId, Balance> {
+    /// Outcome of the swap, `None` if it has failed.
+    pub outcome: Option<SwapOutcomeInfo<Balance>>,
+    /// Error the swap has failed with.
+    pub error: Option<DispatchError>,
+    /// Balances changed by the swap, including the ones of the liquidity sources and fee accounts.
+    pub balance_changes: Vec<BalanceChangeInfo<AccountId, AssetId, Balance>>,
+    /// SCALE-encoded runtime events deposited by the swap.
+    pub events: Vec<Vec<u8>>,
+}
+
 sp_api::decl_runtime_apis! {
-    pub trait LiquidityProxyAPI<DEXId, AssetId, Balance, SwapVariant, LiquiditySourceType, FilterMode> where
+    pub trait LiquidityProxyAPI<DEXId, AssetId, Balance, SwapVariant, LiquiditySourceType, FilterMode, AccountId> where
         DEXId: Codec,
         AssetId: Codec,
         Balance: Codec + MaybeFromStr + MaybeDisplay,
         SwapVariant: Codec,
         LiquiditySourceType: Codec,
         FilterMode: Codec,
+        AccountId: Codec,
     {
         fn quote(
             dex_id: DEXId,
@@ -98,5 +345,36 @@ sp_api::decl_runtime_apis! {
             input_asset_id: AssetId,
             output_asset_id: AssetId,
         ) -> Vec<LiquiditySourceType>;
+
+        fn find_routes(
+            dex_id: DEXId,
+            input_asset_id: AssetId,
+            output_asset_id: AssetId,
+            amount: BalanceWrapper,
+            swap_variant: SwapVariant,
+            selected_source_types: Vec<LiquiditySourceType>,
+            filter_mode: FilterMode,
+        ) -> Vec<RouteInfo<AssetId, Balance>>;
+
+        fn quote_breakdown(
+            dex_id: DEXId,
+            input_asset_id: AssetId,
+            output_asset_id: AssetId,
+            amount: BalanceWrapper,
+            swap_variant: SwapVariant,
+            selected_source_types: Vec<LiquiditySourceType>,
+            filter_mode: FilterMode,
+        ) -> Option<QuoteBreakdownInfo<AssetId, LiquiditySourceType, Balance>>;
+
+        fn simulate_swap(
+            account_id: AccountId,
+            dex_id: DEXId,
+            input_asset_id: AssetId,
+            output_asset_id: AssetId,
+            amount: BalanceWrapper,
+            swap_variant: SwapVariant,
+            selected_source_types: Vec<LiquiditySourceType>,
+            filter_mode: FilterMode,
+        ) -> SwapSimulationInfo<AccountId, AssetId, Balance>;
     }
 }
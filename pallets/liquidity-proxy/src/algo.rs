@@ -32,9 +32,10 @@ use core::convert::TryFrom;
 
 use sp_std::vec::Vec;
 
-use common::prelude::fixnum::ops::CheckedAdd;
+use common::prelude::fixnum::ops::{CheckedAdd, CheckedSub, Zero};
+use common::prelude::fixnum::ArithmeticError;
 use common::prelude::FixedWrapper;
-use common::{balance, fixed, Fixed};
+use common::{balance, fixed, fixed_wrapper, Fixed, SwapCurve};
 
 /// Given a set of monotoneous sequences A_i(n), i = 0..M-1, n = 0..N-1 returns a pair of:
 /// - a vector of "weights" [W_i / N], i = 0..M-1, where W_i are lengths of respective
@@ -105,3 +106,200 @@ pub fn find_distribution(sample_data: Vec<Vec<Fixed>>, inversed: bool) -> (Vec<F
     let best_amount = accumulator[n - 1][s];
     (distribution, best_amount)
 }
+
+/// Number of bisection steps `split_by_curves` takes to find the marginal price of the trade,
+/// enough to exhaust `Fixed` precision once the price is bracketed within a factor of two.
+const CURVE_SPLIT_BISECTION_STEPS: usize = 64;
+
+/// Maximum number of times `split_by_curves` doubles the marginal price to bracket it,
+/// the trade is considered infeasible for the curves if it takes more.
+const CURVE_SPLIT_BRACKET_STEPS: usize = 128;
+
+/// Marginal price, i.e. the input amount per unit of the output, of the curve before the trade.
+fn initial_marginal_price(curve: &SwapCurve) -> Result<Fixed, ArithmeticError> {
+    match *curve {
+        SwapCurve::ConstantProduct {
+            input_reserve,
+            output_reserve,
+            fee,
+        } => (FixedWrapper::from(input_reserve)
+            / (FixedWrapper::from(output_reserve) * (fixed_wrapper!(1) - fee)))
+            .get(),
+        SwapCurve::Linear { price, .. } => Ok(price),
+    }
+}
+
+/// Price of the input asset in the output asset units before the trade, the fee isn't included
+/// unless the curve folds it into the reserves.
+pub fn spot_price(curve: &SwapCurve) -> Result<Fixed, ArithmeticError> {
+    match *curve {
+        SwapCurve::ConstantProduct {
+            input_reserve,
+            output_reserve,
+            ..
+        } => (FixedWrapper::from(output_reserve) / input_reserve).get(),
+        SwapCurve::Linear { price, .. } => (fixed_wrapper!(1) / price).get(),
+    }
+}
+
+/// Amount of the output asset bought from the curve until its marginal price reaches `price`.
+///
+/// ```nocompile
+/// constant product: y = R_out - sqrt(R_in * R_out / ((1 - f) * price))
+/// linear:           y = (price - P) / S
+/// ```
+fn output_at_marginal_price(curve: &SwapCurve, price: Fixed) -> Result<Fixed, ArithmeticError> {
+    if price <= initial_marginal_price(curve)? {
+        return Ok(Fixed::ZERO);
+    }
+    match *curve {
+        SwapCurve::ConstantProduct {
+            input_reserve,
+            output_reserve,
+            fee,
+        } => {
+            let output_reserve = FixedWrapper::from(output_reserve);
+            let remaining = (FixedWrapper::from(input_reserve)
+                / ((fixed_wrapper!(1) - fee) * price))
+                .multiply_and_sqrt(&output_reserve);
+            let output = (output_reserve - remaining).get()?;
+            Ok(output.max(Fixed::ZERO))
+        }
+        SwapCurve::Linear {
+            price: initial_price,
+            slope,
+        } => {
+            if slope <= Fixed::ZERO {
+                return Err(ArithmeticError::DomainViolation);
+            }
+            ((FixedWrapper::from(price) - initial_price) / slope).get()
+        }
+    }
+}
+
+/// Amount of the input asset the curve takes to provide `output` amount of the output asset.
+///
+/// ```nocompile
+/// constant product: x = R_in * y / ((R_out - y) * (1 - f))
+/// linear:           x = P * y + S * y² / 2
+/// ```
+fn input_for_output(curve: &SwapCurve, output: Fixed) -> Result<Fixed, ArithmeticError> {
+    if output == Fixed::ZERO {
+        return Ok(Fixed::ZERO);
+    }
+    match *curve {
+        SwapCurve::ConstantProduct {
+            input_reserve,
+            output_reserve,
+            fee,
+        } => (FixedWrapper::from(input_reserve)
+            * (FixedWrapper::from(output) / (FixedWrapper::from(output_reserve) - output))
+            / (fixed_wrapper!(1) - fee))
+            .get(),
+        SwapCurve::Linear { price, slope } => {
+            let output = FixedWrapper::from(output);
+            (output.clone() * price + output.clone() * output * slope / fixed!(2)).get()
+        }
+    }
+}
+
+/// Amounts of the input and the output assets each curve trades until its marginal price
+/// reaches `price`.
+fn amounts_at_marginal_price(
+    curves: &[SwapCurve],
+    price: Fixed,
+) -> Result<Vec<(Fixed, Fixed)>, ArithmeticError> {
+    curves
+        .iter()
+        .map(|curve| {
+            let output = output_at_marginal_price(curve, price)?;
+            Ok((input_for_output(curve, output)?, output))
+        })
+        .collect()
+}
+
+/// Given a set of liquidity sources described by `curves` finds the split of the trade which
+/// equalizes marginal prices of all sources taking part in it, which is the optimal split
+/// for convex curves. The common marginal price is found by bisection. Returns a pair of:
+/// - a vector of shares of `amount` each source gets, the shares sum up to one,
+/// - the counterpart amount of the entire trade, i.e. the output amount if `amount` is sold
+/// or the input amount if `amount` is bought.
+///
+/// - `amount`: the amount to be sold (bought),
+/// - `inversed`: boolean flag: if true, `amount` is bought (otherwise sold), same as in `find_distribution`.
+pub fn split_by_curves(
+    curves: &[SwapCurve],
+    amount: Fixed,
+    inversed: bool,
+) -> Result<(Vec<Fixed>, Fixed), ArithmeticError> {
+    if curves.is_empty() || amount <= Fixed::ZERO {
+        return Err(ArithmeticError::DomainViolation);
+    }
+    // Part of the trade nominated in `amount` units and its counterpart.
+    let sides = |(input, output): (Fixed, Fixed)| match inversed {
+        true => (output, input),
+        _ => (input, output),
+    };
+    let traded = |price: Fixed| -> Result<Fixed, ArithmeticError> {
+        amounts_at_marginal_price(curves, price)?
+            .into_iter()
+            .try_fold(Fixed::ZERO, |acc, amounts| acc.cadd(sides(amounts).0))
+    };
+
+    let initial_prices = curves
+        .iter()
+        .map(initial_marginal_price)
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut low = initial_prices.iter().copied().min().unwrap_or(Fixed::ZERO);
+    let mut high = initial_prices.iter().copied().max().unwrap_or(Fixed::ZERO);
+    let mut bracket_steps = 0;
+    while traded(high)? < amount {
+        bracket_steps += 1;
+        if bracket_steps > CURVE_SPLIT_BRACKET_STEPS {
+            return Err(ArithmeticError::Overflow);
+        }
+        low = high;
+        high = match high == Fixed::ZERO {
+            true => fixed!(1),
+            _ => high.cadd(high)?,
+        };
+    }
+    for _ in 0..CURVE_SPLIT_BISECTION_STEPS {
+        let middle = Fixed::from_bits(low.into_bits() + (high.into_bits() - low.into_bits()) / 2);
+        if middle == low || middle == high {
+            break;
+        }
+        if traded(middle)? < amount {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+
+    let (parts, counterparts): (Vec<Fixed>, Vec<Fixed>) = amounts_at_marginal_price(curves, high)?
+        .into_iter()
+        .map(sides)
+        .unzip();
+    let total = parts
+        .iter()
+        .try_fold(Fixed::ZERO, |acc, part| acc.cadd(*part))?;
+    let counterpart = counterparts
+        .iter()
+        .try_fold(Fixed::ZERO, |acc, part| acc.cadd(*part))?;
+    // The bracket is closed from above, so the curves trade slightly more than `amount`.
+    let counterpart = (FixedWrapper::from(counterpart) * amount / total).get()?;
+
+    let mut shares = parts
+        .into_iter()
+        .map(|part| (FixedWrapper::from(part) / total).get())
+        .collect::<Result<Vec<_>, _>>()?;
+    // Rounding leftovers go to the largest share so that the shares sum up to one exactly.
+    let largest = (0..shares.len()).max_by_key(|&i| shares[i]).unwrap_or(0);
+    shares[largest] = Fixed::ZERO;
+    let rest = shares
+        .iter()
+        .try_fold(Fixed::ZERO, |acc, share| acc.cadd(*share))?;
+    shares[largest] = fixed!(1).csub(rest)?;
+
+    Ok((shares, counterpart))
+}
@@ -39,19 +39,42 @@ use core::convert::{TryFrom, TryInto};
 use common::prelude::fixnum::ops::{Bounded, CheckedMul, One, Zero as _};
 use common::prelude::{Balance, FixedWrapper, SwapAmount, SwapOutcome, SwapVariant};
 use common::{
-    fixed, fixed_wrapper, linspace, FilterMode, Fixed, FixedInner, GetMarketInfo, GetPoolReserves,
+    balance, fixed, fixed_from_basis_points, fixed_wrapper, in_basis_points_range, linspace,
+    BasisPoints, FilterMode, Fixed, FixedInner, GetMarketInfo, GetPoolReserves, GetSwapCurve,
     IntervalEndpoints, LiquidityRegistry, LiquiditySource, LiquiditySourceFilter,
-    LiquiditySourceId, LiquiditySourceType, TradingPair,
+    LiquiditySourceId, LiquiditySourceType, SwapCurve, TradingPair,
 };
+use frame_support::storage::{with_transaction, TransactionOutcome};
 use frame_support::traits::Get;
 use frame_support::weights::Weight;
 use frame_support::{ensure, fail, RuntimeDebug};
 use frame_system::ensure_signed;
-use sp_runtime::traits::{UniqueSaturatedFrom, Zero};
+use sp_runtime::traits::{Hash, UniqueSaturatedFrom, Zero};
 use sp_runtime::DispatchError;
+use sp_std::collections::btree_map::BTreeMap;
+use sp_std::collections::btree_set::BTreeSet;
 use sp_std::prelude::*;
 
 type LiquiditySourceIdOf<T> = LiquiditySourceId<<T as common::Config>::DEXId, LiquiditySourceType>;
+type AggregatedSwapOutcomeOf<T> =
+    AggregatedSwapOutcome<<T as assets::Config>::AssetId, LiquiditySourceIdOf<T>, Balance>;
+type QuoteBreakdownOf<T> =
+    QuoteBreakdown<<T as assets::Config>::AssetId, LiquiditySourceIdOf<T>, Balance>;
+type SwapBreakdownOf<T> =
+    SwapBreakdown<<T as assets::Config>::AssetId, LiquiditySourceIdOf<T>, Balance>;
+type SwapCommitmentOf<T> =
+    SwapCommitment<<T as assets::Config>::AssetId, <T as frame_system::Config>::BlockNumber>;
+type SwapSimulationOf<T> = SwapSimulation<
+    <T as frame_system::Config>::AccountId,
+    <T as assets::Config>::AssetId,
+    <T as frame_system::Config>::Event,
+>;
+type DcaOrderOf<T> = DcaOrder<
+    <T as frame_system::Config>::AccountId,
+    <T as common::Config>::DEXId,
+    <T as assets::Config>::AssetId,
+    <T as frame_system::Config>::BlockNumber,
+>;
 
 pub mod weights;
 
@@ -66,50 +89,52 @@ pub mod algo;
 pub const TECH_ACCOUNT_PREFIX: &[u8] = b"liquidity-proxy";
 pub const TECH_ACCOUNT_MAIN: &[u8] = b"main";
 
-pub enum ExchangePath<T: Config> {
-    Direct {
-        from_asset_id: T::AssetId,
-        to_asset_id: T::AssetId,
-    },
-    Twofold {
-        from_asset_id: T::AssetId,
-        intermediate_asset_id: T::AssetId,
-        to_asset_id: T::AssetId,
-    },
+/// Sequence of assets a trade goes through, every two adjacent assets are exchanged directly.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ExchangePath<AssetId>(pub Vec<AssetId>);
+
+impl<AssetId: Copy> ExchangePath<AssetId> {
+    /// Pairs of assets exchanged directly, in the order the exchanges are performed.
+    pub fn as_vec(self) -> Vec<(AssetId, AssetId)> {
+        self.0
+            .windows(2)
+            .map(|assets| (assets[0], assets[1]))
+            .collect()
+    }
 }
 
-impl<T: Config> ExchangePath<T> {
-    pub fn as_vec(self) -> Vec<(T::AssetId, T::AssetId)> {
-        match self {
-            ExchangePath::Direct {
-                from_asset_id,
-                to_asset_id,
-            } => [(from_asset_id, to_asset_id)].into(),
-            ExchangePath::Twofold {
-                from_asset_id,
-                intermediate_asset_id,
-                to_asset_id,
-            } => [
-                (from_asset_id, intermediate_asset_id),
-                (intermediate_asset_id, to_asset_id),
-            ]
-            .into(),
-        }
-    }
+/// Part of a trade performed along a single path.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct RouteOutcome<AssetId, AmountType> {
+    /// Path the part of the trade goes through
+    pub path: ExchangePath<AssetId>,
+    /// Share of the entire trade amount the path gets
+    pub share: Fixed,
+    /// Amount of the input asset sold along the path
+    pub input_amount: AmountType,
+    /// Amount of the output asset bought along the path
+    pub output_amount: AmountType,
+    /// Fee amount of the part of the trade, nominated in XOR
+    pub fee: AmountType,
 }
 
 /// Output of the aggregated LiquidityProxy::quote() price.
 #[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, PartialOrd, Ord)]
-pub struct AggregatedSwapOutcome<LiquiditySourceType, AmountType> {
-    /// A distribution of shares each liquidity sources gets to swap in the entire trade
+pub struct AggregatedSwapOutcome<AssetId, LiquiditySourceType, AmountType> {
+    /// A distribution of shares each liquidity sources gets to swap in the entire trade,
+    /// only provided for a trade of a single pair of assets
     pub distribution: Vec<(LiquiditySourceType, Fixed)>,
+    /// Paths the trade is split across, empty for a trade of a single pair of assets
+    pub routes: Vec<RouteOutcome<AssetId, AmountType>>,
     /// The best possible output/input amount for a given trade and a set of liquidity sources
     pub amount: AmountType,
     /// Total fee amount, nominated in XOR
     pub fee: AmountType,
 }
 
-impl<LiquiditySourceIdType, AmountType> AggregatedSwapOutcome<LiquiditySourceIdType, AmountType> {
+impl<AssetId, LiquiditySourceIdType, AmountType>
+    AggregatedSwapOutcome<AssetId, LiquiditySourceIdType, AmountType>
+{
     pub fn new(
         distribution: Vec<(LiquiditySourceIdType, Fixed)>,
         amount: AmountType,
@@ -117,12 +142,145 @@ impl<LiquiditySourceIdType, AmountType> AggregatedSwapOutcome<LiquiditySourceIdT
     ) -> Self {
         Self {
             distribution,
+            routes: Vec::new(),
+            amount,
+            fee,
+        }
+    }
+
+    pub fn with_routes(
+        routes: Vec<RouteOutcome<AssetId, AmountType>>,
+        amount: AmountType,
+        fee: AmountType,
+    ) -> Self {
+        Self {
+            distribution: Vec::new(),
+            routes,
             amount,
             fee,
         }
     }
 }
 
+/// A single trade of `swap_batch`.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+pub struct BatchSwapLeg<AssetId> {
+    pub input_asset_id: AssetId,
+    pub output_asset_id: AssetId,
+    pub swap_amount: SwapAmount<Balance>,
+    pub selected_source_types: Vec<LiquiditySourceType>,
+    pub filter_mode: FilterMode,
+}
+
+/// Identifier of an order created by `create_dca_order`.
+pub type DcaOrderId = u64;
+
+/// Dollar-cost-averaging order selling a fixed amount of the input asset every `interval` blocks.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+pub struct DcaOrder<AccountId, DEXId, AssetId, BlockNumber> {
+    /// Account the swaps are performed on behalf of, the funds for the remaining swaps are reserved on it
+    pub owner: AccountId,
+    pub dex_id: DEXId,
+    pub input_asset_id: AssetId,
+    pub output_asset_id: AssetId,
+    /// Amount of the input asset sold by every swap
+    pub amount: Balance,
+    /// Minimum amount of the output asset bought by every swap
+    pub min_amount_out: Balance,
+    /// Number of blocks between the swaps
+    pub interval: BlockNumber,
+    /// Number of swaps left to perform
+    pub remaining: u32,
+    pub selected_source_types: Vec<LiquiditySourceType>,
+    pub filter_mode: FilterMode,
+    /// Block the next swap is scheduled for
+    pub next_execution: BlockNumber,
+}
+
+/// Swap committed by `commit_swap` and hidden until revealed by `reveal_swap`.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+pub struct SwapCommitment<AssetId, BlockNumber> {
+    /// Asset sold by the swap
+    pub input_asset_id: AssetId,
+    /// Amount of the input asset reserved for the swap, the most the swap can sell
+    pub reserved_amount: Balance,
+    /// Block the commitment has been made in
+    pub committed_at: BlockNumber,
+}
+
+/// Part of an individual swap performed by a single liquidity source.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+pub struct SourceBreakdown<LiquiditySourceIdType, AmountType> {
+    pub source: LiquiditySourceIdType,
+    /// Share of the individual swap amount the source gets
+    pub share: Fixed,
+    pub input_amount: AmountType,
+    pub output_amount: AmountType,
+}
+
+/// Detailed outcome of an individual swap of a poly-swap.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+pub struct SwapBreakdown<AssetId, LiquiditySourceIdType, AmountType> {
+    pub input_asset_id: AssetId,
+    pub output_asset_id: AssetId,
+    pub input_amount: AmountType,
+    pub output_amount: AmountType,
+    /// Parts of the swap performed by each of the liquidity sources
+    pub sources: Vec<SourceBreakdown<LiquiditySourceIdType, AmountType>>,
+    /// Price of the input asset in the output asset units before the swap
+    pub spot_price: Fixed,
+    /// Price of the input asset in the output asset units the swap is performed at
+    pub effective_price: Fixed,
+    /// Difference between the spot price and the effective price in basis points of the spot price
+    pub price_impact: BasisPoints,
+    /// Fee amount, nominated in XOR
+    pub fee: AmountType,
+    /// Fee amount, nominated in the input asset
+    pub fee_in_input_asset: AmountType,
+}
+
+/// Detailed outcome of a part of a trade performed along a single path.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+pub struct RouteBreakdown<AssetId, LiquiditySourceIdType, AmountType> {
+    pub path: ExchangePath<AssetId>,
+    /// Share of the entire trade amount the path gets
+    pub share: Fixed,
+    /// Individual swaps the path consists of, in the order of execution
+    pub swaps: Vec<SwapBreakdown<AssetId, LiquiditySourceIdType, AmountType>>,
+}
+
+/// Detailed output of LiquidityProxy::quote().
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+pub struct QuoteBreakdown<AssetId, LiquiditySourceIdType, AmountType> {
+    /// The best possible output/input amount for a given trade
+    pub amount: AmountType,
+    /// Total fee amount, nominated in XOR
+    pub fee: AmountType,
+    pub routes: Vec<RouteBreakdown<AssetId, LiquiditySourceIdType, AmountType>>,
+}
+
+/// Change of an account balance caused by a simulated swap.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+pub struct BalanceChange<AccountId, AssetId> {
+    pub account_id: AccountId,
+    pub asset_id: AssetId,
+    /// Free balance before the swap
+    pub balance_before: Balance,
+    /// Free balance after the swap
+    pub balance_after: Balance,
+}
+
+/// Output of LiquidityProxy::simulate_swap().
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+pub struct SwapSimulation<AccountId, AssetId, Event> {
+    /// Outcome of the swap or the error it has failed with
+    pub result: Result<SwapOutcome<Balance>, DispatchError>,
+    /// Balances changed by the swap, including the ones of the liquidity sources and fee accounts
+    pub balance_changes: Vec<BalanceChange<AccountId, AssetId>>,
+    /// Events deposited by the swap
+    pub events: Vec<Event>,
+}
+
 /// Indicates that particular object can be used to perform exchanges with aggregation capability.
 pub trait LiquidityProxyTrait<DEXId: PartialEq + Copy, AccountId, AssetId> {
     /// Get spot price of tokens based on desired amount, None returned if liquidity source
@@ -143,6 +301,9 @@ pub trait LiquidityProxyTrait<DEXId: PartialEq + Copy, AccountId, AssetId> {
         amount: SwapAmount<Balance>,
         filter: LiquiditySourceFilter<DEXId, LiquiditySourceType>,
     ) -> Result<SwapOutcome<Balance>, DispatchError>;
+
+    /// Weight of `exchange` with the given swap variant, including routing the trade.
+    fn exchange_weight(amount: SwapVariant) -> Weight;
 }
 
 impl<DEXId: PartialEq + Copy, AccountId, AssetId> LiquidityProxyTrait<DEXId, AccountId, AssetId>
@@ -167,10 +328,25 @@ impl<DEXId: PartialEq + Copy, AccountId, AssetId> LiquidityProxyTrait<DEXId, Acc
     ) -> Result<SwapOutcome<Balance>, DispatchError> {
         unimplemented!()
     }
+
+    fn exchange_weight(_amount: SwapVariant) -> Weight {
+        0
+    }
 }
 
 pub trait WeightInfo {
     fn swap(amount: SwapVariant) -> Weight;
+    fn swap_with_options(amount: SwapVariant) -> Weight;
+    fn quote_path(hops: u32) -> Weight;
+    fn set_referrer_reward_rate() -> Weight;
+    fn set_max_price_impact() -> Weight;
+    fn swap_batch(n: u32) -> Weight;
+    fn create_dca_order() -> Weight;
+    fn cancel_dca_order() -> Weight;
+    fn execute_dca_order() -> Weight;
+    fn commit_swap() -> Weight;
+    fn reveal_swap(amount: SwapVariant) -> Weight;
+    fn refund_swap_commitment() -> Weight;
 }
 
 impl<T: Config> Pallet<T> {
@@ -244,10 +420,10 @@ impl<T: Config> Pallet<T> {
         }
     }
 
-    /// Applies trivial routing (via Base Asset), resulting in a poly-swap which may contain several individual swaps.
-    /// Those individual swaps are subject to liquidity aggregation algorithm.
+    /// Splits the trade across the paths available in the DEX (see `quote`) and performs
+    /// a poly-swap along each of them, either all of the poly-swaps succeed or none.
     ///
-    /// This a wrapper for `exchange_single`.
+    /// This a wrapper for `exchange_path`.
     pub fn exchange(
         sender: &T::AccountId,
         receiver: &T::AccountId,
@@ -255,109 +431,202 @@ impl<T: Config> Pallet<T> {
         output_asset_id: &T::AssetId,
         amount: SwapAmount<Balance>,
         filter: LiquiditySourceFilter<T::DEXId, LiquiditySourceType>,
-    ) -> Result<SwapOutcome<Balance>, DispatchError> {
-        ensure!(
-            input_asset_id != output_asset_id,
-            Error::<T>::UnavailableExchangePath
-        );
+    ) -> Result<AggregatedSwapOutcomeOf<T>, DispatchError> {
+        Self::exchange_with_referrer(
+            sender,
+            receiver,
+            input_asset_id,
+            output_asset_id,
+            amount,
+            filter,
+            None,
+        )
+        .map(|(outcome, _)| outcome)
+    }
+
+    /// Same as `exchange`, but also pays the referrer's share of the fee collected by the liquidity
+    /// sources (see `pay_referrer_reward`), the total reward paid is returned along with the outcome.
+    fn exchange_with_referrer(
+        sender: &T::AccountId,
+        receiver: &T::AccountId,
+        input_asset_id: &T::AssetId,
+        output_asset_id: &T::AssetId,
+        amount: SwapAmount<Balance>,
+        filter: LiquiditySourceFilter<T::DEXId, LiquiditySourceType>,
+        referrer: Option<&T::AccountId>,
+    ) -> Result<(AggregatedSwapOutcomeOf<T>, Balance), DispatchError> {
         common::with_transaction(|| {
-            match Self::construct_trivial_path(*input_asset_id, *output_asset_id) {
-                ExchangePath::Direct {
-                    from_asset_id,
-                    to_asset_id,
-                } => Self::exchange_single(
+            // Sources move the assets through `assets`, which rejects frozen accounts as well,
+            // checking upfront makes the swap fail the same way regardless of the source.
+            assets::Pallet::<T>::ensure_not_frozen(input_asset_id, sender)?;
+            assets::Pallet::<T>::ensure_not_frozen(output_asset_id, receiver)?;
+            let quote = Self::quote(input_asset_id, output_asset_id, amount, filter.clone())?;
+            // The slippage is checked for the entire trade, unless it's not split.
+            let is_split = quote.routes.len() > 1;
+            let mut routes = Vec::with_capacity(quote.routes.len());
+            let mut total_amount = Balance::zero();
+            let mut total_fee = Balance::zero();
+            let mut total_reward = Balance::zero();
+            for route in quote.routes {
+                let route_amount = match amount {
+                    _ if !is_split => amount,
+                    SwapAmount::WithDesiredInput { .. } => {
+                        SwapAmount::with_desired_input(route.input_amount, Balance::zero())
+                    }
+                    SwapAmount::WithDesiredOutput { .. } => {
+                        SwapAmount::with_desired_output(route.output_amount, Balance::MAX)
+                    }
+                };
+                let (outcome, reward) = Self::exchange_path(
                     sender,
                     receiver,
-                    &from_asset_id,
-                    &to_asset_id,
+                    route.path.clone(),
+                    route_amount,
+                    filter.clone(),
+                    referrer,
+                )?;
+                total_reward = total_reward
+                    .checked_add(reward)
+                    .ok_or(Error::<T>::CalculationError)?;
+                let (input_amount, output_amount, fee) = match amount {
+                    SwapAmount::WithDesiredInput { .. } => {
+                        (route.input_amount, outcome.amount, outcome.fee)
+                    }
+                    SwapAmount::WithDesiredOutput { .. } => {
+                        (outcome.amount, route.output_amount, outcome.fee)
+                    }
+                };
+                total_amount = match amount {
+                    SwapAmount::WithDesiredInput { .. } => total_amount.checked_add(output_amount),
+                    SwapAmount::WithDesiredOutput { .. } => total_amount.checked_add(input_amount),
+                }
+                .ok_or(Error::<T>::CalculationError)?;
+                total_fee = total_fee
+                    .checked_add(fee)
+                    .ok_or(Error::<T>::CalculationError)?;
+                routes.push(RouteOutcome {
+                    input_amount,
+                    output_amount,
+                    fee,
+                    ..route
+                });
+            }
+            match amount {
+                SwapAmount::WithDesiredInput { min_amount_out, .. } => ensure!(
+                    total_amount >= min_amount_out,
+                    Error::<T>::SlippageNotTolerated
+                ),
+                SwapAmount::WithDesiredOutput { max_amount_in, .. } => ensure!(
+                    total_amount <= max_amount_in,
+                    Error::<T>::SlippageNotTolerated
+                ),
+            }
+            Ok((
+                AggregatedSwapOutcome::with_routes(routes, total_amount, total_fee),
+                total_reward,
+            ))
+        })
+    }
+
+    /// Performs a poly-swap along the given path, the intermediate assets are kept
+    /// on the technical account between the individual swaps.
+    ///
+    /// Returns the outcome and the reward paid to the referrer.
+    fn exchange_path(
+        sender: &T::AccountId,
+        receiver: &T::AccountId,
+        path: ExchangePath<T::AssetId>,
+        amount: SwapAmount<Balance>,
+        filter: LiquiditySourceFilter<T::DEXId, LiquiditySourceType>,
+        referrer: Option<&T::AccountId>,
+    ) -> Result<(SwapOutcome<Balance>, Balance), DispatchError> {
+        let swaps = path.clone().as_vec();
+        match swaps.as_slice() {
+            [] => fail!(Error::<T>::UnavailableExchangePath),
+            [(from_asset_id, to_asset_id)] => {
+                return Self::exchange_single(
+                    sender,
+                    receiver,
+                    from_asset_id,
+                    to_asset_id,
                     amount,
                     filter,
-                ),
-                ExchangePath::Twofold {
+                    referrer,
+                );
+            }
+            _ => {}
+        }
+        common::with_transaction(|| {
+            let desired_amount_in = match amount {
+                SwapAmount::WithDesiredInput {
+                    desired_amount_in, ..
+                } => desired_amount_in,
+                SwapAmount::WithDesiredOutput {
+                    desired_amount_out,
+                    max_amount_in,
+                } => {
+                    let quote = Self::quote_path(
+                        path,
+                        SwapAmount::with_desired_output(desired_amount_out, Balance::MAX),
+                        filter.clone(),
+                    )?;
+                    ensure!(
+                        quote.amount <= max_amount_in,
+                        Error::<T>::SlippageNotTolerated
+                    );
+                    quote.amount
+                }
+            };
+            let transit_account = T::GetTechnicalAccountId::get();
+            let last_swap_index = swaps.len() - 1;
+            let mut swap_amount = desired_amount_in;
+            let mut cumulative_fee = Balance::zero();
+            let mut cumulative_reward = Balance::zero();
+            for (index, (from_asset_id, to_asset_id)) in swaps.iter().enumerate() {
+                let swap_sender = if index == 0 { sender } else { &transit_account };
+                let swap_receiver = if index == last_swap_index {
+                    receiver
+                } else {
+                    &transit_account
+                };
+                let (swap, reward) = Self::exchange_single(
+                    swap_sender,
+                    swap_receiver,
                     from_asset_id,
-                    intermediate_asset_id,
                     to_asset_id,
-                } => match amount {
-                    SwapAmount::WithDesiredInput {
-                        desired_amount_in,
-                        min_amount_out,
-                    } => {
-                        let transit_account = T::GetTechnicalAccountId::get();
-                        let first_swap = Self::exchange_single(
-                            sender,
-                            &transit_account,
-                            &from_asset_id,
-                            &intermediate_asset_id,
-                            SwapAmount::with_desired_input(desired_amount_in, Balance::zero()),
-                            filter.clone(),
-                        )?;
-                        let second_swap = Self::exchange_single(
-                            &transit_account,
-                            receiver,
-                            &intermediate_asset_id,
-                            &to_asset_id,
-                            SwapAmount::with_desired_input(first_swap.amount, Balance::zero()),
-                            filter,
-                        )?;
-                        ensure!(
-                            second_swap.amount >= min_amount_out,
-                            Error::<T>::SlippageNotTolerated
-                        );
-                        let cumulative_fee = first_swap
-                            .fee
-                            .checked_add(second_swap.fee)
-                            .ok_or(Error::<T>::CalculationError)?;
-                        Ok(SwapOutcome::new(second_swap.amount, cumulative_fee))
-                    }
-                    SwapAmount::WithDesiredOutput {
-                        desired_amount_out,
-                        max_amount_in,
-                    } => {
-                        let second_quote = Self::quote_single(
-                            &intermediate_asset_id,
-                            &to_asset_id,
-                            SwapAmount::with_desired_output(desired_amount_out, Balance::MAX),
-                            filter.clone(),
-                        )?;
-                        let first_quote = Self::quote_single(
-                            &from_asset_id,
-                            &intermediate_asset_id,
-                            SwapAmount::with_desired_output(second_quote.amount, Balance::MAX),
-                            filter.clone(),
-                        )?;
-                        ensure!(
-                            first_quote.amount <= max_amount_in,
-                            Error::<T>::SlippageNotTolerated
-                        );
-                        let transit_account = T::GetTechnicalAccountId::get();
-                        let first_swap = Self::exchange_single(
-                            sender,
-                            &transit_account,
-                            &from_asset_id,
-                            &intermediate_asset_id,
-                            SwapAmount::with_desired_input(first_quote.amount, Balance::zero()),
-                            filter.clone(),
-                        )?;
-                        let second_swap = Self::exchange_single(
-                            &transit_account,
-                            receiver,
-                            &intermediate_asset_id,
-                            &to_asset_id,
-                            SwapAmount::with_desired_input(first_swap.amount, Balance::zero()),
-                            filter,
-                        )?;
-                        let cumulative_fee = first_swap
-                            .fee
-                            .checked_add(second_swap.fee)
-                            .ok_or(Error::<T>::CalculationError)?;
-                        Ok(SwapOutcome::new(first_quote.amount, cumulative_fee))
-                    }
-                },
+                    SwapAmount::with_desired_input(swap_amount, Balance::zero()),
+                    filter.clone(),
+                    referrer,
+                )?;
+                swap_amount = swap.amount;
+                cumulative_fee = cumulative_fee
+                    .checked_add(swap.fee)
+                    .ok_or(Error::<T>::CalculationError)?;
+                cumulative_reward = cumulative_reward
+                    .checked_add(reward)
+                    .ok_or(Error::<T>::CalculationError)?;
+            }
+            match amount {
+                SwapAmount::WithDesiredInput { min_amount_out, .. } => {
+                    ensure!(
+                        swap_amount >= min_amount_out,
+                        Error::<T>::SlippageNotTolerated
+                    );
+                    Ok((
+                        SwapOutcome::new(swap_amount, cumulative_fee),
+                        cumulative_reward,
+                    ))
+                }
+                SwapAmount::WithDesiredOutput { .. } => Ok((
+                    SwapOutcome::new(desired_amount_in, cumulative_fee),
+                    cumulative_reward,
+                )),
             }
         })
     }
-
     /// Performs a swap given a number of liquidity sources and a distribuition of the swap amount across the sources.
+    ///
+    /// Returns the outcome and the reward paid to the referrer.
     fn exchange_single(
         sender: &T::AccountId,
         receiver: &T::AccountId,
@@ -365,7 +634,8 @@ impl<T: Config> Pallet<T> {
         output_asset_id: &T::AssetId,
         amount: SwapAmount<Balance>,
         filter: LiquiditySourceFilter<T::DEXId, LiquiditySourceType>,
-    ) -> Result<SwapOutcome<Balance>, DispatchError> {
+        referrer: Option<&T::AccountId>,
+    ) -> Result<(SwapOutcome<Balance>, Balance), DispatchError> {
         common::with_transaction(|| {
             let fx_amount: SwapAmount<Fixed> = amount
                 .try_into()
@@ -379,20 +649,35 @@ impl<T: Config> Pallet<T> {
                     let filter = filter
                         .try_into()
                         .map_err(|_| Error::CalculationError::<T>)?;
-                    T::LiquidityRegistry::exchange(
+                    let outcome = T::LiquidityRegistry::exchange(
                         sender,
                         receiver,
                         &src,
                         input_asset_id,
                         output_asset_id,
                         filter,
-                    )
+                    )?;
+                    let reward = match referrer {
+                        Some(referrer) => Self::pay_referrer_reward(
+                            &src,
+                            input_asset_id,
+                            output_asset_id,
+                            outcome.fee,
+                            referrer,
+                        )?,
+                        None => Balance::zero(),
+                    };
+                    Ok((outcome, reward))
                 })
-                .collect::<Result<Vec<SwapOutcome<Balance>>, DispatchError>>()?;
+                .collect::<Result<Vec<(SwapOutcome<Balance>, Balance)>, DispatchError>>()?;
 
+            let reward = res
+                .iter()
+                .try_fold(Balance::zero(), |acc, (_, reward)| acc.checked_add(*reward))
+                .ok_or(Error::<T>::CalculationError)?;
             let (amount, fee): (FixedWrapper, FixedWrapper) = res.into_iter().fold(
                 (fixed_wrapper!(0), fixed_wrapper!(0)),
-                |(amount_acc, fee_acc), x| {
+                |(amount_acc, fee_acc), (x, _)| {
                     (
                         amount_acc + FixedWrapper::from(x.amount),
                         fee_acc + FixedWrapper::from(x.fee),
@@ -406,269 +691,958 @@ impl<T: Config> Pallet<T> {
                 .try_into_balance()
                 .map_err(|_| Error::CalculationError::<T>)?;
 
-            Ok(SwapOutcome::new(amount, fee))
+            Ok((SwapOutcome::new(amount, fee), reward))
         })
     }
 
-    /// Applies trivial routing (via Base Asset), resulting in a poly-swap which may contain several individual swaps.
+    /// Transfers the referrer's share of the fee collected by the liquidity source on its fee account,
+    /// the fee is nominated in the base asset of the DEX. No reward is paid from the sources
+    /// that don't keep the fee on a separate account.
+    ///
+    /// The reward is best-effort: if the transfer fails, e.g. for a frozen referrer, the reward
+    /// is skipped with `ReferrerRewardFailed`, so that a referrer can't block the swaps naming them.
+    fn pay_referrer_reward(
+        source: &LiquiditySourceIdOf<T>,
+        input_asset_id: &T::AssetId,
+        output_asset_id: &T::AssetId,
+        fee_amount: Balance,
+        referrer: &T::AccountId,
+    ) -> Result<Balance, DispatchError> {
+        let fee_account =
+            match T::LiquidityRegistry::fee_account(source, input_asset_id, output_asset_id) {
+                Some(fee_account) => fee_account,
+                None => return Ok(Balance::zero()),
+            };
+        let reward = (FixedWrapper::from(fee_amount)
+            * fixed_from_basis_points(Self::referrer_reward_rate()))
+        .try_into_balance()
+        .map_err(|_| Error::<T>::CalculationError)?;
+        if reward == 0 {
+            return Ok(reward);
+        }
+        let base_asset_id = Self::dex_base_asset_id(&source.dex_id)?;
+        let transfer_result = common::with_transaction(|| {
+            assets::Pallet::<T>::transfer_from(&base_asset_id, &fee_account, referrer, reward)
+        });
+        match transfer_result {
+            Ok(()) => Ok(reward),
+            Err(error) => {
+                Self::deposit_event(Event::<T>::ReferrerRewardFailed(
+                    referrer.clone(),
+                    reward,
+                    error,
+                ));
+                Ok(Balance::zero())
+            }
+        }
+    }
+
+    /// Finds the best way to perform the trade: the trade amount is split across the paths
+    /// with the best quotes among the paths available in the DEX (see `quote_routes`),
+    /// every part of the trade results in a poly-swap which may contain several individual swaps.
     /// Those individual swaps are subject to liquidity aggregation algorithm.
     ///
-    /// This a wrapper for `quote_single`.
+    /// The split is computed by sampling the paths with a range of amounts (see `split_routes`).
     pub fn quote(
         input_asset_id: &T::AssetId,
         output_asset_id: &T::AssetId,
         amount: SwapAmount<Balance>,
         filter: LiquiditySourceFilter<T::DEXId, LiquiditySourceType>,
-    ) -> Result<SwapOutcome<Balance>, DispatchError> {
-        ensure!(
-            input_asset_id != output_asset_id,
-            Error::<T>::UnavailableExchangePath
-        );
-        match Self::construct_trivial_path(*input_asset_id, *output_asset_id) {
-            ExchangePath::Direct {
-                from_asset_id,
-                to_asset_id,
-            } => Self::quote_single(&from_asset_id, &to_asset_id, amount, filter)
-                .map(|aso| SwapOutcome::new(aso.amount, aso.fee).into()),
-            ExchangePath::Twofold {
-                from_asset_id,
-                intermediate_asset_id,
-                to_asset_id,
-            } => match amount {
+    ) -> Result<AggregatedSwapOutcomeOf<T>, DispatchError> {
+        let mut routes =
+            Self::quote_routes(input_asset_id, output_asset_id, amount, filter.clone())?;
+        // Quotes of the paths don't account for each other, so the trade is only split across
+        // the paths that don't share a pool with the better ones.
+        let max_routes = T::GetMaxSplitRoutes::get().max(1);
+        let mut used_pools = BTreeSet::new();
+        let mut selected_routes = 0;
+        routes.retain(|(path, _)| {
+            let pools = Self::path_pools(path);
+            if selected_routes >= max_routes || !pools.is_disjoint(&used_pools) {
+                return false;
+            }
+            used_pools.extend(pools);
+            selected_routes += 1;
+            true
+        });
+        if routes.len() == 1 {
+            let (path, outcome) = routes.remove(0);
+            let (input_amount, output_amount) = match amount {
                 SwapAmount::WithDesiredInput {
                     desired_amount_in, ..
-                } => {
-                    let first_quote = Self::quote_single(
-                        &from_asset_id,
-                        &intermediate_asset_id,
-                        SwapAmount::with_desired_input(desired_amount_in, 0),
-                        filter.clone(),
-                    )?;
-                    let second_quote = Self::quote_single(
-                        &intermediate_asset_id,
-                        &to_asset_id,
-                        SwapAmount::with_desired_input(first_quote.amount, 0),
-                        filter,
-                    )?;
-                    let cumulative_fee = first_quote
-                        .fee
-                        .checked_add(second_quote.fee)
-                        .ok_or(Error::<T>::CalculationError)?;
-                    Ok(SwapOutcome::new(second_quote.amount, cumulative_fee))
-                }
+                } => (desired_amount_in, outcome.amount),
                 SwapAmount::WithDesiredOutput {
                     desired_amount_out, ..
-                } => {
-                    let second_quote = Self::quote_single(
-                        &intermediate_asset_id,
-                        &to_asset_id,
-                        SwapAmount::with_desired_output(desired_amount_out, Balance::MAX),
-                        filter.clone(),
-                    )?;
-                    let first_quote = Self::quote_single(
-                        &from_asset_id,
-                        &intermediate_asset_id,
-                        SwapAmount::with_desired_output(second_quote.amount, Balance::MAX),
-                        filter,
-                    )?;
-                    let cumulative_fee = first_quote
-                        .fee
-                        .checked_add(second_quote.fee)
-                        .ok_or(Error::<T>::CalculationError)?;
-                    Ok(SwapOutcome::new(first_quote.amount, cumulative_fee))
-                }
-            },
+                } => (outcome.amount, desired_amount_out),
+            };
+            return Ok(AggregatedSwapOutcome::with_routes(
+                vec![RouteOutcome {
+                    path,
+                    share: fixed!(1.0),
+                    input_amount,
+                    output_amount,
+                    fee: outcome.fee,
+                }],
+                outcome.amount,
+                outcome.fee,
+            ));
         }
+        let paths = routes.into_iter().map(|(path, _)| path).collect();
+        Self::split_routes(paths, amount, filter)
     }
 
-    /// Computes the optimal distribution across available liquidity sources to exectute the requested trade
-    /// given the input and output assets, the trade amount and a liquidity sources filter.
-    ///
-    /// - 'input_asset_id' - ID of the asset to sell,
-    /// - 'output_asset_id' - ID of the asset to buy,
-    /// - 'amount' - the amount with "direction" (sell or buy) together with the maximum price impact (slippage),
-    /// - 'filter' - a filter composed of a list of liquidity sources IDs to accept or ban for this trade.
+    /// Performs `exchange` on behalf of `sender` in a storage transaction that is rolled back,
+    /// reporting the outcome, the changed balances and the deposited events.
     ///
-    fn quote_single(
+    /// Only the balance changes made through the assets pallet are reported.
+    pub fn simulate_swap(
+        sender: &T::AccountId,
         input_asset_id: &T::AssetId,
         output_asset_id: &T::AssetId,
         amount: SwapAmount<Balance>,
         filter: LiquiditySourceFilter<T::DEXId, LiquiditySourceType>,
-    ) -> Result<AggregatedSwapOutcome<LiquiditySourceIdOf<T>, Balance>, DispatchError> {
-        let sources =
-            T::LiquidityRegistry::list_liquidity_sources(input_asset_id, output_asset_id, filter)?;
-
-        ensure!(!sources.is_empty(), Error::<T>::UnavailableExchangePath);
-
-        // Check if we have exactly one source => no split required
-        if sources.len() == 1 {
-            let src = sources.first().unwrap();
-            let outcome =
-                T::LiquidityRegistry::quote(src, input_asset_id, output_asset_id, amount)?;
-            return Ok(AggregatedSwapOutcome::new(
-                vec![(src.clone(), fixed!(1.0))],
-                outcome.amount,
-                outcome.fee,
-            ));
-        }
-
-        // Check if we have exactly two sources: the primary market and the secondary market
-        // Do fast swap amount split
-        if sources.len() == 2 {
-            let mut primary_market: Option<LiquiditySourceIdOf<T>> = None;
-            let mut secondary_market: Option<LiquiditySourceIdOf<T>> = None;
-
-            for src in &sources {
-                if src.liquidity_source_index
-                    == LiquiditySourceType::MulticollateralBondingCurvePool
-                {
-                    primary_market = Some(src.clone());
-                } else {
-                    secondary_market = Some(src.clone());
-                }
-            }
-            if let (Some(mcbc), Some(xyk)) = (primary_market, secondary_market) {
-                return Self::fast_split_primary_vs_secondary(
-                    mcbc,
-                    xyk,
+    ) -> SwapSimulationOf<T> {
+        let events_count = frame_system::Pallet::<T>::events().len();
+        let (result, balances_after, events) = with_transaction(|| {
+            let (result, tracked) = assets::Pallet::<T>::track_balance_changes(|| {
+                Self::exchange(
+                    sender,
+                    sender,
                     input_asset_id,
                     output_asset_id,
                     amount,
-                );
-            }
+                    filter,
+                )
+            });
+            let balances_after: Vec<_> = tracked
+                .into_iter()
+                .map(|(asset_id, account_id)| {
+                    let balance = assets::Pallet::<T>::free_balance(&asset_id, &account_id)
+                        .unwrap_or_default();
+                    (account_id, asset_id, balance)
+                })
+                .collect();
+            let events: Vec<_> = frame_system::Pallet::<T>::events()
+                .into_iter()
+                .skip(events_count)
+                .map(|record| record.event)
+                .collect();
+            TransactionOutcome::Rollback((result, balances_after, events))
+        });
+        let balance_changes = balances_after
+            .into_iter()
+            .filter_map(|(account_id, asset_id, balance_after)| {
+                let balance_before =
+                    assets::Pallet::<T>::free_balance(&asset_id, &account_id).unwrap_or_default();
+                if balance_before == balance_after {
+                    return None;
+                }
+                Some(BalanceChange {
+                    account_id,
+                    asset_id,
+                    balance_before,
+                    balance_after,
+                })
+            })
+            .collect();
+        SwapSimulation {
+            result: result.map(|outcome| SwapOutcome::new(outcome.amount, outcome.fee)),
+            balance_changes,
+            events,
         }
+    }
 
-        // Otherwise, fall back to the general source-agnostic procedure based on sampling
-        Self::generic_split(sources, input_asset_id, output_asset_id, amount)
+    /// Same as `quote`, but also details how every individual swap of the trade is performed.
+    pub fn quote_breakdown(
+        input_asset_id: &T::AssetId,
+        output_asset_id: &T::AssetId,
+        amount: SwapAmount<Balance>,
+        filter: LiquiditySourceFilter<T::DEXId, LiquiditySourceType>,
+    ) -> Result<QuoteBreakdownOf<T>, DispatchError> {
+        let quote = Self::quote(input_asset_id, output_asset_id, amount, filter.clone())?;
+        let mut routes = Vec::with_capacity(quote.routes.len());
+        for route in quote.routes {
+            let swaps = route.path.clone().as_vec();
+            let swaps = match amount {
+                SwapAmount::WithDesiredInput { .. } => {
+                    let mut swap_amount = route.input_amount;
+                    let mut breakdowns = Vec::with_capacity(swaps.len());
+                    for (from_asset_id, to_asset_id) in swaps {
+                        let breakdown = Self::swap_breakdown(
+                            from_asset_id,
+                            to_asset_id,
+                            SwapAmount::with_desired_input(swap_amount, Balance::zero()),
+                            filter.clone(),
+                        )?;
+                        swap_amount = breakdown.output_amount;
+                        breakdowns.push(breakdown);
+                    }
+                    breakdowns
+                }
+                SwapAmount::WithDesiredOutput { .. } => {
+                    let mut swap_amount = route.output_amount;
+                    let mut breakdowns = Vec::with_capacity(swaps.len());
+                    for (from_asset_id, to_asset_id) in swaps.into_iter().rev() {
+                        let breakdown = Self::swap_breakdown(
+                            from_asset_id,
+                            to_asset_id,
+                            SwapAmount::with_desired_output(swap_amount, Balance::MAX),
+                            filter.clone(),
+                        )?;
+                        swap_amount = breakdown.input_amount;
+                        breakdowns.push(breakdown);
+                    }
+                    breakdowns.reverse();
+                    breakdowns
+                }
+            };
+            routes.push(RouteBreakdown {
+                path: route.path,
+                share: route.share,
+                swaps,
+            });
+        }
+        Ok(QuoteBreakdown {
+            amount: quote.amount,
+            fee: quote.fee,
+            routes,
+        })
     }
 
-    pub fn construct_trivial_path(
-        input_asset_id: T::AssetId,
-        output_asset_id: T::AssetId,
-    ) -> ExchangePath<T> {
-        let base_asset_id = T::GetBaseAssetId::get();
-        if input_asset_id == base_asset_id || output_asset_id == base_asset_id {
-            ExchangePath::Direct {
-                from_asset_id: input_asset_id,
-                to_asset_id: output_asset_id,
-            }
+    /// Pre-trade spot price of the output asset in terms of the input asset in the DEX, i.e.
+    /// the amount of the output asset received per unit of the input asset, as the best of
+    /// the secondary market reserves ratio and the primary market price. Swaps between two
+    /// non-base assets are priced through the base asset of the DEX. `None` if neither market
+    /// quotes the pair or the DEX doesn't exist.
+    pub fn spot_price(
+        dex_id: &T::DEXId,
+        input_asset_id: &T::AssetId,
+        output_asset_id: &T::AssetId,
+    ) -> Option<Fixed> {
+        let base_asset_id = &Self::dex_base_asset_id(dex_id).ok()?;
+        if input_asset_id == output_asset_id {
+            return None;
+        }
+        if input_asset_id == base_asset_id {
+            let (reserves_base, reserves_other) =
+                T::SecondaryMarket::reserves(dex_id, base_asset_id, output_asset_id);
+            let secondary_price = if reserves_base > 0 {
+                (FixedWrapper::from(reserves_other) / reserves_base)
+                    .get()
+                    .ok()
+            } else {
+                None
+            };
+            let primary_price = T::PrimaryMarket::sell_price(base_asset_id, output_asset_id).ok();
+            Self::best_spot_price(secondary_price, primary_price)
+        } else if output_asset_id == base_asset_id {
+            let (reserves_base, reserves_other) =
+                T::SecondaryMarket::reserves(dex_id, base_asset_id, input_asset_id);
+            let secondary_price = if reserves_other > 0 {
+                (FixedWrapper::from(reserves_base) / reserves_other)
+                    .get()
+                    .ok()
+            } else {
+                None
+            };
+            let primary_price = T::PrimaryMarket::buy_price(base_asset_id, input_asset_id)
+                .ok()
+                .filter(|price| *price > Fixed::ZERO)
+                .and_then(|price| (fixed_wrapper!(1) / price).get().ok());
+            Self::best_spot_price(secondary_price, primary_price)
         } else {
-            ExchangePath::Twofold {
-                from_asset_id: input_asset_id,
-                intermediate_asset_id: base_asset_id,
-                to_asset_id: output_asset_id,
-            }
+            let first_hop = Self::spot_price(dex_id, input_asset_id, base_asset_id)?;
+            let second_hop = Self::spot_price(dex_id, base_asset_id, output_asset_id)?;
+            (FixedWrapper::from(first_hop) * second_hop).get().ok()
         }
     }
 
-    /// Check if given two arbitrary tokens can be used to perform an exchange via any available sources.
-    pub fn is_path_available(
-        dex_id: T::DEXId,
+    fn best_spot_price(first: Option<Fixed>, second: Option<Fixed>) -> Option<Fixed> {
+        let first = first.filter(|price| *price > Fixed::ZERO);
+        let second = second.filter(|price| *price > Fixed::ZERO);
+        match (first, second) {
+            (Some(first), Some(second)) => Some(first.max(second)),
+            (price, None) | (None, price) => price,
+        }
+    }
+
+    /// Relative shortfall of `price` against `spot_price` in basis points, zero if `price` is the
+    /// spot price or better.
+    pub fn price_impact(spot_price: Fixed, price: Fixed) -> Result<BasisPoints, DispatchError> {
+        if spot_price <= price {
+            return Ok(0);
+        }
+        ((FixedWrapper::from(spot_price) - price) / spot_price * fixed_wrapper!(10000))
+            .try_into_balance()
+            .map(|price_impact| (price_impact / balance!(1)) as BasisPoints)
+            .map_err(|_| Error::<T>::CalculationError.into())
+    }
+
+    /// Details how an individual swap is performed by the liquidity sources.
+    ///
+    /// The spot price is the best one among the curves of the sources the swap is distributed
+    /// across (see `algo::spot_price`), the price derived from the pool reserves and the bonding
+    /// curve (see `spot_price`) is taken if none of the sources describes its curve.
+    fn swap_breakdown(
         input_asset_id: T::AssetId,
         output_asset_id: T::AssetId,
-    ) -> Result<bool, DispatchError> {
-        let path = Self::construct_trivial_path(input_asset_id, output_asset_id);
-        let path_exists = match path {
-            ExchangePath::Direct {
-                from_asset_id,
-                to_asset_id,
-            } => {
-                let pair = Self::weak_sort_pair(from_asset_id, to_asset_id);
-                !trading_pair::Pallet::<T>::list_enabled_sources_for_trading_pair(
-                    &dex_id,
-                    &pair.base_asset_id,
-                    &pair.target_asset_id,
-                )?
-                .is_empty()
+        amount: SwapAmount<Balance>,
+        filter: LiquiditySourceFilter<T::DEXId, LiquiditySourceType>,
+    ) -> Result<SwapBreakdownOf<T>, DispatchError> {
+        let quote = Self::quote_single(&input_asset_id, &output_asset_id, amount, filter.clone())?;
+        let (input_amount, output_amount) = match amount {
+            SwapAmount::WithDesiredInput {
+                desired_amount_in, ..
+            } => (desired_amount_in, quote.amount),
+            SwapAmount::WithDesiredOutput {
+                desired_amount_out, ..
+            } => (quote.amount, desired_amount_out),
+        };
+
+        let mut sources = Vec::with_capacity(quote.distribution.len());
+        let mut curves_spot_price: Option<Fixed> = None;
+        for (source, share) in quote.distribution {
+            if share == Fixed::ZERO {
+                continue;
             }
-            ExchangePath::Twofold {
-                from_asset_id,
-                intermediate_asset_id,
-                to_asset_id,
-            } => {
-                !trading_pair::Pallet::<T>::list_enabled_sources_for_trading_pair(
-                    &dex_id,
-                    &intermediate_asset_id,
-                    &from_asset_id,
-                )?
-                .is_empty()
-                    && !trading_pair::Pallet::<T>::list_enabled_sources_for_trading_pair(
-                        &dex_id,
-                        &intermediate_asset_id,
-                        &to_asset_id,
-                    )?
-                    .is_empty()
+            let source_amount = (FixedWrapper::from(amount.amount()) * share)
+                .try_into_balance()
+                .map_err(|_| Error::CalculationError::<T>)?;
+            let source_amount = match amount {
+                SwapAmount::WithDesiredInput { .. } => {
+                    SwapAmount::with_desired_input(source_amount, Balance::zero())
+                }
+                SwapAmount::WithDesiredOutput { .. } => {
+                    SwapAmount::with_desired_output(source_amount, Balance::MAX)
+                }
+            };
+            let outcome = T::LiquidityRegistry::quote(
+                &source,
+                &input_asset_id,
+                &output_asset_id,
+                source_amount,
+            )?;
+            let (source_input_amount, source_output_amount) = match source_amount {
+                SwapAmount::WithDesiredInput {
+                    desired_amount_in, ..
+                } => (desired_amount_in, outcome.amount),
+                SwapAmount::WithDesiredOutput {
+                    desired_amount_out, ..
+                } => (outcome.amount, desired_amount_out),
+            };
+            if let Some(curve) =
+                T::SwapCurves::swap_curve(&source, &input_asset_id, &output_asset_id)
+            {
+                let source_spot_price =
+                    algo::spot_price(&curve).map_err(|_| Error::CalculationError::<T>)?;
+                curves_spot_price = curves_spot_price.max(Some(source_spot_price));
             }
+            sources.push(SourceBreakdown {
+                source,
+                share,
+                input_amount: source_input_amount,
+                output_amount: source_output_amount,
+            });
+        }
+
+        let effective_price = (FixedWrapper::from(output_amount) / input_amount)
+            .get()
+            .map_err(|_| Error::CalculationError::<T>)?;
+        let spot_price = curves_spot_price
+            .or_else(|| Self::spot_price(&filter.dex_id, &input_asset_id, &output_asset_id))
+            .ok_or(Error::<T>::UnavailableSpotPrice)?;
+        let price_impact = Self::price_impact(spot_price, effective_price)?;
+
+        let base_asset_id = Self::dex_base_asset_id(&filter.dex_id)?;
+        let fee_in_input_asset = if input_asset_id == base_asset_id || quote.fee == 0 {
+            quote.fee
+        } else {
+            Self::quote(
+                &base_asset_id,
+                &input_asset_id,
+                SwapAmount::with_desired_input(quote.fee, Balance::zero()),
+                filter,
+            )
+            .map(|outcome| outcome.amount)?
         };
-        Ok(path_exists)
+
+        Ok(SwapBreakdown {
+            input_asset_id,
+            output_asset_id,
+            input_amount,
+            output_amount,
+            sources,
+            spot_price,
+            effective_price,
+            price_impact,
+            fee: quote.fee,
+            fee_in_input_asset,
+        })
     }
 
-    /// Given two arbitrary tokens return all sources that can be used in exchange if path exists.
-    pub fn list_enabled_sources_for_path(
-        dex_id: T::DEXId,
-        input_asset_id: T::AssetId,
-        output_asset_id: T::AssetId,
-    ) -> Result<Vec<LiquiditySourceType>, DispatchError> {
-        let path = Self::construct_trivial_path(input_asset_id, output_asset_id);
-        match path {
-            ExchangePath::Direct {
-                from_asset_id,
-                to_asset_id,
-            } => {
-                let pair = Self::weak_sort_pair(from_asset_id, to_asset_id);
-                let sources = trading_pair::Pallet::<T>::list_enabled_sources_for_trading_pair(
-                    &dex_id,
-                    &pair.base_asset_id,
-                    &pair.target_asset_id,
-                )?;
-                if sources.is_empty() {
-                    fail!(Error::<T>::UnavailableExchangePath);
+    /// Quotes every path available in the DEX for the given trade (see `candidate_paths`).
+    ///
+    /// The paths that can't be quoted are skipped, the rest are sorted from the best quote
+    /// to the worst one, shorter paths go first among equal quotes.
+    pub fn quote_routes(
+        input_asset_id: &T::AssetId,
+        output_asset_id: &T::AssetId,
+        amount: SwapAmount<Balance>,
+        filter: LiquiditySourceFilter<T::DEXId, LiquiditySourceType>,
+    ) -> Result<Vec<(ExchangePath<T::AssetId>, SwapOutcome<Balance>)>, DispatchError> {
+        ensure!(
+            input_asset_id != output_asset_id,
+            Error::<T>::UnavailableExchangePath
+        );
+        let trivial_path =
+            Self::construct_trivial_path(filter.dex_id, *input_asset_id, *output_asset_id)?;
+        let paths = Self::candidate_paths(*input_asset_id, *output_asset_id, &filter)?;
+        Self::rank_paths(paths, &trivial_path, amount, filter)
+    }
+
+    /// Quotes the given paths, the ones that can't be quoted are skipped, the rest are sorted
+    /// from the best quote to the worst one keeping the order of the paths among equal quotes.
+    ///
+    /// If none of the paths can be quoted, the reason the trivial path has failed is reported.
+    fn rank_paths(
+        paths: Vec<ExchangePath<T::AssetId>>,
+        trivial_path: &ExchangePath<T::AssetId>,
+        amount: SwapAmount<Balance>,
+        filter: LiquiditySourceFilter<T::DEXId, LiquiditySourceType>,
+    ) -> Result<Vec<(ExchangePath<T::AssetId>, SwapOutcome<Balance>)>, DispatchError> {
+        let mut trivial_path_error = None;
+        let mut routes = Vec::new();
+        for path in paths {
+            match Self::quote_path(path.clone(), amount, filter.clone()) {
+                Ok(outcome) => routes.push((path, outcome)),
+                Err(error) if &path == trivial_path => trivial_path_error = Some(error),
+                Err(_) => {}
+            }
+        }
+        if routes.is_empty() {
+            // Report the reason the trivial path has failed, since it's the only path
+            // available unless the DEX has trading pairs with non-base assets.
+            return Err(trivial_path_error.unwrap_or(Error::<T>::UnavailableExchangePath.into()));
+        }
+        match amount {
+            SwapAmount::WithDesiredInput { .. } => {
+                routes.sort_by(|(_, a), (_, b)| b.amount.cmp(&a.amount))
+            }
+            SwapAmount::WithDesiredOutput { .. } => {
+                routes.sort_by(|(_, a), (_, b)| a.amount.cmp(&b.amount))
+            }
+        }
+        Ok(routes)
+    }
+
+    /// Partitions a trade between several paths using the same sampling procedure as
+    /// `generic_split` does for the liquidity sources.
+    ///
+    /// - 'paths' - paths to split the trade across,
+    /// - 'amount' - the amount with "direction" (sell or buy) together with the maximum price impact (slippage),
+    /// - 'filter' - a filter composed of a list of liquidity sources IDs to accept or ban for this trade.
+    ///
+    /// The paths must not share a pool, since every path is sampled as if it was the only one
+    /// trading against the pools along it.
+    fn split_routes(
+        paths: Vec<ExchangePath<T::AssetId>>,
+        amount: SwapAmount<Balance>,
+        filter: LiquiditySourceFilter<T::DEXId, LiquiditySourceType>,
+    ) -> Result<AggregatedSwapOutcomeOf<T>, DispatchError> {
+        let mut used_pools = BTreeSet::new();
+        for path in paths.iter() {
+            let pools = Self::path_pools(path);
+            ensure!(
+                pools.is_disjoint(&used_pools),
+                Error::<T>::OverlappingRoutes
+            );
+            used_pools.extend(pools);
+        }
+        let fx_amount = <SwapAmount<Fixed>>::unique_saturated_from(amount);
+        let num_samples = T::GetNumSamples::get();
+        let sample_data: Vec<Vec<Fixed>> = paths
+            .iter()
+            .map(|path| Self::sample_path(path, fx_amount, num_samples, &filter))
+            .collect();
+
+        let (distr, best) = match amount {
+            SwapAmount::WithDesiredInput { .. } => algo::find_distribution(sample_data, false),
+            _ => algo::find_distribution(sample_data, true),
+        };
+
+        ensure!(
+            best > Fixed::ZERO && best < Fixed::MAX,
+            Error::<T>::AggregationError
+        );
+
+        let shares: Vec<_> = paths
+            .into_iter()
+            .zip(distr.into_iter())
+            .filter(|(_, share)| *share > Fixed::ZERO)
+            .collect();
+        let total_amount = amount.amount();
+        let mut amount_left = total_amount;
+        let mut routes = Vec::with_capacity(shares.len());
+        let mut cumulative_amount = Balance::zero();
+        let mut cumulative_fee = Balance::zero();
+        let last_route_index = shares.len().saturating_sub(1);
+        for (index, (path, share)) in shares.into_iter().enumerate() {
+            // The last part gets the remainder, so the parts sum up exactly to the trade amount.
+            let route_amount = if index == last_route_index {
+                amount_left
+            } else {
+                (FixedWrapper::from(total_amount) * FixedWrapper::from(share))
+                    .try_into_balance()
+                    .map_err(|_| Error::CalculationError::<T>)?
+            };
+            amount_left = amount_left
+                .checked_sub(route_amount)
+                .ok_or(Error::<T>::CalculationError)?;
+            let (input_amount, output_amount, outcome) = match amount {
+                SwapAmount::WithDesiredInput { .. } => {
+                    let outcome = Self::quote_path(
+                        path.clone(),
+                        SwapAmount::with_desired_input(route_amount, Balance::zero()),
+                        filter.clone(),
+                    )?;
+                    (route_amount, outcome.amount, outcome)
+                }
+                SwapAmount::WithDesiredOutput { .. } => {
+                    let outcome = Self::quote_path(
+                        path.clone(),
+                        SwapAmount::with_desired_output(route_amount, Balance::MAX),
+                        filter.clone(),
+                    )?;
+                    (outcome.amount, route_amount, outcome)
+                }
+            };
+            cumulative_amount = cumulative_amount
+                .checked_add(outcome.amount)
+                .ok_or(Error::<T>::CalculationError)?;
+            cumulative_fee = cumulative_fee
+                .checked_add(outcome.fee)
+                .ok_or(Error::<T>::CalculationError)?;
+            routes.push(RouteOutcome {
+                path,
+                share,
+                input_amount,
+                output_amount,
+                fee: outcome.fee,
+            });
+        }
+        Ok(AggregatedSwapOutcome::with_routes(
+            routes,
+            cumulative_amount,
+            cumulative_fee,
+        ))
+    }
+
+    /// Pools the poly-swap along the path trades against, every pool is identified by the pair
+    /// of assets it exchanges regardless of the direction.
+    fn path_pools(path: &ExchangePath<T::AssetId>) -> BTreeSet<(T::AssetId, T::AssetId)> {
+        path.clone()
+            .as_vec()
+            .into_iter()
+            .map(|(asset_a, asset_b)| {
+                if asset_a < asset_b {
+                    (asset_a, asset_b)
                 } else {
-                    Ok(sources.into_iter().collect())
+                    (asset_b, asset_a)
+                }
+            })
+            .collect()
+    }
+
+    /// Sample a path with a range of swap amounts to get respective prices for the poly-swap.
+    fn sample_path(
+        path: &ExchangePath<T::AssetId>,
+        amount: SwapAmount<Fixed>,
+        num_samples: usize,
+        filter: &LiquiditySourceFilter<T::DEXId, LiquiditySourceType>,
+    ) -> Vec<Fixed> {
+        let (amount, is_desired_input) = match amount {
+            SwapAmount::WithDesiredInput {
+                desired_amount_in, ..
+            } => (desired_amount_in, true),
+            SwapAmount::WithDesiredOutput {
+                desired_amount_out, ..
+            } => (desired_amount_out, false),
+        };
+        linspace(Fixed::ZERO, amount, num_samples, IntervalEndpoints::Right)
+            .into_iter()
+            .map(|x| {
+                let sample_amount = match x.into_bits().try_into() {
+                    Ok(x) if is_desired_input => {
+                        Ok(SwapAmount::with_desired_input(x, Balance::zero()))
+                    }
+                    Ok(x) => Ok(SwapAmount::with_desired_output(x, Balance::MAX)),
+                    Err(_) => Err(Error::<T>::CalculationError.into()),
+                };
+                let outcome = sample_amount
+                    .and_then(|sample_amount| {
+                        Self::quote_path(path.clone(), sample_amount, filter.clone())
+                    })
+                    .and_then(|outcome| {
+                        FixedInner::try_from(outcome.amount)
+                            .map(Fixed::from_bits)
+                            .map_err(|_| Error::<T>::CalculationError.into())
+                    });
+                match outcome {
+                    Ok(amount) => amount,
+                    Err(_) if is_desired_input => Fixed::ZERO,
+                    Err(_) => Fixed::MAX,
                 }
+            })
+            .collect()
+    }
+
+    /// Quotes a poly-swap along the given path.
+    fn quote_path(
+        path: ExchangePath<T::AssetId>,
+        amount: SwapAmount<Balance>,
+        filter: LiquiditySourceFilter<T::DEXId, LiquiditySourceType>,
+    ) -> Result<SwapOutcome<Balance>, DispatchError> {
+        let swaps = path.as_vec();
+        match swaps.as_slice() {
+            [] => fail!(Error::<T>::UnavailableExchangePath),
+            [(from_asset_id, to_asset_id)] => {
+                return Self::quote_single(from_asset_id, to_asset_id, amount, filter)
+                    .map(|aso| SwapOutcome::new(aso.amount, aso.fee));
             }
-            ExchangePath::Twofold {
-                from_asset_id,
-                intermediate_asset_id,
-                to_asset_id,
+            _ => {}
+        }
+        let mut cumulative_fee = Balance::zero();
+        let amount = match amount {
+            SwapAmount::WithDesiredInput {
+                desired_amount_in, ..
             } => {
-                let first_swap = trading_pair::Pallet::<T>::list_enabled_sources_for_trading_pair(
-                    &dex_id,
-                    &intermediate_asset_id,
-                    &from_asset_id,
-                )?;
-                let second_swap = trading_pair::Pallet::<T>::list_enabled_sources_for_trading_pair(
-                    &dex_id,
-                    &intermediate_asset_id,
-                    &to_asset_id,
-                )?;
-                if !first_swap.is_empty() && !second_swap.is_empty() {
-                    Ok(first_swap.union(&second_swap).cloned().collect())
+                let mut swap_amount = desired_amount_in;
+                for (from_asset_id, to_asset_id) in swaps.iter() {
+                    let quote = Self::quote_single(
+                        from_asset_id,
+                        to_asset_id,
+                        SwapAmount::with_desired_input(swap_amount, Balance::zero()),
+                        filter.clone(),
+                    )?;
+                    swap_amount = quote.amount;
+                    cumulative_fee = cumulative_fee
+                        .checked_add(quote.fee)
+                        .ok_or(Error::<T>::CalculationError)?;
+                }
+                swap_amount
+            }
+            SwapAmount::WithDesiredOutput {
+                desired_amount_out, ..
+            } => {
+                let mut swap_amount = desired_amount_out;
+                for (from_asset_id, to_asset_id) in swaps.iter().rev() {
+                    let quote = Self::quote_single(
+                        from_asset_id,
+                        to_asset_id,
+                        SwapAmount::with_desired_output(swap_amount, Balance::MAX),
+                        filter.clone(),
+                    )?;
+                    swap_amount = quote.amount;
+                    cumulative_fee = cumulative_fee
+                        .checked_add(quote.fee)
+                        .ok_or(Error::<T>::CalculationError)?;
+                }
+                swap_amount
+            }
+        };
+        Ok(SwapOutcome::new(amount, cumulative_fee))
+    }
+    /// Computes the optimal distribution across available liquidity sources to exectute the requested trade
+    /// given the input and output assets, the trade amount and a liquidity sources filter.
+    ///
+    /// - 'input_asset_id' - ID of the asset to sell,
+    /// - 'output_asset_id' - ID of the asset to buy,
+    /// - 'amount' - the amount with "direction" (sell or buy) together with the maximum price impact (slippage),
+    /// - 'filter' - a filter composed of a list of liquidity sources IDs to accept or ban for this trade.
+    ///
+    fn quote_single(
+        input_asset_id: &T::AssetId,
+        output_asset_id: &T::AssetId,
+        amount: SwapAmount<Balance>,
+        filter: LiquiditySourceFilter<T::DEXId, LiquiditySourceType>,
+    ) -> Result<AggregatedSwapOutcomeOf<T>, DispatchError> {
+        let sources =
+            T::LiquidityRegistry::list_liquidity_sources(input_asset_id, output_asset_id, filter)?;
+
+        ensure!(!sources.is_empty(), Error::<T>::UnavailableExchangePath);
+
+        // Check if we have exactly one source => no split required
+        if sources.len() == 1 {
+            let src = sources.first().unwrap();
+            let outcome =
+                T::LiquidityRegistry::quote(src, input_asset_id, output_asset_id, amount)?;
+            return Ok(AggregatedSwapOutcome::new(
+                vec![(src.clone(), fixed!(1.0))],
+                outcome.amount,
+                outcome.fee,
+            ));
+        }
+
+        // Check if we have exactly two sources: the primary market and the secondary market
+        // Do fast swap amount split
+        if sources.len() == 2 {
+            let mut primary_market: Option<LiquiditySourceIdOf<T>> = None;
+            let mut secondary_market: Option<LiquiditySourceIdOf<T>> = None;
+
+            for src in &sources {
+                if src.liquidity_source_index
+                    == LiquiditySourceType::MulticollateralBondingCurvePool
+                {
+                    primary_market = Some(src.clone());
                 } else {
-                    fail!(Error::<T>::UnavailableExchangePath);
+                    secondary_market = Some(src.clone());
                 }
             }
+            if let (Some(mcbc), Some(xyk)) = (primary_market, secondary_market) {
+                return Self::fast_split_primary_vs_secondary(
+                    mcbc,
+                    xyk,
+                    input_asset_id,
+                    output_asset_id,
+                    amount,
+                );
+            }
         }
+
+        // Otherwise, fall back to the general source-agnostic procedure based on sampling
+        Self::generic_split(sources, input_asset_id, output_asset_id, amount)
+    }
+
+    /// Base asset of the DEX, fails if the DEX is not registered.
+    pub fn dex_base_asset_id(dex_id: &T::DEXId) -> Result<T::AssetId, DispatchError> {
+        Ok(dex_manager::Pallet::<T>::get_dex_info(dex_id)?.base_asset_id)
     }
 
-    // Not full sort, just ensure that if there is XOR then it's first.
-    fn weak_sort_pair(
+    pub fn construct_trivial_path(
+        dex_id: T::DEXId,
         input_asset_id: T::AssetId,
         output_asset_id: T::AssetId,
-    ) -> TradingPair<T::AssetId> {
-        if input_asset_id == T::GetBaseAssetId::get() {
-            TradingPair {
-                base_asset_id: input_asset_id,
-                target_asset_id: output_asset_id,
-            }
+    ) -> Result<ExchangePath<T::AssetId>, DispatchError> {
+        let base_asset_id = Self::dex_base_asset_id(&dex_id)?;
+        if input_asset_id == base_asset_id || output_asset_id == base_asset_id {
+            Ok(ExchangePath(vec![input_asset_id, output_asset_id]))
         } else {
-            TradingPair {
-                base_asset_id: output_asset_id,
-                target_asset_id: input_asset_id,
+            Ok(ExchangePath(vec![
+                input_asset_id,
+                base_asset_id,
+                output_asset_id,
+            ]))
+        }
+    }
+
+    /// Paths considered for a trade: the ones found by `find_paths` and the trivial path
+    /// (via Base Asset), shorter paths go first.
+    ///
+    /// The trivial path is always considered, the search only covers the trading pairs with
+    /// the sources matching the filter. At most `GetMaxCandidatePaths` paths are considered,
+    /// since every one of them gets quoted.
+    fn candidate_paths(
+        input_asset_id: T::AssetId,
+        output_asset_id: T::AssetId,
+        filter: &LiquiditySourceFilter<T::DEXId, LiquiditySourceType>,
+    ) -> Result<Vec<ExchangePath<T::AssetId>>, DispatchError> {
+        let trivial_path =
+            Self::construct_trivial_path(filter.dex_id, input_asset_id, output_asset_id)?;
+        let max_paths = T::GetMaxCandidatePaths::get().max(1);
+        let mut paths =
+            Self::find_paths(input_asset_id, output_asset_id, filter).unwrap_or_default();
+        if !paths.contains(&trivial_path) {
+            paths.truncate(max_paths - 1);
+            paths.push(trivial_path);
+        }
+        paths.sort_by_key(|path| path.0.len());
+        paths.truncate(max_paths);
+        Ok(paths)
+    }
+
+    /// Searches the trading pairs of the DEX for paths of up to `GetMaxRouteHops` individual swaps
+    /// between the given assets, shorter paths go first.
+    ///
+    /// Only the trading pairs with at least one of the enabled sources matching the filter are used,
+    /// see `search_paths`. The pairs are looked up by their assets starting from the input asset,
+    /// at most `GetMaxRoutingPairs` pairs are considered.
+    pub fn find_paths(
+        input_asset_id: T::AssetId,
+        output_asset_id: T::AssetId,
+        filter: &LiquiditySourceFilter<T::DEXId, LiquiditySourceType>,
+    ) -> Result<Vec<ExchangePath<T::AssetId>>, DispatchError> {
+        dex_manager::Pallet::<T>::ensure_dex_exists(&filter.dex_id)?;
+        let max_hops = T::GetMaxRouteHops::get();
+        let mut remaining_pairs = T::GetMaxRoutingPairs::get();
+        let mut neighbours: BTreeMap<T::AssetId, Vec<T::AssetId>> = BTreeMap::new();
+        let mut assets_to_expand = vec![input_asset_id];
+        // Paths end at the output asset and never get longer than `max_hops`, so only the assets
+        // reachable in fewer swaps than that need their trading pairs looked up.
+        for _ in 0..max_hops {
+            let mut reached_assets = Vec::new();
+            for asset_id in assets_to_expand {
+                if asset_id == output_asset_id || neighbours.contains_key(&asset_id) {
+                    continue;
+                }
+                let pairs = trading_pair::Pallet::<T>::list_trading_pairs_of_asset(
+                    &filter.dex_id,
+                    &asset_id,
+                    remaining_pairs,
+                );
+                remaining_pairs -= pairs.len();
+                let asset_neighbours = neighbours.entry(asset_id).or_default();
+                for pair in pairs {
+                    let is_pair_available =
+                        trading_pair::Pallet::<T>::enabled_sources(&filter.dex_id, &pair)
+                            .map(|sources| {
+                                sources
+                                    .into_iter()
+                                    .any(|source| filter.matches_index(source))
+                            })
+                            .unwrap_or(false);
+                    if is_pair_available {
+                        let neighbour_id = if pair.base_asset_id == asset_id {
+                            pair.target_asset_id
+                        } else {
+                            pair.base_asset_id
+                        };
+                        asset_neighbours.push(neighbour_id);
+                        reached_assets.push(neighbour_id);
+                    }
+                }
+            }
+            assets_to_expand = reached_assets;
+        }
+
+        Ok(Self::search_paths(
+            &neighbours,
+            input_asset_id,
+            output_asset_id,
+            max_hops,
+        ))
+    }
+
+    /// Breadth-first search of paths of up to `max_hops` individual swaps between the given assets
+    /// in the graph of assets exchanged directly, shorter paths go first.
+    ///
+    /// Every asset appears in a path at most once.
+    pub fn search_paths(
+        neighbours: &BTreeMap<T::AssetId, Vec<T::AssetId>>,
+        input_asset_id: T::AssetId,
+        output_asset_id: T::AssetId,
+        max_hops: usize,
+    ) -> Vec<ExchangePath<T::AssetId>> {
+        let mut paths = Vec::new();
+        let mut unfinished_paths = vec![vec![input_asset_id]];
+        for _ in 0..max_hops {
+            let mut extended_paths = Vec::new();
+            for path in unfinished_paths {
+                let last_asset_id = match path.last() {
+                    Some(asset_id) => asset_id,
+                    None => continue,
+                };
+                for asset_id in neighbours.get(last_asset_id).into_iter().flatten() {
+                    if path.contains(asset_id) {
+                        continue;
+                    }
+                    let mut extended_path = path.clone();
+                    extended_path.push(*asset_id);
+                    if *asset_id == output_asset_id {
+                        paths.push(ExchangePath(extended_path));
+                    } else {
+                        extended_paths.push(extended_path);
+                    }
+                }
             }
+            unfinished_paths = extended_paths;
         }
+        paths
+    }
+
+    /// Weight of routing a trade, charged on top of the swap weight which only accounts for
+    /// a single path: looking up the trading pairs to find the paths (see `find_paths`),
+    /// quoting the paths considered for the trade (see `candidate_paths`),
+    /// sampling the paths the trade is split across (see `split_routes`), performing
+    /// the poly-swaps along the additional paths and the exchanges with every source
+    /// on each hop of the paths, including recording them in the trading pair statistics
+    /// (see `LiquiditySource::exchange_weight`).
+    pub fn routing_weight(amount: SwapVariant) -> Weight {
+        let max_split_routes = T::GetMaxSplitRoutes::get().max(1) as Weight;
+        let max_hops = T::GetMaxRouteHops::get() as Weight;
+        let path_quotes = (T::GetMaxCandidatePaths::get() as Weight)
+            .saturating_add(max_split_routes.saturating_mul(T::GetNumSamples::get() as Weight + 1));
+        // The DEX info, then the index entry and the enabled sources of every pair considered.
+        let path_search = T::DbWeight::get()
+            .reads((T::GetMaxRoutingPairs::get() as Weight).saturating_mul(2) + 1);
+        <T as Config>::WeightInfo::quote_path(max_hops as u32)
+            .saturating_mul(path_quotes)
+            .saturating_add(path_search)
+            .saturating_add(
+                <T as Config>::WeightInfo::swap(amount).saturating_mul(max_split_routes - 1),
+            )
+            .saturating_add(
+                T::LiquidityRegistry::exchange_weight()
+                    .saturating_mul(max_hops.saturating_mul(max_split_routes)),
+            )
+    }
+
+    /// Check if given two arbitrary tokens can be used to perform an exchange via any available sources.
+    pub fn is_path_available(
+        dex_id: T::DEXId,
+        input_asset_id: T::AssetId,
+        output_asset_id: T::AssetId,
+    ) -> Result<bool, DispatchError> {
+        let paths = Self::find_paths(
+            input_asset_id,
+            output_asset_id,
+            &LiquiditySourceFilter::empty(dex_id),
+        )?;
+        Ok(!paths.is_empty())
     }
 
+    /// Given two arbitrary tokens return all sources that can be used in exchange if path exists.
+    pub fn list_enabled_sources_for_path(
+        dex_id: T::DEXId,
+        input_asset_id: T::AssetId,
+        output_asset_id: T::AssetId,
+    ) -> Result<Vec<LiquiditySourceType>, DispatchError> {
+        let paths = Self::find_paths(
+            input_asset_id,
+            output_asset_id,
+            &LiquiditySourceFilter::empty(dex_id),
+        )?;
+        if paths.is_empty() {
+            fail!(Error::<T>::UnavailableExchangePath);
+        }
+        let mut sources = BTreeSet::new();
+        for (from_asset_id, to_asset_id) in paths.into_iter().flat_map(|path| path.as_vec()) {
+            sources.extend(Self::list_enabled_sources_for_pair(
+                &dex_id,
+                from_asset_id,
+                to_asset_id,
+            ));
+        }
+        Ok(sources.into_iter().collect())
+    }
+
+    /// Sources enabled for the trading pair of the given assets regardless of which of them is the base one.
+    fn list_enabled_sources_for_pair(
+        dex_id: &T::DEXId,
+        asset_id_a: T::AssetId,
+        asset_id_b: T::AssetId,
+    ) -> BTreeSet<LiquiditySourceType> {
+        let pair = TradingPair {
+            base_asset_id: asset_id_a,
+            target_asset_id: asset_id_b,
+        };
+        let reversed_pair = TradingPair {
+            base_asset_id: asset_id_b,
+            target_asset_id: asset_id_a,
+        };
+        trading_pair::Pallet::<T>::enabled_sources(dex_id, &pair)
+            .or_else(|| trading_pair::Pallet::<T>::enabled_sources(dex_id, &reversed_pair))
+            .unwrap_or_default()
+    }
     /// A wrapper function around the "fast" split algorithm implementations.
     /// Dispatches the call to the correct variant of the algorithm depending on
     /// whether the base asset is being bought or sold.
@@ -685,8 +1659,8 @@ impl<T: Config> Pallet<T> {
         input_asset_id: &T::AssetId,
         output_asset_id: &T::AssetId,
         amount: SwapAmount<Balance>,
-    ) -> Result<AggregatedSwapOutcome<LiquiditySourceIdOf<T>, Balance>, DispatchError> {
-        let base_asset = &T::GetBaseAssetId::get();
+    ) -> Result<AggregatedSwapOutcomeOf<T>, DispatchError> {
+        let base_asset = &Self::dex_base_asset_id(&primary_source_id.dex_id)?;
 
         ensure!(
             input_asset_id == base_asset || output_asset_id == base_asset,
@@ -699,7 +1673,8 @@ impl<T: Config> Pallet<T> {
         };
 
         // Ensure secondary market exists for the pair of assets
-        let (reserves_base, reserves_other) = T::SecondaryMarket::reserves(base_asset, other_asset);
+        let (reserves_base, reserves_other) =
+            T::SecondaryMarket::reserves(&primary_source_id.dex_id, base_asset, other_asset);
         if reserves_base == 0 || reserves_other == 0 {
             // No reserves in secondaty market, resort to the primary market
             let outcome = T::LiquidityRegistry::quote(
@@ -745,6 +1720,9 @@ impl<T: Config> Pallet<T> {
     /// Implements a generic source-agnostic split algorithm to partition a trade between
     /// an arbitrary number of liquidity sources of arbitrary types.
     ///
+    /// Sources able to describe their prices with a `SwapCurve` are split analytically
+    /// (see `curve_split`), the general procedure based on sampling is used otherwise.
+    ///
     /// - 'sources' - a vector of liquidity sources IDs,
     /// - 'input_asset_id' - ID of the asset to sell,
     /// - 'output_asset_id' - ID of the asset to buy,
@@ -755,7 +1733,30 @@ impl<T: Config> Pallet<T> {
         input_asset_id: &T::AssetId,
         output_asset_id: &T::AssetId,
         amount: SwapAmount<Balance>,
-    ) -> Result<AggregatedSwapOutcome<LiquiditySourceIdOf<T>, Balance>, DispatchError> {
+    ) -> Result<AggregatedSwapOutcomeOf<T>, DispatchError> {
+        let has_curves = sources
+            .iter()
+            .any(|src| T::SwapCurves::swap_curve(src, input_asset_id, output_asset_id).is_some());
+        if has_curves {
+            if let Ok(outcome) =
+                Self::curve_split(sources.clone(), input_asset_id, output_asset_id, amount)
+            {
+                return Ok(outcome);
+            }
+        }
+        Self::sampling_split(sources, input_asset_id, output_asset_id, amount)
+    }
+
+    /// Splits a trade between liquidity sources by sampling each of them with a range of amounts
+    /// (see `sample_liquidity_source`) and picking the best combination of the samples.
+    ///
+    /// Public for benchmarks comparing it with `curve_split`.
+    pub fn sampling_split(
+        sources: Vec<LiquiditySourceIdOf<T>>,
+        input_asset_id: &T::AssetId,
+        output_asset_id: &T::AssetId,
+        amount: SwapAmount<Balance>,
+    ) -> Result<AggregatedSwapOutcomeOf<T>, DispatchError> {
         let amount = <SwapAmount<Fixed>>::unique_saturated_from(amount);
         let num_samples = T::GetNumSamples::get();
         let (sample_data, sample_fees): (Vec<Vec<Fixed>>, Vec<Vec<Fixed>>) = sources
@@ -810,6 +1811,142 @@ impl<T: Config> Pallet<T> {
         ))
     }
 
+    /// Splits a trade between liquidity sources so that marginal prices of all sources taking part
+    /// in it are equal. Sources described by a `SwapCurve` (see `Config::SwapCurves`) are split
+    /// analytically with `algo::split_by_curves`. If there are sources without a curve, the curved
+    /// sources are sampled as a whole along with the others and the trade is split between them
+    /// with `algo::find_distribution`.
+    ///
+    /// Every source is quoted with its share afterwards, so the outcome is exact.
+    ///
+    /// Public for benchmarks comparing it with `sampling_split`.
+    pub fn curve_split(
+        sources: Vec<LiquiditySourceIdOf<T>>,
+        input_asset_id: &T::AssetId,
+        output_asset_id: &T::AssetId,
+        amount: SwapAmount<Balance>,
+    ) -> Result<AggregatedSwapOutcomeOf<T>, DispatchError> {
+        let (desired_amount, inversed) = match amount {
+            SwapAmount::WithDesiredInput {
+                desired_amount_in, ..
+            } => (desired_amount_in, false),
+            SwapAmount::WithDesiredOutput {
+                desired_amount_out, ..
+            } => (desired_amount_out, true),
+        };
+        let fx_amount = FixedInner::try_from(desired_amount)
+            .map(Fixed::from_bits)
+            .map_err(|_| Error::<T>::CalculationError)?;
+
+        let (curved, opaque): (Vec<_>, Vec<_>) = sources
+            .iter()
+            .enumerate()
+            .map(|(i, src)| {
+                (
+                    i,
+                    T::SwapCurves::swap_curve(src, input_asset_id, output_asset_id),
+                )
+            })
+            .partition(|(_, curve)| curve.is_some());
+        let (curved_indices, curves): (Vec<usize>, Vec<SwapCurve>) = curved
+            .into_iter()
+            .filter_map(|(i, curve)| curve.map(|curve| (i, curve)))
+            .unzip();
+        ensure!(!curves.is_empty(), Error::<T>::AggregationError);
+
+        // Share of the trade the curved sources get as a whole.
+        let (curved_share, opaque_shares) = if opaque.is_empty() {
+            (fixed!(1), Vec::new())
+        } else {
+            let num_samples = T::GetNumSamples::get();
+            let failed_sample = match inversed {
+                true => Fixed::MAX,
+                _ => Fixed::ZERO,
+            };
+            let curved_samples: Vec<Fixed> = linspace(
+                Fixed::ZERO,
+                fx_amount,
+                num_samples,
+                IntervalEndpoints::Right,
+            )
+            .into_iter()
+            .map(|x| {
+                algo::split_by_curves(&curves, x, inversed)
+                    .map(|(_, counterpart)| counterpart)
+                    .unwrap_or(failed_sample)
+            })
+            .collect();
+            let fx_swap_amount = <SwapAmount<Fixed>>::unique_saturated_from(amount);
+            let sample_data: Vec<Vec<Fixed>> = sp_std::iter::once(curved_samples)
+                .chain(opaque.iter().map(|(i, _)| {
+                    Self::sample_liquidity_source(
+                        &sources[*i],
+                        input_asset_id,
+                        output_asset_id,
+                        fx_swap_amount,
+                        num_samples,
+                    )
+                    .into_iter()
+                    .map(|x| x.amount)
+                    .collect()
+                }))
+                .collect();
+            let (distr, best) = algo::find_distribution(sample_data, inversed);
+            ensure!(
+                best > Fixed::ZERO && best < Fixed::MAX,
+                Error::<T>::AggregationError
+            );
+            (distr[0], distr[1..].to_vec())
+        };
+
+        let mut distribution = vec![fixed!(0); sources.len()];
+        for ((i, _), share) in opaque.iter().zip(opaque_shares.into_iter()) {
+            distribution[*i] = share;
+        }
+        if curved_share > Fixed::ZERO {
+            let curved_amount = (FixedWrapper::from(fx_amount) * curved_share)
+                .get()
+                .map_err(|_| Error::<T>::CalculationError)?;
+            let (shares, _) = algo::split_by_curves(&curves, curved_amount, inversed)
+                .map_err(|_| Error::<T>::CalculationError)?;
+            for (i, share) in curved_indices.into_iter().zip(shares.into_iter()) {
+                distribution[i] = (FixedWrapper::from(share) * curved_share)
+                    .get()
+                    .map_err(|_| Error::<T>::CalculationError)?;
+            }
+        }
+
+        let mut best = fixed_wrapper!(0);
+        let mut total_fee = fixed_wrapper!(0);
+        for (src, share) in sources.iter().zip(distribution.iter_mut()) {
+            let source_amount = (FixedWrapper::from(desired_amount) * *share)
+                .try_into_balance()
+                .map_err(|_| Error::<T>::CalculationError)?;
+            // Leftovers too small to be traded are not worth a swap.
+            if source_amount == 0 {
+                *share = Fixed::ZERO;
+                continue;
+            }
+            let source_amount = match inversed {
+                true => SwapAmount::with_desired_output(source_amount, Balance::MAX),
+                _ => SwapAmount::with_desired_input(source_amount, 0),
+            };
+            let outcome =
+                T::LiquidityRegistry::quote(src, input_asset_id, output_asset_id, source_amount)?;
+            best = best + outcome.amount;
+            total_fee = total_fee + outcome.fee;
+        }
+
+        Ok(AggregatedSwapOutcome::new(
+            sources.into_iter().zip(distribution.into_iter()).collect(),
+            best.try_into_balance()
+                .map_err(|_| Error::<T>::CalculationError)?,
+            total_fee
+                .try_into_balance()
+                .map_err(|_| Error::<T>::CalculationError)?,
+        ))
+    }
+
     /// Implements a "fast" split algorithm to partition a trade between a single primary liquidity source
     /// (e.g. a multi-collateral bonding curve pool) and a single secondary source (e.g. an XYK pool)
     /// for the case when the base asset is being bought.
@@ -827,7 +1964,7 @@ impl<T: Config> Pallet<T> {
         collateral_asset_id: &T::AssetId,
         amount: SwapAmount<Balance>,
         secondary_market_reserves: (Balance, Balance),
-    ) -> Result<AggregatedSwapOutcome<LiquiditySourceIdOf<T>, Balance>, DispatchError> {
+    ) -> Result<AggregatedSwapOutcomeOf<T>, DispatchError> {
         let (reserves_base, reserves_other) = secondary_market_reserves;
         let x: FixedWrapper = reserves_base.into();
         let y: FixedWrapper = reserves_other.into();
@@ -949,7 +2086,7 @@ impl<T: Config> Pallet<T> {
         collateral_asset_id: &T::AssetId,
         amount: SwapAmount<Balance>,
         secondary_market_reserves: (Balance, Balance),
-    ) -> Result<AggregatedSwapOutcome<LiquiditySourceIdOf<T>, Balance>, DispatchError> {
+    ) -> Result<AggregatedSwapOutcomeOf<T>, DispatchError> {
         let (reserves_base, reserves_other) = secondary_market_reserves;
         let x: FixedWrapper = reserves_base.into();
         let y: FixedWrapper = reserves_other.into();
@@ -1078,75 +2215,22 @@ impl<T: Config> Pallet<T> {
 }
 
 impl<T: Config> LiquidityProxyTrait<T::DEXId, T::AccountId, T::AssetId> for Pallet<T> {
-    /// Applies trivial routing (via Base Asset), resulting in a poly-swap which may contain several individual swaps.
+    /// Splits the trade across the paths available in the DEX, every part of the trade
+    /// results in a poly-swap which may contain several individual swaps.
     /// Those individual swaps are subject to liquidity aggregation algorithm.
-    ///
-    /// This a wrapper for `quote_single`.
     fn quote(
         input_asset_id: &T::AssetId,
         output_asset_id: &T::AssetId,
         amount: SwapAmount<Balance>,
         filter: LiquiditySourceFilter<T::DEXId, LiquiditySourceType>,
     ) -> Result<SwapOutcome<Balance>, DispatchError> {
-        ensure!(
-            input_asset_id != output_asset_id,
-            Error::<T>::UnavailableExchangePath
-        );
-        match Self::construct_trivial_path(*input_asset_id, *output_asset_id) {
-            ExchangePath::Direct {
-                from_asset_id,
-                to_asset_id,
-            } => Self::quote_single(&from_asset_id, &to_asset_id, amount, filter)
-                .map(|aso| SwapOutcome::new(aso.amount, aso.fee).into()),
-            ExchangePath::Twofold {
-                from_asset_id,
-                intermediate_asset_id,
-                to_asset_id,
-            } => match amount {
-                SwapAmount::WithDesiredInput {
-                    desired_amount_in, ..
-                } => {
-                    let first_quote = Self::quote_single(
-                        &from_asset_id,
-                        &intermediate_asset_id,
-                        SwapAmount::with_desired_input(desired_amount_in, Balance::zero()),
-                        filter.clone(),
-                    )?;
-                    let second_quote = Self::quote_single(
-                        &intermediate_asset_id,
-                        &to_asset_id,
-                        SwapAmount::with_desired_input(first_quote.amount, Balance::zero()),
-                        filter,
-                    )?;
-                    let cumulative_fee = first_quote.fee + second_quote.fee;
-                    Ok(SwapOutcome::new(second_quote.amount, cumulative_fee))
-                }
-                SwapAmount::WithDesiredOutput {
-                    desired_amount_out, ..
-                } => {
-                    let second_quote = Self::quote_single(
-                        &intermediate_asset_id,
-                        &to_asset_id,
-                        SwapAmount::with_desired_output(desired_amount_out, Balance::MAX),
-                        filter.clone(),
-                    )?;
-                    let first_quote = Self::quote_single(
-                        &from_asset_id,
-                        &intermediate_asset_id,
-                        SwapAmount::with_desired_output(second_quote.amount, Balance::MAX),
-                        filter,
-                    )?;
-                    let cumulative_fee = first_quote.fee + second_quote.fee;
-                    Ok(SwapOutcome::new(first_quote.amount, cumulative_fee))
-                }
-            },
-        }
+        Pallet::<T>::quote(input_asset_id, output_asset_id, amount, filter)
+            .map(|outcome| SwapOutcome::new(outcome.amount, outcome.fee))
     }
 
-    /// Applies trivial routing (via Base Asset), resulting in a poly-swap which may contain several individual swaps.
+    /// Splits the trade across the paths available in the DEX, every part of the trade
+    /// results in a poly-swap which may contain several individual swaps.
     /// Those individual swaps are subject to liquidity aggregation algorithm.
-    ///
-    /// This a wrapper for `exchange_single`.
     fn exchange(
         sender: &T::AccountId,
         receiver: &T::AccountId,
@@ -1155,99 +2239,19 @@ impl<T: Config> LiquidityProxyTrait<T::DEXId, T::AccountId, T::AssetId> for Pall
         amount: SwapAmount<Balance>,
         filter: LiquiditySourceFilter<T::DEXId, LiquiditySourceType>,
     ) -> Result<SwapOutcome<Balance>, DispatchError> {
-        ensure!(
-            input_asset_id != output_asset_id,
-            Error::<T>::UnavailableExchangePath
-        );
-        common::with_transaction(|| {
-            match Self::construct_trivial_path(*input_asset_id, *output_asset_id) {
-                ExchangePath::Direct {
-                    from_asset_id,
-                    to_asset_id,
-                } => Self::exchange_single(
-                    sender,
-                    receiver,
-                    &from_asset_id,
-                    &to_asset_id,
-                    amount,
-                    filter,
-                ),
-                ExchangePath::Twofold {
-                    from_asset_id,
-                    intermediate_asset_id,
-                    to_asset_id,
-                } => match amount {
-                    SwapAmount::WithDesiredInput {
-                        desired_amount_in,
-                        min_amount_out,
-                    } => {
-                        let transit_account = T::GetTechnicalAccountId::get();
-                        let first_swap = Self::exchange_single(
-                            sender,
-                            &transit_account,
-                            &from_asset_id,
-                            &intermediate_asset_id,
-                            SwapAmount::with_desired_input(desired_amount_in, Balance::zero()),
-                            filter.clone(),
-                        )?;
-                        let second_swap = Self::exchange_single(
-                            &transit_account,
-                            receiver,
-                            &intermediate_asset_id,
-                            &to_asset_id,
-                            SwapAmount::with_desired_input(first_swap.amount, Balance::zero()),
-                            filter,
-                        )?;
-                        ensure!(
-                            second_swap.amount >= min_amount_out,
-                            Error::<T>::SlippageNotTolerated
-                        );
-                        let cumulative_fee = first_swap.fee + second_swap.fee;
-                        Ok(SwapOutcome::new(second_swap.amount, cumulative_fee))
-                    }
-                    SwapAmount::WithDesiredOutput {
-                        desired_amount_out,
-                        max_amount_in,
-                    } => {
-                        let second_quote = Self::quote_single(
-                            &intermediate_asset_id,
-                            &to_asset_id,
-                            SwapAmount::with_desired_output(desired_amount_out, Balance::MAX),
-                            filter.clone(),
-                        )?;
-                        let first_quote = Self::quote_single(
-                            &from_asset_id,
-                            &intermediate_asset_id,
-                            SwapAmount::with_desired_output(second_quote.amount, Balance::MAX),
-                            filter.clone(),
-                        )?;
-                        ensure!(
-                            first_quote.amount <= max_amount_in,
-                            Error::<T>::SlippageNotTolerated
-                        );
-                        let transit_account = T::GetTechnicalAccountId::get();
-                        let first_swap = Self::exchange_single(
-                            sender,
-                            &transit_account,
-                            &from_asset_id,
-                            &intermediate_asset_id,
-                            SwapAmount::with_desired_input(first_quote.amount, Balance::zero()),
-                            filter.clone(),
-                        )?;
-                        let second_swap = Self::exchange_single(
-                            &transit_account,
-                            receiver,
-                            &intermediate_asset_id,
-                            &to_asset_id,
-                            SwapAmount::with_desired_input(first_swap.amount, Balance::zero()),
-                            filter,
-                        )?;
-                        let cumulative_fee = first_swap.fee + second_swap.fee;
-                        Ok(SwapOutcome::new(first_quote.amount, cumulative_fee))
-                    }
-                },
-            }
-        })
+        Pallet::<T>::exchange(
+            sender,
+            receiver,
+            input_asset_id,
+            output_asset_id,
+            amount,
+            filter,
+        )
+        .map(|outcome| SwapOutcome::new(outcome.amount, outcome.fee))
+    }
+
+    fn exchange_weight(amount: SwapVariant) -> Weight {
+        <T as Config>::WeightInfo::swap(amount).saturating_add(Pallet::<T>::routing_weight(amount))
     }
 }
 
@@ -1275,9 +2279,28 @@ pub mod pallet {
             DispatchError,
         >;
         type GetNumSamples: Get<usize>;
+        /// Maximum number of individual swaps in a path found by `find_paths`.
+        type GetMaxRouteHops: Get<usize>;
+        /// Maximum number of trading pairs looked up to find the paths of a trade, see `find_paths`.
+        type GetMaxRoutingPairs: Get<usize>;
+        /// Maximum number of paths quoted to route a trade, see `candidate_paths`.
+        type GetMaxCandidatePaths: Get<usize>;
+        /// Maximum number of paths a trade is split across.
+        type GetMaxSplitRoutes: Get<usize>;
+        /// Maximum number of trades in a single `swap_batch` call.
+        type MaxBatchSwapLegs: Get<u32>;
         type GetTechnicalAccountId: Get<Self::AccountId>;
         type PrimaryMarket: GetMarketInfo<Self::AssetId>;
-        type SecondaryMarket: GetPoolReserves<Self::AssetId>;
+        type SecondaryMarket: GetPoolReserves<Self::DEXId, Self::AssetId>;
+        /// Closed-form prices of liquidity sources used to split trades without sampling.
+        type SwapCurves: GetSwapCurve<LiquiditySourceIdOf<Self>, Self::AssetId>;
+        /// Maximum weight spent on executing scheduled DCA orders in a single block.
+        type DcaExecutionWeightBudget: Get<Weight>;
+        /// Maximum number of DCA orders scheduled for a single block.
+        type MaxDcaOrdersPerBlock: Get<u32>;
+        /// Number of blocks after a swap commitment it can be revealed in,
+        /// the commitment can be refunded afterwards.
+        type CommitRevealWindow: Get<Self::BlockNumber>;
         /// Weight information for the extrinsics in this Pallet.
         type WeightInfo: WeightInfo;
     }
@@ -1287,7 +2310,16 @@ pub mod pallet {
     pub struct Pallet<T>(PhantomData<T>);
 
     #[pallet::hooks]
-    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(now: T::BlockNumber) -> Weight {
+            Self::execute_dca_orders(now)
+        }
+
+        fn integrity_test() {
+            assert!(T::MaxDcaOrdersPerBlock::get() > 0);
+            assert!(Self::dca_budget_fits_order());
+        }
+    }
 
     #[pallet::call]
     impl<T: Config> Pallet<T> {
@@ -1300,7 +2332,10 @@ pub mod pallet {
         /// - `swap_amount`: the exact amount to be sold (either in input_asset_id or output_asset_id units with corresponding slippage tolerance absolute bound),
         /// - `selected_source_types`: list of selected LiquiditySource types, selection effect is determined by filter_mode,
         /// - `filter_mode`: indicate either to allow or forbid selected types only, or disable filtering.
-        #[pallet::weight(<T as Config>::WeightInfo::swap((*swap_amount).into()))]
+        #[pallet::weight(
+            <T as Config>::WeightInfo::swap((*swap_amount).into())
+                .saturating_add(Pallet::<T>::routing_weight((*swap_amount).into()))
+        )]
         pub fn swap(
             origin: OriginFor<T>,
             dex_id: T::DEXId,
@@ -1311,42 +2346,680 @@ pub mod pallet {
             filter_mode: FilterMode,
         ) -> DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
-            let outcome = Self::exchange(
+            Self::do_swap(
+                who.clone(),
+                who,
+                dex_id,
+                input_asset_id,
+                output_asset_id,
+                swap_amount,
+                selected_source_types,
+                filter_mode,
+                None,
+                None,
+            )?;
+            Ok(().into())
+        }
+
+        /// Perform swap of tokens (input/output defined via SwapAmount direction) with the bought tokens
+        /// credited to the given account.
+        ///
+        /// - `origin`: the account on whose behalf the transaction is being executed,
+        /// - `receiver`: the account to receive the bought tokens, the caller if not specified,
+        /// - `dex_id`: DEX ID for which liquidity sources aggregation is being done,
+        /// - `input_asset_id`: ID of the asset being sold,
+        /// - `output_asset_id`: ID of the asset being bought,
+        /// - `swap_amount`: the exact amount to be sold (either in input_asset_id or output_asset_id units with corresponding slippage tolerance absolute bound),
+        /// - `selected_source_types`: list of selected LiquiditySource types, selection effect is determined by filter_mode,
+        /// - `filter_mode`: indicate either to allow or forbid selected types only, or disable filtering,
+        /// - `deadline`: the last block number the swap can be executed in,
+        /// - `referrer`: the account to receive the share of the fee defined by `ReferrerRewardRate`,
+        /// the reward is taken in the base asset from the fee collected by the liquidity sources,
+        /// - `max_price_impact`: the largest tolerated move of the spot price caused by the swap, in
+        /// basis points, the swap fails if the spot price of the assets isn't available.
+        #[pallet::weight(
+            <T as Config>::WeightInfo::swap_with_options((*swap_amount).into())
+                .saturating_add(Pallet::<T>::routing_weight((*swap_amount).into()))
+        )]
+        #[allow(clippy::too_many_arguments)]
+        pub fn swap_with_options(
+            origin: OriginFor<T>,
+            receiver: Option<T::AccountId>,
+            dex_id: T::DEXId,
+            input_asset_id: T::AssetId,
+            output_asset_id: T::AssetId,
+            swap_amount: SwapAmount<Balance>,
+            selected_source_types: Vec<LiquiditySourceType>,
+            filter_mode: FilterMode,
+            deadline: T::BlockNumber,
+            referrer: Option<T::AccountId>,
+            max_price_impact: Option<BasisPoints>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            ensure!(
+                frame_system::Pallet::<T>::block_number() <= deadline,
+                Error::<T>::DeadlineExpired
+            );
+            ensure!(referrer.as_ref() != Some(&who), Error::<T>::InvalidReferrer);
+            let receiver = receiver.unwrap_or_else(|| who.clone());
+            Self::do_swap(
+                who,
+                receiver,
+                dex_id,
+                input_asset_id,
+                output_asset_id,
+                swap_amount,
+                selected_source_types,
+                filter_mode,
+                referrer,
+                max_price_impact,
+            )?;
+            Ok(().into())
+        }
+
+        /// Set the largest price impact in basis points a single swap is allowed to have,
+        /// swaps exceeding it are rejected regardless of their own bounds. The price impact is
+        /// how far the swap moves the spot price of the assets, swaps of the assets having no
+        /// spot price (see `spot_price`) are not limited.
+        ///
+        /// - `origin`: the root account,
+        /// - `limit`: the price impact limit in basis points, `None` to lift the limit.
+        #[pallet::weight(<T as Config>::WeightInfo::set_max_price_impact())]
+        pub fn set_max_price_impact(
+            origin: OriginFor<T>,
+            limit: Option<BasisPoints>,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            if let Some(limit) = limit {
+                ensure!(
+                    in_basis_points_range(limit),
+                    Error::<T>::InvalidPriceImpactValue
+                );
+            }
+            MaxPriceImpact::<T>::set(limit);
+            Self::deposit_event(Event::<T>::MaxPriceImpactChanged(limit));
+            Ok(().into())
+        }
+
+        /// Set the share of the swap fee paid to the referrer of a swap.
+        ///
+        /// - `origin`: the root account,
+        /// - `rate`: the share of the fee in basis points.
+        #[pallet::weight(<T as Config>::WeightInfo::set_referrer_reward_rate())]
+        pub fn set_referrer_reward_rate(
+            origin: OriginFor<T>,
+            rate: BasisPoints,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            ensure!(in_basis_points_range(rate), Error::<T>::InvalidFeeValue);
+            ReferrerRewardRate::<T>::put(rate);
+            Self::deposit_event(Event::<T>::ReferrerRewardRateChanged(rate));
+            Ok(().into())
+        }
+
+        /// Perform several swaps of tokens in a single call.
+        ///
+        /// - `origin`: the account on whose behalf the transaction is being executed,
+        /// - `dex_id`: DEX ID for which liquidity sources aggregation is being done,
+        /// - `legs`: the swaps to perform, see `swap` for the meaning of the parameters,
+        /// - `all_or_nothing`: if set, any failed swap reverts the entire batch, otherwise
+        /// failed swaps are skipped and reported via `BatchSwapLegFailed` events.
+        #[pallet::weight(
+            <T as Config>::WeightInfo::swap_batch(legs.len() as u32)
+                .saturating_add(
+                    Pallet::<T>::routing_weight(SwapVariant::WithDesiredOutput)
+                        .saturating_mul(legs.len() as Weight)
+                )
+        )]
+        pub fn swap_batch(
+            origin: OriginFor<T>,
+            dex_id: T::DEXId,
+            legs: Vec<BatchSwapLeg<T::AssetId>>,
+            all_or_nothing: bool,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            ensure!(
+                legs.len() <= T::MaxBatchSwapLegs::get() as usize,
+                Error::<T>::TooManyBatchSwapLegs
+            );
+            let legs_count = legs.len() as u32;
+            let mut succeeded_count = 0u32;
+            common::with_transaction(|| {
+                for (index, leg) in legs.into_iter().enumerate() {
+                    let result = Self::do_swap(
+                        who.clone(),
+                        who.clone(),
+                        dex_id,
+                        leg.input_asset_id,
+                        leg.output_asset_id,
+                        leg.swap_amount,
+                        leg.selected_source_types,
+                        leg.filter_mode,
+                        None,
+                        None,
+                    );
+                    match result {
+                        Ok(()) => succeeded_count += 1,
+                        Err(error) if all_or_nothing => return Err(error),
+                        Err(error) => Self::deposit_event(Event::<T>::BatchSwapLegFailed(
+                            who.clone(),
+                            index as u32,
+                            error,
+                        )),
+                    }
+                }
+                Ok(())
+            })?;
+            Self::deposit_event(Event::<T>::BatchSwapExecuted(
+                who,
+                dex_id,
+                legs_count,
+                succeeded_count,
+            ));
+            Ok(().into())
+        }
+
+        /// Create a dollar-cost-averaging order selling `amount` of the input asset every `interval` blocks.
+        /// The funds for all the swaps are reserved upfront, the first swap is performed `interval` blocks
+        /// after the order is created.
+        ///
+        /// - `origin`: the account on whose behalf the swaps are performed,
+        /// - `dex_id`: DEX ID for which liquidity sources aggregation is being done,
+        /// - `input_asset_id`: ID of the asset being sold,
+        /// - `output_asset_id`: ID of the asset being bought,
+        /// - `amount`: the amount of the input asset sold by every swap,
+        /// - `min_amount_out`: the minimum amount of the output asset bought by every swap,
+        /// - `interval`: the number of blocks between the swaps,
+        /// - `count`: the number of swaps to perform,
+        /// - `selected_source_types`: list of selected LiquiditySource types, selection effect is determined by filter_mode,
+        /// - `filter_mode`: indicate either to allow or forbid selected types only, or disable filtering.
+        #[pallet::weight(<T as Config>::WeightInfo::create_dca_order())]
+        #[allow(clippy::too_many_arguments)]
+        pub fn create_dca_order(
+            origin: OriginFor<T>,
+            dex_id: T::DEXId,
+            input_asset_id: T::AssetId,
+            output_asset_id: T::AssetId,
+            amount: Balance,
+            min_amount_out: Balance,
+            interval: T::BlockNumber,
+            count: u32,
+            selected_source_types: Vec<LiquiditySourceType>,
+            filter_mode: FilterMode,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            ensure!(
+                input_asset_id != output_asset_id && amount > 0 && count > 0 && !interval.is_zero(),
+                Error::<T>::InvalidDcaOrder
+            );
+            ensure!(
+                Self::dca_budget_fits_order(),
+                Error::<T>::DcaOrderExceedsWeightBudget
+            );
+            let next_execution = frame_system::Pallet::<T>::block_number() + interval;
+            ensure!(
+                DcaSchedule::<T>::decode_len(next_execution).unwrap_or(0)
+                    < T::MaxDcaOrdersPerBlock::get() as usize,
+                Error::<T>::DcaScheduleFull
+            );
+            let total_amount = amount
+                .checked_mul(count as Balance)
+                .ok_or(Error::<T>::CalculationError)?;
+            assets::Pallet::<T>::reserve(input_asset_id, &who, total_amount)?;
+            let order_id = NextDcaOrderId::<T>::mutate(|next_id| {
+                let order_id = *next_id;
+                *next_id = next_id.wrapping_add(1);
+                order_id
+            });
+            DcaOrders::<T>::insert(
+                order_id,
+                DcaOrder {
+                    owner: who.clone(),
+                    dex_id,
+                    input_asset_id,
+                    output_asset_id,
+                    amount,
+                    min_amount_out,
+                    interval,
+                    remaining: count,
+                    selected_source_types,
+                    filter_mode,
+                    next_execution,
+                },
+            );
+            DcaSchedule::<T>::append(next_execution, order_id);
+            Self::deposit_event(Event::<T>::DcaOrderCreated(order_id, who));
+            Ok(().into())
+        }
+
+        /// Cancel a dollar-cost-averaging order and unreserve the funds of the remaining swaps.
+        ///
+        /// - `origin`: the owner of the order,
+        /// - `order_id`: ID of the order to cancel.
+        #[pallet::weight(<T as Config>::WeightInfo::cancel_dca_order())]
+        pub fn cancel_dca_order(
+            origin: OriginFor<T>,
+            order_id: DcaOrderId,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let order = DcaOrders::<T>::get(order_id).ok_or(Error::<T>::UnknownDcaOrder)?;
+            ensure!(order.owner == who, Error::<T>::NotDcaOrderOwner);
+            let remaining_amount = order.amount.saturating_mul(order.remaining as Balance);
+            assets::Pallet::<T>::unreserve(order.input_asset_id, &who, remaining_amount)?;
+            DcaSchedule::<T>::mutate(order.next_execution, |order_ids| {
+                order_ids.retain(|id| *id != order_id)
+            });
+            DcaOrders::<T>::remove(order_id);
+            Self::deposit_event(Event::<T>::DcaOrderCancelled(
+                order_id,
+                who,
+                remaining_amount,
+            ));
+            Ok(().into())
+        }
+
+        /// Commit to a swap without disclosing its parameters, the swap is performed by `reveal_swap`
+        /// in one of the next `CommitRevealWindow` blocks.
+        ///
+        /// The commitment only hides the swap until it's revealed: the reveal itself can still be
+        /// sandwiched within its block, the swap is protected by its slippage bound alone then.
+        /// The swaps made directly with the sources, e.g. `pool_xyk::swap_pair` or `dex_api::swap`,
+        /// don't go through the commitment at all.
+        ///
+        /// - `origin`: the account on whose behalf the swap is performed,
+        /// - `commitment`: hash of the swap parameters and a salt, see `swap_commitment_hash`,
+        /// - `input_asset_id`: ID of the asset being sold,
+        /// - `reserved_amount`: the amount of the input asset reserved for the swap, the most the swap can sell.
+        #[pallet::weight(<T as Config>::WeightInfo::commit_swap())]
+        pub fn commit_swap(
+            origin: OriginFor<T>,
+            commitment: T::Hash,
+            input_asset_id: T::AssetId,
+            reserved_amount: Balance,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            ensure!(reserved_amount > 0, Error::<T>::InvalidSwapCommitment);
+            ensure!(
+                !SwapCommitments::<T>::contains_key(&who, &commitment),
+                Error::<T>::SwapCommitmentAlreadyExists
+            );
+            assets::Pallet::<T>::reserve(input_asset_id, &who, reserved_amount)?;
+            SwapCommitments::<T>::insert(
                 &who,
+                &commitment,
+                SwapCommitment {
+                    input_asset_id,
+                    reserved_amount,
+                    committed_at: frame_system::Pallet::<T>::block_number(),
+                },
+            );
+            Self::deposit_event(Event::<T>::SwapCommitted(
+                who,
+                commitment,
+                input_asset_id,
+                reserved_amount,
+            ));
+            Ok(().into())
+        }
+
+        /// Reveal and perform a swap committed by `commit_swap` in one of the previous `CommitRevealWindow` blocks.
+        /// The reserved amount is unreserved, the swap can't sell more than it.
+        ///
+        /// - `origin`: the account the swap has been committed by,
+        /// - `dex_id`: DEX ID for which liquidity sources aggregation is being done,
+        /// - `input_asset_id`: ID of the asset being sold,
+        /// - `output_asset_id`: ID of the asset being bought,
+        /// - `swap_amount`: the exact amount to be sold (either in input_asset_id or output_asset_id units with corresponding slippage tolerance absolute bound),
+        /// - `selected_source_types`: list of selected LiquiditySource types, selection effect is determined by filter_mode,
+        /// - `filter_mode`: indicate either to allow or forbid selected types only, or disable filtering,
+        /// - `salt`: the salt the commitment has been made with.
+        #[pallet::weight(
+            <T as Config>::WeightInfo::reveal_swap((*swap_amount).into())
+                .saturating_add(Pallet::<T>::routing_weight((*swap_amount).into()))
+        )]
+        #[allow(clippy::too_many_arguments)]
+        pub fn reveal_swap(
+            origin: OriginFor<T>,
+            dex_id: T::DEXId,
+            input_asset_id: T::AssetId,
+            output_asset_id: T::AssetId,
+            swap_amount: SwapAmount<Balance>,
+            selected_source_types: Vec<LiquiditySourceType>,
+            filter_mode: FilterMode,
+            salt: [u8; 32],
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let commitment_hash = Self::swap_commitment_hash(
                 &who,
+                &dex_id,
                 &input_asset_id,
                 &output_asset_id,
-                swap_amount,
-                LiquiditySourceFilter::with_mode(dex_id, filter_mode, selected_source_types),
-            )?;
-            let (input_amount, output_amount, fee_amount) = match swap_amount {
+                &swap_amount,
+                &selected_source_types,
+                &filter_mode,
+                &salt,
+            );
+            let commitment = SwapCommitments::<T>::get(&who, &commitment_hash)
+                .ok_or(Error::<T>::UnknownSwapCommitment)?;
+            ensure!(
+                commitment.input_asset_id == input_asset_id,
+                Error::<T>::UnknownSwapCommitment
+            );
+            let now = frame_system::Pallet::<T>::block_number();
+            ensure!(
+                now > commitment.committed_at,
+                Error::<T>::SwapCommitmentRevealedTooEarly
+            );
+            ensure!(
+                now <= commitment.committed_at + T::CommitRevealWindow::get(),
+                Error::<T>::SwapCommitmentExpired
+            );
+            let max_input_amount = match swap_amount {
                 SwapAmount::WithDesiredInput {
                     desired_amount_in, ..
-                } => (desired_amount_in, outcome.amount, outcome.fee),
-                SwapAmount::WithDesiredOutput {
-                    desired_amount_out, ..
-                } => (outcome.amount, desired_amount_out, outcome.fee),
+                } => desired_amount_in,
+                SwapAmount::WithDesiredOutput { max_amount_in, .. } => max_amount_in,
             };
-            Self::deposit_event(Event::<T>::Exchange(
+            ensure!(
+                max_input_amount <= commitment.reserved_amount,
+                Error::<T>::InsufficientCommittedAmount
+            );
+            // The funds stay reserved for the refund if the swap fails.
+            common::with_transaction(|| {
+                assets::Pallet::<T>::unreserve(input_asset_id, &who, commitment.reserved_amount)?;
+                Self::do_swap(
+                    who.clone(),
+                    who.clone(),
+                    dex_id,
+                    input_asset_id,
+                    output_asset_id,
+                    swap_amount,
+                    selected_source_types,
+                    filter_mode,
+                    None,
+                    None,
+                )?;
+                SwapCommitments::<T>::remove(&who, &commitment_hash);
+                Ok(())
+            })?;
+            Self::deposit_event(Event::<T>::SwapRevealed(who, commitment_hash));
+            Ok(().into())
+        }
+
+        /// Unreserve the amount of a swap commitment which hasn't been revealed in time.
+        ///
+        /// - `origin`: the account the swap has been committed by,
+        /// - `commitment`: hash of the committed swap.
+        #[pallet::weight(<T as Config>::WeightInfo::refund_swap_commitment())]
+        pub fn refund_swap_commitment(
+            origin: OriginFor<T>,
+            commitment: T::Hash,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let swap_commitment = SwapCommitments::<T>::get(&who, &commitment)
+                .ok_or(Error::<T>::UnknownSwapCommitment)?;
+            ensure!(
+                frame_system::Pallet::<T>::block_number()
+                    > swap_commitment.committed_at + T::CommitRevealWindow::get(),
+                Error::<T>::SwapCommitmentNotExpired
+            );
+            assets::Pallet::<T>::unreserve(
+                swap_commitment.input_asset_id,
+                &who,
+                swap_commitment.reserved_amount,
+            )?;
+            SwapCommitments::<T>::remove(&who, &commitment);
+            Self::deposit_event(Event::<T>::SwapCommitmentRefunded(
+                who,
+                commitment,
+                swap_commitment.reserved_amount,
+            ));
+            Ok(().into())
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// Hash of the swap parameters committed by `commit_swap`.
+        #[allow(clippy::too_many_arguments)]
+        pub fn swap_commitment_hash(
+            who: &T::AccountId,
+            dex_id: &T::DEXId,
+            input_asset_id: &T::AssetId,
+            output_asset_id: &T::AssetId,
+            swap_amount: &SwapAmount<Balance>,
+            selected_source_types: &[LiquiditySourceType],
+            filter_mode: &FilterMode,
+            salt: &[u8; 32],
+        ) -> T::Hash {
+            T::Hashing::hash_of(&(
                 who,
                 dex_id,
                 input_asset_id,
                 output_asset_id,
-                input_amount,
-                output_amount,
-                fee_amount,
-            ));
-            Ok(().into())
+                swap_amount,
+                selected_source_types,
+                filter_mode,
+                salt,
+            ))
+        }
+
+        /// Weight of a single DCA order swap, see `execute_dca_order`.
+        fn dca_order_weight() -> Weight {
+            <T as Config>::WeightInfo::execute_dca_order()
+                .saturating_add(Self::routing_weight(SwapVariant::WithDesiredInput))
+        }
+
+        /// Weight of moving a DCA order to the next block, not including the lookup of a block
+        /// with less than `MaxDcaOrdersPerBlock` orders.
+        fn dca_deferral_weight() -> Weight {
+            T::DbWeight::get().reads_writes(2, 2)
+        }
+
+        /// Whether `DcaExecutionWeightBudget` fits a swap of a DCA order along with deferring
+        /// the rest of the orders of the block, otherwise the scheduled orders would be deferred
+        /// block after block without ever being executed.
+        fn dca_budget_fits_order() -> bool {
+            let max_deferred = T::MaxDcaOrdersPerBlock::get().saturating_sub(1) as Weight;
+            T::DbWeight::get()
+                .reads_writes(1, 1)
+                .saturating_add(Self::dca_order_weight())
+                .saturating_add(Self::dca_deferral_weight().saturating_mul(max_deferred))
+                <= T::DcaExecutionWeightBudget::get()
+        }
+
+        /// Appends the DCA order to the schedule of the first block starting from `block` which
+        /// has less than `MaxDcaOrdersPerBlock` orders, returns the block and the weight of the lookup.
+        fn schedule_dca_order(
+            order_id: DcaOrderId,
+            mut block: T::BlockNumber,
+        ) -> (T::BlockNumber, Weight) {
+            let max_orders = T::MaxDcaOrdersPerBlock::get() as usize;
+            let mut weight: Weight = 0;
+            while DcaSchedule::<T>::decode_len(block).unwrap_or(0) >= max_orders {
+                weight = weight.saturating_add(T::DbWeight::get().reads(1));
+                block += 1u32.into();
+            }
+            DcaSchedule::<T>::append(block, order_id);
+            (block, weight)
+        }
+
+        /// Performs the swaps of DCA orders scheduled for the block while they fit into
+        /// `DcaExecutionWeightBudget`, the rest are deferred to the next blocks. The weight of
+        /// reading the schedule and deferring the orders that are left is kept within the budget.
+        fn execute_dca_orders(now: T::BlockNumber) -> Weight {
+            let budget = T::DcaExecutionWeightBudget::get();
+            let order_weight = Self::dca_order_weight();
+            let deferral_weight = Self::dca_deferral_weight();
+            let order_ids = DcaSchedule::<T>::take(now);
+            let mut weight = T::DbWeight::get().reads_writes(1, 1);
+            let mut orders_left = order_ids.len() as Weight;
+            for order_id in order_ids {
+                orders_left -= 1;
+                let reserved_weight = deferral_weight.saturating_mul(orders_left);
+                if weight
+                    .saturating_add(order_weight)
+                    .saturating_add(reserved_weight)
+                    > budget
+                {
+                    let (next_execution, lookup_weight) =
+                        Self::schedule_dca_order(order_id, now + 1u32.into());
+                    DcaOrders::<T>::mutate(order_id, |order| {
+                        if let Some(order) = order {
+                            order.next_execution = next_execution;
+                        }
+                    });
+                    Self::deposit_event(Event::<T>::DcaSwapSkipped(order_id));
+                    weight = weight
+                        .saturating_add(deferral_weight)
+                        .saturating_add(lookup_weight);
+                    continue;
+                }
+                weight = weight
+                    .saturating_add(order_weight)
+                    .saturating_add(Self::execute_dca_order(order_id, now));
+            }
+            weight
+        }
+
+        /// Performs a single swap of the DCA order and schedules the next one,
+        /// returns the weight of the schedule lookup.
+        fn execute_dca_order(order_id: DcaOrderId, now: T::BlockNumber) -> Weight {
+            let mut order = match DcaOrders::<T>::get(order_id) {
+                Some(order) => order,
+                None => return 0,
+            };
+            let result = common::with_transaction(|| {
+                assets::Pallet::<T>::unreserve(order.input_asset_id, &order.owner, order.amount)?;
+                Self::do_swap(
+                    order.owner.clone(),
+                    order.owner.clone(),
+                    order.dex_id,
+                    order.input_asset_id,
+                    order.output_asset_id,
+                    SwapAmount::with_desired_input(order.amount, order.min_amount_out),
+                    order.selected_source_types.clone(),
+                    order.filter_mode.clone(),
+                    None,
+                    None,
+                )
+            });
+            match result {
+                Ok(()) => Self::deposit_event(Event::<T>::DcaSwapExecuted(order_id)),
+                Err(error) => {
+                    // The funds of the failed swap are released to the owner.
+                    let _ = assets::Pallet::<T>::unreserve(
+                        order.input_asset_id,
+                        &order.owner,
+                        order.amount,
+                    );
+                    Self::deposit_event(Event::<T>::DcaSwapFailed(order_id, error));
+                }
+            }
+            order.remaining = order.remaining.saturating_sub(1);
+            if order.remaining == 0 {
+                DcaOrders::<T>::remove(order_id);
+                Self::deposit_event(Event::<T>::DcaOrderCompleted(order_id));
+                0
+            } else {
+                let (next_execution, lookup_weight) =
+                    Self::schedule_dca_order(order_id, now + order.interval);
+                order.next_execution = next_execution;
+                DcaOrders::<T>::insert(order_id, order);
+                lookup_weight
+            }
+        }
+
+        #[allow(clippy::too_many_arguments)]
+        fn do_swap(
+            who: T::AccountId,
+            receiver: T::AccountId,
+            dex_id: T::DEXId,
+            input_asset_id: T::AssetId,
+            output_asset_id: T::AssetId,
+            swap_amount: SwapAmount<Balance>,
+            selected_source_types: Vec<LiquiditySourceType>,
+            filter_mode: FilterMode,
+            referrer: Option<T::AccountId>,
+            max_price_impact: Option<BasisPoints>,
+        ) -> DispatchResult {
+            common::with_transaction(|| {
+                let price_impact_limit = Self::max_price_impact();
+                let check_price_impact = max_price_impact.is_some() || price_impact_limit.is_some();
+                let spot_price = if check_price_impact {
+                    Self::spot_price(&dex_id, &input_asset_id, &output_asset_id)
+                } else {
+                    None
+                };
+                let (outcome, referrer_reward) = Self::exchange_with_referrer(
+                    &who,
+                    &receiver,
+                    &input_asset_id,
+                    &output_asset_id,
+                    swap_amount,
+                    LiquiditySourceFilter::with_mode(dex_id, filter_mode, selected_source_types),
+                    referrer.as_ref(),
+                )?;
+                let (input_amount, output_amount, fee_amount) = match swap_amount {
+                    SwapAmount::WithDesiredInput {
+                        desired_amount_in, ..
+                    } => (desired_amount_in, outcome.amount, outcome.fee),
+                    SwapAmount::WithDesiredOutput {
+                        desired_amount_out, ..
+                    } => (outcome.amount, desired_amount_out, outcome.fee),
+                };
+                if check_price_impact {
+                    // the swap is measured by how far it has moved the spot price, which doesn't
+                    // include the fee, a drained market is taken as the largest possible move
+                    let price_impact = match spot_price {
+                        Some(spot_price) => {
+                            let spot_price_after =
+                                Self::spot_price(&dex_id, &input_asset_id, &output_asset_id)
+                                    .unwrap_or(Fixed::ZERO);
+                            Some(Self::price_impact(spot_price, spot_price_after)?)
+                        }
+                        None => None,
+                    };
+                    if let Some(max_price_impact) = max_price_impact {
+                        let price_impact = price_impact.ok_or(Error::<T>::UnavailableSpotPrice)?;
+                        ensure!(
+                            price_impact <= max_price_impact,
+                            Error::<T>::PriceImpactNotTolerated
+                        );
+                    }
+                    if let (Some(limit), Some(price_impact)) = (price_impact_limit, price_impact) {
+                        ensure!(price_impact <= limit, Error::<T>::PriceImpactLimitExceeded);
+                    }
+                }
+                Self::deposit_event(Event::<T>::Exchange(
+                    who,
+                    receiver,
+                    dex_id,
+                    input_asset_id,
+                    output_asset_id,
+                    input_amount,
+                    output_amount,
+                    fee_amount,
+                    referrer,
+                    referrer_reward,
+                ));
+                Ok(())
+            })
         }
     }
 
     #[pallet::event]
-    #[pallet::metadata(AccountIdOf<T> = "AccountId", AssetIdOf<T> = "AssetId", DexIdOf<T> = "DEXId")]
+    #[pallet::metadata(
+        AccountIdOf<T> = "AccountId",
+        AssetIdOf<T> = "AssetId",
+        DexIdOf<T> = "DEXId",
+        T::Hash = "Hash"
+    )]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
         /// Exchange of tokens has been performed
-        /// [Caller Account, DEX Id, Input Asset Id, Output Asset Id, Input Amount, Output Amount, Fee Amount]
+        /// [Caller Account, Receiver Account, DEX Id, Input Asset Id, Output Asset Id, Input Amount, Output Amount, Fee Amount, Referrer Account, Referrer Reward]
         Exchange(
+            AccountIdOf<T>,
             AccountIdOf<T>,
             DexIdOf<T>,
             AssetIdOf<T>,
@@ -1354,7 +3027,37 @@ pub mod pallet {
             Balance,
             Balance,
             Balance,
+            Option<AccountIdOf<T>>,
+            Balance,
         ),
+        /// Share of the swap fee paid to the referrer has been changed [New Rate in Basis Points]
+        ReferrerRewardRateChanged(BasisPoints),
+        /// Price impact limit for a single swap has been changed [New Limit in Basis Points]
+        MaxPriceImpactChanged(Option<BasisPoints>),
+        /// Swap of `swap_batch` has failed and has been skipped [Caller Account, Swap Index, Error]
+        BatchSwapLegFailed(AccountIdOf<T>, u32, DispatchError),
+        /// Batch of swaps has been executed [Caller Account, DEX Id, Number of Swaps, Number of Succeeded Swaps]
+        BatchSwapExecuted(AccountIdOf<T>, DexIdOf<T>, u32, u32),
+        /// Dollar-cost-averaging order has been created [Order Id, Owner Account]
+        DcaOrderCreated(DcaOrderId, AccountIdOf<T>),
+        /// Dollar-cost-averaging order has been cancelled [Order Id, Owner Account, Unreserved Amount]
+        DcaOrderCancelled(DcaOrderId, AccountIdOf<T>, Balance),
+        /// Scheduled swap of a DCA order has been performed [Order Id]
+        DcaSwapExecuted(DcaOrderId),
+        /// Scheduled swap of a DCA order has failed, its funds have been unreserved [Order Id, Error]
+        DcaSwapFailed(DcaOrderId, DispatchError),
+        /// Scheduled swap of a DCA order exceeded the block weight budget and has been deferred to the next block [Order Id]
+        DcaSwapSkipped(DcaOrderId),
+        /// All swaps of a DCA order have been performed [Order Id]
+        DcaOrderCompleted(DcaOrderId),
+        /// Swap has been committed [Caller Account, Commitment Hash, Input Asset Id, Reserved Amount]
+        SwapCommitted(AccountIdOf<T>, T::Hash, AssetIdOf<T>, Balance),
+        /// Committed swap has been revealed and performed [Caller Account, Commitment Hash]
+        SwapRevealed(AccountIdOf<T>, T::Hash),
+        /// Amount of an expired swap commitment has been unreserved [Caller Account, Commitment Hash, Unreserved Amount]
+        SwapCommitmentRefunded(AccountIdOf<T>, T::Hash, Balance),
+        /// Referrer reward couldn't be paid and has been skipped [Referrer Account, Reward, Error]
+        ReferrerRewardFailed(AccountIdOf<T>, Balance, DispatchError),
     }
 
     #[pallet::error]
@@ -1373,5 +3076,76 @@ pub mod pallet {
         CalculationError,
         /// Slippage either exceeds minimum tolerated output or maximum tolerated input.
         SlippageNotTolerated,
+        /// The swap deadline block number has passed.
+        DeadlineExpired,
+        /// Number of swaps in the batch exceeds `MaxBatchSwapLegs`.
+        TooManyBatchSwapLegs,
+        /// DCA order has equal assets, zero amount, interval or number of swaps.
+        InvalidDcaOrder,
+        /// DCA order with the given ID doesn't exist.
+        UnknownDcaOrder,
+        /// The caller is not the owner of the DCA order.
+        NotDcaOrderOwner,
+        /// The block the DCA order is scheduled for has `MaxDcaOrdersPerBlock` orders already.
+        DcaScheduleFull,
+        /// `DcaExecutionWeightBudget` doesn't fit a swap of a DCA order.
+        DcaOrderExceedsWeightBudget,
+        /// Swap commitment reserves zero amount.
+        InvalidSwapCommitment,
+        /// The caller has already made a swap commitment with the same hash.
+        SwapCommitmentAlreadyExists,
+        /// Swap commitment with the given hash doesn't exist for the caller.
+        UnknownSwapCommitment,
+        /// Swap commitment can't be revealed in the block it has been made in.
+        SwapCommitmentRevealedTooEarly,
+        /// Swap commitment hasn't been revealed within `CommitRevealWindow` blocks.
+        SwapCommitmentExpired,
+        /// Swap commitment can only be refunded after `CommitRevealWindow` blocks.
+        SwapCommitmentNotExpired,
+        /// Revealed swap can sell more than the amount reserved by the commitment.
+        InsufficientCommittedAmount,
+        /// Price impact of the swap exceeds the maximum tolerated by the caller.
+        PriceImpactNotTolerated,
+        /// Price impact of the swap exceeds the `MaxPriceImpact` limit.
+        PriceImpactLimitExceeded,
+        /// Price impact value outside of the basis points range [0..10000]
+        InvalidPriceImpactValue,
+        /// Spot price of the assets can't be determined to check the price impact of the swap.
+        UnavailableSpotPrice,
+        /// The caller can't be the referrer of own swap.
+        InvalidReferrer,
+        /// Paths a trade is split across share a pool.
+        OverlappingRoutes,
     }
+
+    /// Share of the swap fee paid to the referrer of a swap, in basis points.
+    #[pallet::storage]
+    #[pallet::getter(fn referrer_reward_rate)]
+    pub type ReferrerRewardRate<T> = StorageValue<_, BasisPoints, ValueQuery>;
+
+    /// The largest move of the spot price a single swap may cause in basis points, unlimited if
+    /// not set.
+    #[pallet::storage]
+    #[pallet::getter(fn max_price_impact)]
+    pub type MaxPriceImpact<T> = StorageValue<_, BasisPoints>;
+
+    /// ID of the next DCA order to be created.
+    #[pallet::storage]
+    pub type NextDcaOrderId<T> = StorageValue<_, DcaOrderId, ValueQuery>;
+
+    /// Active dollar-cost-averaging orders.
+    #[pallet::storage]
+    #[pallet::getter(fn dca_order)]
+    pub type DcaOrders<T: Config> = StorageMap<_, Twox64Concat, DcaOrderId, DcaOrderOf<T>>;
+
+    /// IDs of the DCA orders scheduled for execution at a given block.
+    #[pallet::storage]
+    pub type DcaSchedule<T: Config> =
+        StorageMap<_, Twox64Concat, T::BlockNumber, Vec<DcaOrderId>, ValueQuery>;
+
+    /// Swaps committed by an account and not revealed yet, by commitment hash.
+    #[pallet::storage]
+    #[pallet::getter(fn swap_commitment)]
+    pub type SwapCommitments<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Identity, T::Hash, SwapCommitmentOf<T>>;
 }
@@ -30,27 +30,30 @@
 
 use crate::{self as liquidity_proxy, Config};
 use common::mock::ExistentialDeposits;
+use common::weights::constants::EXTRINSIC_FIXED_WEIGHT;
 use common::{
     self, balance, fixed, fixed_from_basis_points, fixed_wrapper, hash, Amount, AssetId32,
-    AssetName, AssetSymbol, DEXInfo, Fixed, FromGenericPair, GetMarketInfo, LiquiditySource,
-    LiquiditySourceType, TechPurpose, DOT, KSM, PSWAP, USDT, VAL, XOR,
+    AssetName, AssetSymbol, BasisPoints, DEXInfo, Fixed, FromGenericPair, GetMarketInfo,
+    GetSwapCurve, LiquiditySource, LiquiditySourceId, LiquiditySourceType, SwapCurve,
+    TaggedLiquiditySource, TechPurpose, DOT, KSM, PSWAP, USDT, VAL, XOR,
 };
 use currencies::BasicCurrencyAdapter;
 
 use core::convert::TryInto;
 
-use frame_support::traits::GenesisBuild;
+use frame_support::traits::{GenesisBuild, Get};
 use frame_support::weights::Weight;
 use frame_support::{construct_runtime, parameter_types};
 use frame_system;
 use traits::MultiCurrency;
 
-use common::prelude::{Balance, FixedWrapper, SwapAmount, SwapOutcome};
+use common::prelude::{Balance, FixedWrapper, SwapAmount, SwapOutcome, SwapVariant};
 use permissions::{Scope, INIT_DEX, MANAGE_DEX};
 use sp_core::H256;
 use sp_runtime::testing::Header;
 use sp_runtime::traits::{BlakeTwo256, IdentityLookup};
 use sp_runtime::{AccountId32, DispatchError, Perbill};
+use std::cell::Cell;
 use std::collections::HashMap;
 
 pub type AccountId = AccountId32;
@@ -58,7 +61,7 @@ pub type BlockNumber = u64;
 pub type DEXId = u32;
 type TechAccountId = common::TechAccountId<AccountId, TechAssetId, DEXId>;
 type TechAssetId = common::TechAssetId<common::PredefinedAssetId>;
-type AssetId = AssetId32<common::PredefinedAssetId>;
+pub type AssetId = AssetId32<common::PredefinedAssetId>;
 type ReservesInit = Vec<(DEXId, AssetId, (Fixed, Fixed))>;
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
 type Block = frame_system::mocking::MockBlock<Runtime>;
@@ -92,12 +95,23 @@ parameter_types! {
         account_id
     };
     pub const GetNumSamples: usize = 40;
+    pub const GetMaxRouteHops: usize = 3;
+    pub const GetMaxCandidatePaths: usize = 8;
+    pub const GetMaxSplitRoutes: usize = 3;
+    pub const MaxBatchSwapLegs: u32 = 16;
+    pub const CommitRevealWindow: BlockNumber = 10;
+    pub DcaExecutionWeightBudget: Weight = 2 * (
+        EXTRINSIC_FIXED_WEIGHT
+            + crate::Pallet::<Runtime>::routing_weight(SwapVariant::WithDesiredInput)
+    );
+    pub const MaxDcaOrdersPerBlock: u32 = 3;
     pub const GetBaseAssetId: AssetId = XOR;
     pub const ExistentialDeposit: u128 = 0;
     pub GetFee0: Fixed = fixed_from_basis_points(0u16);
     pub GetFee10: Fixed = fixed_from_basis_points(10u16);
     pub GetFee20: Fixed = fixed_from_basis_points(20u16);
     pub GetFee30: Fixed = fixed_from_basis_points(30u16);
+    pub const MaxDexFee: BasisPoints = 100;
 }
 
 construct_runtime! {
@@ -112,7 +126,7 @@ construct_runtime! {
         Currencies: currencies::{Pallet, Call, Storage, Event<T>},
         Assets: assets::{Pallet, Call, Config<T>, Storage, Event<T>},
         Balances: pallet_balances::{Pallet, Call, Storage, Event<T>},
-        DexManager: dex_manager::{Pallet, Call, Storage},
+        DexManager: dex_manager::{Pallet, Call, Storage, Event<T>},
         MockLiquiditySource: mock_liquidity_source::<Instance1>::{Pallet, Call, Config<T>, Storage},
         MockLiquiditySource2: mock_liquidity_source::<Instance2>::{Pallet, Call, Config<T>, Storage},
         MockLiquiditySource3: mock_liquidity_source::<Instance3>::{Pallet, Call, Config<T>, Storage},
@@ -120,7 +134,7 @@ construct_runtime! {
         Technical: technical::{Pallet, Call, Storage, Event<T>},
         Permissions: permissions::{Pallet, Call, Config<T>, Storage, Event<T>},
         DexApi: dex_api::{Pallet, Call, Config, Storage, Event<T>},
-        TradingPair: trading_pair::{Pallet, Call, Storage, Event<T>},
+        TradingPair: trading_pair::{Pallet, Call, Config<T>, Storage, Event<T>},
 
            ParachainSystem: cumulus_pallet_parachain_system::{Pallet, Call, Storage, Inherent, Event},
 
@@ -169,10 +183,19 @@ impl Config for Runtime {
     type Event = Event;
     type LiquidityRegistry = dex_api::Module<Runtime>;
     type GetNumSamples = GetNumSamples;
+    type GetMaxRouteHops = GetMaxRouteHops;
+    type GetMaxRoutingPairs = GetMaxRoutingPairs;
+    type GetMaxCandidatePaths = GetMaxCandidatePaths;
+    type GetMaxSplitRoutes = GetMaxSplitRoutes;
+    type MaxBatchSwapLegs = MaxBatchSwapLegs;
     type GetTechnicalAccountId = GetLiquidityProxyAccountId;
     type WeightInfo = ();
     type PrimaryMarket = MockMCBCPool;
     type SecondaryMarket = mock_liquidity_source::Module<Runtime, mock_liquidity_source::Instance1>;
+    type SwapCurves = MockSwapCurves;
+    type DcaExecutionWeightBudget = DcaExecutionWeightBudget;
+    type MaxDcaOrdersPerBlock = MaxDcaOrdersPerBlock;
+    type CommitRevealWindow = CommitRevealWindow;
 }
 
 impl tokens::Config for Runtime {
@@ -201,6 +224,7 @@ impl assets::Config for Runtime {
     type AssetId = AssetId;
     type GetBaseAssetId = GetBaseAssetId;
     type Currency = currencies::Module<Runtime>;
+    type IsAssetInUse = ();
     type WeightInfo = ();
 }
 
@@ -219,7 +243,11 @@ impl pallet_balances::Config for Runtime {
     type MaxLocks = ();
 }
 
-impl dex_manager::Config for Runtime {}
+impl dex_manager::Config for Runtime {
+    type Event = Event;
+    type MaxFee = MaxDexFee;
+    type WeightInfo = ();
+}
 
 impl mock_liquidity_source::Config<mock_liquidity_source::Instance1> for Runtime {
     type GetFee = GetFee0;
@@ -261,17 +289,13 @@ impl permissions::Config for Runtime {
 
 impl dex_api::Config for Runtime {
     type Event = Event;
-    type MockLiquiditySource =
-        mock_liquidity_source::Module<Runtime, mock_liquidity_source::Instance1>;
-    type MockLiquiditySource2 =
-        mock_liquidity_source::Module<Runtime, mock_liquidity_source::Instance2>;
-    type MockLiquiditySource3 =
-        mock_liquidity_source::Module<Runtime, mock_liquidity_source::Instance3>;
-    type MockLiquiditySource4 =
-        mock_liquidity_source::Module<Runtime, mock_liquidity_source::Instance4>;
-    type BondingCurvePool = ();
-    type XYKPool = ();
-    type MulticollateralBondingCurvePool = MockMCBCPool;
+    type LiquiditySources = (
+        MockMCBCPool,
+        mock_liquidity_source::Module<Runtime, mock_liquidity_source::Instance1>,
+        mock_liquidity_source::Module<Runtime, mock_liquidity_source::Instance2>,
+        mock_liquidity_source::Module<Runtime, mock_liquidity_source::Instance3>,
+        mock_liquidity_source::Module<Runtime, mock_liquidity_source::Instance4>,
+    );
     type WeightInfo = ();
 }
 
@@ -291,44 +315,81 @@ pub struct ExtBuilder {
     pub initial_permissions: Vec<(AccountId, Scope, Vec<u32>)>,
     pub source_types: Vec<LiquiditySourceType>,
     pub endowed_accounts: Vec<(AccountId, AssetId, Balance, AssetSymbol, AssetName, u8)>,
+    pub enabled_sources: Vec<(DEXId, AssetId, AssetId, LiquiditySourceType)>,
 }
 
 impl Default for ExtBuilder {
     fn default() -> Self {
+        let reserves = vec![
+            (DEX_A_ID, DOT, (fixed!(5000), fixed!(7000))),
+            (DEX_A_ID, KSM, (fixed!(5500), fixed!(4000))),
+            (DEX_B_ID, DOT, (fixed!(100), fixed!(45))),
+            (DEX_C_ID, DOT, (fixed!(520), fixed!(550))),
+            (DEX_D_ID, VAL, (fixed!(1000), fixed!(200000))),
+            (DEX_D_ID, KSM, (fixed!(1000), fixed!(1000))),
+            (DEX_D_ID, DOT, (fixed!(1000), fixed!(9000))),
+        ];
+        let reserves_2 = vec![
+            (DEX_A_ID, DOT, (fixed!(6000), fixed!(6000))),
+            (DEX_A_ID, KSM, (fixed!(6500), fixed!(3000))),
+            (DEX_B_ID, DOT, (fixed!(200), fixed!(45))),
+            (DEX_C_ID, DOT, (fixed!(550), fixed!(700))),
+        ];
+        let reserves_3 = vec![
+            (DEX_A_ID, DOT, (fixed!(7000), fixed!(5000))),
+            (DEX_A_ID, KSM, (fixed!(7500), fixed!(2000))),
+            (DEX_B_ID, DOT, (fixed!(300), fixed!(45))),
+            (DEX_C_ID, DOT, (fixed!(400), fixed!(380))),
+        ];
+        let reserves_4 = vec![
+            (DEX_A_ID, DOT, (fixed!(8000), fixed!(4000))),
+            (DEX_A_ID, KSM, (fixed!(8500), fixed!(1000))),
+            (DEX_B_ID, DOT, (fixed!(400), fixed!(45))),
+            (DEX_C_ID, DOT, (fixed!(1300), fixed!(1800))),
+        ];
+        // Mock sources are enabled for the trading pairs of XOR with the assets they have reserves
+        // of, the multicollateral bonding curve pool for the ones it has collateral of.
+        let enabled_sources = [
+            (&reserves, LiquiditySourceType::MockPool),
+            (&reserves_2, LiquiditySourceType::MockPool2),
+            (&reserves_3, LiquiditySourceType::MockPool3),
+            (&reserves_4, LiquiditySourceType::MockPool4),
+        ]
+        .iter()
+        .flat_map(|(reserves, source_type)| {
+            reserves
+                .iter()
+                .map(move |(dex_id, asset_id, _)| (*dex_id, XOR, *asset_id, *source_type))
+        })
+        .chain(
+            [DEX_A_ID, DEX_B_ID, DEX_C_ID, DEX_D_ID]
+                .iter()
+                .flat_map(|dex_id| {
+                    get_mcbc_reserves_normal()
+                        .into_iter()
+                        .map(move |(asset_id, _)| {
+                            (
+                                *dex_id,
+                                XOR,
+                                asset_id,
+                                LiquiditySourceType::MulticollateralBondingCurvePool,
+                            )
+                        })
+                }),
+        )
+        .collect();
         Self {
-            reserves: vec![
-                (DEX_A_ID, DOT, (fixed!(5000), fixed!(7000))),
-                (DEX_A_ID, KSM, (fixed!(5500), fixed!(4000))),
-                (DEX_B_ID, DOT, (fixed!(100), fixed!(45))),
-                (DEX_C_ID, DOT, (fixed!(520), fixed!(550))),
-                (DEX_D_ID, VAL, (fixed!(1000), fixed!(200000))),
-                (DEX_D_ID, KSM, (fixed!(1000), fixed!(1000))),
-                (DEX_D_ID, DOT, (fixed!(1000), fixed!(9000))),
-            ],
-            reserves_2: vec![
-                (DEX_A_ID, DOT, (fixed!(6000), fixed!(6000))),
-                (DEX_A_ID, KSM, (fixed!(6500), fixed!(3000))),
-                (DEX_B_ID, DOT, (fixed!(200), fixed!(45))),
-                (DEX_C_ID, DOT, (fixed!(550), fixed!(700))),
-            ],
-            reserves_3: vec![
-                (DEX_A_ID, DOT, (fixed!(7000), fixed!(5000))),
-                (DEX_A_ID, KSM, (fixed!(7500), fixed!(2000))),
-                (DEX_B_ID, DOT, (fixed!(300), fixed!(45))),
-                (DEX_C_ID, DOT, (fixed!(400), fixed!(380))),
-            ],
-            reserves_4: vec![
-                (DEX_A_ID, DOT, (fixed!(8000), fixed!(4000))),
-                (DEX_A_ID, KSM, (fixed!(8500), fixed!(1000))),
-                (DEX_B_ID, DOT, (fixed!(400), fixed!(45))),
-                (DEX_C_ID, DOT, (fixed!(1300), fixed!(1800))),
-            ],
+            reserves,
+            reserves_2,
+            reserves_3,
+            reserves_4,
             dex_list: vec![
                 (
                     DEX_A_ID,
                     DEXInfo {
                         base_asset_id: GetBaseAssetId::get(),
                         is_public: true,
+                        default_fee: 30,
                     },
                 ),
                 (
@@ -336,6 +397,7 @@ impl Default for ExtBuilder {
                     DEXInfo {
                         base_asset_id: GetBaseAssetId::get(),
                         is_public: true,
+                        default_fee: 30,
                     },
                 ),
                 (
@@ -343,6 +405,7 @@ impl Default for ExtBuilder {
                     DEXInfo {
                         base_asset_id: GetBaseAssetId::get(),
                         is_public: true,
+                        default_fee: 30,
                     },
                 ),
                 (
@@ -350,6 +413,7 @@ impl Default for ExtBuilder {
                     DEXInfo {
                         base_asset_id: GetBaseAssetId::get(),
                         is_public: true,
+                        default_fee: 30,
                     },
                 ),
             ],
@@ -396,6 +460,7 @@ impl Default for ExtBuilder {
                     18,
                 ),
             ],
+            enabled_sources,
         }
     }
 }
@@ -559,6 +624,20 @@ impl LiquiditySource<DEXId, AccountId, AssetId, Balance, DispatchError> for Mock
     }
 }
 
+impl GetSwapCurve<DEXId, AssetId> for MockMCBCPool {
+    fn swap_curve(
+        _dex_id: &DEXId,
+        _input_asset_id: &AssetId,
+        _output_asset_id: &AssetId,
+    ) -> Option<SwapCurve> {
+        None
+    }
+}
+
+impl TaggedLiquiditySource<DEXId, AccountId, AssetId> for MockMCBCPool {
+    const SOURCE_TYPE: LiquiditySourceType = LiquiditySourceType::MulticollateralBondingCurvePool;
+}
+
 impl GetMarketInfo<AssetId> for MockMCBCPool {
     fn buy_price(
         _base_asset: &AssetId,
@@ -587,6 +666,50 @@ impl GetMarketInfo<AssetId> for MockMCBCPool {
     }
 }
 
+thread_local! {
+    static SWAP_CURVES_ENABLED: Cell<bool> = Cell::new(false);
+    static MAX_ROUTING_PAIRS: Cell<usize> = Cell::new(64);
+}
+
+/// Maximum number of trading pairs looked up by the path search, adjustable by the tests.
+pub struct GetMaxRoutingPairs;
+
+impl GetMaxRoutingPairs {
+    pub fn set(value: usize) {
+        MAX_ROUTING_PAIRS.with(|max_pairs| max_pairs.set(value));
+    }
+}
+
+impl Get<usize> for GetMaxRoutingPairs {
+    fn get() -> usize {
+        MAX_ROUTING_PAIRS.with(|max_pairs| max_pairs.get())
+    }
+}
+
+/// Closed-form prices of the liquidity sources, only provided after `MockSwapCurves::enable`
+/// so that trades are split by sampling by default.
+pub struct MockSwapCurves;
+
+impl MockSwapCurves {
+    pub fn enable() {
+        SWAP_CURVES_ENABLED.with(|enabled| enabled.set(true));
+    }
+}
+
+impl GetSwapCurve<LiquiditySourceId<DEXId, LiquiditySourceType>, AssetId> for MockSwapCurves {
+    fn swap_curve(
+        source: &LiquiditySourceId<DEXId, LiquiditySourceType>,
+        input_asset_id: &AssetId,
+        output_asset_id: &AssetId,
+    ) -> Option<SwapCurve> {
+        if SWAP_CURVES_ENABLED.with(|enabled| enabled.get()) {
+            DexApi::swap_curve(source, input_asset_id, output_asset_id)
+        } else {
+            None
+        }
+    }
+}
+
 pub fn get_reference_prices() -> HashMap<AssetId, Balance> {
     let prices = vec![
         (VAL, balance!(2.0)),
@@ -702,6 +825,36 @@ impl ExtBuilder {
         .assimilate_storage(&mut t)
         .unwrap();
 
-        t.into()
+        let enabled_sources = self.enabled_sources;
+        let mut trading_pairs: Vec<(DEXId, common::TradingPair<AssetId>)> = Vec::new();
+        for (dex_id, base_asset_id, target_asset_id, _) in &enabled_sources {
+            let pair = (
+                *dex_id,
+                common::TradingPair {
+                    base_asset_id: *base_asset_id,
+                    target_asset_id: *target_asset_id,
+                },
+            );
+            if !trading_pairs.contains(&pair) {
+                trading_pairs.push(pair);
+            }
+        }
+        trading_pair::GenesisConfig::<Runtime> { trading_pairs }
+            .assimilate_storage(&mut t)
+            .unwrap();
+
+        let mut ext: sp_io::TestExternalities = t.into();
+        ext.execute_with(|| {
+            for (dex_id, base_asset_id, target_asset_id, source_type) in enabled_sources {
+                trading_pair::Pallet::<Runtime>::enable_source_for_trading_pair(
+                    &dex_id,
+                    &base_asset_id,
+                    &target_asset_id,
+                    source_type,
+                )
+                .unwrap();
+            }
+        });
+        ext
     }
 }
@@ -29,16 +29,21 @@
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 use crate::mock::*;
-use crate::Error;
-use common::prelude::fixnum::ops::CheckedSub;
-use common::prelude::{Balance, SwapAmount};
+use crate::{algo, BatchSwapLeg, Error, ExchangePath, RouteOutcome};
+use common::prelude::fixnum::ops::{CheckedAdd, CheckedSub};
+use common::prelude::{Balance, FixedWrapper, SwapAmount, SwapOutcome};
 use common::{
-    balance, fixed, FilterMode, Fixed, LiquiditySourceFilter, LiquiditySourceId,
-    LiquiditySourceType, DOT, KSM, PSWAP, VAL, XOR,
+    balance, fixed, fixed_wrapper, BasisPoints, FilterMode, Fixed, LiquiditySourceFilter,
+    LiquiditySourceId, LiquiditySourceType, SwapCurve, DOT, KSM, PSWAP, VAL, XOR,
 };
 use core::convert::TryInto;
 use frame_support::assert_noop;
-use sp_runtime::DispatchError;
+use frame_support::dispatch::DispatchResultWithPostInfo;
+use frame_support::storage::{with_transaction, TransactionOutcome};
+use frame_support::traits::Hooks;
+use sp_runtime::{AccountId32, DispatchError};
+use sp_std::collections::btree_map::BTreeMap;
+use traits::MultiCurrency;
 
 #[inline]
 fn mcbc_excluding_filter(dex: DEXId) -> LiquiditySourceFilter<DEXId, LiquiditySourceType> {
@@ -604,6 +609,35 @@ fn test_quote_should_fail_with_unavailable_exchange_path() {
     });
 }
 
+#[test]
+fn test_quote_should_skip_paused_sources() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        let scope = dex_api::PauseScope::SourceType(LiquiditySourceType::MockPool);
+        frame_support::assert_ok!(dex_api::Module::<Runtime>::pause_trading(
+            Origin::root(),
+            scope
+        ));
+        let result = LiquidityProxy::quote_single(
+            &GetBaseAssetId::get(),
+            &DOT,
+            SwapAmount::with_desired_input(balance!(100), 0),
+            LiquiditySourceFilter::with_allowed(DEX_C_ID, [LiquiditySourceType::MockPool].into()),
+        );
+        assert_noop!(result, <Error<Runtime>>::UnavailableExchangePath);
+        let outcome = LiquidityProxy::quote_single(
+            &GetBaseAssetId::get(),
+            &DOT,
+            SwapAmount::with_desired_input(balance!(100), 0),
+            mcbc_excluding_filter(DEX_C_ID),
+        )
+        .expect("Failed to get a quote");
+        assert!(outcome.distribution.iter().all(
+            |(source_id, _)| source_id.liquidity_source_index != LiquiditySourceType::MockPool
+        ));
+    });
+}
+
 #[test]
 fn test_quote_should_fail_with_unavailable_exchange_path_2() {
     let mut ext = ExtBuilder::default().build();
@@ -1335,7 +1369,7 @@ fn test_quote_fast_split_exact_output_target_undercollateralized_should_pass() {
 #[test]
 #[rustfmt::skip]
 fn test_list_enabled_sources_for_path_query_should_pass_1() {
-    let mut ext = ExtBuilder::default().build();
+    let mut ext = ExtBuilder { enabled_sources: Vec::new(), ..Default::default() }.build();
     ext.execute_with(|| {
         use LiquiditySourceType::*;
         TradingPair::register(Origin::signed(alice()), 0, XOR, VAL).expect("failed to register pair");
@@ -1359,7 +1393,7 @@ fn test_list_enabled_sources_for_path_query_should_pass_1() {
 #[test]
 #[rustfmt::skip]
 fn test_list_enabled_sources_for_path_query_should_pass_2() {
-    let mut ext = ExtBuilder::default().build();
+    let mut ext = ExtBuilder { enabled_sources: Vec::new(), ..Default::default() }.build();
     ext.execute_with(|| {
         use LiquiditySourceType::*;
         TradingPair::register(Origin::signed(alice()), 0, XOR, VAL).expect("failed to register pair");
@@ -1384,7 +1418,7 @@ fn test_list_enabled_sources_for_path_query_should_pass_2() {
 #[test]
 #[rustfmt::skip]
 fn test_list_enabled_sources_for_path_query_should_pass_3() {
-    let mut ext = ExtBuilder::default().build();
+    let mut ext = ExtBuilder { enabled_sources: Vec::new(), ..Default::default() }.build();
     ext.execute_with(|| {
         use LiquiditySourceType::*;
         TradingPair::register(Origin::signed(alice()), 0, XOR, VAL).expect("failed to register pair");
@@ -1410,7 +1444,7 @@ fn test_list_enabled_sources_for_path_query_should_pass_3() {
 #[test]
 #[rustfmt::skip]
 fn test_list_enabled_sources_for_path_query_should_pass_4() {
-    let mut ext = ExtBuilder::default().build();
+    let mut ext = ExtBuilder { enabled_sources: Vec::new(), ..Default::default() }.build();
     ext.execute_with(|| {
         use LiquiditySourceType::*;
         TradingPair::register(Origin::signed(alice()), 0, XOR, VAL).expect("failed to register pair");
@@ -1436,10 +1470,34 @@ fn test_list_enabled_sources_for_path_query_should_pass_4() {
     });
 }
 
+#[test]
+#[rustfmt::skip]
+fn test_list_enabled_sources_for_path_should_respect_disabled_sources() {
+    let mut ext = ExtBuilder { enabled_sources: Vec::new(), ..Default::default() }.build();
+    ext.execute_with(|| {
+        use LiquiditySourceType::*;
+        TradingPair::register(Origin::signed(alice()), DEX_A_ID, XOR, VAL).expect("failed to register pair");
+        TradingPair::register(Origin::signed(alice()), DEX_A_ID, XOR, PSWAP).expect("failed to register pair");
+        TradingPair::enable_source_for_trading_pair(&DEX_A_ID, &XOR, &VAL, XYKPool).expect("failed to enable source");
+        TradingPair::enable_source_for_trading_pair(&DEX_A_ID, &XOR, &PSWAP, XYKPool).expect("failed to enable source");
+        TradingPair::enable_source_for_trading_pair(&DEX_A_ID, &XOR, &PSWAP, MockPool3).expect("failed to enable source");
+        assert_eq!(LiquidityProxy::list_enabled_sources_for_path(DEX_A_ID, VAL, PSWAP).unwrap(), vec![XYKPool]);
+        TradingPair::disable_source_for_trading_pair(Origin::signed(alice()), DEX_A_ID, XOR, PSWAP, MockPool3).expect("failed to disable source");
+        assert_eq!(LiquidityProxy::list_enabled_sources_for_path(DEX_A_ID, XOR, PSWAP).unwrap(), vec![XYKPool]);
+        TradingPair::disable_source_for_trading_pair(Origin::signed(alice()), DEX_A_ID, XOR, VAL, XYKPool).expect("failed to disable source");
+        assert_eq!(LiquidityProxy::list_enabled_sources_for_path(DEX_A_ID, XOR, VAL).unwrap_err(), Error::<Runtime>::UnavailableExchangePath.into());
+        assert_eq!(LiquidityProxy::list_enabled_sources_for_path(DEX_A_ID, VAL, PSWAP).unwrap_err(), Error::<Runtime>::UnavailableExchangePath.into());
+        TradingPair::deregister(Origin::signed(alice()), DEX_A_ID, XOR, VAL).expect("failed to deregister pair");
+        assert!(!LiquidityProxy::is_path_available(DEX_A_ID, VAL, PSWAP).unwrap());
+        assert!(!LiquidityProxy::is_path_available(DEX_A_ID, XOR, VAL).unwrap());
+        assert!(LiquidityProxy::is_path_available(DEX_A_ID, XOR, PSWAP).unwrap());
+    });
+}
+
 #[test]
 #[rustfmt::skip]
 fn test_is_path_available_should_pass_1() {
-    let mut ext = ExtBuilder::default().build();
+    let mut ext = ExtBuilder { enabled_sources: Vec::new(), ..Default::default() }.build();
     ext.execute_with(|| {
         use LiquiditySourceType::*;
         TradingPair::register(Origin::signed(alice()), 0, XOR, VAL).expect("failed to register pair");
@@ -1458,7 +1516,7 @@ fn test_is_path_available_should_pass_1() {
 #[test]
 #[rustfmt::skip]
 fn test_is_path_available_should_pass_2() {
-    let mut ext = ExtBuilder::default().build();
+    let mut ext = ExtBuilder { enabled_sources: Vec::new(), ..Default::default() }.build();
     ext.execute_with(|| {
         use LiquiditySourceType::*;
         TradingPair::register(Origin::signed(alice()), 0, XOR, VAL).expect("failed to register pair");
@@ -1476,7 +1534,7 @@ fn test_is_path_available_should_pass_2() {
 #[test]
 #[rustfmt::skip]
 fn test_is_path_available_should_pass_3() {
-    let mut ext = ExtBuilder::default().build();
+    let mut ext = ExtBuilder { enabled_sources: Vec::new(), ..Default::default() }.build();
     ext.execute_with(|| {
         use LiquiditySourceType::*;
         TradingPair::register(Origin::signed(alice()), 0, XOR, VAL).expect("failed to register pair");
@@ -1495,7 +1553,7 @@ fn test_is_path_available_should_pass_3() {
 #[test]
 #[rustfmt::skip]
 fn test_is_path_available_should_pass_4() {
-    let mut ext = ExtBuilder::default().build();
+    let mut ext = ExtBuilder { enabled_sources: Vec::new(), ..Default::default() }.build();
     ext.execute_with(|| {
         use LiquiditySourceType::*;
         TradingPair::register(Origin::signed(alice()), 0, XOR, VAL).expect("failed to register pair");
@@ -1511,3 +1569,1652 @@ fn test_is_path_available_should_pass_4() {
         assert_eq!(LiquidityProxy::is_path_available(0, PSWAP, VAL).unwrap(), true);
     });
 }
+
+#[test]
+#[rustfmt::skip]
+fn test_find_paths_should_pass() {
+    let mut ext = ExtBuilder { enabled_sources: Vec::new(), ..Default::default() }.build();
+    ext.execute_with(|| {
+        use LiquiditySourceType::*;
+        TradingPair::register(Origin::signed(alice()), 0, XOR, VAL).expect("failed to register pair");
+        TradingPair::register(Origin::signed(alice()), 0, XOR, PSWAP).expect("failed to register pair");
+        TradingPair::enable_source_for_trading_pair(&0, &XOR, &VAL, MockPool).expect("failed to enable source");
+        TradingPair::enable_source_for_trading_pair(&0, &XOR, &PSWAP, XYKPool).expect("failed to enable source");
+        let filter = LiquiditySourceFilter::empty(0);
+        assert_eq!(LiquidityProxy::find_paths(XOR, VAL, &filter).unwrap(), vec![ExchangePath(vec![XOR, VAL])]);
+        assert_eq!(LiquidityProxy::find_paths(PSWAP, XOR, &filter).unwrap(), vec![ExchangePath(vec![PSWAP, XOR])]);
+        assert_eq!(LiquidityProxy::find_paths(VAL, PSWAP, &filter).unwrap(), vec![ExchangePath(vec![VAL, XOR, PSWAP])]);
+        assert_eq!(LiquidityProxy::find_paths(PSWAP, VAL, &filter).unwrap(), vec![ExchangePath(vec![PSWAP, XOR, VAL])]);
+        assert_eq!(LiquidityProxy::find_paths(VAL, DOT, &filter).unwrap(), vec![]);
+    });
+}
+
+#[test]
+#[rustfmt::skip]
+fn test_find_paths_should_respect_filter() {
+    let mut ext = ExtBuilder { enabled_sources: Vec::new(), ..Default::default() }.build();
+    ext.execute_with(|| {
+        use LiquiditySourceType::*;
+        TradingPair::register(Origin::signed(alice()), 0, XOR, VAL).expect("failed to register pair");
+        TradingPair::register(Origin::signed(alice()), 0, XOR, PSWAP).expect("failed to register pair");
+        TradingPair::enable_source_for_trading_pair(&0, &XOR, &VAL, MockPool).expect("failed to enable source");
+        TradingPair::enable_source_for_trading_pair(&0, &XOR, &PSWAP, XYKPool).expect("failed to enable source");
+        let filter = LiquiditySourceFilter::with_forbidden(0, vec![XYKPool]);
+        assert_eq!(LiquidityProxy::find_paths(XOR, VAL, &filter).unwrap(), vec![ExchangePath(vec![XOR, VAL])]);
+        assert_eq!(LiquidityProxy::find_paths(XOR, PSWAP, &filter).unwrap(), vec![]);
+        assert_eq!(LiquidityProxy::find_paths(VAL, PSWAP, &filter).unwrap(), vec![]);
+        let filter = LiquiditySourceFilter::with_allowed(0, vec![XYKPool]);
+        assert_eq!(LiquidityProxy::find_paths(XOR, VAL, &filter).unwrap(), vec![]);
+        assert_eq!(LiquidityProxy::find_paths(XOR, PSWAP, &filter).unwrap(), vec![ExchangePath(vec![XOR, PSWAP])]);
+    });
+}
+
+#[test]
+#[rustfmt::skip]
+fn test_find_paths_should_consider_limited_number_of_pairs() {
+    let mut ext = ExtBuilder { enabled_sources: Vec::new(), ..Default::default() }.build();
+    ext.execute_with(|| {
+        use LiquiditySourceType::*;
+        TradingPair::register(Origin::signed(alice()), 0, XOR, VAL).expect("failed to register pair");
+        TradingPair::register(Origin::signed(alice()), 0, XOR, PSWAP).expect("failed to register pair");
+        TradingPair::enable_source_for_trading_pair(&0, &XOR, &VAL, MockPool).expect("failed to enable source");
+        TradingPair::enable_source_for_trading_pair(&0, &XOR, &PSWAP, MockPool).expect("failed to enable source");
+        let filter = LiquiditySourceFilter::empty(0);
+        GetMaxRoutingPairs::set(1);
+        assert_eq!(LiquidityProxy::find_paths(VAL, XOR, &filter).unwrap(), vec![ExchangePath(vec![VAL, XOR])]);
+        // The only pair considered is the one of the input asset, pairs of XOR aren't looked up.
+        assert_eq!(LiquidityProxy::find_paths(VAL, PSWAP, &filter).unwrap(), vec![]);
+        GetMaxRoutingPairs::set(3);
+        assert_eq!(LiquidityProxy::find_paths(VAL, PSWAP, &filter).unwrap(), vec![ExchangePath(vec![VAL, XOR, PSWAP])]);
+    });
+}
+
+#[test]
+fn test_find_paths_should_fail_with_unknown_dex() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        let result = LiquidityProxy::find_paths(XOR, VAL, &LiquiditySourceFilter::empty(42));
+        assert_noop!(result, dex_manager::Error::<Runtime>::DEXDoesNotExist);
+    });
+}
+
+#[test]
+fn test_search_paths_should_pass_through_non_base_assets() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        let mut neighbours = BTreeMap::new();
+        for (asset_a, asset_b) in
+            [(XOR, VAL), (XOR, DOT), (XOR, KSM), (VAL, KSM), (KSM, DOT)].iter()
+        {
+            neighbours
+                .entry(*asset_a)
+                .or_insert_with(Vec::new)
+                .push(*asset_b);
+            neighbours
+                .entry(*asset_b)
+                .or_insert_with(Vec::new)
+                .push(*asset_a);
+        }
+        assert_eq!(
+            LiquidityProxy::search_paths(&neighbours, VAL, DOT, 2),
+            vec![
+                ExchangePath(vec![VAL, XOR, DOT]),
+                ExchangePath(vec![VAL, KSM, DOT]),
+            ]
+        );
+        assert_eq!(
+            LiquidityProxy::search_paths(&neighbours, VAL, DOT, 3),
+            vec![
+                ExchangePath(vec![VAL, XOR, DOT]),
+                ExchangePath(vec![VAL, KSM, DOT]),
+                ExchangePath(vec![VAL, XOR, KSM, DOT]),
+                ExchangePath(vec![VAL, KSM, XOR, DOT]),
+            ]
+        );
+        assert_eq!(
+            LiquidityProxy::search_paths(&neighbours, VAL, DOT, 1),
+            vec![]
+        );
+    });
+}
+
+#[test]
+fn test_rank_paths_should_choose_best_route() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        let amount = SwapAmount::with_desired_input(balance!(100), 0);
+        let filter = LiquiditySourceFilter::empty(DEX_D_ID);
+        let trivial_path = ExchangePath(vec![VAL, XOR, DOT]);
+        // The source exchanges the non-base intermediate asset through the base asset,
+        // so the routes through it take more exchanges and get a worse price than the trivial path.
+        let paths = vec![
+            ExchangePath(vec![VAL, KSM, XOR, DOT]),
+            ExchangePath(vec![VAL, KSM, DOT]),
+            trivial_path.clone(),
+        ];
+        let routes =
+            LiquidityProxy::rank_paths(paths.clone(), &trivial_path, amount, filter.clone())
+                .expect("Failed to rank paths");
+        assert_eq!(routes.len(), 3);
+        assert_eq!(routes[0].0, trivial_path);
+        for (path, outcome) in routes.iter() {
+            assert!(paths.contains(path));
+            let quote = LiquidityProxy::quote_path(path.clone(), amount, filter.clone())
+                .expect("Failed to quote path");
+            assert_eq!(*outcome, quote);
+        }
+        assert!(routes[0].1.amount > routes[1].1.amount);
+        assert!(routes[1].1.amount >= routes[2].1.amount);
+
+        let routes = LiquidityProxy::rank_paths(
+            vec![
+                ExchangePath(vec![VAL, KSM, DOT]),
+                ExchangePath(vec![VAL, PSWAP, DOT]),
+            ],
+            &trivial_path,
+            amount,
+            filter,
+        )
+        .expect("Failed to rank paths");
+        assert_eq!(routes.len(), 1);
+        assert_eq!(routes[0].0, ExchangePath(vec![VAL, KSM, DOT]));
+    });
+}
+
+#[test]
+fn test_quote_routes_should_pass() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        let amount = SwapAmount::with_desired_input(balance!(100), 0);
+        let filter = LiquiditySourceFilter::empty(DEX_D_ID);
+        let routes = LiquidityProxy::quote_routes(&VAL, &DOT, amount, filter.clone())
+            .expect("Failed to quote routes");
+        let quote = LiquidityProxy::quote(&VAL, &DOT, amount, filter).expect("Failed to quote");
+        assert_eq!(routes.len(), 1);
+        assert_eq!(routes[0].0, ExchangePath(vec![VAL, XOR, DOT]));
+        assert_eq!(routes[0].1, SwapOutcome::new(quote.amount, quote.fee));
+    });
+}
+
+#[test]
+fn test_exchange_should_follow_quoted_path() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        let alice = alice();
+        let amount = SwapAmount::with_desired_input(balance!(100), 0);
+        let filter = LiquiditySourceFilter::empty(DEX_D_ID);
+        let quote =
+            LiquidityProxy::quote(&VAL, &DOT, amount, filter.clone()).expect("Failed to quote");
+        let result = LiquidityProxy::exchange(&alice, &alice, &VAL, &DOT, amount, filter)
+            .expect("Failed to swap assets");
+        assert_eq!(result, quote);
+    });
+}
+
+#[test]
+fn test_exchange_should_fail_with_frozen_account() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        let alice = alice();
+        let amount = SwapAmount::with_desired_input(balance!(100), 0);
+        let filter = LiquiditySourceFilter::empty(DEX_D_ID);
+        Assets::freeze_account(Origin::signed(alice.clone()), VAL, alice.clone())
+            .expect("Failed to freeze account");
+        assert_noop!(
+            LiquidityProxy::exchange(&alice, &alice, &VAL, &DOT, amount, filter.clone()),
+            assets::Error::<Runtime>::AccountIsFrozen
+        );
+        Assets::thaw_account(Origin::signed(alice.clone()), VAL, alice.clone())
+            .expect("Failed to thaw account");
+        assert!(LiquidityProxy::exchange(&alice, &alice, &VAL, &DOT, amount, filter).is_ok());
+    });
+}
+
+#[test]
+fn test_swap_should_fail_with_source_disabled_for_trading_pair() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        System::set_block_number(1);
+        let swap = || {
+            LiquidityProxy::swap(
+                Origin::signed(alice()),
+                DEX_A_ID,
+                DOT,
+                XOR,
+                SwapAmount::with_desired_input(balance!(100), 0),
+                [LiquiditySourceType::MockPool].into(),
+                FilterMode::AllowSelected,
+            )
+        };
+        swap().expect("Failed to swap assets");
+        TradingPair::disable_source_for_trading_pair(
+            Origin::signed(alice()),
+            DEX_A_ID,
+            XOR,
+            DOT,
+            LiquiditySourceType::MockPool,
+        )
+        .expect("failed to disable source");
+        assert_noop!(swap(), Error::<Runtime>::UnavailableExchangePath);
+    });
+}
+
+#[test]
+fn test_swap_should_fail_with_deregistered_trading_pair() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        System::set_block_number(1);
+        let swap = || {
+            LiquidityProxy::swap(
+                Origin::signed(alice()),
+                DEX_A_ID,
+                DOT,
+                XOR,
+                SwapAmount::with_desired_input(balance!(100), 0),
+                [LiquiditySourceType::MulticollateralBondingCurvePool].into(),
+                FilterMode::ForbidSelected,
+            )
+        };
+        swap().expect("Failed to swap assets");
+        for source_type in [
+            LiquiditySourceType::MockPool,
+            LiquiditySourceType::MockPool2,
+            LiquiditySourceType::MockPool3,
+            LiquiditySourceType::MockPool4,
+        ]
+        .iter()
+        {
+            TradingPair::disable_source_for_trading_pair(
+                Origin::signed(alice()),
+                DEX_A_ID,
+                XOR,
+                DOT,
+                *source_type,
+            )
+            .expect("failed to disable source");
+        }
+        TradingPair::deregister(Origin::signed(alice()), DEX_A_ID, XOR, DOT)
+            .expect("failed to deregister pair");
+        assert_noop!(swap(), Error::<Runtime>::UnavailableExchangePath);
+        // The sources keep trading the pairs which are still registered.
+        LiquidityProxy::swap(
+            Origin::signed(alice()),
+            DEX_A_ID,
+            KSM,
+            XOR,
+            SwapAmount::with_desired_input(balance!(100), 0),
+            [LiquiditySourceType::MulticollateralBondingCurvePool].into(),
+            FilterMode::ForbidSelected,
+        )
+        .expect("Failed to swap assets");
+    });
+}
+
+#[test]
+fn test_exchange_should_update_pair_stats() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        System::set_block_number(1);
+        let sources = [
+            LiquiditySourceType::MockPool,
+            LiquiditySourceType::MockPool2,
+            LiquiditySourceType::MockPool3,
+            LiquiditySourceType::MockPool4,
+        ];
+        let result = LiquidityProxy::exchange(
+            &alice(),
+            &alice(),
+            &KSM,
+            &DOT,
+            SwapAmount::with_desired_input(balance!(100), 0),
+            mcbc_excluding_filter(DEX_A_ID),
+        )
+        .expect("Failed to swap assets");
+
+        let total_stats = |asset_id| {
+            sources
+                .iter()
+                .fold((0, 0, 0), |(base, target, count), source| {
+                    let stats = TradingPair::get_pair_stats(&DEX_A_ID, &XOR, &asset_id, *source);
+                    (
+                        base + stats.base_volume,
+                        target + stats.target_volume,
+                        count + stats.trade_count,
+                    )
+                })
+        };
+        let (ksm_base_volume, ksm_volume, ksm_trades) = total_stats(KSM);
+        let (dot_base_volume, dot_volume, dot_trades) = total_stats(DOT);
+        // Amounts may be off by rounding due to the split between the sources.
+        let abs_diff = |a: Balance, b: Balance| a.max(b) - a.min(b);
+        assert!(abs_diff(ksm_volume, balance!(100)) < balance!(0.000001));
+        assert!(abs_diff(dot_volume, result.amount) < balance!(0.000001));
+        assert!(ksm_base_volume > 0);
+        assert!(dot_base_volume > 0);
+        assert!(ksm_trades > 0);
+        assert!(dot_trades > 0);
+    });
+}
+
+#[test]
+fn test_quote_routes_should_fail_with_identical_assets() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        let result = LiquidityProxy::quote_routes(
+            &VAL,
+            &VAL,
+            SwapAmount::with_desired_input(balance!(100), 0),
+            LiquiditySourceFilter::empty(DEX_D_ID),
+        );
+        assert_noop!(result, Error::<Runtime>::UnavailableExchangePath);
+    });
+}
+
+#[test]
+fn test_quote_single_route_should_pass() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        let quote = LiquidityProxy::quote(
+            &KSM,
+            &DOT,
+            SwapAmount::with_desired_input(balance!(100), 0),
+            LiquiditySourceFilter::empty(DEX_A_ID),
+        )
+        .expect("Failed to get a quote");
+        assert_eq!(quote.amount, balance!(934.572151021276260545));
+        assert_eq!(quote.fee, balance!(2.318181818181818181));
+        assert_eq!(
+            quote.routes,
+            vec![RouteOutcome {
+                path: ExchangePath(vec![KSM, XOR, DOT]),
+                share: fixed!(1),
+                input_amount: balance!(100),
+                output_amount: balance!(934.572151021276260545),
+                fee: balance!(2.318181818181818181),
+            }]
+        );
+    });
+}
+
+#[test]
+fn test_split_routes_should_skip_unavailable_path() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        let quote = LiquidityProxy::split_routes(
+            vec![
+                ExchangePath(vec![KSM, DOT]),
+                ExchangePath(vec![KSM, XOR, DOT]),
+            ],
+            SwapAmount::with_desired_input(balance!(100), 0),
+            LiquiditySourceFilter::empty(DEX_A_ID),
+        )
+        .expect("Failed to split the trade");
+        assert_eq!(quote.amount, balance!(934.572151021276260545));
+        assert_eq!(quote.routes.len(), 1);
+        assert_eq!(quote.routes[0].path, ExchangePath(vec![KSM, XOR, DOT]));
+        assert_eq!(quote.routes[0].share, fixed!(1));
+    });
+}
+
+#[test]
+fn test_split_routes_should_pass() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        let amount = balance!(1000);
+        let filter = LiquiditySourceFilter::empty(DEX_A_ID);
+        let quote = LiquidityProxy::split_routes(
+            vec![
+                ExchangePath(vec![KSM, XOR, DOT]),
+                ExchangePath(vec![KSM, DOT]),
+            ],
+            SwapAmount::with_desired_input(amount, 0),
+            filter.clone(),
+        )
+        .expect("Failed to split the trade");
+        assert_eq!(quote.routes.len(), 2);
+        assert!(quote.routes[0].share > fixed!(0));
+        assert!(quote.routes[1].share > fixed!(0));
+        assert_eq!(
+            quote.routes[0].share.cadd(quote.routes[1].share).unwrap(),
+            fixed!(1)
+        );
+        assert_eq!(
+            quote.routes[0].input_amount + quote.routes[1].input_amount,
+            amount
+        );
+        assert_eq!(
+            quote.amount,
+            quote.routes[0].output_amount + quote.routes[1].output_amount
+        );
+        for route in quote.routes {
+            let route_quote = LiquidityProxy::quote_path(
+                route.path,
+                SwapAmount::with_desired_input(route.input_amount, 0),
+                filter.clone(),
+            )
+            .expect("Failed to quote path");
+            assert_eq!(route_quote.amount, route.output_amount);
+        }
+    });
+}
+
+#[test]
+fn test_split_routes_should_fail_with_overlapping_routes() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        let path = ExchangePath(vec![KSM, XOR, DOT]);
+        let result = LiquidityProxy::split_routes(
+            vec![path.clone(), path],
+            SwapAmount::with_desired_input(balance!(1000), 0),
+            LiquiditySourceFilter::empty(DEX_A_ID),
+        );
+        assert_noop!(result, Error::<Runtime>::OverlappingRoutes);
+        let result = LiquidityProxy::split_routes(
+            vec![
+                ExchangePath(vec![KSM, XOR, DOT]),
+                ExchangePath(vec![KSM, VAL, XOR, DOT]),
+            ],
+            SwapAmount::with_desired_input(balance!(1000), 0),
+            LiquiditySourceFilter::empty(DEX_A_ID),
+        );
+        assert_noop!(result, Error::<Runtime>::OverlappingRoutes);
+    });
+}
+
+/// Builds the environment with trading pairs of VAL and DOT with both XOR and KSM in DEX D,
+/// so that the trade between VAL and DOT can go through either of them without sharing a pool.
+fn parallel_routes_ext() -> sp_io::TestExternalities {
+    let mut enabled_sources: Vec<_> = ExtBuilder::default()
+        .enabled_sources
+        .into_iter()
+        .filter(|(dex_id, ..)| *dex_id != DEX_D_ID)
+        .collect();
+    for base_asset_id in [XOR, KSM].iter() {
+        for target_asset_id in [VAL, DOT].iter() {
+            enabled_sources.push((
+                DEX_D_ID,
+                *base_asset_id,
+                *target_asset_id,
+                LiquiditySourceType::MockPool,
+            ));
+        }
+    }
+    ExtBuilder {
+        enabled_sources,
+        ..Default::default()
+    }
+    .build()
+}
+
+#[test]
+fn test_quote_should_split_trade_across_parallel_routes() {
+    let mut ext = parallel_routes_ext();
+    ext.execute_with(|| {
+        let quote = LiquidityProxy::quote(
+            &VAL,
+            &DOT,
+            SwapAmount::with_desired_input(balance!(1000), 0),
+            LiquiditySourceFilter::empty(DEX_D_ID),
+        )
+        .expect("Failed to get a quote");
+        let paths: Vec<_> = quote
+            .routes
+            .iter()
+            .map(|route| route.path.clone())
+            .collect();
+        assert_eq!(
+            paths,
+            vec![
+                ExchangePath(vec![VAL, XOR, DOT]),
+                ExchangePath(vec![VAL, KSM, DOT]),
+            ]
+        );
+    });
+}
+
+#[test]
+fn test_swap_should_split_trade_across_parallel_routes() {
+    let mut ext = parallel_routes_ext();
+    ext.execute_with(|| {
+        System::set_block_number(1);
+        let amount = SwapAmount::with_desired_input(balance!(1000), balance!(1));
+        let filter = LiquiditySourceFilter::empty(DEX_D_ID);
+        let quote =
+            LiquidityProxy::quote(&VAL, &DOT, amount, filter).expect("Failed to get a quote");
+        assert_eq!(quote.routes.len(), 2);
+        LiquidityProxy::swap(
+            Origin::signed(alice()),
+            DEX_D_ID,
+            VAL,
+            DOT,
+            amount,
+            Vec::new(),
+            FilterMode::Disabled,
+        )
+        .expect("Failed to swap assets");
+        assert!(System::events().iter().any(|record| record.event
+            == crate::Event::Exchange(
+                alice(),
+                alice(),
+                DEX_D_ID,
+                VAL,
+                DOT,
+                balance!(1000),
+                quote.amount,
+                quote.fee,
+                None,
+                0,
+            )
+            .into()));
+    });
+}
+
+#[test]
+fn test_exchange_should_fail_with_slippage_across_routes() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        let result = LiquidityProxy::exchange(
+            &alice(),
+            &alice(),
+            &KSM,
+            &DOT,
+            SwapAmount::with_desired_input(balance!(100), balance!(935)),
+            LiquiditySourceFilter::empty(DEX_A_ID),
+        );
+        assert_noop!(result, Error::<Runtime>::SlippageNotTolerated);
+    });
+}
+
+#[test]
+fn test_swap_with_options_should_pass() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        System::set_block_number(1);
+        let referrer = AccountId32::from([2u8; 32]);
+        let receiver = AccountId32::from([3u8; 32]);
+        let fee_account = AccountId32::from([4u8; 32]);
+        let amount = SwapAmount::with_desired_input(balance!(500), balance!(300));
+        MockLiquiditySource::set_fee_account_id(fee_account.clone());
+        MockLiquiditySource2::set_fee_account_id(fee_account.clone());
+        MockLiquiditySource3::set_fee_account_id(fee_account.clone());
+        MockLiquiditySource4::set_fee_account_id(fee_account.clone());
+        LiquidityProxy::set_referrer_reward_rate(Origin::root(), 5000)
+            .expect("Failed to set referrer reward rate");
+        let quote = LiquidityProxy::quote(&DOT, &XOR, amount, mcbc_excluding_filter(DEX_C_ID))
+            .expect("Failed to get a quote");
+        // Mock sources don't move funds, so the fee is credited as if it has been collected.
+        Currencies::deposit(XOR, &fee_account, quote.fee).unwrap();
+        LiquidityProxy::swap_with_options(
+            Origin::signed(alice()),
+            Some(receiver.clone()),
+            DEX_C_ID,
+            DOT,
+            XOR,
+            amount,
+            [LiquiditySourceType::MulticollateralBondingCurvePool].into(),
+            FilterMode::ForbidSelected,
+            1,
+            Some(referrer.clone()),
+            None,
+        )
+        .expect("Failed to swap assets");
+        let referrer_reward = Assets::free_balance(&XOR, &referrer).unwrap();
+        let expected_reward: Balance = (FixedWrapper::from(quote.fee) * fixed_wrapper!(0.5))
+            .try_into_balance()
+            .unwrap();
+        assert!(referrer_reward > 0);
+        assert!(expected_reward - referrer_reward <= 4);
+        assert_eq!(Assets::free_balance(&XOR, &alice()).unwrap(), 0);
+        assert_eq!(
+            Assets::free_balance(&XOR, &fee_account).unwrap(),
+            quote.fee - referrer_reward
+        );
+        assert!(System::events().iter().any(|record| record.event
+            == crate::Event::Exchange(
+                alice(),
+                receiver.clone(),
+                DEX_C_ID,
+                DOT,
+                XOR,
+                balance!(500),
+                quote.amount,
+                quote.fee,
+                Some(referrer.clone()),
+                referrer_reward,
+            )
+            .into()));
+    });
+}
+
+#[test]
+fn test_swap_with_options_should_skip_reward_of_frozen_referrer() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        System::set_block_number(1);
+        let referrer = AccountId32::from([2u8; 32]);
+        let fee_account = AccountId32::from([4u8; 32]);
+        let amount = SwapAmount::with_desired_input(balance!(100), 0);
+        let filter =
+            LiquiditySourceFilter::with_allowed(DEX_C_ID, [LiquiditySourceType::MockPool].into());
+        MockLiquiditySource::set_fee_account_id(fee_account.clone());
+        LiquidityProxy::set_referrer_reward_rate(Origin::root(), 5000)
+            .expect("Failed to set referrer reward rate");
+        Assets::freeze_account(Origin::signed(alice()), XOR, referrer.clone())
+            .expect("Failed to freeze account");
+        let quote =
+            LiquidityProxy::quote(&DOT, &XOR, amount, filter).expect("Failed to get a quote");
+        Currencies::deposit(XOR, &fee_account, quote.fee).unwrap();
+        LiquidityProxy::swap_with_options(
+            Origin::signed(alice()),
+            None,
+            DEX_C_ID,
+            DOT,
+            XOR,
+            amount,
+            [LiquiditySourceType::MockPool].into(),
+            FilterMode::AllowSelected,
+            1,
+            Some(referrer.clone()),
+            None,
+        )
+        .expect("Failed to swap assets");
+        let reward: Balance = (FixedWrapper::from(quote.fee) * fixed_wrapper!(0.5))
+            .try_into_balance()
+            .unwrap();
+        assert!(reward > 0);
+        assert_eq!(Assets::free_balance(&XOR, &referrer).unwrap(), 0);
+        assert_eq!(Assets::free_balance(&XOR, &fee_account).unwrap(), quote.fee);
+        assert!(System::events().iter().any(|record| record.event
+            == crate::Event::ReferrerRewardFailed(
+                referrer.clone(),
+                reward,
+                assets::Error::<Runtime>::AccountIsFrozen.into(),
+            )
+            .into()));
+        assert!(System::events().iter().any(|record| record.event
+            == crate::Event::Exchange(
+                alice(),
+                alice(),
+                DEX_C_ID,
+                DOT,
+                XOR,
+                balance!(100),
+                quote.amount,
+                quote.fee,
+                Some(referrer.clone()),
+                0,
+            )
+            .into()));
+    });
+}
+
+#[test]
+fn test_swap_with_options_should_fail_with_self_referral() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        let result = LiquidityProxy::swap_with_options(
+            Origin::signed(alice()),
+            None,
+            DEX_C_ID,
+            DOT,
+            XOR,
+            SwapAmount::with_desired_input(balance!(500), balance!(300)),
+            Vec::new(),
+            FilterMode::Disabled,
+            1,
+            Some(alice()),
+            None,
+        );
+        assert_noop!(result, Error::<Runtime>::InvalidReferrer);
+    });
+}
+
+#[test]
+fn test_swap_with_options_should_fail_after_deadline() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        System::set_block_number(10);
+        let result = LiquidityProxy::swap_with_options(
+            Origin::signed(alice()),
+            None,
+            DEX_C_ID,
+            DOT,
+            XOR,
+            SwapAmount::with_desired_input(balance!(500), balance!(300)),
+            Vec::new(),
+            FilterMode::Disabled,
+            9,
+            None,
+            None,
+        );
+        assert_noop!(result, Error::<Runtime>::DeadlineExpired);
+    });
+}
+
+#[test]
+fn test_set_referrer_reward_rate_should_fail_with_invalid_rate() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        assert_noop!(
+            LiquidityProxy::set_referrer_reward_rate(Origin::root(), 10001),
+            Error::<Runtime>::InvalidFeeValue
+        );
+        assert_noop!(
+            LiquidityProxy::set_referrer_reward_rate(Origin::signed(alice()), 100),
+            DispatchError::BadOrigin
+        );
+    });
+}
+
+fn price_impact_of(amount: SwapAmount<Balance>) -> BasisPoints {
+    with_transaction(|| {
+        let spot_price =
+            LiquidityProxy::spot_price(&DEX_D_ID, &XOR, &DOT).expect("Failed to get spot price");
+        LiquidityProxy::exchange(
+            &alice(),
+            &alice(),
+            &XOR,
+            &DOT,
+            amount,
+            LiquiditySourceFilter::with_allowed(DEX_D_ID, [LiquiditySourceType::MockPool].into()),
+        )
+        .expect("Failed to swap assets");
+        let spot_price_after =
+            LiquidityProxy::spot_price(&DEX_D_ID, &XOR, &DOT).expect("Failed to get spot price");
+        TransactionOutcome::Rollback(
+            LiquidityProxy::price_impact(spot_price, spot_price_after).unwrap(),
+        )
+    })
+}
+
+fn swap_with_price_impact(
+    amount: SwapAmount<Balance>,
+    max_price_impact: Option<BasisPoints>,
+) -> DispatchResultWithPostInfo {
+    LiquidityProxy::swap_with_options(
+        Origin::signed(alice()),
+        None,
+        DEX_D_ID,
+        XOR,
+        DOT,
+        amount,
+        [LiquiditySourceType::MockPool].into(),
+        FilterMode::AllowSelected,
+        1,
+        None,
+        max_price_impact,
+    )
+}
+
+#[test]
+fn test_swap_should_fail_with_price_impact_not_tolerated() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        System::set_block_number(1);
+        MockLiquiditySource::enable_reserves_movement();
+        let amount = SwapAmount::with_desired_input(balance!(500), 0);
+        let price_impact = price_impact_of(amount);
+        assert!(price_impact > 0);
+        assert_noop!(
+            swap_with_price_impact(amount, Some(price_impact - 1)),
+            Error::<Runtime>::PriceImpactNotTolerated
+        );
+        swap_with_price_impact(amount, Some(price_impact)).expect("Failed to swap assets");
+    });
+}
+
+#[test]
+fn test_swap_should_fail_with_price_impact_limit_exceeded() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        System::set_block_number(1);
+        MockLiquiditySource::enable_reserves_movement();
+        let amount = SwapAmount::with_desired_input(balance!(500), 0);
+        let price_impact = price_impact_of(amount);
+        LiquidityProxy::set_max_price_impact(Origin::root(), Some(price_impact - 1))
+            .expect("Failed to set price impact limit");
+        assert_eq!(LiquidityProxy::max_price_impact(), Some(price_impact - 1));
+        assert!(System::events().iter().any(|record| record.event
+            == crate::Event::MaxPriceImpactChanged(Some(price_impact - 1)).into()));
+        assert_noop!(
+            swap_with_price_impact(amount, None),
+            Error::<Runtime>::PriceImpactLimitExceeded
+        );
+        let small_amount = SwapAmount::with_desired_input(balance!(1), 0);
+        assert!(price_impact_of(small_amount) < price_impact - 1);
+        swap_with_price_impact(small_amount, None).expect("Failed to swap assets");
+        LiquidityProxy::set_max_price_impact(Origin::root(), None)
+            .expect("Failed to lift price impact limit");
+        swap_with_price_impact(amount, None).expect("Failed to swap assets");
+    });
+}
+
+#[test]
+fn test_spot_price_should_use_reserves_of_dex() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        // the reserves ratios are above the primary market price in both DEXes
+        assert_eq!(
+            LiquidityProxy::spot_price(&DEX_A_ID, &DOT, &XOR),
+            Some(
+                (FixedWrapper::from(balance!(5000)) / balance!(7000))
+                    .get()
+                    .unwrap()
+            )
+        );
+        assert_eq!(
+            LiquidityProxy::spot_price(&DEX_D_ID, &DOT, &XOR),
+            Some(
+                (FixedWrapper::from(balance!(1000)) / balance!(9000))
+                    .get()
+                    .unwrap()
+            )
+        );
+    });
+}
+
+#[test]
+fn test_spot_price_should_be_unavailable_in_unknown_dex() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        assert_eq!(LiquidityProxy::spot_price(&42, &DOT, &XOR), None);
+        assert_noop!(
+            LiquidityProxy::dex_base_asset_id(&42),
+            dex_manager::Error::<Runtime>::DEXDoesNotExist
+        );
+    });
+}
+
+#[test]
+fn test_set_max_price_impact_should_fail_with_invalid_value() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        assert_noop!(
+            LiquidityProxy::set_max_price_impact(Origin::root(), Some(10001)),
+            Error::<Runtime>::InvalidPriceImpactValue
+        );
+        assert_noop!(
+            LiquidityProxy::set_max_price_impact(Origin::signed(alice()), Some(100)),
+            DispatchError::BadOrigin
+        );
+    });
+}
+
+#[test]
+fn test_simulate_swap_should_pass() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        System::set_block_number(1);
+        let filter =
+            LiquiditySourceFilter::with_allowed(DEX_D_ID, [LiquiditySourceType::MockPool].into());
+        let amount = SwapAmount::with_desired_input(balance!(500), 0);
+        let quote = LiquidityProxy::quote(&XOR, &DOT, amount, filter.clone())
+            .expect("Failed to get a quote");
+        let simulation =
+            LiquidityProxy::simulate_swap(&alice(), &XOR, &DOT, amount, filter.clone());
+        assert_eq!(
+            simulation.result,
+            Ok(SwapOutcome::new(quote.amount, quote.fee))
+        );
+        // The mock liquidity sources don't move funds.
+        assert!(simulation.balance_changes.is_empty());
+
+        let amount = SwapAmount::with_desired_input(balance!(500), quote.amount + 1);
+        let simulation = LiquidityProxy::simulate_swap(&alice(), &XOR, &DOT, amount, filter);
+        assert_eq!(
+            simulation.result,
+            Err(Error::<Runtime>::SlippageNotTolerated.into())
+        );
+        assert!(simulation.balance_changes.is_empty());
+        assert!(simulation.events.is_empty());
+    });
+}
+
+#[test]
+fn test_quote_breakdown_single_swap_should_pass() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        let amount = SwapAmount::with_desired_input(balance!(500), 0);
+        let filter = mcbc_excluding_filter(DEX_C_ID);
+        let quote =
+            LiquidityProxy::quote_single(&GetBaseAssetId::get(), &DOT, amount, filter.clone())
+                .expect("Failed to get a quote");
+        let breakdown =
+            LiquidityProxy::quote_breakdown(&GetBaseAssetId::get(), &DOT, amount, filter)
+                .expect("Failed to get a quote breakdown");
+        assert_eq!(breakdown.amount, quote.amount);
+        assert_eq!(breakdown.fee, quote.fee);
+        assert_eq!(breakdown.routes.len(), 1);
+        assert_eq!(breakdown.routes[0].path, ExchangePath(vec![XOR, DOT]));
+        assert_eq!(breakdown.routes[0].swaps.len(), 1);
+        let swap = &breakdown.routes[0].swaps[0];
+        assert_eq!(swap.input_amount, balance!(500));
+        assert_eq!(swap.output_amount, quote.amount);
+        assert_eq!(swap.fee, quote.fee);
+        assert_eq!(swap.fee_in_input_asset, quote.fee);
+        let mut distribution: Vec<_> = quote
+            .distribution
+            .into_iter()
+            .filter(|(_, share)| *share > fixed!(0))
+            .collect();
+        distribution.sort();
+        let mut sources: Vec<_> = swap
+            .sources
+            .iter()
+            .map(|source| (source.source.clone(), source.share))
+            .collect();
+        sources.sort();
+        assert_eq!(sources, distribution);
+        assert_eq!(
+            swap.sources
+                .iter()
+                .map(|source| source.input_amount)
+                .sum::<Balance>(),
+            balance!(500)
+        );
+        assert!(swap.effective_price < swap.spot_price);
+        assert!(swap.price_impact > 0);
+    });
+}
+
+#[test]
+fn test_quote_breakdown_poly_swap_should_pass() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        let amount = SwapAmount::with_desired_input(balance!(100), 0);
+        let filter = LiquiditySourceFilter::empty(DEX_A_ID);
+        let breakdown = LiquidityProxy::quote_breakdown(&KSM, &DOT, amount, filter)
+            .expect("Failed to get a quote breakdown");
+        assert_eq!(breakdown.amount, balance!(934.572151021276260545));
+        assert_eq!(breakdown.fee, balance!(2.318181818181818181));
+        assert_eq!(breakdown.routes.len(), 1);
+        let swaps = &breakdown.routes[0].swaps;
+        assert_eq!(swaps.len(), 2);
+        assert_eq!(
+            (swaps[0].input_asset_id, swaps[0].output_asset_id),
+            (KSM, XOR)
+        );
+        assert_eq!(
+            (swaps[1].input_asset_id, swaps[1].output_asset_id),
+            (XOR, DOT)
+        );
+        assert_eq!(swaps[0].input_amount, balance!(100));
+        assert_eq!(swaps[0].output_amount, swaps[1].input_amount);
+        assert_eq!(swaps[1].output_amount, breakdown.amount);
+        assert_eq!(swaps[0].fee + swaps[1].fee, breakdown.fee);
+        assert_eq!(swaps[1].fee_in_input_asset, swaps[1].fee);
+    });
+}
+
+#[test]
+fn test_quote_breakdown_should_take_spot_price_from_source_curves() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        let amount = SwapAmount::with_desired_input(balance!(100), 0);
+        let filter = LiquiditySourceFilter::empty(DEX_D_ID);
+        let breakdown = LiquidityProxy::quote_breakdown(&XOR, &DOT, amount, filter)
+            .expect("Failed to get a quote breakdown");
+        let swap = &breakdown.routes[0].swaps[0];
+        assert_eq!(swap.spot_price, fixed!(9));
+        assert!(swap.effective_price < swap.spot_price);
+        assert_eq!(
+            swap.price_impact,
+            LiquidityProxy::price_impact(swap.spot_price, swap.effective_price).unwrap()
+        );
+    });
+}
+
+fn batch_swap_leg(
+    input_asset_id: AssetId,
+    output_asset_id: AssetId,
+    swap_amount: SwapAmount<Balance>,
+) -> BatchSwapLeg<AssetId> {
+    BatchSwapLeg {
+        input_asset_id,
+        output_asset_id,
+        swap_amount,
+        selected_source_types: [LiquiditySourceType::MulticollateralBondingCurvePool].into(),
+        filter_mode: FilterMode::ForbidSelected,
+    }
+}
+
+#[test]
+fn test_swap_batch_should_pass() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        System::set_block_number(1);
+        let legs = vec![
+            batch_swap_leg(
+                DOT,
+                XOR,
+                SwapAmount::with_desired_input(balance!(500), balance!(300)),
+            ),
+            batch_swap_leg(
+                XOR,
+                DOT,
+                SwapAmount::with_desired_input(balance!(500), balance!(510)),
+            ),
+        ];
+        let quotes: Vec<_> = legs
+            .iter()
+            .map(|leg| {
+                LiquidityProxy::quote(
+                    &leg.input_asset_id,
+                    &leg.output_asset_id,
+                    leg.swap_amount,
+                    mcbc_excluding_filter(DEX_C_ID),
+                )
+                .expect("Failed to get a quote")
+            })
+            .collect();
+        LiquidityProxy::swap_batch(Origin::signed(alice()), DEX_C_ID, legs.clone(), true)
+            .expect("Failed to swap assets");
+        for (leg, quote) in legs.into_iter().zip(quotes.into_iter()) {
+            assert!(System::events().iter().any(|record| record.event
+                == crate::Event::Exchange(
+                    alice(),
+                    alice(),
+                    DEX_C_ID,
+                    leg.input_asset_id,
+                    leg.output_asset_id,
+                    leg.swap_amount.amount(),
+                    quote.amount,
+                    quote.fee,
+                    None,
+                    0,
+                )
+                .into()));
+        }
+        assert!(System::events().iter().any(|record| record.event
+            == crate::Event::BatchSwapExecuted(alice(), DEX_C_ID, 2, 2).into()));
+    });
+}
+
+#[test]
+fn test_swap_batch_all_or_nothing_should_fail() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        let result = LiquidityProxy::swap_batch(
+            Origin::signed(alice()),
+            DEX_C_ID,
+            vec![
+                batch_swap_leg(
+                    DOT,
+                    XOR,
+                    SwapAmount::with_desired_input(balance!(500), balance!(300)),
+                ),
+                batch_swap_leg(XOR, KSM, SwapAmount::with_desired_input(balance!(500), 0)),
+            ],
+            true,
+        );
+        assert_noop!(result, Error::<Runtime>::UnavailableExchangePath);
+    });
+}
+
+#[test]
+fn test_swap_batch_best_effort_should_skip_failed_legs() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        System::set_block_number(1);
+        LiquidityProxy::swap_batch(
+            Origin::signed(alice()),
+            DEX_C_ID,
+            vec![
+                batch_swap_leg(
+                    DOT,
+                    XOR,
+                    SwapAmount::with_desired_input(balance!(500), balance!(300)),
+                ),
+                batch_swap_leg(XOR, KSM, SwapAmount::with_desired_input(balance!(500), 0)),
+            ],
+            false,
+        )
+        .expect("Failed to swap assets");
+        assert!(System::events().iter().any(|record| record.event
+            == crate::Event::BatchSwapLegFailed(
+                alice(),
+                1,
+                Error::<Runtime>::UnavailableExchangePath.into()
+            )
+            .into()));
+        assert!(System::events().iter().any(|record| record.event
+            == crate::Event::BatchSwapExecuted(alice(), DEX_C_ID, 2, 1).into()));
+    });
+}
+
+#[test]
+fn test_swap_batch_should_fail_with_too_many_legs() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        let legs = (0..=MaxBatchSwapLegs::get())
+            .map(|_| batch_swap_leg(DOT, XOR, SwapAmount::with_desired_input(balance!(1), 0)))
+            .collect();
+        assert_noop!(
+            LiquidityProxy::swap_batch(Origin::signed(alice()), DEX_C_ID, legs, true),
+            Error::<Runtime>::TooManyBatchSwapLegs
+        );
+    });
+}
+
+fn mock_sources(dex_id: DEXId) -> Vec<LiquiditySourceId<DEXId, LiquiditySourceType>> {
+    [
+        LiquiditySourceType::MockPool,
+        LiquiditySourceType::MockPool2,
+        LiquiditySourceType::MockPool3,
+        LiquiditySourceType::MockPool4,
+    ]
+    .iter()
+    .map(|source_type| LiquiditySourceId::new(dex_id, *source_type))
+    .collect()
+}
+
+#[test]
+fn test_split_by_curves_should_split_evenly_between_equal_curves() {
+    let curve = SwapCurve::ConstantProduct {
+        input_reserve: balance!(1000),
+        output_reserve: balance!(1000),
+        fee: fixed!(0),
+    };
+    let (shares, output) = algo::split_by_curves(&[curve, curve], fixed!(100), false)
+        .expect("Failed to split the trade");
+    assert_eq!(shares, vec![fixed!(0.5), fixed!(0.5)]);
+    // Each pool sells 1000 * 50 / (1000 + 50) of the output asset.
+    assert!(output > fixed!(95.238095237) && output < fixed!(95.238095239));
+}
+
+#[test]
+fn test_split_by_curves_should_skip_expensive_curve() {
+    let curves = [
+        SwapCurve::ConstantProduct {
+            input_reserve: balance!(1000),
+            output_reserve: balance!(1000),
+            fee: fixed!(0.003),
+        },
+        SwapCurve::Linear {
+            price: fixed!(2),
+            slope: fixed!(1),
+        },
+    ];
+    let (shares, _) =
+        algo::split_by_curves(&curves, fixed!(10), false).expect("Failed to split the trade");
+    assert_eq!(shares, vec![fixed!(1), fixed!(0)]);
+    let (shares, _) =
+        algo::split_by_curves(&curves, fixed!(10), true).expect("Failed to split the trade");
+    assert_eq!(shares, vec![fixed!(1), fixed!(0)]);
+}
+
+#[test]
+fn test_split_by_curves_should_fail_with_infeasible_amount() {
+    let curve = SwapCurve::ConstantProduct {
+        input_reserve: balance!(1000),
+        output_reserve: balance!(1000),
+        fee: fixed!(0),
+    };
+    assert!(algo::split_by_curves(&[curve], fixed!(1000), true).is_err());
+}
+
+#[test]
+fn test_curve_split_exact_input_should_outperform_sampling() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        MockSwapCurves::enable();
+        let amount = SwapAmount::with_desired_input(balance!(500), 0);
+        let sampled =
+            LiquidityProxy::sampling_split(mock_sources(DEX_C_ID), &XOR, &DOT, amount).unwrap();
+        let split =
+            LiquidityProxy::curve_split(mock_sources(DEX_C_ID), &XOR, &DOT, amount).unwrap();
+        assert!(split.amount >= sampled.amount);
+        let total_share = split
+            .distribution
+            .iter()
+            .fold(fixed!(0), |acc, (_, share)| acc.cadd(*share).unwrap());
+        assert_eq!(total_share, fixed!(1));
+
+        let quote =
+            LiquidityProxy::quote_single(&XOR, &DOT, amount, mcbc_excluding_filter(DEX_C_ID))
+                .expect("Failed to get a quote");
+        assert_eq!(quote, split);
+        let result = LiquidityProxy::exchange(
+            &alice(),
+            &alice(),
+            &XOR,
+            &DOT,
+            amount,
+            mcbc_excluding_filter(DEX_C_ID),
+        )
+        .expect("Failed to swap assets");
+        assert_eq!(result.amount, split.amount);
+    });
+}
+
+#[test]
+fn test_curve_split_exact_output_should_outperform_sampling() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        MockSwapCurves::enable();
+        let amount = SwapAmount::with_desired_output(balance!(200), balance!(1000));
+        let sampled =
+            LiquidityProxy::sampling_split(mock_sources(DEX_C_ID), &DOT, &XOR, amount).unwrap();
+        let split =
+            LiquidityProxy::curve_split(mock_sources(DEX_C_ID), &DOT, &XOR, amount).unwrap();
+        assert!(split.amount <= sampled.amount);
+        let total_share = split
+            .distribution
+            .iter()
+            .fold(fixed!(0), |acc, (_, share)| acc.cadd(*share).unwrap());
+        assert_eq!(total_share, fixed!(1));
+    });
+}
+
+#[test]
+fn test_curve_split_with_opaque_source_should_pass() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        MockSwapCurves::enable();
+        MockMCBCPool::init(get_mcbc_reserves_normal()).unwrap();
+        let mut sources = mock_sources(DEX_C_ID);
+        sources.push(LiquiditySourceId::new(
+            DEX_C_ID,
+            LiquiditySourceType::MulticollateralBondingCurvePool,
+        ));
+        let amount = SwapAmount::with_desired_input(balance!(500), 0);
+        let sampled = LiquidityProxy::sampling_split(sources.clone(), &DOT, &XOR, amount).unwrap();
+        let split = LiquidityProxy::curve_split(sources, &DOT, &XOR, amount).unwrap();
+        // The curved sources are sampled as a whole, so rounding of their shares may cost
+        // a negligible amount compared with sampling each of them.
+        assert!(split.amount + balance!(0.000001) >= sampled.amount);
+        assert_eq!(split.distribution.len(), 5);
+    });
+}
+
+fn create_mcbc_excluding_dca_order(
+    amount: Balance,
+    min_amount_out: Balance,
+    interval: u64,
+    count: u32,
+) {
+    LiquidityProxy::create_dca_order(
+        Origin::signed(alice()),
+        DEX_C_ID,
+        DOT,
+        XOR,
+        amount,
+        min_amount_out,
+        interval,
+        count,
+        [LiquiditySourceType::MulticollateralBondingCurvePool].into(),
+        FilterMode::ForbidSelected,
+    )
+    .expect("Failed to create DCA order");
+}
+
+#[test]
+fn test_dca_order_should_execute_scheduled_swaps() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        System::set_block_number(1);
+        Currencies::deposit(DOT, &alice(), balance!(300)).unwrap();
+        create_mcbc_excluding_dca_order(balance!(100), balance!(1), 2, 3);
+        assert_eq!(Assets::free_balance(&DOT, &alice()).unwrap(), 0);
+        assert!(System::events()
+            .iter()
+            .any(|record| record.event == crate::Event::DcaOrderCreated(0, alice()).into()));
+        assert_eq!(LiquidityProxy::dca_order(0).unwrap().next_execution, 3);
+
+        LiquidityProxy::on_initialize(2);
+        assert_eq!(LiquidityProxy::dca_order(0).unwrap().remaining, 3);
+
+        let quote = LiquidityProxy::quote(
+            &DOT,
+            &XOR,
+            SwapAmount::with_desired_input(balance!(100), balance!(1)),
+            mcbc_excluding_filter(DEX_C_ID),
+        )
+        .expect("Failed to get a quote");
+        LiquidityProxy::on_initialize(3);
+        assert!(System::events().iter().any(|record| record.event
+            == crate::Event::Exchange(
+                alice(),
+                alice(),
+                DEX_C_ID,
+                DOT,
+                XOR,
+                balance!(100),
+                quote.amount,
+                quote.fee,
+                None,
+                0,
+            )
+            .into()));
+        assert!(System::events()
+            .iter()
+            .any(|record| record.event == crate::Event::DcaSwapExecuted(0).into()));
+        let order = LiquidityProxy::dca_order(0).unwrap();
+        assert_eq!(order.remaining, 2);
+        assert_eq!(order.next_execution, 5);
+
+        LiquidityProxy::on_initialize(5);
+        LiquidityProxy::on_initialize(7);
+        assert!(LiquidityProxy::dca_order(0).is_none());
+        assert!(System::events()
+            .iter()
+            .any(|record| record.event == crate::Event::DcaOrderCompleted(0).into()));
+    });
+}
+
+#[test]
+fn test_dca_order_should_fail_with_invalid_parameters() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        Currencies::deposit(DOT, &alice(), balance!(300)).unwrap();
+        for (amount, interval, count) in
+            vec![(0, 1, 1), (balance!(100), 0, 1), (balance!(100), 1, 0)]
+        {
+            assert_noop!(
+                LiquidityProxy::create_dca_order(
+                    Origin::signed(alice()),
+                    DEX_C_ID,
+                    DOT,
+                    XOR,
+                    amount,
+                    0,
+                    interval,
+                    count,
+                    Vec::new(),
+                    FilterMode::Disabled,
+                ),
+                Error::<Runtime>::InvalidDcaOrder
+            );
+        }
+        assert!(LiquidityProxy::create_dca_order(
+            Origin::signed(alice()),
+            DEX_C_ID,
+            DOT,
+            XOR,
+            balance!(100),
+            0,
+            1,
+            4,
+            Vec::new(),
+            FilterMode::Disabled,
+        )
+        .is_err());
+    });
+}
+
+#[test]
+fn test_cancel_dca_order_should_unreserve_remaining_amount() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        System::set_block_number(1);
+        Currencies::deposit(DOT, &alice(), balance!(300)).unwrap();
+        create_mcbc_excluding_dca_order(balance!(100), balance!(1), 2, 3);
+        assert_noop!(
+            LiquidityProxy::cancel_dca_order(Origin::signed(AccountId32::from([2u8; 32])), 0),
+            Error::<Runtime>::NotDcaOrderOwner
+        );
+        assert_noop!(
+            LiquidityProxy::cancel_dca_order(Origin::signed(alice()), 1),
+            Error::<Runtime>::UnknownDcaOrder
+        );
+        LiquidityProxy::cancel_dca_order(Origin::signed(alice()), 0)
+            .expect("Failed to cancel DCA order");
+        assert_eq!(Assets::free_balance(&DOT, &alice()).unwrap(), balance!(300));
+        assert!(LiquidityProxy::dca_order(0).is_none());
+        assert!(System::events().iter().any(|record| record.event
+            == crate::Event::DcaOrderCancelled(0, alice(), balance!(300)).into()));
+
+        LiquidityProxy::on_initialize(3);
+        assert!(!System::events()
+            .iter()
+            .any(|record| record.event == crate::Event::DcaSwapExecuted(0).into()));
+    });
+}
+
+#[test]
+fn test_dca_order_should_be_deferred_over_weight_budget() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        System::set_block_number(1);
+        Currencies::deposit(DOT, &alice(), balance!(300)).unwrap();
+        for _ in 0..3 {
+            create_mcbc_excluding_dca_order(balance!(100), balance!(1), 1, 1);
+        }
+        LiquidityProxy::on_initialize(2);
+        for order_id in 0..2 {
+            assert!(System::events()
+                .iter()
+                .any(|record| record.event == crate::Event::DcaSwapExecuted(order_id).into()));
+        }
+        assert!(System::events()
+            .iter()
+            .any(|record| record.event == crate::Event::DcaSwapSkipped(2).into()));
+        assert_eq!(LiquidityProxy::dca_order(2).unwrap().next_execution, 3);
+
+        LiquidityProxy::on_initialize(3);
+        assert!(LiquidityProxy::dca_order(2).is_none());
+        assert!(System::events()
+            .iter()
+            .any(|record| record.event == crate::Event::DcaSwapExecuted(2).into()));
+    });
+}
+
+#[test]
+fn test_dca_order_should_fail_with_full_schedule() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        System::set_block_number(1);
+        Currencies::deposit(DOT, &alice(), balance!(400)).unwrap();
+        for _ in 0..MaxDcaOrdersPerBlock::get() {
+            create_mcbc_excluding_dca_order(balance!(100), balance!(1), 1, 1);
+        }
+        assert_noop!(
+            LiquidityProxy::create_dca_order(
+                Origin::signed(alice()),
+                DEX_C_ID,
+                DOT,
+                XOR,
+                balance!(100),
+                balance!(1),
+                1,
+                1,
+                Vec::new(),
+                FilterMode::Disabled,
+            ),
+            Error::<Runtime>::DcaScheduleFull
+        );
+        create_mcbc_excluding_dca_order(balance!(100), balance!(1), 2, 1);
+        assert_eq!(LiquidityProxy::dca_order(3).unwrap().next_execution, 3);
+    });
+}
+
+#[test]
+fn test_deferred_dca_order_should_skip_full_schedule() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        System::set_block_number(1);
+        Currencies::deposit(DOT, &alice(), balance!(600)).unwrap();
+        for interval in [1, 2].iter() {
+            for _ in 0..MaxDcaOrdersPerBlock::get() {
+                create_mcbc_excluding_dca_order(balance!(100), balance!(1), *interval, 1);
+            }
+        }
+        LiquidityProxy::on_initialize(2);
+        assert!(System::events()
+            .iter()
+            .any(|record| record.event == crate::Event::DcaSwapSkipped(2).into()));
+        assert_eq!(LiquidityProxy::dca_order(2).unwrap().next_execution, 4);
+        assert_eq!(crate::DcaSchedule::<Runtime>::get(4), vec![2]);
+    });
+}
+
+#[test]
+fn test_failed_dca_swap_should_release_funds() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        System::set_block_number(1);
+        Currencies::deposit(DOT, &alice(), balance!(100)).unwrap();
+        create_mcbc_excluding_dca_order(balance!(100), balance!(1000000), 1, 1);
+        LiquidityProxy::on_initialize(2);
+        assert!(System::events().iter().any(|record| record.event
+            == crate::Event::DcaSwapFailed(0, Error::<Runtime>::SlippageNotTolerated.into())
+                .into()));
+        assert!(LiquidityProxy::dca_order(0).is_none());
+        assert_eq!(Assets::free_balance(&DOT, &alice()).unwrap(), balance!(100));
+    });
+}
+
+fn commit_mcbc_excluding_swap(
+    swap_amount: SwapAmount<Balance>,
+    reserved_amount: Balance,
+    salt: [u8; 32],
+) -> sp_core::H256 {
+    let commitment = LiquidityProxy::swap_commitment_hash(
+        &alice(),
+        &DEX_C_ID,
+        &DOT,
+        &XOR,
+        &swap_amount,
+        &[LiquiditySourceType::MulticollateralBondingCurvePool],
+        &FilterMode::ForbidSelected,
+        &salt,
+    );
+    LiquidityProxy::commit_swap(Origin::signed(alice()), commitment, DOT, reserved_amount)
+        .expect("Failed to commit swap");
+    commitment
+}
+
+fn reveal_mcbc_excluding_swap(
+    swap_amount: SwapAmount<Balance>,
+    salt: [u8; 32],
+) -> DispatchResultWithPostInfo {
+    LiquidityProxy::reveal_swap(
+        Origin::signed(alice()),
+        DEX_C_ID,
+        DOT,
+        XOR,
+        swap_amount,
+        [LiquiditySourceType::MulticollateralBondingCurvePool].into(),
+        FilterMode::ForbidSelected,
+        salt,
+    )
+}
+
+#[test]
+fn test_reveal_swap_should_perform_committed_swap() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        System::set_block_number(1);
+        Currencies::deposit(DOT, &alice(), balance!(200)).unwrap();
+        let swap_amount = SwapAmount::with_desired_input(balance!(100), balance!(1));
+        let commitment = commit_mcbc_excluding_swap(swap_amount, balance!(150), [1u8; 32]);
+        assert_eq!(Assets::free_balance(&DOT, &alice()).unwrap(), balance!(50));
+        assert_noop!(
+            reveal_mcbc_excluding_swap(swap_amount, [1u8; 32]),
+            Error::<Runtime>::SwapCommitmentRevealedTooEarly
+        );
+
+        System::set_block_number(2);
+        assert_noop!(
+            reveal_mcbc_excluding_swap(swap_amount, [2u8; 32]),
+            Error::<Runtime>::UnknownSwapCommitment
+        );
+        let quote = LiquidityProxy::quote(&DOT, &XOR, swap_amount, mcbc_excluding_filter(DEX_C_ID))
+            .expect("Failed to get a quote");
+        reveal_mcbc_excluding_swap(swap_amount, [1u8; 32]).expect("Failed to reveal swap");
+        assert!(LiquidityProxy::swap_commitment(alice(), commitment).is_none());
+        assert_eq!(Assets::free_balance(&DOT, &alice()).unwrap(), balance!(200));
+        assert!(System::events().iter().any(|record| record.event
+            == crate::Event::Exchange(
+                alice(),
+                alice(),
+                DEX_C_ID,
+                DOT,
+                XOR,
+                balance!(100),
+                quote.amount,
+                quote.fee,
+                None,
+                0,
+            )
+            .into()));
+        assert!(System::events()
+            .iter()
+            .any(|record| record.event == crate::Event::SwapRevealed(alice(), commitment).into()));
+    });
+}
+
+#[test]
+fn test_reveal_swap_should_fail_outside_of_commitment() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        System::set_block_number(1);
+        Currencies::deposit(DOT, &alice(), balance!(200)).unwrap();
+        let swap_amount = SwapAmount::with_desired_output(balance!(100), balance!(120));
+        commit_mcbc_excluding_swap(swap_amount, balance!(100), [1u8; 32]);
+        System::set_block_number(2);
+        assert_noop!(
+            reveal_mcbc_excluding_swap(swap_amount, [1u8; 32]),
+            Error::<Runtime>::InsufficientCommittedAmount
+        );
+
+        let swap_amount = SwapAmount::with_desired_input(balance!(100), balance!(1));
+        commit_mcbc_excluding_swap(swap_amount, balance!(100), [2u8; 32]);
+        System::set_block_number(3 + CommitRevealWindow::get());
+        assert_noop!(
+            reveal_mcbc_excluding_swap(swap_amount, [2u8; 32]),
+            Error::<Runtime>::SwapCommitmentExpired
+        );
+    });
+}
+
+#[test]
+fn test_failed_reveal_swap_should_keep_commitment_for_refund() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        System::set_block_number(1);
+        Currencies::deposit(DOT, &alice(), balance!(200)).unwrap();
+        let swap_amount = SwapAmount::with_desired_input(balance!(100), balance!(1000000));
+        let commitment = commit_mcbc_excluding_swap(swap_amount, balance!(100), [1u8; 32]);
+        System::set_block_number(2);
+        assert_noop!(
+            reveal_mcbc_excluding_swap(swap_amount, [1u8; 32]),
+            Error::<Runtime>::SlippageNotTolerated
+        );
+        assert_eq!(Assets::free_balance(&DOT, &alice()).unwrap(), balance!(100));
+        assert!(LiquidityProxy::swap_commitment(alice(), commitment).is_some());
+
+        System::set_block_number(2 + CommitRevealWindow::get());
+        LiquidityProxy::refund_swap_commitment(Origin::signed(alice()), commitment)
+            .expect("Failed to refund swap commitment");
+        assert_eq!(Assets::free_balance(&DOT, &alice()).unwrap(), balance!(200));
+        assert!(LiquidityProxy::swap_commitment(alice(), commitment).is_none());
+    });
+}
+
+#[test]
+fn test_refund_swap_commitment_should_unreserve_after_expiry() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        System::set_block_number(1);
+        Currencies::deposit(DOT, &alice(), balance!(200)).unwrap();
+        let swap_amount = SwapAmount::with_desired_input(balance!(100), balance!(1));
+        let commitment = commit_mcbc_excluding_swap(swap_amount, balance!(100), [1u8; 32]);
+        assert_noop!(
+            LiquidityProxy::commit_swap(Origin::signed(alice()), commitment, DOT, balance!(100)),
+            Error::<Runtime>::SwapCommitmentAlreadyExists
+        );
+        System::set_block_number(1 + CommitRevealWindow::get());
+        assert_noop!(
+            LiquidityProxy::refund_swap_commitment(Origin::signed(alice()), commitment),
+            Error::<Runtime>::SwapCommitmentNotExpired
+        );
+        System::set_block_number(2 + CommitRevealWindow::get());
+        LiquidityProxy::refund_swap_commitment(Origin::signed(alice()), commitment)
+            .expect("Failed to refund swap commitment");
+        assert_eq!(Assets::free_balance(&DOT, &alice()).unwrap(), balance!(200));
+        assert!(LiquidityProxy::swap_commitment(alice(), commitment).is_none());
+        assert!(System::events().iter().any(|record| record.event
+            == crate::Event::SwapCommitmentRefunded(alice(), commitment, balance!(100)).into()));
+    });
+}
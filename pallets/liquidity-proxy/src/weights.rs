@@ -47,10 +47,95 @@ impl<T: frame_system::Config> crate::WeightInfo for WeightInfo<T> {
                 .saturating_add(T::DbWeight::get().writes(11 as Weight)),
         }
     }
+    fn swap_with_options(amount: SwapVariant) -> Weight {
+        <Self as crate::WeightInfo>::swap(amount)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    fn quote_path(hops: u32) -> Weight {
+        (20_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_mul(hops as Weight)
+    }
+    fn set_referrer_reward_rate() -> Weight {
+        (21_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_max_price_impact() -> Weight {
+        (21_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn swap_batch(n: u32) -> Weight {
+        (30_000_000 as Weight).saturating_add(
+            <Self as crate::WeightInfo>::swap(SwapVariant::WithDesiredOutput)
+                .saturating_mul(n as Weight),
+        )
+    }
+    fn create_dca_order() -> Weight {
+        (65_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(4 as Weight))
+    }
+    fn cancel_dca_order() -> Weight {
+        (60_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(4 as Weight))
+    }
+    fn execute_dca_order() -> Weight {
+        <Self as crate::WeightInfo>::swap(SwapVariant::WithDesiredInput)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(4 as Weight))
+    }
+    fn commit_swap() -> Weight {
+        (55_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    fn reveal_swap(amount: SwapVariant) -> Weight {
+        <Self as crate::WeightInfo>::swap(amount)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    fn refund_swap_commitment() -> Weight {
+        (50_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
 }
 
 impl crate::WeightInfo for () {
     fn swap(_amount: SwapVariant) -> Weight {
         EXTRINSIC_FIXED_WEIGHT
     }
+    fn swap_with_options(_amount: SwapVariant) -> Weight {
+        EXTRINSIC_FIXED_WEIGHT
+    }
+    fn quote_path(_hops: u32) -> Weight {
+        EXTRINSIC_FIXED_WEIGHT
+    }
+    fn set_referrer_reward_rate() -> Weight {
+        EXTRINSIC_FIXED_WEIGHT
+    }
+    fn set_max_price_impact() -> Weight {
+        EXTRINSIC_FIXED_WEIGHT
+    }
+    fn swap_batch(n: u32) -> Weight {
+        EXTRINSIC_FIXED_WEIGHT.saturating_mul(n.max(1) as Weight)
+    }
+    fn create_dca_order() -> Weight {
+        EXTRINSIC_FIXED_WEIGHT
+    }
+    fn cancel_dca_order() -> Weight {
+        EXTRINSIC_FIXED_WEIGHT
+    }
+    fn execute_dca_order() -> Weight {
+        EXTRINSIC_FIXED_WEIGHT
+    }
+    fn commit_swap() -> Weight {
+        EXTRINSIC_FIXED_WEIGHT
+    }
+    fn reveal_swap(_amount: SwapVariant) -> Weight {
+        EXTRINSIC_FIXED_WEIGHT
+    }
+    fn refund_swap_commitment() -> Weight {
+        EXTRINSIC_FIXED_WEIGHT
+    }
 }
@@ -32,7 +32,10 @@
 
 use common::fixnum::ops::One;
 use common::prelude::{FixedWrapper, SwapAmount, SwapOutcome};
-use common::{balance, fixed, Balance, Fixed, GetPoolReserves, LiquiditySource};
+use common::{
+    balance, fixed, Balance, Fixed, GetPoolReserves, GetSwapCurve, LiquiditySource,
+    LiquiditySourceType, SwapCurve, TaggedLiquiditySource,
+};
 use core::convert::TryInto;
 use frame_support::dispatch::DispatchError;
 use frame_support::ensure;
@@ -230,6 +233,111 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
         }
         Ok(())
     }
+
+    /// Sets the account reported as the one the fee is collected on, the fee isn't actually
+    /// transferred to it, since exchanges don't happen.
+    pub fn set_fee_account_id(account_id: T::AccountId) {
+        FeeAcc::<T, I>::put(account_id);
+    }
+
+    /// Makes exchanges move the reserves the way a real pool would, so that the price of the
+    /// pool reacts to the trades. Funds are still not transferred.
+    pub fn enable_reserves_movement() {
+        MoveReserves::<T, I>::put(true);
+    }
+
+    fn move_reserves(
+        dex_id: &T::DEXId,
+        input_asset_id: &T::AssetId,
+        output_asset_id: &T::AssetId,
+        swap_amount: SwapAmount<Balance>,
+        outcome: &SwapOutcome<Balance>,
+    ) -> Result<(), DispatchError> {
+        let base_asset_id = &T::GetBaseAssetId::get();
+        let (input_amount, output_amount) = match swap_amount {
+            SwapAmount::WithDesiredInput {
+                desired_amount_in, ..
+            } => (desired_amount_in, outcome.amount),
+            SwapAmount::WithDesiredOutput {
+                desired_amount_out, ..
+            } => (outcome.amount, desired_amount_out),
+        };
+        if input_asset_id == base_asset_id {
+            Self::move_pool_reserves(
+                dex_id,
+                output_asset_id,
+                (input_amount, outcome.fee),
+                (0, output_amount),
+            )
+        } else if output_asset_id == base_asset_id {
+            Self::move_pool_reserves(
+                dex_id,
+                input_asset_id,
+                (0, output_amount + outcome.fee),
+                (input_amount, 0),
+            )
+        } else {
+            // the base asset amount passed between the pools along with the fee of each of them
+            let (base_amount, first_fee, second_fee) = match swap_amount {
+                SwapAmount::WithDesiredInput { .. } => {
+                    let first = Self::quote(
+                        dex_id,
+                        input_asset_id,
+                        base_asset_id,
+                        SwapAmount::with_desired_input(input_amount, 0),
+                    )?;
+                    (
+                        first.amount,
+                        first.fee,
+                        outcome.fee.saturating_sub(first.fee),
+                    )
+                }
+                SwapAmount::WithDesiredOutput { .. } => {
+                    let second = Self::quote(
+                        dex_id,
+                        base_asset_id,
+                        output_asset_id,
+                        SwapAmount::with_desired_output(output_amount, Balance::MAX),
+                    )?;
+                    (
+                        second.amount,
+                        outcome.fee.saturating_sub(second.fee),
+                        second.fee,
+                    )
+                }
+            };
+            Self::move_pool_reserves(
+                dex_id,
+                input_asset_id,
+                (0, base_amount + first_fee),
+                (input_amount, 0),
+            )?;
+            Self::move_pool_reserves(
+                dex_id,
+                output_asset_id,
+                (base_amount, second_fee),
+                (0, output_amount),
+            )
+        }
+    }
+
+    /// Moves the reserves of the pool by the `(in, out)` amounts of the base and target assets.
+    fn move_pool_reserves(
+        dex_id: &T::DEXId,
+        target_asset_id: &T::AssetId,
+        (base_in, base_out): (Balance, Balance),
+        (target_in, target_out): (Balance, Balance),
+    ) -> Result<(), DispatchError> {
+        <Reserves<T, I>>::try_mutate(dex_id, target_asset_id, |(base_reserve, target_reserve)| {
+            *base_reserve = (FixedWrapper::from(*base_reserve) + base_in - base_out)
+                .get()
+                .map_err(|_| Error::<T, I>::InsufficientLiquidity)?;
+            *target_reserve = (FixedWrapper::from(*target_reserve) + target_in - target_out)
+                .get()
+                .map_err(|_| Error::<T, I>::InsufficientLiquidity)?;
+            Ok(())
+        })
+    }
 }
 
 impl<T: Config<I>, I: 'static>
@@ -373,16 +481,34 @@ impl<T: Config<I>, I: 'static>
         desired_amount: SwapAmount<Balance>,
     ) -> Result<SwapOutcome<Balance>, DispatchError> {
         // actual exchange does not happen
-        Self::quote(dex_id, input_asset_id, output_asset_id, desired_amount)
+        let outcome = Self::quote(dex_id, input_asset_id, output_asset_id, desired_amount)?;
+        if MoveReserves::<T, I>::get() {
+            Self::move_reserves(
+                dex_id,
+                input_asset_id,
+                output_asset_id,
+                desired_amount,
+                &outcome,
+            )?;
+        }
+        Ok(outcome)
+    }
+
+    fn fee_account(
+        _dex_id: &T::DEXId,
+        _input_asset_id: &T::AssetId,
+        _output_asset_id: &T::AssetId,
+    ) -> Option<T::AccountId> {
+        FeeAcc::<T, I>::get()
     }
 }
 
-impl<T: Config<I>, I: 'static> GetPoolReserves<T::AssetId> for Pallet<T, I> {
-    fn reserves(_base_asset: &T::AssetId, other_asset: &T::AssetId) -> (Balance, Balance) {
-        // This will only work for the dex_id being common::DEXId::Polkaswap
-        // Letting the dex_id being passed as a parameter by the caller would require changing
-        // the trait interface which is not desirable
-        let dex_id: T::DEXId = common::DEXId::Polkaswap.into();
+impl<T: Config<I>, I: 'static> GetPoolReserves<T::DEXId, T::AssetId> for Pallet<T, I> {
+    fn reserves(
+        dex_id: &T::DEXId,
+        _base_asset: &T::AssetId,
+        other_asset: &T::AssetId,
+    ) -> (Balance, Balance) {
         let (base_reserve, target_reserve) = <Reserves<T, I>>::get(dex_id, other_asset);
         (
             base_reserve.into_bits().try_into().unwrap_or(balance!(0)),
@@ -391,6 +517,60 @@ impl<T: Config<I>, I: 'static> GetPoolReserves<T::AssetId> for Pallet<T, I> {
     }
 }
 
+impl<T: Config<I>, I: 'static> GetSwapCurve<T::DEXId, T::AssetId> for Pallet<T, I> {
+    fn swap_curve(
+        dex_id: &T::DEXId,
+        input_asset_id: &T::AssetId,
+        output_asset_id: &T::AssetId,
+    ) -> Option<SwapCurve> {
+        let base_asset_id = &T::GetBaseAssetId::get();
+        let to_balance = |value: Fixed| -> Option<Balance> { value.into_bits().try_into().ok() };
+        if input_asset_id == base_asset_id {
+            if !<Reserves<T, I>>::contains_key(dex_id, output_asset_id) {
+                return None;
+            }
+            let (base_reserve, target_reserve) = <Reserves<T, I>>::get(dex_id, output_asset_id);
+            Some(SwapCurve::ConstantProduct {
+                input_reserve: to_balance(base_reserve)?,
+                output_reserve: to_balance(target_reserve)?,
+                fee: T::GetFee::get(),
+            })
+        } else if output_asset_id == base_asset_id {
+            if !<Reserves<T, I>>::contains_key(dex_id, input_asset_id) {
+                return None;
+            }
+            let (base_reserve, target_reserve) = <Reserves<T, I>>::get(dex_id, input_asset_id);
+            // Fee taken from the output shrinks the output reserve in the same proportion.
+            let base_reserve = (FixedWrapper::from(base_reserve)
+                * (FixedWrapper::from(fixed!(1)) - T::GetFee::get()))
+            .get()
+            .ok()?;
+            Some(SwapCurve::ConstantProduct {
+                input_reserve: to_balance(target_reserve)?,
+                output_reserve: to_balance(base_reserve)?,
+                fee: fixed!(0),
+            })
+        } else {
+            None
+        }
+    }
+}
+
+macro_rules! impl_tagged_liquidity_source {
+    ($instance:ident, $source_type:ident) => {
+        impl<T: Config<$instance>> TaggedLiquiditySource<T::DEXId, T::AccountId, T::AssetId>
+            for Pallet<T, $instance>
+        {
+            const SOURCE_TYPE: LiquiditySourceType = LiquiditySourceType::$source_type;
+        }
+    };
+}
+
+impl_tagged_liquidity_source!(Instance1, MockPool);
+impl_tagged_liquidity_source!(Instance2, MockPool2);
+impl_tagged_liquidity_source!(Instance3, MockPool3);
+impl_tagged_liquidity_source!(Instance4, MockPool4);
+
 pub use pallet::*;
 
 #[frame_support::pallet]
@@ -482,6 +662,13 @@ pub mod pallet {
     pub type ReservesAcc<T: Config<I>, I: 'static = ()> =
         StorageValue<_, T::TechAccountId, ValueQuery>;
 
+    #[pallet::storage]
+    #[pallet::getter(fn fee_account_id)]
+    pub type FeeAcc<T: Config<I>, I: 'static = ()> = StorageValue<_, T::AccountId>;
+
+    #[pallet::storage]
+    pub type MoveReserves<T: Config<I>, I: 'static = ()> = StorageValue<_, bool, ValueQuery>;
+
     #[pallet::genesis_config]
     pub struct GenesisConfig<T: Config<I>, I: 'static = ()> {
         pub phantom: sp_std::marker::PhantomData<I>,
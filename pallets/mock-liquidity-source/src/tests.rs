@@ -29,6 +29,7 @@
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 use crate::mock::*;
+use common::fixnum::ops::{CheckedAdd, CheckedSub};
 use common::prelude::*;
 use common::{balance, fixed};
 
@@ -267,3 +268,87 @@ fn test_quote_different_modules_should_pass() {
         assert_eq!(outcome.amount, balance!(53.413575727271103809));
     });
 }
+
+#[test]
+fn test_exchange_should_move_reserves_if_enabled() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        MockLiquiditySource::set_reserve(
+            Origin::signed(alice()),
+            DEX_A_ID,
+            DOT,
+            fixed!(5000),
+            fixed!(7000),
+        )
+        .expect("Failed to set reserve.");
+        MockLiquiditySource::set_reserve(
+            Origin::signed(alice()),
+            DEX_A_ID,
+            KSM,
+            fixed!(5500),
+            fixed!(3000),
+        )
+        .expect("Failed to set reserve.");
+        let amount = SwapAmount::with_desired_input(balance!(100), 0);
+        MockLiquiditySource::exchange(
+            &alice(),
+            &alice(),
+            &DEX_A_ID,
+            &GetBaseAssetId::get(),
+            &DOT,
+            amount,
+        )
+        .expect("Failed to exchange.");
+        assert_eq!(
+            MockLiquiditySource::reserves(DEX_A_ID, DOT),
+            (fixed!(5000), fixed!(7000))
+        );
+
+        MockLiquiditySource::enable_reserves_movement();
+        let outcome = MockLiquiditySource::exchange(
+            &alice(),
+            &alice(),
+            &DEX_A_ID,
+            &GetBaseAssetId::get(),
+            &DOT,
+            amount,
+        )
+        .expect("Failed to exchange.");
+        let to_fixed = |value: Balance| Fixed::from_bits(value as i128);
+        assert_eq!(
+            MockLiquiditySource::reserves(DEX_A_ID, DOT),
+            (
+                to_fixed(balance!(5100) - outcome.fee),
+                to_fixed(balance!(7000) - outcome.amount)
+            )
+        );
+
+        let (dot_base_reserve, dot_reserve) = MockLiquiditySource::reserves(DEX_A_ID, DOT);
+        let outcome = MockLiquiditySource::exchange(
+            &alice(),
+            &alice(),
+            &DEX_A_ID,
+            &KSM,
+            &DOT,
+            SwapAmount::with_desired_output(balance!(100), balance!(1000)),
+        )
+        .expect("Failed to exchange.");
+        let (ksm_base_reserve_after, ksm_reserve_after) =
+            MockLiquiditySource::reserves(DEX_A_ID, KSM);
+        let (dot_base_reserve_after, dot_reserve_after) =
+            MockLiquiditySource::reserves(DEX_A_ID, DOT);
+        assert_eq!(ksm_reserve_after, to_fixed(balance!(3000) + outcome.amount));
+        assert_eq!(
+            dot_reserve_after,
+            dot_reserve.csub(to_fixed(balance!(100))).unwrap()
+        );
+        assert_eq!(
+            ksm_base_reserve_after.cadd(dot_base_reserve_after).unwrap(),
+            to_fixed(balance!(5500))
+                .cadd(dot_base_reserve)
+                .unwrap()
+                .csub(to_fixed(outcome.fee))
+                .unwrap()
+        );
+    });
+}
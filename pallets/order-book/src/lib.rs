@@ -0,0 +1,813 @@
+// This file is part of the SORA network and Polkaswap app.
+
+// Copyright (c) 2020, 2021, Polka Biome Ltd. All rights reserved.
+// SPDX-License-Identifier: BSD-4-Clause
+
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+
+// Redistributions of source code must retain the above copyright notice, this list
+// of conditions and the following disclaimer.
+// Redistributions in binary form must reproduce the above copyright notice, this
+// list of conditions and the following disclaimer in the documentation and/or other
+// materials provided with the distribution.
+//
+// All advertising materials mentioning features or use of this software must display
+// the following acknowledgement: This product includes software developed by Polka Biome
+// Ltd., SORA, and Polkaswap.
+//
+// Neither the name of the Polka Biome Ltd. nor the names of its contributors may be used
+// to endorse or promote products derived from this software without specific prior written permission.
+
+// THIS SOFTWARE IS PROVIDED BY Polka Biome Ltd. AS IS AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL Polka Biome Ltd. BE LIABLE FOR ANY
+// DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS;
+// OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT,
+// STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode};
+use common::fixnum::ops::{RoundMode, RoundingDiv, RoundingMul};
+use common::prelude::{Balance, SwapAmount, SwapOutcome};
+use common::{
+    fixed, Fixed, GetSwapCurve, LiquiditySource, LiquiditySourceType, SwapCurve,
+    TaggedLiquiditySource,
+};
+use core::convert::TryInto;
+use frame_support::dispatch::{DispatchError, DispatchResult};
+use frame_support::traits::Get;
+use frame_support::weights::Weight;
+use frame_support::{ensure, RuntimeDebug};
+use frame_system::ensure_signed;
+use sp_std::prelude::*;
+
+pub mod weights;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+pub trait WeightInfo {
+    fn place_order() -> Weight;
+    fn cancel_order() -> Weight;
+    fn expire_orders(n: u32) -> Weight;
+    fn fill_order() -> Weight;
+}
+
+/// Identifier of a limit order.
+pub type OrderId = u64;
+
+/// Side of a limit order, relative to the target asset of the pair.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq)]
+pub enum OrderSide {
+    /// Buy the target asset for the base asset
+    Buy,
+    /// Sell the target asset for the base asset
+    Sell,
+}
+
+/// Resting order to exchange the target asset for the base asset of the DEX at a fixed price.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+pub struct LimitOrder<AccountId, DEXId, AssetId, BlockNumber> {
+    pub owner: AccountId,
+    pub dex_id: DEXId,
+    pub target_asset_id: AssetId,
+    pub side: OrderSide,
+    /// Price of the target asset nominated in the base asset
+    pub price: Fixed,
+    /// Amount of the target asset left to fill
+    pub amount: Balance,
+    /// Amount reserved on the owner's account, in the base asset for buy orders
+    /// and in the target asset for sell orders
+    pub reserved: Balance,
+    /// Block the order is removed at, the order never expires if not set
+    pub expires_at: Option<BlockNumber>,
+}
+
+/// Orders of a single price of one side of the book.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+pub struct PriceLevel {
+    pub price: Fixed,
+    /// Total amount of the target asset left to fill
+    pub amount: Balance,
+    pub orders: u32,
+}
+
+/// Part of a trade filled against a single order.
+#[derive(Clone, RuntimeDebug, PartialEq, Eq)]
+struct Fill {
+    order_id: OrderId,
+    target_amount: Balance,
+    base_amount: Balance,
+}
+
+type LimitOrderOf<T> = LimitOrder<
+    <T as frame_system::Config>::AccountId,
+    <T as common::Config>::DEXId,
+    <T as assets::Config>::AssetId,
+    <T as frame_system::Config>::BlockNumber,
+>;
+
+impl<T: Config> Pallet<T> {
+    /// Price levels of one side of the book, best price first.
+    pub fn depth(
+        dex_id: &T::DEXId,
+        target_asset_id: &T::AssetId,
+        side: OrderSide,
+        max_levels: u32,
+    ) -> Vec<PriceLevel> {
+        let mut levels: Vec<PriceLevel> = Vec::new();
+        for (price, order_id) in Self::book(dex_id, target_asset_id, side) {
+            let amount = match Orders::<T>::get(order_id) {
+                Some(order) => order.amount,
+                None => continue,
+            };
+            match levels.last_mut() {
+                Some(level) if level.price == price => {
+                    level.amount = level.amount.saturating_add(amount);
+                    level.orders += 1;
+                }
+                _ if levels.len() >= max_levels as usize => break,
+                _ => levels.push(PriceLevel {
+                    price,
+                    amount,
+                    orders: 1,
+                }),
+            }
+        }
+        levels
+    }
+
+    /// Prices and IDs of the orders of one side of the book, best price first.
+    pub fn book(
+        dex_id: &T::DEXId,
+        target_asset_id: &T::AssetId,
+        side: OrderSide,
+    ) -> Vec<(Fixed, OrderId)> {
+        match side {
+            OrderSide::Buy => Bids::<T>::get(dex_id, target_asset_id),
+            OrderSide::Sell => Asks::<T>::get(dex_id, target_asset_id),
+        }
+    }
+
+    fn mutate_book<R>(
+        dex_id: &T::DEXId,
+        target_asset_id: &T::AssetId,
+        side: OrderSide,
+        f: impl FnOnce(&mut Vec<(Fixed, OrderId)>) -> R,
+    ) -> R {
+        match side {
+            OrderSide::Buy => Bids::<T>::mutate(dex_id, target_asset_id, f),
+            OrderSide::Sell => Asks::<T>::mutate(dex_id, target_asset_id, f),
+        }
+    }
+
+    /// Base asset of the DEX, the prices of its orders are nominated in.
+    fn base_asset_id(dex_id: &T::DEXId) -> Result<T::AssetId, DispatchError> {
+        Ok(dex_manager::Pallet::<T>::get_dex_info(dex_id)?.base_asset_id)
+    }
+
+    /// Asset reserved for an order of the given side.
+    fn reserved_asset_id(
+        dex_id: &T::DEXId,
+        target_asset_id: T::AssetId,
+        side: OrderSide,
+    ) -> Result<T::AssetId, DispatchError> {
+        match side {
+            OrderSide::Buy => Self::base_asset_id(dex_id),
+            OrderSide::Sell => Ok(target_asset_id),
+        }
+    }
+
+    fn base_amount(
+        target_amount: Balance,
+        price: Fixed,
+        mode: RoundMode,
+    ) -> Result<Balance, DispatchError> {
+        let target_amount = Fixed::from_bits(
+            target_amount
+                .try_into()
+                .map_err(|_| Error::<T>::CalculationError)?,
+        );
+        let base_amount = target_amount
+            .rmul(price, mode)
+            .map_err(|_| Error::<T>::CalculationError)?;
+        Ok(base_amount
+            .into_bits()
+            .try_into()
+            .map_err(|_| Error::<T>::CalculationError)?)
+    }
+
+    fn target_amount(
+        base_amount: Balance,
+        price: Fixed,
+        mode: RoundMode,
+    ) -> Result<Balance, DispatchError> {
+        let base_amount = Fixed::from_bits(
+            base_amount
+                .try_into()
+                .map_err(|_| Error::<T>::CalculationError)?,
+        );
+        let target_amount = base_amount
+            .rdiv(price, mode)
+            .map_err(|_| Error::<T>::CalculationError)?;
+        Ok(target_amount
+            .into_bits()
+            .try_into()
+            .map_err(|_| Error::<T>::CalculationError)?)
+    }
+
+    /// Target asset of the pair and the side of the book a trade is filled against.
+    fn trade_side(
+        dex_id: &T::DEXId,
+        input_asset_id: &T::AssetId,
+        output_asset_id: &T::AssetId,
+    ) -> Option<(T::AssetId, OrderSide)> {
+        let base_asset_id = Self::base_asset_id(dex_id).ok()?;
+        if input_asset_id == output_asset_id {
+            None
+        } else if input_asset_id == &base_asset_id {
+            Some((*output_asset_id, OrderSide::Sell))
+        } else if output_asset_id == &base_asset_id {
+            Some((*input_asset_id, OrderSide::Buy))
+        } else {
+            None
+        }
+    }
+
+    /// Splits a trade into fills of the best orders, the amounts are rounded in favour of the order owners.
+    /// The trade fails if it takes more than `MaxFillsPerExchange` orders to fill.
+    fn match_orders(
+        dex_id: &T::DEXId,
+        input_asset_id: &T::AssetId,
+        output_asset_id: &T::AssetId,
+        swap_amount: SwapAmount<Balance>,
+    ) -> Result<(OrderSide, Vec<Fill>), DispatchError> {
+        let (target_asset_id, side) = Self::trade_side(dex_id, input_asset_id, output_asset_id)
+            .ok_or(Error::<T>::UnsupportedPair)?;
+        let base_asset_id = Self::base_asset_id(dex_id)?;
+        let (desired_amount, in_base_asset) = match swap_amount {
+            SwapAmount::WithDesiredInput {
+                desired_amount_in, ..
+            } => (desired_amount_in, input_asset_id == &base_asset_id),
+            SwapAmount::WithDesiredOutput {
+                desired_amount_out, ..
+            } => (desired_amount_out, output_asset_id == &base_asset_id),
+        };
+        ensure!(desired_amount > 0, Error::<T>::InvalidAmount);
+        // Owners of buy orders pay the base asset and receive the target one.
+        let (base_rounding, target_rounding) = match side {
+            OrderSide::Buy => (RoundMode::Floor, RoundMode::Ceil),
+            OrderSide::Sell => (RoundMode::Ceil, RoundMode::Floor),
+        };
+        let max_fills = T::MaxFillsPerExchange::get() as usize;
+        let mut amount_left = desired_amount;
+        let mut fills = Vec::new();
+        for (visited, (price, order_id)) in Self::book(dex_id, &target_asset_id, side)
+            .into_iter()
+            .enumerate()
+        {
+            if amount_left == 0 || visited >= max_fills {
+                break;
+            }
+            let order = Orders::<T>::get(order_id).ok_or(Error::<T>::UnknownOrder)?;
+            // Orders of frozen owners can't be settled, they are passed over so that they don't
+            // block the rest of the book. They still count against `MaxFillsPerExchange`
+            // as they are read all the same.
+            if assets::Pallet::<T>::ensure_not_frozen(&base_asset_id, &order.owner).is_err()
+                || assets::Pallet::<T>::ensure_not_frozen(&target_asset_id, &order.owner).is_err()
+            {
+                continue;
+            }
+            let fill = if in_base_asset {
+                let order_value = Self::base_amount(order.amount, price, base_rounding)?;
+                if amount_left >= order_value {
+                    Fill {
+                        order_id,
+                        target_amount: order.amount,
+                        base_amount: order_value,
+                    }
+                } else {
+                    Fill {
+                        order_id,
+                        target_amount: Self::target_amount(amount_left, price, target_rounding)?
+                            .min(order.amount),
+                        base_amount: amount_left,
+                    }
+                }
+            } else {
+                let target_amount = amount_left.min(order.amount);
+                Fill {
+                    order_id,
+                    target_amount,
+                    base_amount: Self::base_amount(target_amount, price, base_rounding)?,
+                }
+            };
+            amount_left -= if in_base_asset {
+                fill.base_amount
+            } else {
+                fill.target_amount
+            };
+            fills.push(fill);
+        }
+        ensure!(amount_left == 0, Error::<T>::InsufficientLiquidity);
+        Ok((side, fills))
+    }
+
+    /// Amounts of the input and the output asset of a trade made of the given fills.
+    fn fills_total(side: OrderSide, fills: &[Fill]) -> (Balance, Balance) {
+        let (target_amount, base_amount) = fills.iter().fold((0, 0), |(target, base), fill| {
+            (
+                target.saturating_add(fill.target_amount),
+                base.saturating_add(fill.base_amount),
+            )
+        });
+        match side {
+            OrderSide::Buy => (target_amount, base_amount),
+            OrderSide::Sell => (base_amount, target_amount),
+        }
+    }
+
+    /// Settles a single fill: the owner's reserved funds go to the receiver, the sender pays the owner.
+    fn settle_fill(
+        sender: &T::AccountId,
+        receiver: &T::AccountId,
+        target_asset_id: &T::AssetId,
+        fill: &Fill,
+    ) -> DispatchResult {
+        let mut order = Orders::<T>::get(fill.order_id).ok_or(Error::<T>::UnknownOrder)?;
+        let base_asset_id = Self::base_asset_id(&order.dex_id)?;
+        let (paid_asset_id, paid_amount, received_asset_id, received_amount) = match order.side {
+            OrderSide::Buy => (
+                base_asset_id,
+                fill.base_amount,
+                *target_asset_id,
+                fill.target_amount,
+            ),
+            OrderSide::Sell => (
+                *target_asset_id,
+                fill.target_amount,
+                base_asset_id,
+                fill.base_amount,
+            ),
+        };
+        if paid_amount > 0 {
+            assets::Pallet::<T>::unreserve(paid_asset_id, &order.owner, paid_amount)?;
+            assets::Pallet::<T>::transfer_from(
+                &paid_asset_id,
+                &order.owner,
+                receiver,
+                paid_amount,
+            )?;
+        }
+        if received_amount > 0 {
+            assets::Pallet::<T>::transfer_from(
+                &received_asset_id,
+                sender,
+                &order.owner,
+                received_amount,
+            )?;
+        }
+        order.reserved = order.reserved.saturating_sub(paid_amount);
+        order.amount = order.amount.saturating_sub(fill.target_amount);
+        Self::deposit_event(Event::<T>::OrderFilled(
+            fill.order_id,
+            fill.target_amount,
+            fill.base_amount,
+        ));
+        if order.amount == 0 {
+            Self::remove_order(fill.order_id, &order)?;
+        } else if Self::base_amount(order.amount, order.price, RoundMode::Floor)?
+            < T::MinOrderAmount::get()
+        {
+            // The remainder is too small to be placed on its own, so it's not left in the book.
+            Self::remove_order(fill.order_id, &order)?;
+            Self::deposit_event(Event::<T>::OrderCancelled(fill.order_id, order.owner));
+        } else {
+            Orders::<T>::insert(fill.order_id, order);
+        }
+        Ok(())
+    }
+
+    /// Removes the order from the book and unreserves the funds left.
+    fn remove_order(order_id: OrderId, order: &LimitOrderOf<T>) -> DispatchResult {
+        if order.reserved > 0 {
+            assets::Pallet::<T>::unreserve(
+                Self::reserved_asset_id(&order.dex_id, order.target_asset_id, order.side)?,
+                &order.owner,
+                order.reserved,
+            )?;
+        }
+        Self::mutate_book(&order.dex_id, &order.target_asset_id, order.side, |book| {
+            book.retain(|(_, id)| *id != order_id)
+        });
+        if let Some(expires_at) = order.expires_at {
+            OrderExpirations::<T>::mutate_exists(expires_at, |order_ids| {
+                if let Some(ids) = order_ids {
+                    ids.retain(|id| *id != order_id);
+                    if ids.is_empty() {
+                        *order_ids = None;
+                    }
+                }
+            });
+        }
+        Orders::<T>::remove(order_id);
+        Ok(())
+    }
+
+    /// Removes the orders expiring at the block, returns the number of removed orders.
+    fn expire_orders(now: T::BlockNumber) -> u32 {
+        let mut count = 0;
+        for order_id in OrderExpirations::<T>::take(now) {
+            if let Some(order) = Orders::<T>::get(order_id) {
+                if Self::remove_order(order_id, &order).is_ok() {
+                    count += 1;
+                    Self::deposit_event(Event::<T>::OrderExpired(order_id, order.owner));
+                }
+            }
+        }
+        count
+    }
+}
+
+impl<T: Config> LiquiditySource<T::DEXId, T::AccountId, T::AssetId, Balance, DispatchError>
+    for Pallet<T>
+{
+    fn can_exchange(
+        dex_id: &T::DEXId,
+        input_asset_id: &T::AssetId,
+        output_asset_id: &T::AssetId,
+    ) -> bool {
+        match Self::trade_side(dex_id, input_asset_id, output_asset_id) {
+            Some((target_asset_id, OrderSide::Buy)) => {
+                Bids::<T>::decode_len(dex_id, target_asset_id).unwrap_or(0) > 0
+            }
+            Some((target_asset_id, OrderSide::Sell)) => {
+                Asks::<T>::decode_len(dex_id, target_asset_id).unwrap_or(0) > 0
+            }
+            None => false,
+        }
+    }
+
+    fn quote(
+        dex_id: &T::DEXId,
+        input_asset_id: &T::AssetId,
+        output_asset_id: &T::AssetId,
+        swap_amount: SwapAmount<Balance>,
+    ) -> Result<SwapOutcome<Balance>, DispatchError> {
+        let (side, fills) =
+            Self::match_orders(dex_id, input_asset_id, output_asset_id, swap_amount)?;
+        let (input_amount, output_amount) = Self::fills_total(side, &fills);
+        match swap_amount {
+            SwapAmount::WithDesiredInput { .. } => Ok(SwapOutcome::new(output_amount, 0)),
+            SwapAmount::WithDesiredOutput { .. } => Ok(SwapOutcome::new(input_amount, 0)),
+        }
+    }
+
+    fn exchange(
+        sender: &T::AccountId,
+        receiver: &T::AccountId,
+        dex_id: &T::DEXId,
+        input_asset_id: &T::AssetId,
+        output_asset_id: &T::AssetId,
+        swap_amount: SwapAmount<Balance>,
+    ) -> Result<SwapOutcome<Balance>, DispatchError> {
+        common::with_transaction(|| {
+            let (side, fills) =
+                Self::match_orders(dex_id, input_asset_id, output_asset_id, swap_amount)?;
+            let (input_amount, output_amount) = Self::fills_total(side, &fills);
+            let outcome = match swap_amount {
+                SwapAmount::WithDesiredInput { min_amount_out, .. } => {
+                    ensure!(
+                        output_amount >= min_amount_out,
+                        Error::<T>::SlippageLimitExceeded
+                    );
+                    SwapOutcome::new(output_amount, 0)
+                }
+                SwapAmount::WithDesiredOutput { max_amount_in, .. } => {
+                    ensure!(
+                        input_amount <= max_amount_in,
+                        Error::<T>::SlippageLimitExceeded
+                    );
+                    SwapOutcome::new(input_amount, 0)
+                }
+            };
+            let target_asset_id = match side {
+                OrderSide::Buy => input_asset_id,
+                OrderSide::Sell => output_asset_id,
+            };
+            for fill in fills.iter() {
+                Self::settle_fill(sender, receiver, target_asset_id, fill)?;
+            }
+            Ok(outcome)
+        })
+    }
+
+    fn exchange_weight() -> Weight {
+        // Every order visited while matching is read along with the freeze status of the asset
+        // and of the owner's account in both assets, even if it's passed over.
+        let match_order = T::DbWeight::get().reads(5);
+        <T as Config>::WeightInfo::fill_order()
+            .saturating_add(match_order)
+            .saturating_mul(T::MaxFillsPerExchange::get() as Weight)
+    }
+}
+
+impl<T: Config> GetSwapCurve<T::DEXId, T::AssetId> for Pallet<T> {
+    /// Order book prices are piecewise constant, so trades are split by sampling.
+    fn swap_curve(
+        _dex_id: &T::DEXId,
+        _input_asset_id: &T::AssetId,
+        _output_asset_id: &T::AssetId,
+    ) -> Option<SwapCurve> {
+        None
+    }
+}
+
+impl<T: Config> TaggedLiquiditySource<T::DEXId, T::AccountId, T::AssetId> for Pallet<T> {
+    const SOURCE_TYPE: LiquiditySourceType = LiquiditySourceType::OrderBook;
+}
+
+pub use pallet::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+    use assets::AssetIdOf;
+    use common::{AccountIdOf, DexIdOf, EnableSourceForTradingPair, EnsureTradingPairExists};
+    use frame_support::pallet_prelude::*;
+    use frame_system::pallet_prelude::*;
+
+    #[pallet::config]
+    pub trait Config:
+        frame_system::Config + common::Config + assets::Config + dex_manager::Config
+    {
+        type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+        type EnsureTradingPairExists: EnsureTradingPairExists<
+            Self::DEXId,
+            Self::AssetId,
+            DispatchError,
+        >;
+        /// Enables the order book as a liquidity source for the trading pairs orders are placed for.
+        type EnableSourceForTradingPair: EnableSourceForTradingPair<
+            Self::DEXId,
+            Self::AssetId,
+            DispatchError,
+        >;
+        /// Maximum number of orders on one side of the book of a pair.
+        type MaxOrdersPerSide: Get<u32>;
+        /// Maximum number of orders expiring at the same block.
+        type MaxExpiringOrdersPerBlock: Get<u32>;
+        /// Maximum number of orders filled by a single exchange.
+        type MaxFillsPerExchange: Get<u32>;
+        /// Minimum value of an order nominated in the base asset.
+        type MinOrderAmount: Get<Balance>;
+        /// Weight information for extrinsics in this pallet.
+        type WeightInfo: WeightInfo;
+    }
+
+    #[pallet::pallet]
+    #[pallet::generate_store(pub(super) trait Store)]
+    pub struct Pallet<T>(PhantomData<T>);
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(now: T::BlockNumber) -> Weight {
+            <T as Config>::WeightInfo::expire_orders(Self::expire_orders(now))
+        }
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Place a limit order on the pair of the base asset and the target asset.
+        /// The order can't cross the opposite side of the book, swaps are used to trade immediately.
+        ///
+        /// - `origin`: the owner of the order,
+        /// - `dex_id`: ID of the exchange,
+        /// - `target_asset_id`: ID of the asset traded for the base asset,
+        /// - `side`: either to buy or to sell the target asset,
+        /// - `price`: price of the target asset nominated in the base asset,
+        /// - `amount`: amount of the target asset to trade,
+        /// - `expires_at`: the block the order is removed at, the order never expires if not set.
+        #[pallet::weight(<T as Config>::WeightInfo::place_order())]
+        #[allow(clippy::too_many_arguments)]
+        pub fn place_order(
+            origin: OriginFor<T>,
+            dex_id: T::DEXId,
+            target_asset_id: T::AssetId,
+            side: OrderSide,
+            price: Fixed,
+            amount: Balance,
+            expires_at: Option<T::BlockNumber>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let base_asset_id = Self::base_asset_id(&dex_id)?;
+            T::EnsureTradingPairExists::ensure_trading_pair_exists(
+                &dex_id,
+                &base_asset_id,
+                &target_asset_id,
+            )?;
+            ensure!(amount > 0 && price > fixed!(0), Error::<T>::InvalidAmount);
+            ensure!(
+                Self::base_amount(amount, price, RoundMode::Floor)? >= T::MinOrderAmount::get(),
+                Error::<T>::OrderAmountTooSmall
+            );
+            if let Some(expires_at) = expires_at {
+                ensure!(
+                    expires_at > frame_system::Pallet::<T>::block_number(),
+                    Error::<T>::InvalidExpiration
+                );
+                ensure!(
+                    OrderExpirations::<T>::decode_len(expires_at).unwrap_or(0)
+                        < T::MaxExpiringOrdersPerBlock::get() as usize,
+                    Error::<T>::TooManyExpiringOrders
+                );
+            }
+            let book = Self::book(&dex_id, &target_asset_id, side);
+            ensure!(
+                book.len() < T::MaxOrdersPerSide::get() as usize,
+                Error::<T>::OrderBookIsFull
+            );
+            let opposite_side = match side {
+                OrderSide::Buy => OrderSide::Sell,
+                OrderSide::Sell => OrderSide::Buy,
+            };
+            if let Some((best_price, _)) = Self::book(&dex_id, &target_asset_id, opposite_side)
+                .first()
+                .copied()
+            {
+                let crosses = match side {
+                    OrderSide::Buy => price >= best_price,
+                    OrderSide::Sell => price <= best_price,
+                };
+                ensure!(!crosses, Error::<T>::OrderCrossesBook);
+            }
+            let reserved = match side {
+                OrderSide::Buy => Self::base_amount(amount, price, RoundMode::Ceil)?,
+                OrderSide::Sell => amount,
+            };
+            assets::Pallet::<T>::reserve(
+                Self::reserved_asset_id(&dex_id, target_asset_id, side)?,
+                &who,
+                reserved,
+            )?;
+            T::EnableSourceForTradingPair::enable_source_for_trading_pair(
+                &dex_id,
+                &base_asset_id,
+                &target_asset_id,
+                LiquiditySourceType::OrderBook,
+            )?;
+            let order_id = NextOrderId::<T>::mutate(|next_id| {
+                let order_id = *next_id;
+                *next_id = next_id.wrapping_add(1);
+                order_id
+            });
+            // Orders of the same price are filled in the order they were placed.
+            let index = book
+                .iter()
+                .position(|(book_price, _)| match side {
+                    OrderSide::Buy => *book_price < price,
+                    OrderSide::Sell => *book_price > price,
+                })
+                .unwrap_or_else(|| book.len());
+            Self::mutate_book(&dex_id, &target_asset_id, side, |book| {
+                book.insert(index, (price, order_id))
+            });
+            if let Some(expires_at) = expires_at {
+                OrderExpirations::<T>::append(expires_at, order_id);
+            }
+            Orders::<T>::insert(
+                order_id,
+                LimitOrder {
+                    owner: who.clone(),
+                    dex_id,
+                    target_asset_id,
+                    side,
+                    price,
+                    amount,
+                    reserved,
+                    expires_at,
+                },
+            );
+            Self::deposit_event(Event::<T>::OrderPlaced(
+                order_id,
+                who,
+                dex_id,
+                target_asset_id,
+                side,
+                price,
+                amount,
+            ));
+            Ok(().into())
+        }
+
+        /// Cancel a limit order and unreserve the funds left.
+        ///
+        /// - `origin`: the owner of the order,
+        /// - `order_id`: ID of the order to cancel.
+        #[pallet::weight(<T as Config>::WeightInfo::cancel_order())]
+        pub fn cancel_order(origin: OriginFor<T>, order_id: OrderId) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let order = Orders::<T>::get(order_id).ok_or(Error::<T>::UnknownOrder)?;
+            ensure!(order.owner == who, Error::<T>::NotOrderOwner);
+            Self::remove_order(order_id, &order)?;
+            Self::deposit_event(Event::<T>::OrderCancelled(order_id, who));
+            Ok(().into())
+        }
+    }
+
+    #[pallet::event]
+    #[pallet::metadata(AccountIdOf<T> = "AccountId", AssetIdOf<T> = "AssetId", DexIdOf<T> = "DEXId")]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// Limit order has been placed [Order Id, Owner Account, DEX Id, Target Asset Id, Side, Price, Amount]
+        OrderPlaced(
+            OrderId,
+            AccountIdOf<T>,
+            DexIdOf<T>,
+            AssetIdOf<T>,
+            OrderSide,
+            Fixed,
+            Balance,
+        ),
+        /// Limit order has been filled, fully or partially [Order Id, Target Asset Amount, Base Asset Amount]
+        OrderFilled(OrderId, Balance, Balance),
+        /// Limit order has been cancelled by the owner or because the remainder left after
+        /// a partial fill is less than `MinOrderAmount` [Order Id, Owner Account]
+        OrderCancelled(OrderId, AccountIdOf<T>),
+        /// Limit order has expired and has been removed [Order Id, Owner Account]
+        OrderExpired(OrderId, AccountIdOf<T>),
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// Order amount or price is zero.
+        InvalidAmount,
+        /// Order value nominated in the base asset is less than `MinOrderAmount`.
+        OrderAmountTooSmall,
+        /// Order expiration block has already passed.
+        InvalidExpiration,
+        /// Number of orders expiring at the block exceeds `MaxExpiringOrdersPerBlock`.
+        TooManyExpiringOrders,
+        /// Number of orders on the side of the book exceeds `MaxOrdersPerSide`.
+        OrderBookIsFull,
+        /// Order price reaches the best price of the opposite side of the book.
+        OrderCrossesBook,
+        /// Order with the given ID doesn't exist.
+        UnknownOrder,
+        /// The caller is not the owner of the order.
+        NotOrderOwner,
+        /// Orders are only placed for pairs of the base asset and another asset.
+        UnsupportedPair,
+        /// Orders on the book are not enough to fill the trade.
+        InsufficientLiquidity,
+        /// Slippage either exceeds minimum tolerated output or maximum tolerated input.
+        SlippageLimitExceeded,
+        /// Specified parameters lead to arithmetic error.
+        CalculationError,
+    }
+
+    /// ID of the next order to be placed.
+    #[pallet::storage]
+    pub type NextOrderId<T> = StorageValue<_, OrderId, ValueQuery>;
+
+    /// Active limit orders.
+    #[pallet::storage]
+    #[pallet::getter(fn order)]
+    pub type Orders<T: Config> = StorageMap<_, Twox64Concat, OrderId, LimitOrderOf<T>>;
+
+    /// Prices and IDs of the buy orders of a pair, best price first.
+    #[pallet::storage]
+    pub type Bids<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        T::DEXId,
+        Blake2_128Concat,
+        T::AssetId,
+        Vec<(Fixed, OrderId)>,
+        ValueQuery,
+    >;
+
+    /// Prices and IDs of the sell orders of a pair, best price first.
+    #[pallet::storage]
+    pub type Asks<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        T::DEXId,
+        Blake2_128Concat,
+        T::AssetId,
+        Vec<(Fixed, OrderId)>,
+        ValueQuery,
+    >;
+
+    /// IDs of the orders expiring at a given block.
+    #[pallet::storage]
+    pub type OrderExpirations<T: Config> =
+        StorageMap<_, Twox64Concat, T::BlockNumber, Vec<OrderId>, ValueQuery>;
+}
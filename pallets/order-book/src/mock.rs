@@ -0,0 +1,336 @@
+// This file is part of the SORA network and Polkaswap app.
+
+// Copyright (c) 2020, 2021, Polka Biome Ltd. All rights reserved.
+// SPDX-License-Identifier: BSD-4-Clause
+
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+
+// Redistributions of source code must retain the above copyright notice, this list
+// of conditions and the following disclaimer.
+// Redistributions in binary form must reproduce the above copyright notice, this
+// list of conditions and the following disclaimer in the documentation and/or other
+// materials provided with the distribution.
+//
+// All advertising materials mentioning features or use of this software must display
+// the following acknowledgement: This product includes software developed by Polka Biome
+// Ltd., SORA, and Polkaswap.
+//
+// Neither the name of the Polka Biome Ltd. nor the names of its contributors may be used
+// to endorse or promote products derived from this software without specific prior written permission.
+
+// THIS SOFTWARE IS PROVIDED BY Polka Biome Ltd. AS IS AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL Polka Biome Ltd. BE LIABLE FOR ANY
+// DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS;
+// OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT,
+// STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::{self as order_book, Config};
+use common::mock::ExistentialDeposits;
+use common::prelude::{Balance, DEXInfo, TradingPair};
+use common::{
+    balance, AssetId32, AssetName, AssetSymbol, BalancePrecision, BasisPoints, DOT, KSM, XOR,
+};
+use currencies::BasicCurrencyAdapter;
+use frame_support::traits::GenesisBuild;
+use frame_support::weights::Weight;
+use frame_support::{construct_runtime, parameter_types};
+use frame_system;
+use sp_core::H256;
+use sp_runtime::testing::Header;
+use sp_runtime::traits::{BlakeTwo256, IdentityLookup};
+use sp_runtime::Perbill;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
+type Block = frame_system::mocking::MockBlock<Runtime>;
+
+construct_runtime! {
+    pub enum Runtime where
+        Block = Block,
+        NodeBlock = Block,
+        UncheckedExtrinsic = UncheckedExtrinsic,
+    {
+        System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+        OrderBook: order_book::{Pallet, Call, Storage, Event<T>},
+        TradingPair: trading_pair::{Pallet, Call, Config<T>, Storage, Event<T>},
+        Tokens: tokens::{Pallet, Call, Config<T>, Storage, Event<T>},
+        Currencies: currencies::{Pallet, Call, Storage, Event<T>},
+        Assets: assets::{Pallet, Call, Config<T>, Storage, Event<T>},
+        Balances: pallet_balances::{Pallet, Call, Storage, Event<T>},
+        Permissions: permissions::{Pallet, Call, Config<T>, Storage, Event<T>},
+        DexManager: dex_manager::{Pallet, Call, Config<T>, Storage, Event<T>},
+        ParachainSystem: cumulus_pallet_parachain_system::{Pallet, Call, Storage, Inherent, Event<T>},
+        ParachainInfo: parachain_info::{Pallet, Storage, Config},
+    }
+}
+
+impl parachain_info::Config for Runtime {}
+
+pub type AccountId = u128;
+pub type BlockNumber = u64;
+pub type Amount = i128;
+type AssetId = AssetId32<common::PredefinedAssetId>;
+type DEXId = u32;
+
+pub const ALICE: AccountId = 1;
+pub const BOB: AccountId = 2;
+pub const CHARLIE: AccountId = 3;
+pub const DEX_ID: DEXId = 0;
+/// DEX with DOT as the base asset.
+pub const DOT_DEX_ID: DEXId = 1;
+
+parameter_types! {
+    pub const BlockHashCount: u64 = 250;
+    pub const MaximumBlockWeight: Weight = 1024;
+    pub const MaximumBlockLength: u32 = 2 * 1024;
+    pub const AvailableBlockRatio: Perbill = Perbill::from_percent(75);
+    pub const GetBaseAssetId: AssetId = XOR;
+    pub const MaxOrdersPerSide: u32 = 4;
+    pub const MaxExpiringOrdersPerBlock: u32 = 2;
+    pub const MaxFillsPerExchange: u32 = 3;
+    pub const MinOrderAmount: Balance = balance!(1);
+    pub const ExistentialDeposit: u128 = 0;
+    pub const MaxDexFee: BasisPoints = 100;
+}
+
+impl frame_system::Config for Runtime {
+    type BaseCallFilter = ();
+    type BlockWeights = ();
+    type BlockLength = ();
+    type Origin = Origin;
+    type Call = Call;
+    type Index = u64;
+    type BlockNumber = u64;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = AccountId;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Header = Header;
+    type Event = Event;
+    type BlockHashCount = BlockHashCount;
+    type DbWeight = ();
+    type Version = ();
+    type AccountData = pallet_balances::AccountData<Balance>;
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+    type PalletInfo = PalletInfo;
+    type SS58Prefix = ();
+    type OnSetCode = cumulus_pallet_parachain_system::ParachainSetCode<Self>;
+}
+
+impl cumulus_pallet_parachain_system::Config for Runtime {
+    type Event = Event;
+    type OnValidationData = ();
+    type SelfParaId = parachain_info::Pallet<Runtime>;
+    type DownwardMessageHandlers = ();
+}
+
+impl Config for Runtime {
+    type Event = Event;
+    type EnsureTradingPairExists = trading_pair::Pallet<Runtime>;
+    type EnableSourceForTradingPair = trading_pair::Pallet<Runtime>;
+    type MaxOrdersPerSide = MaxOrdersPerSide;
+    type MaxExpiringOrdersPerBlock = MaxExpiringOrdersPerBlock;
+    type MaxFillsPerExchange = MaxFillsPerExchange;
+    type MinOrderAmount = MinOrderAmount;
+    type WeightInfo = ();
+}
+
+impl trading_pair::Config for Runtime {
+    type Event = Event;
+    type EnsureDEXManager = dex_manager::Pallet<Runtime>;
+    type WeightInfo = ();
+}
+
+impl tokens::Config for Runtime {
+    type Event = Event;
+    type Balance = Balance;
+    type Amount = Amount;
+    type CurrencyId = <Runtime as assets::Config>::AssetId;
+    type WeightInfo = ();
+    type ExistentialDeposits = ExistentialDeposits;
+    type OnDust = ();
+}
+
+impl currencies::Config for Runtime {
+    type Event = Event;
+    type MultiCurrency = Tokens;
+    type NativeCurrency = BasicCurrencyAdapter<Runtime, Balances, Amount, BlockNumber>;
+    type GetNativeCurrencyId = <Runtime as assets::Config>::GetBaseAssetId;
+    type WeightInfo = ();
+}
+
+impl common::Config for Runtime {
+    type DEXId = DEXId;
+    type LstId = common::LiquiditySourceType;
+}
+
+impl assets::Config for Runtime {
+    type Event = Event;
+    type ExtraAccountId = AccountId;
+    type ExtraAssetRecordArg =
+        common::AssetIdExtraAssetRecordArg<DEXId, common::LiquiditySourceType, AccountId>;
+    type AssetId = AssetId;
+    type GetBaseAssetId = GetBaseAssetId;
+    type Currency = currencies::Module<Runtime>;
+    type IsAssetInUse = ();
+    type WeightInfo = ();
+}
+
+impl pallet_balances::Config for Runtime {
+    type Balance = Balance;
+    type Event = Event;
+    type DustRemoval = ();
+    type ExistentialDeposit = ExistentialDeposit;
+    type AccountStore = System;
+    type WeightInfo = ();
+    type MaxLocks = ();
+}
+
+impl permissions::Config for Runtime {
+    type Event = Event;
+}
+
+impl dex_manager::Config for Runtime {
+    type Event = Event;
+    type MaxFee = MaxDexFee;
+    type WeightInfo = ();
+}
+
+pub struct ExtBuilder {
+    endowed_assets: Vec<(
+        AssetId,
+        AccountId,
+        AssetSymbol,
+        AssetName,
+        BalancePrecision,
+        Balance,
+        bool,
+    )>,
+    endowed_accounts: Vec<(AccountId, AssetId, Balance)>,
+    dex_list: Vec<(DEXId, DEXInfo<AssetId>)>,
+    trading_pairs: Vec<(DEXId, TradingPair<AssetId>)>,
+}
+
+impl Default for ExtBuilder {
+    fn default() -> Self {
+        Self {
+            endowed_assets: vec![
+                (
+                    XOR,
+                    ALICE,
+                    AssetSymbol(b"XOR".to_vec()),
+                    AssetName(b"SORA".to_vec()),
+                    18,
+                    0,
+                    true,
+                ),
+                (
+                    DOT,
+                    ALICE,
+                    AssetSymbol(b"DOT".to_vec()),
+                    AssetName(b"Polkadot".to_vec()),
+                    18,
+                    0,
+                    true,
+                ),
+                (
+                    KSM,
+                    ALICE,
+                    AssetSymbol(b"KSM".to_vec()),
+                    AssetName(b"Kusama".to_vec()),
+                    18,
+                    0,
+                    true,
+                ),
+            ],
+            endowed_accounts: vec![
+                (ALICE, DOT, balance!(1000)),
+                (BOB, DOT, balance!(1000)),
+                (CHARLIE, DOT, balance!(1000)),
+            ],
+            dex_list: vec![
+                (
+                    DEX_ID,
+                    DEXInfo {
+                        base_asset_id: XOR,
+                        is_public: true,
+                        default_fee: 30,
+                    },
+                ),
+                (
+                    DOT_DEX_ID,
+                    DEXInfo {
+                        base_asset_id: DOT,
+                        is_public: true,
+                        default_fee: 30,
+                    },
+                ),
+            ],
+            trading_pairs: vec![
+                (
+                    DEX_ID,
+                    TradingPair {
+                        base_asset_id: XOR,
+                        target_asset_id: DOT,
+                    },
+                ),
+                (
+                    DOT_DEX_ID,
+                    TradingPair {
+                        base_asset_id: DOT,
+                        target_asset_id: XOR,
+                    },
+                ),
+            ],
+        }
+    }
+}
+
+impl ExtBuilder {
+    pub fn build(self) -> sp_io::TestExternalities {
+        let mut t = frame_system::GenesisConfig::default()
+            .build_storage::<Runtime>()
+            .unwrap();
+
+        pallet_balances::GenesisConfig::<Runtime> {
+            balances: vec![
+                (ALICE, balance!(1000)),
+                (BOB, balance!(1000)),
+                (CHARLIE, balance!(1000)),
+            ],
+        }
+        .assimilate_storage(&mut t)
+        .unwrap();
+
+        assets::GenesisConfig::<Runtime> {
+            endowed_assets: self.endowed_assets,
+        }
+        .assimilate_storage(&mut t)
+        .unwrap();
+
+        tokens::GenesisConfig::<Runtime> {
+            endowed_accounts: self.endowed_accounts,
+        }
+        .assimilate_storage(&mut t)
+        .unwrap();
+
+        dex_manager::GenesisConfig::<Runtime> {
+            dex_list: self.dex_list,
+        }
+        .assimilate_storage(&mut t)
+        .unwrap();
+
+        trading_pair::GenesisConfig::<Runtime> {
+            trading_pairs: self.trading_pairs,
+        }
+        .assimilate_storage(&mut t)
+        .unwrap();
+
+        t.into()
+    }
+}
@@ -0,0 +1,466 @@
+// This file is part of the SORA network and Polkaswap app.
+
+// Copyright (c) 2020, 2021, Polka Biome Ltd. All rights reserved.
+// SPDX-License-Identifier: BSD-4-Clause
+
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+
+// Redistributions of source code must retain the above copyright notice, this list
+// of conditions and the following disclaimer.
+// Redistributions in binary form must reproduce the above copyright notice, this
+// list of conditions and the following disclaimer in the documentation and/or other
+// materials provided with the distribution.
+//
+// All advertising materials mentioning features or use of this software must display
+// the following acknowledgement: This product includes software developed by Polka Biome
+// Ltd., SORA, and Polkaswap.
+//
+// Neither the name of the Polka Biome Ltd. nor the names of its contributors may be used
+// to endorse or promote products derived from this software without specific prior written permission.
+
+// THIS SOFTWARE IS PROVIDED BY Polka Biome Ltd. AS IS AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL Polka Biome Ltd. BE LIABLE FOR ANY
+// DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS;
+// OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT,
+// STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::mock::*;
+use crate::{Error, OrderSide, PriceLevel};
+use common::prelude::{Balance, SwapAmount, SwapOutcome};
+use common::{balance, fixed, Fixed, LiquiditySource, LiquiditySourceType, DOT, KSM, XOR};
+use frame_support::traits::Hooks;
+use frame_support::{assert_noop, assert_ok};
+
+fn place_order(who: AccountId, side: OrderSide, price: Fixed, amount: Balance) {
+    assert_ok!(OrderBook::place_order(
+        Origin::signed(who),
+        DEX_ID,
+        DOT,
+        side,
+        price,
+        amount,
+        None
+    ));
+}
+
+fn free_balance(
+    asset_id: &common::AssetId32<common::PredefinedAssetId>,
+    who: &AccountId,
+) -> Balance {
+    Assets::free_balance(asset_id, who).unwrap()
+}
+
+#[test]
+fn test_place_order_should_reserve_funds() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        place_order(ALICE, OrderSide::Sell, fixed!(2), balance!(10));
+        place_order(BOB, OrderSide::Buy, fixed!(1.5), balance!(10));
+        assert_eq!(free_balance(&DOT, &ALICE), balance!(990));
+        assert_eq!(free_balance(&XOR, &BOB), balance!(985));
+        let order = OrderBook::order(1).unwrap();
+        assert_eq!(order.owner, BOB);
+        assert_eq!(order.reserved, balance!(15));
+        assert!(OrderBook::can_exchange(&DEX_ID, &XOR, &DOT));
+        assert!(OrderBook::can_exchange(&DEX_ID, &DOT, &XOR));
+        assert!(!OrderBook::can_exchange(&DEX_ID, &XOR, &KSM));
+        assert!(
+            trading_pair::Pallet::<Runtime>::is_source_enabled_for_trading_pair(
+                &DEX_ID,
+                &XOR,
+                &DOT,
+                LiquiditySourceType::OrderBook,
+            )
+            .unwrap()
+        );
+    });
+}
+
+#[test]
+fn test_place_order_should_fail_with_invalid_parameters() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        assert_noop!(
+            OrderBook::place_order(
+                Origin::signed(ALICE),
+                DEX_ID,
+                DOT,
+                OrderSide::Sell,
+                fixed!(0),
+                balance!(10),
+                None
+            ),
+            Error::<Runtime>::InvalidAmount
+        );
+        assert_noop!(
+            OrderBook::place_order(
+                Origin::signed(ALICE),
+                DEX_ID,
+                KSM,
+                OrderSide::Sell,
+                fixed!(2),
+                balance!(10),
+                None
+            ),
+            trading_pair::Error::<Runtime>::TradingPairDoesntExist
+        );
+        assert_noop!(
+            OrderBook::place_order(
+                Origin::signed(ALICE),
+                DEX_ID,
+                DOT,
+                OrderSide::Sell,
+                fixed!(2),
+                balance!(10),
+                Some(0)
+            ),
+            Error::<Runtime>::InvalidExpiration
+        );
+        assert_noop!(
+            OrderBook::place_order(
+                Origin::signed(ALICE),
+                DEX_ID,
+                DOT,
+                OrderSide::Sell,
+                fixed!(2),
+                balance!(0.4),
+                None
+            ),
+            Error::<Runtime>::OrderAmountTooSmall
+        );
+    });
+}
+
+#[test]
+fn test_place_order_should_not_cross_book() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        place_order(ALICE, OrderSide::Sell, fixed!(2), balance!(10));
+        assert_noop!(
+            OrderBook::place_order(
+                Origin::signed(BOB),
+                DEX_ID,
+                DOT,
+                OrderSide::Buy,
+                fixed!(2),
+                balance!(10),
+                None
+            ),
+            Error::<Runtime>::OrderCrossesBook
+        );
+        place_order(BOB, OrderSide::Buy, fixed!(1.9), balance!(10));
+    });
+}
+
+#[test]
+fn test_place_order_should_fail_on_full_book() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        for _ in 0..MaxOrdersPerSide::get() {
+            place_order(ALICE, OrderSide::Sell, fixed!(2), balance!(1));
+        }
+        assert_noop!(
+            OrderBook::place_order(
+                Origin::signed(ALICE),
+                DEX_ID,
+                DOT,
+                OrderSide::Sell,
+                fixed!(2),
+                balance!(1),
+                None
+            ),
+            Error::<Runtime>::OrderBookIsFull
+        );
+    });
+}
+
+#[test]
+fn test_exchange_with_desired_input_should_fill_best_sell_orders() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        System::set_block_number(1);
+        place_order(BOB, OrderSide::Sell, fixed!(3), balance!(10));
+        place_order(ALICE, OrderSide::Sell, fixed!(2), balance!(10));
+        let swap_amount = SwapAmount::with_desired_input(balance!(35), balance!(15));
+        assert_eq!(
+            OrderBook::quote(&DEX_ID, &XOR, &DOT, swap_amount).unwrap(),
+            SwapOutcome::new(balance!(15), 0)
+        );
+        assert_eq!(
+            OrderBook::exchange(&CHARLIE, &CHARLIE, &DEX_ID, &XOR, &DOT, swap_amount).unwrap(),
+            SwapOutcome::new(balance!(15), 0)
+        );
+        assert_eq!(free_balance(&XOR, &CHARLIE), balance!(965));
+        assert_eq!(free_balance(&DOT, &CHARLIE), balance!(1015));
+        assert_eq!(free_balance(&XOR, &ALICE), balance!(1020));
+        assert_eq!(free_balance(&DOT, &ALICE), balance!(990));
+        assert_eq!(free_balance(&XOR, &BOB), balance!(1015));
+        assert_eq!(free_balance(&DOT, &BOB), balance!(990));
+        assert!(OrderBook::order(1).is_none());
+        let order = OrderBook::order(0).unwrap();
+        assert_eq!(order.amount, balance!(5));
+        assert_eq!(order.reserved, balance!(5));
+        assert!(System::events().iter().any(|record| record.event
+            == crate::Event::<Runtime>::OrderFilled(0, balance!(5), balance!(15)).into()));
+    });
+}
+
+#[test]
+fn test_exchange_should_pass_over_orders_of_frozen_owners() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        System::set_block_number(1);
+        place_order(BOB, OrderSide::Sell, fixed!(3), balance!(10));
+        place_order(ALICE, OrderSide::Sell, fixed!(2), balance!(10));
+        assert_ok!(Assets::freeze_account(Origin::signed(ALICE), DOT, ALICE));
+        let swap_amount = SwapAmount::with_desired_input(balance!(15), balance!(5));
+        assert_eq!(
+            OrderBook::exchange(&CHARLIE, &CHARLIE, &DEX_ID, &XOR, &DOT, swap_amount).unwrap(),
+            SwapOutcome::new(balance!(5), 0)
+        );
+        assert_eq!(free_balance(&XOR, &BOB), balance!(1015));
+        assert_eq!(OrderBook::order(0).unwrap().amount, balance!(5));
+        assert_eq!(OrderBook::order(1).unwrap().amount, balance!(10));
+    });
+}
+
+#[test]
+fn test_orders_of_frozen_owners_should_count_against_max_fills() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        place_order(BOB, OrderSide::Sell, fixed!(3), balance!(10));
+        for _ in 0..MaxFillsPerExchange::get() {
+            place_order(ALICE, OrderSide::Sell, fixed!(2), balance!(1));
+        }
+        assert_ok!(Assets::freeze_account(Origin::signed(ALICE), DOT, ALICE));
+        assert_noop!(
+            OrderBook::quote(
+                &DEX_ID,
+                &XOR,
+                &DOT,
+                SwapAmount::with_desired_output(balance!(1), balance!(100))
+            ),
+            Error::<Runtime>::InsufficientLiquidity
+        );
+    });
+}
+
+#[test]
+fn test_exchange_should_cancel_remainder_below_min_order_amount() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        System::set_block_number(1);
+        place_order(BOB, OrderSide::Sell, fixed!(2), balance!(10));
+        let swap_amount = SwapAmount::with_desired_output(balance!(9.8), balance!(100));
+        assert_eq!(
+            OrderBook::exchange(&CHARLIE, &CHARLIE, &DEX_ID, &XOR, &DOT, swap_amount).unwrap(),
+            SwapOutcome::new(balance!(19.6), 0)
+        );
+        // The remainder of 0.2 DOT is worth 0.4 XOR, less than `MinOrderAmount`.
+        assert!(OrderBook::order(0).is_none());
+        assert!(OrderBook::book(&DEX_ID, &DOT, OrderSide::Sell).is_empty());
+        assert_eq!(free_balance(&DOT, &BOB), balance!(990.2));
+        assert!(System::events()
+            .iter()
+            .any(|record| record.event == crate::Event::<Runtime>::OrderCancelled(0, BOB).into()));
+    });
+}
+
+#[test]
+fn test_orders_should_be_nominated_in_base_asset_of_dex() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        assert_ok!(OrderBook::place_order(
+            Origin::signed(BOB),
+            DOT_DEX_ID,
+            XOR,
+            OrderSide::Buy,
+            fixed!(1.5),
+            balance!(10),
+            None
+        ));
+        assert_eq!(free_balance(&DOT, &BOB), balance!(985));
+        assert!(OrderBook::can_exchange(&DOT_DEX_ID, &XOR, &DOT));
+        assert!(!OrderBook::can_exchange(&DEX_ID, &XOR, &DOT));
+        let swap_amount = SwapAmount::with_desired_input(balance!(10), balance!(15));
+        assert_eq!(
+            OrderBook::exchange(&CHARLIE, &CHARLIE, &DOT_DEX_ID, &XOR, &DOT, swap_amount).unwrap(),
+            SwapOutcome::new(balance!(15), 0)
+        );
+        assert_eq!(free_balance(&XOR, &CHARLIE), balance!(990));
+        assert_eq!(free_balance(&DOT, &CHARLIE), balance!(1015));
+        assert_eq!(free_balance(&XOR, &BOB), balance!(1010));
+        assert_eq!(free_balance(&DOT, &BOB), balance!(985));
+    });
+}
+
+#[test]
+fn test_exchange_with_desired_output_should_fill_best_buy_orders() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        place_order(ALICE, OrderSide::Buy, fixed!(2), balance!(10));
+        place_order(BOB, OrderSide::Buy, fixed!(1.5), balance!(10));
+        let swap_amount = SwapAmount::with_desired_output(balance!(26), balance!(13));
+        assert_eq!(
+            OrderBook::quote(&DEX_ID, &DOT, &XOR, swap_amount).unwrap(),
+            SwapOutcome::new(balance!(14), 0)
+        );
+        assert_noop!(
+            OrderBook::exchange(&CHARLIE, &CHARLIE, &DEX_ID, &DOT, &XOR, swap_amount),
+            Error::<Runtime>::SlippageLimitExceeded
+        );
+        let swap_amount = SwapAmount::with_desired_output(balance!(26), balance!(14));
+        OrderBook::exchange(&CHARLIE, &CHARLIE, &DEX_ID, &DOT, &XOR, swap_amount).unwrap();
+        assert_eq!(free_balance(&DOT, &CHARLIE), balance!(986));
+        assert_eq!(free_balance(&XOR, &CHARLIE), balance!(1026));
+        assert_eq!(free_balance(&XOR, &ALICE), balance!(980));
+        assert_eq!(free_balance(&DOT, &ALICE), balance!(1010));
+        assert_eq!(free_balance(&XOR, &BOB), balance!(985));
+        assert_eq!(free_balance(&DOT, &BOB), balance!(1004));
+        let order = OrderBook::order(1).unwrap();
+        assert_eq!(order.amount, balance!(6));
+        assert_eq!(order.reserved, balance!(9));
+    });
+}
+
+#[test]
+fn test_exchange_should_fail_with_insufficient_orders() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        place_order(ALICE, OrderSide::Sell, fixed!(2), balance!(10));
+        assert_noop!(
+            OrderBook::quote(
+                &DEX_ID,
+                &XOR,
+                &DOT,
+                SwapAmount::with_desired_output(balance!(11), balance!(100))
+            ),
+            Error::<Runtime>::InsufficientLiquidity
+        );
+    });
+}
+
+#[test]
+fn test_exchange_should_fail_over_max_fills() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        for _ in 0..MaxFillsPerExchange::get() + 1 {
+            place_order(ALICE, OrderSide::Sell, fixed!(2), balance!(1));
+        }
+        let max_fills_amount = balance!(1) * MaxFillsPerExchange::get() as Balance;
+        assert_ok!(OrderBook::quote(
+            &DEX_ID,
+            &XOR,
+            &DOT,
+            SwapAmount::with_desired_output(max_fills_amount, balance!(100))
+        ));
+        assert_noop!(
+            OrderBook::quote(
+                &DEX_ID,
+                &XOR,
+                &DOT,
+                SwapAmount::with_desired_output(max_fills_amount + balance!(1), balance!(100))
+            ),
+            Error::<Runtime>::InsufficientLiquidity
+        );
+    });
+}
+
+#[test]
+fn test_cancel_order_should_unreserve_funds() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        place_order(ALICE, OrderSide::Buy, fixed!(2), balance!(10));
+        assert_noop!(
+            OrderBook::cancel_order(Origin::signed(BOB), 0),
+            Error::<Runtime>::NotOrderOwner
+        );
+        assert_ok!(OrderBook::cancel_order(Origin::signed(ALICE), 0));
+        assert_eq!(free_balance(&XOR, &ALICE), balance!(1000));
+        assert!(OrderBook::order(0).is_none());
+        assert!(!OrderBook::can_exchange(&DEX_ID, &DOT, &XOR));
+        assert_noop!(
+            OrderBook::cancel_order(Origin::signed(ALICE), 0),
+            Error::<Runtime>::UnknownOrder
+        );
+    });
+}
+
+#[test]
+fn test_expired_orders_should_be_removed() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        System::set_block_number(1);
+        for _ in 0..MaxExpiringOrdersPerBlock::get() {
+            assert_ok!(OrderBook::place_order(
+                Origin::signed(ALICE),
+                DEX_ID,
+                DOT,
+                OrderSide::Sell,
+                fixed!(2),
+                balance!(10),
+                Some(3)
+            ));
+        }
+        assert_noop!(
+            OrderBook::place_order(
+                Origin::signed(ALICE),
+                DEX_ID,
+                DOT,
+                OrderSide::Sell,
+                fixed!(2),
+                balance!(10),
+                Some(3)
+            ),
+            Error::<Runtime>::TooManyExpiringOrders
+        );
+        assert_ok!(OrderBook::cancel_order(Origin::signed(ALICE), 0));
+        OrderBook::on_initialize(2);
+        assert!(OrderBook::order(1).is_some());
+        OrderBook::on_initialize(3);
+        assert!(OrderBook::order(1).is_none());
+        assert_eq!(free_balance(&DOT, &ALICE), balance!(1000));
+        assert!(System::events()
+            .iter()
+            .any(|record| record.event == crate::Event::<Runtime>::OrderExpired(1, ALICE).into()));
+    });
+}
+
+#[test]
+fn test_depth_should_aggregate_price_levels() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        place_order(ALICE, OrderSide::Sell, fixed!(3), balance!(1));
+        place_order(BOB, OrderSide::Sell, fixed!(2), balance!(2));
+        place_order(CHARLIE, OrderSide::Sell, fixed!(2), balance!(3));
+        place_order(ALICE, OrderSide::Buy, fixed!(1), balance!(4));
+        assert_eq!(
+            OrderBook::depth(&DEX_ID, &DOT, OrderSide::Sell, 10),
+            vec![
+                PriceLevel {
+                    price: fixed!(2),
+                    amount: balance!(5),
+                    orders: 2,
+                },
+                PriceLevel {
+                    price: fixed!(3),
+                    amount: balance!(1),
+                    orders: 1,
+                },
+            ]
+        );
+        assert_eq!(OrderBook::depth(&DEX_ID, &DOT, OrderSide::Sell, 1).len(), 1);
+        assert_eq!(
+            OrderBook::depth(&DEX_ID, &DOT, OrderSide::Buy, 10),
+            vec![PriceLevel {
+                price: fixed!(1),
+                amount: balance!(4),
+                orders: 1,
+            }]
+        );
+    });
+}
@@ -96,6 +96,7 @@ pub const UNLOCK_FROM_FARM: PermissionId = 13;
 pub const CLAIM_FROM_FARM: PermissionId = 10;
 pub const GET_FARM_INFO: PermissionId = 11;
 pub const GET_FARMER_INFO: PermissionId = 12;
+pub const PAUSE_TRADING: PermissionId = 14;
 
 /// Permissions module declaration.
 impl<T: Config> Pallet<T> {
@@ -162,6 +163,7 @@ impl<T: Config> Pallet<T> {
                     permissions.insert(index, permission_id);
                 }
             });
+            ScopeHolders::<T>::insert(&scope, &account_id, ());
             Self::deposit_event(Event::<T>::PermissionGranted(permission_id, account_id));
             Ok(())
         } else if permission_found {
@@ -171,6 +173,36 @@ impl<T: Config> Pallet<T> {
         }
     }
 
+    /// Method revokes a permission with defined scope from an Account.
+    pub fn revoke_permission_with_scope(
+        who: OwnerId<T>,
+        account_id: HolderId<T>,
+        permission_id: PermissionId,
+        scope: Scope,
+    ) -> Result<(), Error<T>> {
+        let owns_permission = Owners::<T>::get(permission_id, &scope).contains(&who)
+            || (scope != Scope::Unlimited
+                && Owners::<T>::get(permission_id, Scope::Unlimited).contains(&who));
+        ensure!(owns_permission, Error::PermissionNotOwned);
+        Permissions::<T>::try_mutate_exists(&account_id, &scope, |permissions| {
+            let held = permissions.as_mut().ok_or(Error::<T>::PermissionNotFound)?;
+            let index = held
+                .binary_search(&permission_id)
+                .map_err(|_| Error::<T>::PermissionNotFound)?;
+            let _ = held.remove(index);
+            if held.is_empty() {
+                *permissions = None;
+                ScopeHolders::<T>::remove(&scope, &account_id);
+            }
+            Ok::<_, Error<T>>(())
+        })?;
+        if Permissions::<T>::iter_prefix_values(&account_id).count() == 0 {
+            frame_system::Pallet::<T>::dec_consumers(&account_id);
+        }
+        Self::deposit_event(Event::<T>::PermissionRevoked(permission_id, account_id));
+        Ok(())
+    }
+
     /// Method transfers a permission from owner to another Account.
     pub fn transfer_permission(
         who: OwnerId<T>,
@@ -225,10 +257,33 @@ impl<T: Config> Pallet<T> {
                 permissions.insert(index, permission_id);
             }
         });
+        ScopeHolders::<T>::insert(scope, &account_id, ());
         Self::deposit_event(Event::<T>::PermissionCreated(permission_id, account_id));
         Ok(())
     }
 
+    /// Removes `owner` from the owners of `permission_id` in `scope` and releases the reference
+    /// taken when the ownership has been given.
+    pub fn remove_owner(
+        owner: &OwnerId<T>,
+        permission_id: PermissionId,
+        scope: Scope,
+    ) -> Result<(), Error<T>> {
+        let mut owners = Owners::<T>::get(permission_id, &scope);
+        let index = owners
+            .iter()
+            .position(|o| o == owner)
+            .ok_or(Error::<T>::PermissionNotOwned)?;
+        owners.remove(index);
+        if owners.is_empty() {
+            Owners::<T>::remove(permission_id, &scope);
+        } else {
+            Owners::<T>::insert(permission_id, &scope, owners);
+        }
+        frame_system::Pallet::<T>::dec_consumers(owner);
+        Ok(())
+    }
+
     /// Makes `owner` be the owner of `permission_id` in `scope`.
     /// Also, if the permission, that `permission_id` represents, has mode `Mode::Permit`, adds the permission to `holder_id`
     pub fn assign_permission(
@@ -255,6 +310,7 @@ impl<T: Config> Pallet<T> {
                 frame_system::Pallet::<T>::inc_consumers(&holder_id)
                     .map_err(|_| Error::<T>::IncRefError)?;
             }
+            ScopeHolders::<T>::insert(scope, &holder_id, ());
             Permissions::<T>::mutate(&holder_id, scope, |permissions| {
                 if let Err(index) = permissions.binary_search(&permission_id) {
                     permissions.insert(index, permission_id);
@@ -273,6 +329,34 @@ impl<T: Config> Pallet<T> {
         }
     }
 
+    /// Removes every owner of the permissions in the scope and revokes the permissions of every
+    /// holder listed in `ScopeHolders`, e.g. when the entity the scope stands for ceases to exist.
+    pub fn remove_scope(scope: Scope, permission_ids: &[PermissionId]) {
+        for permission_id in permission_ids {
+            for owner in Owners::<T>::take(permission_id, &scope) {
+                frame_system::Pallet::<T>::dec_consumers(&owner);
+            }
+        }
+        let holders: Vec<HolderId<T>> = ScopeHolders::<T>::drain_prefix(&scope)
+            .map(|(holder_id, _)| holder_id)
+            .collect();
+        for holder_id in holders {
+            let revoked = Permissions::<T>::take(&holder_id, &scope);
+            if revoked.is_empty() {
+                continue;
+            }
+            for permission_id in revoked {
+                Self::deposit_event(Event::<T>::PermissionRevoked(
+                    permission_id,
+                    holder_id.clone(),
+                ));
+            }
+            if Permissions::<T>::iter_prefix_values(&holder_id).count() == 0 {
+                frame_system::Pallet::<T>::dec_consumers(&holder_id);
+            }
+        }
+    }
+
     fn account_has_permission(
         holder_id: &HolderId<T>,
         scope: &Scope,
@@ -319,6 +403,8 @@ pub mod pallet {
         PermissionCreated(u32, AccountIdOf<T>),
         /// Permission was assigned to the account in the scope. [permission, who]
         PermissionAssigned(u32, AccountIdOf<T>),
+        /// Permission was revoked from a holder. [permission, who]
+        PermissionRevoked(u32, AccountIdOf<T>),
     }
 
     #[pallet::error]
@@ -366,6 +452,12 @@ pub mod pallet {
         ValueQuery,
     >;
 
+    /// Holders of the permissions in every scope. The holders are listed in the scope since
+    /// the index has been introduced, the earlier holders are only kept in `Permissions`.
+    #[pallet::storage]
+    pub type ScopeHolders<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, Scope, Blake2_128Concat, HolderId<T>, (), ValueQuery>;
+
     #[pallet::genesis_config]
     pub struct GenesisConfig<T: Config> {
         pub initial_permission_owners: Vec<(PermissionId, Scope, Vec<OwnerId<T>>)>,
@@ -408,6 +500,7 @@ pub mod pallet {
                 (CLAIM_FROM_FARM, Mode::Permit),
                 (GET_FARM_INFO, Mode::Permit),
                 (GET_FARMER_INFO, Mode::Permit),
+                (PAUSE_TRADING, Mode::Permit),
             ]
             .iter()
             .for_each(|(permission, mode)| {
@@ -421,6 +514,7 @@ pub mod pallet {
                     permissions.sort();
                     frame_system::Pallet::<T>::inc_consumers(&holder_id).unwrap();
                     Permissions::<T>::insert(holder_id, scope, permissions);
+                    ScopeHolders::<T>::insert(scope, holder_id, ());
                 });
         }
     }
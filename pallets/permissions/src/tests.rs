@@ -302,3 +302,56 @@ fn permission_create_fails_with_permission_already_exists_error() {
         }
     });
 }
+
+#[test]
+fn permission_remove_owner_passes() {
+    ExtBuilder::default().build().execute_with(|| {
+        let scope = Scope::Limited(H512::repeat_byte(1));
+        assert_ok!(Permissions::assign_permission(ALICE, &BOB, BURN, scope));
+        let consumers = frame_system::Pallet::<Runtime>::consumers(&ALICE);
+        assert_ok!(Permissions::remove_owner(&ALICE, BURN, scope));
+        assert!(Owners::<Runtime>::get(BURN, scope).is_empty());
+        assert_eq!(
+            frame_system::Pallet::<Runtime>::consumers(&ALICE),
+            consumers - 1
+        );
+        match Permissions::remove_owner(&ALICE, BURN, scope) {
+            Err(Error::<Runtime>::PermissionNotOwned) => {}
+            result => panic!("{:?}", result),
+        }
+    });
+}
+
+#[test]
+fn permission_remove_scope_passes() {
+    ExtBuilder::default().build().execute_with(|| {
+        let scope = Scope::Limited(H512::repeat_byte(1));
+        let other_scope = Scope::Limited(H512::repeat_byte(2));
+        let bob_consumers = frame_system::Pallet::<Runtime>::consumers(&BOB);
+        assert_ok!(Permissions::assign_permission(ALICE, &BOB, BURN, scope));
+        assert_ok!(Permissions::assign_permission(ALICE, &JOHN, MINT, scope));
+        assert_ok!(Permissions::assign_permission(
+            ALICE,
+            &JOHN,
+            MINT,
+            other_scope
+        ));
+        Permissions::remove_scope(scope, &[BURN, MINT]);
+        assert!(Owners::<Runtime>::get(BURN, scope).is_empty());
+        assert!(Owners::<Runtime>::get(MINT, scope).is_empty());
+        assert!(crate::Permissions::<Runtime>::get(BOB, scope).is_empty());
+        assert!(crate::Permissions::<Runtime>::get(JOHN, scope).is_empty());
+        assert!(Permissions::check_permission_with_scope(BOB, BURN, &scope).is_err());
+        assert_eq!(
+            frame_system::Pallet::<Runtime>::consumers(&BOB),
+            bob_consumers
+        );
+        // Other scopes are left intact.
+        assert_eq!(Owners::<Runtime>::get(MINT, other_scope), vec![ALICE]);
+        assert_ok!(Permissions::check_permission_with_scope(
+            JOHN,
+            MINT,
+            &other_scope
+        ));
+    });
+}
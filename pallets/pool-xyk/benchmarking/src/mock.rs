@@ -32,7 +32,7 @@
 
 use crate::{Config, *};
 use common::mock::ExistentialDeposits;
-use common::{hash, Amount, DEXInfo};
+use common::{hash, Amount, BasisPoints, DEXInfo};
 use currencies::BasicCurrencyAdapter;
 
 use frame_support::traits::GenesisBuild;
@@ -71,6 +71,7 @@ parameter_types! {
     pub const GetBurnUpdateFrequency: BlockNumber = 14400;
     pub GetIncentiveAssetId: AssetId = common::PSWAP.into();
     pub GetParliamentAccountId: AccountId = AccountId32::from([8; 32]);
+    pub const MaxDexFee: BasisPoints = 100;
 }
 
 construct_runtime! {
@@ -84,7 +85,7 @@ construct_runtime! {
         Currencies: currencies::{Module, Call, Storage, Event<T>},
         Assets: assets::{Module, Call, Config<T>, Storage, Event<T>},
         Balances: pallet_balances::{Module, Call, Storage, Event<T>},
-        DexManager: dex_manager::{Module, Call, Config<T>, Storage},
+        DexManager: dex_manager::{Module, Call, Config<T>, Storage, Event<T>},
         TradingPair: trading_pair::{Module, Call, Config<T>, Storage, Event<T>},
         Permissions: permissions::{Module, Call, Config<T>, Storage, Event<T>},
         DexApi: dex_api::{Module, Call, Config, Storage, Event<T>},
@@ -145,6 +146,7 @@ impl assets::Config for Runtime {
     type AssetId = AssetId;
     type GetBaseAssetId = GetBaseAssetId;
     type Currency = currencies::Module<Runtime>;
+    type IsAssetInUse = ();
     type WeightInfo = ();
 }
 
@@ -163,7 +165,11 @@ impl pallet_balances::Config for Runtime {
     type MaxLocks = ();
 }
 
-impl dex_manager::Config for Runtime {}
+impl dex_manager::Config for Runtime {
+    type Event = Event;
+    type MaxFee = MaxDexFee;
+    type WeightInfo = ();
+}
 
 impl trading_pair::Config for Runtime {
     type Event = Event;
@@ -177,13 +183,7 @@ impl permissions::Config for Runtime {
 
 impl dex_api::Config for Runtime {
     type Event = Event;
-    type MockLiquiditySource = ();
-    type MockLiquiditySource2 = ();
-    type MockLiquiditySource3 = ();
-    type MockLiquiditySource4 = ();
-    type BondingCurvePool = ();
-    type MulticollateralBondingCurvePool = ();
-    type XYKPool = pool_xyk::Module<Runtime>;
+    type LiquiditySources = (pool_xyk::Module<Runtime>,);
     type WeightInfo = ();
 }
 
@@ -208,6 +208,7 @@ impl pool_xyk::Config for Runtime {
     type PolySwapAction =
         pool_xyk::PolySwapAction<AssetId, TechAssetId, Balance, AccountId, TechAccountId>;
     type EnsureDEXManager = dex_manager::Pallet<Runtime>;
+    type EnsureTradingNotPaused = ();
     type WeightInfo = ();
 }
 
@@ -243,6 +244,7 @@ impl Default for ExtBuilder {
                 DEXInfo {
                     base_asset_id: GetBaseAssetId::get(),
                     is_public: true,
+                    default_fee: 30,
                 },
             )],
             initial_permission_owners: vec![
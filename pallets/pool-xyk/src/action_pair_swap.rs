@@ -122,7 +122,10 @@ impl<T: Config> common::SwapRulesValidation<AccountIdOf<T>, TechAccountIdOf<T>,
 
         match self.get_fee_from_destination {
             None => {
+                let (_, base_asset_id) =
+                    Pallet::<T>::get_dex_and_base_asset_of_pool(&self.pool_account)?;
                 let is_fee_from_d = Pallet::<T>::decide_is_fee_from_destination(
+                    &base_asset_id,
                     &self.source.asset,
                     &self.destination.asset,
                 )?;
@@ -407,7 +410,11 @@ impl<T: Config> common::SwapAction<AccountIdOf<T>, TechAccountIdOf<T>, T>
                 <assets::Pallet<T>>::free_balance(&self.source.asset, &pool_account_repr_sys)?;
             let balance_b =
                 <assets::Pallet<T>>::free_balance(&self.destination.asset, &pool_account_repr_sys)?;
+            let (dex_id, base_asset_id) =
+                Pallet::<T>::get_dex_and_base_asset_of_pool(&self.pool_account)?;
             Pallet::<T>::update_reserves(
+                &dex_id,
+                &base_asset_id,
                 &self.source.asset,
                 &self.destination.asset,
                 (&balance_a, &balance_b),
@@ -260,7 +260,11 @@ impl<T: Config> common::SwapAction<AccountIdOf<T>, TechAccountIdOf<T>, T>
             <assets::Pallet<T>>::free_balance(&(self.destination.0).asset, &pool_account_repr_sys)?;
         let balance_b =
             <assets::Pallet<T>>::free_balance(&(self.destination.1).asset, &pool_account_repr_sys)?;
+        let (dex_id, base_asset_id) =
+            Pallet::<T>::get_dex_and_base_asset_of_pool(&self.pool_account)?;
         Pallet::<T>::update_reserves(
+            &dex_id,
+            &base_asset_id,
             &(self.destination.0).asset,
             &(self.destination.1).asset,
             (&balance_a, &balance_b),
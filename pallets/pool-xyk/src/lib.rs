@@ -30,27 +30,29 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+use codec::{Decode, Encode};
 use frame_support::dispatch::{DispatchError, DispatchResult};
-use frame_support::traits::Get;
 use frame_support::weights::Weight;
 use frame_support::{ensure, Parameter};
 use frame_system::ensure_signed;
 use sp_std::collections::btree_set::BTreeSet;
 
-use common::prelude::{Balance, EnsureDEXManager, SwapAmount, SwapOutcome};
+use common::prelude::{Balance, EnsureDEXManager, FixedWrapper, SwapAmount, SwapOutcome};
 use common::{
-    balance, hash, AssetName, AssetSymbol, EnsureTradingPairExists, FromGenericPair,
-    GetPoolReserves, LiquiditySource, LiquiditySourceType, ManagementMode, ToFeeAccount,
+    balance, hash, AssetName, AssetSymbol, EnsureTradingNotPaused, EnsureTradingPairExists,
+    FromGenericPair, GetPoolReserves, GetSwapCurve, IsAssetInUse, LiquiditySource,
+    LiquiditySourceType, ManagementMode, SwapCurve, TaggedLiquiditySource, ToFeeAccount,
 };
 use orml_traits::currency::MultiCurrency;
 use permissions::{Scope, BURN, MINT};
 
 mod aliases;
 use aliases::{
-    AccountIdOf, AssetIdOf, DEXIdOf, DepositLiquidityActionOf, PairSwapActionOf,
+    AccountIdOf, AssetIdOf, DEXIdOf, DEXManager, DepositLiquidityActionOf, PairSwapActionOf,
     PolySwapActionStructOf, TechAccountIdOf, TechAssetIdOf, WithdrawLiquidityActionOf,
 };
 
+pub mod migrations;
 pub mod weights;
 
 #[cfg(test)]
@@ -78,6 +80,15 @@ pub use operations::*;
 
 const MIN_LIQUIDITY: u128 = 1000;
 
+/// Storage layout version, used to run the migrations only once.
+#[derive(Debug, Encode, Decode, Clone, Copy, PartialEq, Eq)]
+pub enum StorageVersion {
+    /// `Reserves` and `Properties` are keyed by the asset pair only.
+    V1,
+    /// `Reserves` and `Properties` are keyed by the DEX and the asset pair.
+    V2,
+}
+
 pub trait WeightInfo {
     fn swap_pair() -> Weight;
     fn deposit_liquidity() -> Weight;
@@ -94,7 +105,7 @@ impl<T: Config> Pallet<T> {
         fees_account_id: &T::AccountId,
         marker_asset_id: &T::AssetId,
     ) -> DispatchResult {
-        let base_asset_id: T::AssetId = T::GetBaseAssetId::get();
+        let base_asset_id = DEXManager::<T>::get_dex_info(dex_id)?.base_asset_id;
         let (sorted_asset_a, sorted_asset_b) = if &base_asset_id == asset_a {
             (asset_a, asset_b)
         } else if &base_asset_id == asset_b {
@@ -112,8 +123,8 @@ impl<T: Config> Pallet<T> {
             LiquiditySourceType::XYKPool,
         )?;
         Properties::<T>::insert(
-            sorted_asset_a,
-            sorted_asset_b,
+            dex_id,
+            (sorted_asset_a, sorted_asset_b),
             (
                 reserves_account_id.clone(),
                 fees_account_id.clone(),
@@ -124,15 +135,16 @@ impl<T: Config> Pallet<T> {
     }
 
     fn update_reserves(
+        dex_id: &T::DEXId,
+        base_asset_id: &T::AssetId,
         asset_a: &T::AssetId,
         asset_b: &T::AssetId,
         balance_pair: (&Balance, &Balance),
     ) {
-        let base_asset_id: T::AssetId = T::GetBaseAssetId::get();
-        if base_asset_id == asset_a.clone() {
-            Reserves::<T>::insert(asset_a, asset_b, (balance_pair.0, balance_pair.1));
-        } else if base_asset_id == asset_b.clone() {
-            Reserves::<T>::insert(asset_b, asset_a, (balance_pair.1, balance_pair.0));
+        if base_asset_id == asset_a {
+            Reserves::<T>::insert(dex_id, (asset_a, asset_b), (balance_pair.0, balance_pair.1));
+        } else if base_asset_id == asset_b {
+            Reserves::<T>::insert(dex_id, (asset_b, asset_a), (balance_pair.1, balance_pair.0));
         } else {
             let hash_key = common::comm_merkle_op(asset_a, asset_b);
             let (pair_u, pair_v) = common::sort_with_hash_key(
@@ -140,7 +152,7 @@ impl<T: Config> Pallet<T> {
                 (asset_a, balance_pair.0),
                 (asset_b, balance_pair.1),
             );
-            Reserves::<T>::insert(pair_u.0, pair_v.0, (pair_u.1, pair_v.1));
+            Reserves::<T>::insert(dex_id, (pair_u.0, pair_v.0), (pair_u.1, pair_v.1));
         }
     }
 
@@ -380,6 +392,24 @@ impl<T: Config> LiquiditySource<T::DEXId, T::AccountId, T::AssetId, Balance, Dis
             &mut action,
             Some(sender),
         )?;
+        // Pool stays in place when it is disabled for the trading pair or the pair is removed, so
+        // it is checked here that the pool may still be traded.
+        let base_asset_id = DEXManager::<T>::get_dex_info(dex_id)?.base_asset_id;
+        let target_asset_id = if *input_asset_id == base_asset_id {
+            output_asset_id
+        } else {
+            input_asset_id
+        };
+        ensure!(
+            trading_pair::Pallet::<T>::is_source_enabled_for_trading_pair(
+                dex_id,
+                &base_asset_id,
+                target_asset_id,
+                LiquiditySourceType::XYKPool,
+            )
+            .unwrap_or(false),
+            Error::<T>::PoolIsNotEnabledForTradingPair
+        );
 
         // It is guarantee that unwrap is always ok.
         // Clone is used here because action is used for perform_create_swap_unchecked.
@@ -410,14 +440,96 @@ impl<T: Config> LiquiditySource<T::DEXId, T::AccountId, T::AssetId, Balance, Dis
 
         retval
     }
+
+    fn fee_account(
+        dex_id: &T::DEXId,
+        input_asset_id: &T::AssetId,
+        output_asset_id: &T::AssetId,
+    ) -> Option<T::AccountId> {
+        let (_, tech_acc_id) = Pallet::<T>::tech_account_from_dex_and_asset_pair(
+            *dex_id,
+            *input_asset_id,
+            *output_asset_id,
+        )
+        .ok()?;
+        let fee_acc_id = Pallet::<T>::get_fee_account(&tech_acc_id).ok()?;
+        technical::Pallet::<T>::tech_account_id_to_account_id(&fee_acc_id).ok()
+    }
 }
 
-impl<T: Config> GetPoolReserves<T::AssetId> for Pallet<T> {
-    fn reserves(base_asset: &T::AssetId, other_asset: &T::AssetId) -> (Balance, Balance) {
-        Reserves::<T>::get(base_asset, other_asset)
+impl<T: Config> GetPoolReserves<T::DEXId, T::AssetId> for Pallet<T> {
+    fn reserves(
+        dex_id: &T::DEXId,
+        base_asset: &T::AssetId,
+        other_asset: &T::AssetId,
+    ) -> (Balance, Balance) {
+        Reserves::<T>::get(dex_id, (base_asset, other_asset))
     }
 }
 
+impl<T: Config> IsAssetInUse<T::AssetId> for Pallet<T> {
+    fn is_asset_in_use(asset_id: &T::AssetId) -> bool {
+        MarkerTokensIndex::<T>::get().contains(asset_id)
+            || DEXManager::<T>::list_dex_ids().into_iter().any(|dex_id| {
+                DEXManager::<T>::get_dex_info(&dex_id)
+                    .map(|dex_info| {
+                        Properties::<T>::contains_key(dex_id, (dex_info.base_asset_id, *asset_id))
+                    })
+                    .unwrap_or(false)
+            })
+    }
+}
+
+impl<T: Config> GetSwapCurve<T::DEXId, T::AssetId> for Pallet<T> {
+    fn swap_curve(
+        dex_id: &T::DEXId,
+        input_asset_id: &T::AssetId,
+        output_asset_id: &T::AssetId,
+    ) -> Option<SwapCurve> {
+        let (trading_pair, tech_acc_id) = Pallet::<T>::tech_account_from_dex_and_asset_pair(
+            *dex_id,
+            *input_asset_id,
+            *output_asset_id,
+        )
+        .ok()?;
+        let get_fee_from_destination = Pallet::<T>::decide_is_fee_from_destination(
+            &trading_pair.base_asset_id.into(),
+            input_asset_id,
+            output_asset_id,
+        )
+        .ok()?;
+        let unit = balance!(1);
+        let (input_reserve, output_reserve, fee) = if get_fee_from_destination {
+            let (base_reserve, target_reserve) =
+                Reserves::<T>::get(dex_id, (output_asset_id, input_asset_id));
+            let fee =
+                Pallet::<T>::get_fee_for_destination(output_asset_id, &tech_acc_id, &unit).ok()?;
+            // Fee taken from the output shrinks the output reserve in the same proportion.
+            let base_reserve = (FixedWrapper::from(base_reserve) * FixedWrapper::from(unit - fee))
+                .try_into_balance()
+                .ok()?;
+            (target_reserve, base_reserve, 0)
+        } else {
+            let (base_reserve, target_reserve) =
+                Reserves::<T>::get(dex_id, (input_asset_id, output_asset_id));
+            let fee = Pallet::<T>::get_fee_for_source(input_asset_id, &tech_acc_id, &unit).ok()?;
+            (base_reserve, target_reserve, fee)
+        };
+        if input_reserve == 0 || output_reserve == 0 {
+            return None;
+        }
+        Some(SwapCurve::ConstantProduct {
+            input_reserve,
+            output_reserve,
+            fee: FixedWrapper::from(fee).get().ok()?,
+        })
+    }
+}
+
+impl<T: Config> TaggedLiquiditySource<T::DEXId, T::AccountId, T::AssetId> for Pallet<T> {
+    const SOURCE_TYPE: LiquiditySourceType = LiquiditySourceType::XYKPool;
+}
+
 pub use pallet::*;
 
 #[frame_support::pallet]
@@ -446,6 +558,13 @@ pub mod pallet {
             + Into<<Self as technical::Config>::SwapAction>
             + From<PolySwapActionStructOf<Self>>;
         type EnsureDEXManager: EnsureDEXManager<Self::DEXId, Self::AccountId, DispatchError>;
+        /// Emergency pause checked before swaps and liquidity deposits, withdrawals are
+        /// always allowed.
+        type EnsureTradingNotPaused: EnsureTradingNotPaused<
+            Self::DEXId,
+            Self::AssetId,
+            DispatchError,
+        >;
 
         /// Weight information for extrinsics in this pallet.
         type WeightInfo: WeightInfo;
@@ -456,7 +575,11 @@ pub mod pallet {
     pub struct Pallet<T>(PhantomData<T>);
 
     #[pallet::hooks]
-    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_runtime_upgrade() -> Weight {
+            migrations::migrate_pools::<T>()
+        }
+    }
 
     #[pallet::call]
     impl<T: Config> Pallet<T> {
@@ -470,6 +593,12 @@ pub mod pallet {
             swap_amount: SwapAmount<Balance>,
         ) -> DispatchResultWithPostInfo {
             let source = ensure_signed(origin)?;
+            T::EnsureTradingNotPaused::ensure_trading_not_paused(
+                &dex_id,
+                LiquiditySourceType::XYKPool,
+                &input_asset_id,
+                &output_asset_id,
+            )?;
             Pallet::<T>::exchange(
                 &source,
                 &receiver,
@@ -493,6 +622,12 @@ pub mod pallet {
             input_b_min: Balance,
         ) -> DispatchResultWithPostInfo {
             let source = ensure_signed(origin)?;
+            T::EnsureTradingNotPaused::ensure_trading_not_paused(
+                &dex_id,
+                LiquiditySourceType::XYKPool,
+                &input_asset_a,
+                &input_asset_b,
+            )?;
             Pallet::<T>::deposit_liquidity_unchecked(
                 source,
                 dex_id,
@@ -715,10 +850,12 @@ pub mod pallet {
         UnableToConvertAssetToTechAssetId,
         /// Unable to get XOR part from marker asset.
         UnableToGetXORPartFromMarkerAsset,
+        /// The pool is not enabled as a liquidity source for the trading pair.
+        PoolIsNotEnabledForTradingPair,
     }
 
     /// Updated after last liquidity change operation.
-    /// [Base Asset Id (XOR) -> Target Asset Id => (Base Balance, Target Balance)].
+    /// [DEX Id -> (Base Asset Id, Target Asset Id) => (Base Balance, Target Balance)].
     /// This storage records is not used as source of information, but used as quick cache for
     /// information that comes from balances for assets from technical accounts.
     /// For example, communication with technical accounts and their storage is not needed, and this
@@ -728,9 +865,9 @@ pub mod pallet {
     pub type Reserves<T: Config> = StorageDoubleMap<
         _,
         Blake2_128Concat,
-        T::AssetId,
+        T::DEXId,
         Blake2_128Concat,
-        T::AssetId,
+        (T::AssetId, T::AssetId),
         (Balance, Balance),
         ValueQuery,
     >;
@@ -740,15 +877,38 @@ pub mod pallet {
     #[pallet::getter(fn marker_tokens_index)]
     pub type MarkerTokensIndex<T: Config> = StorageValue<_, BTreeSet<T::AssetId>, ValueQuery>;
 
-    /// Properties of particular pool. [Reserves Account Id, Fees Account Id, Marker Asset Id]
+    /// Properties of particular pool, keyed by the DEX and the (Base Asset Id, Target Asset Id).
+    /// [Reserves Account Id, Fees Account Id, Marker Asset Id]
     #[pallet::storage]
     #[pallet::getter(fn properties)]
     pub type Properties<T: Config> = StorageDoubleMap<
         _,
         Blake2_128Concat,
-        T::AssetId,
+        T::DEXId,
         Blake2_128Concat,
-        T::AssetId,
+        (T::AssetId, T::AssetId),
         (T::AccountId, T::AccountId, T::AssetId),
     >;
+
+    #[pallet::type_value]
+    pub(super) fn DefaultForPalletStorageVersion() -> StorageVersion {
+        StorageVersion::V1
+    }
+
+    /// Storage layout version, chains started before the versioning have `V1`.
+    #[pallet::storage]
+    #[pallet::getter(fn pallet_storage_version)]
+    pub(super) type PalletStorageVersion<T: Config> =
+        StorageValue<_, StorageVersion, ValueQuery, DefaultForPalletStorageVersion>;
+
+    #[pallet::genesis_config]
+    #[derive(Default)]
+    pub struct GenesisConfig {}
+
+    #[pallet::genesis_build]
+    impl<T: Config> GenesisBuild<T> for GenesisConfig {
+        fn build(&self) {
+            PalletStorageVersion::<T>::put(StorageVersion::V2);
+        }
+    }
 }
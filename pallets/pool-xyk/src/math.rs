@@ -33,38 +33,50 @@
 use frame_support::dispatch::DispatchError;
 use frame_support::ensure;
 
-use common::balance;
 use common::prelude::{Balance, FixedWrapper};
+use common::{balance, BasisPoints, TechAccountId};
 
-use crate::aliases::{AssetIdOf, TechAccountIdOf};
+use crate::aliases::{AccountIdOf, AssetIdOf, DEXIdOf, DEXManager, TechAccountIdOf, TechAssetIdOf};
 use crate::{to_balance, to_fixed_wrapper};
 
 use crate::{Config, Error, Pallet};
 
 impl<T: Config> Pallet<T> {
+    /// Default fee of the DEX the pool belongs to, in basis points.
+    fn get_pool_fee(tech_acc: &TechAccountIdOf<T>) -> Result<BasisPoints, DispatchError> {
+        let tech_acc: TechAccountId<AccountIdOf<T>, TechAssetIdOf<T>, DEXIdOf<T>> =
+            tech_acc.clone().into();
+        match tech_acc {
+            TechAccountId::Pure(dex_id, _) => {
+                Ok(DEXManager::<T>::get_dex_info(&dex_id)?.default_fee)
+            }
+            _ => Err(Error::<T>::PoolIsInvalid.into()),
+        }
+    }
+
     #[inline]
     pub fn get_fee_for_source(
         _asset_id: &AssetIdOf<T>,
-        _tech_acc: &TechAccountIdOf<T>,
+        tech_acc: &TechAccountIdOf<T>,
         x_in: &Balance,
     ) -> Result<Balance, DispatchError> {
         let fxw_x_in = FixedWrapper::from(*x_in);
-        //TODO: get this value from DEXInfo.
-        let result =
-            (fxw_x_in * FixedWrapper::from(balance!(3))) / FixedWrapper::from(balance!(1000));
+        let fee = Balance::from(Pallet::<T>::get_pool_fee(tech_acc)?);
+        let result = (fxw_x_in * FixedWrapper::from(fee * balance!(1)))
+            / FixedWrapper::from(balance!(10000));
         Ok(to_balance!(result))
     }
 
     #[inline]
     pub fn get_fee_for_destination(
         _asset_id: &AssetIdOf<T>,
-        _tech_acc: &TechAccountIdOf<T>,
+        tech_acc: &TechAccountIdOf<T>,
         y_out: &Balance,
     ) -> Result<Balance, DispatchError> {
         let fxw_y_out = FixedWrapper::from(*y_out);
-        //TODO: get this value from DEXInfo.
-        let result =
-            (fxw_y_out * FixedWrapper::from(balance!(3))) / FixedWrapper::from(balance!(1000));
+        let fee = Balance::from(Pallet::<T>::get_pool_fee(tech_acc)?);
+        let result = (fxw_y_out * FixedWrapper::from(fee * balance!(1)))
+            / FixedWrapper::from(balance!(10000));
         Ok(to_balance!(result))
     }
 
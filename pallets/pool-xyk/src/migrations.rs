@@ -0,0 +1,104 @@
+// This file is part of the SORA network and Polkaswap app.
+
+// Copyright (c) 2020, 2021, Polka Biome Ltd. All rights reserved.
+// SPDX-License-Identifier: BSD-4-Clause
+
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+
+// Redistributions of source code must retain the above copyright notice, this list
+// of conditions and the following disclaimer.
+// Redistributions in binary form must reproduce the above copyright notice, this
+// list of conditions and the following disclaimer in the documentation and/or other
+// materials provided with the distribution.
+//
+// All advertising materials mentioning features or use of this software must display
+// the following acknowledgement: This product includes software developed by Polka Biome
+// Ltd., SORA, and Polkaswap.
+//
+// Neither the name of the Polka Biome Ltd. nor the names of its contributors may be used
+// to endorse or promote products derived from this software without specific prior written permission.
+
+// THIS SOFTWARE IS PROVIDED BY Polka Biome Ltd. AS IS AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL Polka Biome Ltd. BE LIABLE FOR ANY
+// DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS;
+// OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT,
+// STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+use crate::{Config, Pallet, PalletStorageVersion, Properties, Reserves, StorageVersion};
+use codec::Decode;
+use common::prelude::Balance;
+use frame_support::storage::migration::StorageIterator;
+use frame_support::storage::StoragePrefixedMap;
+use frame_support::traits::Get;
+use frame_support::weights::Weight;
+use sp_std::collections::btree_map::BTreeMap;
+use sp_std::vec::Vec;
+
+/// Length of the `Blake2_128Concat` hash preceding every encoded key.
+const BLAKE2_128_HASH_LENGTH: usize = 16;
+
+/// Decodes the `[Base Asset Id, Target Asset Id]` keys of a `StorageVersion::V1` double map.
+fn decode_pair_key<T: Config>(mut key: &[u8]) -> Option<(T::AssetId, T::AssetId)> {
+    key = key.get(BLAKE2_128_HASH_LENGTH..)?;
+    let base_asset_id = T::AssetId::decode(&mut key).ok()?;
+    key = key.get(BLAKE2_128_HASH_LENGTH..)?;
+    let target_asset_id = T::AssetId::decode(&mut key).ok()?;
+    Some((base_asset_id, target_asset_id))
+}
+
+/// Keys the `Properties` and `Reserves` by the DEX of the pool. Runs only for
+/// `StorageVersion::V1`.
+///
+/// The DEX is taken from the technical account holding the pool reserves, the entries that
+/// can't be attributed to a DEX are dropped.
+pub fn migrate_pools<T: Config>() -> Weight {
+    if PalletStorageVersion::<T>::get() != StorageVersion::V1 {
+        return T::DbWeight::get().reads(1);
+    }
+    PalletStorageVersion::<T>::put(StorageVersion::V2);
+    let properties: Vec<_> = StorageIterator::<(T::AccountId, T::AccountId, T::AssetId)>::new(
+        Properties::<T>::module_prefix(),
+        Properties::<T>::storage_prefix(),
+    )
+    .drain()
+    .collect();
+    let reserves: Vec<_> = StorageIterator::<(Balance, Balance)>::new(
+        Reserves::<T>::module_prefix(),
+        Reserves::<T>::storage_prefix(),
+    )
+    .drain()
+    .collect();
+    let mut reads: Weight = 1;
+    let mut writes: Weight = 1;
+    let mut pool_dex_ids = BTreeMap::new();
+    for (key, pool_properties) in properties {
+        reads += 2;
+        writes += 1;
+        let pair = match decode_pair_key::<T>(&key) {
+            Some(pair) => pair,
+            None => continue,
+        };
+        let dex_id = technical::Pallet::<T>::lookup_tech_account_id(&pool_properties.0)
+            .and_then(|pool_account| Pallet::<T>::get_dex_and_base_asset_of_pool(&pool_account));
+        if let Ok((dex_id, _)) = dex_id {
+            writes += 1;
+            Properties::<T>::insert(dex_id, pair, pool_properties);
+            pool_dex_ids.insert(pair, dex_id);
+        }
+    }
+    for (key, pool_reserves) in reserves {
+        reads += 1;
+        writes += 1;
+        let pair = decode_pair_key::<T>(&key);
+        if let Some((pair, dex_id)) =
+            pair.and_then(|pair| pool_dex_ids.get(&pair).map(|dex_id| (pair, dex_id)))
+        {
+            writes += 1;
+            Reserves::<T>::insert(dex_id, pair, pool_reserves);
+        }
+    }
+    T::DbWeight::get().reads_writes(reads, writes)
+}
@@ -30,7 +30,7 @@
 
 use crate::{self as pool_xyk, Config};
 use common::prelude::Balance;
-use common::{balance, hash, DEXInfo};
+use common::{balance, hash, BasisPoints, DEXInfo};
 use currencies::BasicCurrencyAdapter;
 use frame_support::traits::GenesisBuild;
 use frame_support::weights::Weight;
@@ -72,6 +72,7 @@ parameter_types! {
     pub const GetDefaultSubscriptionFrequency: BlockNumber = 10;
     pub const GetBurnUpdateFrequency: BlockNumber = 14400;
     pub GetParliamentAccountId: AccountId = AccountId32::from([8; 32]);
+    pub const MaxDexFee: BasisPoints = 100;
 }
 
 parameter_type_with_key! {
@@ -88,7 +89,7 @@ construct_runtime! {
     {
         System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
         Permissions: permissions::{Pallet, Call, Config<T>, Storage, Event<T>},
-        DexManager: dex_manager::{Pallet, Call, Config<T>, Storage},
+        DexManager: dex_manager::{Pallet, Call, Config<T>, Storage, Event<T>},
         TradingPair: trading_pair::{Pallet, Call, Config<T>, Storage, Event<T>},
         Balances: pallet_balances::{Pallet, Call, Storage, Event<T>},
         Tokens: tokens::{Pallet, Call, Config<T>, Storage, Event<T>},
@@ -144,7 +145,11 @@ impl permissions::Config for Runtime {
     type Event = Event;
 }
 
-impl dex_manager::Config for Runtime {}
+impl dex_manager::Config for Runtime {
+    type Event = Event;
+    type MaxFee = MaxDexFee;
+    type WeightInfo = ();
+}
 
 impl trading_pair::Config for Runtime {
     type Event = Event;
@@ -194,6 +199,7 @@ impl assets::Config for Runtime {
     type AssetId = AssetId;
     type GetBaseAssetId = GetBaseAssetId;
     type Currency = currencies::Module<Runtime>;
+    type IsAssetInUse = (trading_pair::Pallet<Runtime>, pool_xyk::Pallet<Runtime>);
     type WeightInfo = ();
 }
 
@@ -234,6 +240,7 @@ impl Config for Runtime {
     type PolySwapAction =
         crate::PolySwapAction<AssetId, TechAssetId, Balance, AccountId, TechAccountId>;
     type EnsureDEXManager = dex_manager::Pallet<Runtime>;
+    type EnsureTradingNotPaused = ();
     type WeightInfo = ();
 }
 
@@ -248,6 +255,7 @@ pub fn BOB() -> AccountId {
 }
 
 pub const DEX_A_ID: DEXId = 220;
+pub const DEX_B_ID: DEXId = 221;
 
 pub struct ExtBuilder {
     initial_dex_list: Vec<(DEXId, DEXInfo<AssetId>)>,
@@ -259,13 +267,24 @@ pub struct ExtBuilder {
 impl Default for ExtBuilder {
     fn default() -> Self {
         Self {
-            initial_dex_list: vec![(
-                DEX_A_ID,
-                DEXInfo {
-                    base_asset_id: GoldenTicket.into(),
-                    is_public: true,
-                },
-            )],
+            initial_dex_list: vec![
+                (
+                    DEX_A_ID,
+                    DEXInfo {
+                        base_asset_id: GoldenTicket.into(),
+                        is_public: true,
+                        default_fee: 30,
+                    },
+                ),
+                (
+                    DEX_B_ID,
+                    DEXInfo {
+                        base_asset_id: GoldenTicket.into(),
+                        is_public: true,
+                        default_fee: 30,
+                    },
+                ),
+            ],
             endowed_accounts: vec![
                 (ALICE(), RedPepper.into(), balance!(99000)),
                 (ALICE(), BlackPepper.into(), balance!(2000000)),
@@ -273,9 +292,13 @@ impl Default for ExtBuilder {
             ],
             initial_permission_owners: vec![
                 (MANAGE_DEX, Scope::Limited(hash(&DEX_A_ID)), vec![BOB()]),
+                (MANAGE_DEX, Scope::Limited(hash(&DEX_B_ID)), vec![BOB()]),
                 (TRANSFER, Scope::Unlimited, vec![ALICE()]),
             ],
-            initial_permissions: vec![(BOB(), Scope::Limited(hash(&DEX_A_ID)), vec![MANAGE_DEX])],
+            initial_permissions: vec![
+                (BOB(), Scope::Limited(hash(&DEX_A_ID)), vec![MANAGE_DEX]),
+                (BOB(), Scope::Limited(hash(&DEX_B_ID)), vec![MANAGE_DEX]),
+            ],
         }
     }
 }
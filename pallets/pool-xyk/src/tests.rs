@@ -28,11 +28,14 @@
 // STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+use codec::Encode;
 use common::prelude::{SwapAmount, SwapOutcome};
 use common::{
-    balance, AssetName, AssetSymbol, Balance, LiquiditySource, LiquiditySourceType, ToFeeAccount,
+    balance, AssetName, AssetSymbol, Balance, GetPoolReserves, LiquiditySource,
+    LiquiditySourceType, ToFeeAccount,
 };
-use frame_support::{assert_noop, assert_ok};
+use frame_support::storage::{unhashed, StoragePrefixedMap};
+use frame_support::{assert_noop, assert_ok, Blake2_128Concat, StorageHasher};
 
 use crate::mock::*;
 
@@ -160,7 +163,7 @@ impl<'a> crate::Module<Runtime> {
                 .expect("Failed to get marking asset")
                 .into();
             assert_eq!(
-                crate::Module::<Runtime>::properties(base_asset, target_asset),
+                crate::Module::<Runtime>::properties(dex_id, (base_asset, target_asset)),
                 Some((repr.clone(), fee_repr.clone(), tech_asset))
             );
             /*
@@ -783,6 +786,37 @@ fn swap_pair_swap_fail_with_invalid_balance() {
     )]);
 }
 
+#[test]
+fn swap_pair_should_fail_with_pool_disabled_for_trading_pair() {
+    crate::Module::<Runtime>::preset_deposited_pool(vec![Rc::new(
+        |dex_id, _, _, _, _, _, _, _| {
+            assert_ok!(
+                trading_pair::Pallet::<Runtime>::disable_source_for_trading_pair(
+                    Origin::signed(BOB()),
+                    dex_id,
+                    GoldenTicket.into(),
+                    BlackPepper.into(),
+                    LiquiditySourceType::XYKPool,
+                )
+            );
+            assert_noop!(
+                crate::Module::<Runtime>::swap_pair(
+                    Origin::signed(ALICE()),
+                    ALICE(),
+                    dex_id,
+                    GoldenTicket.into(),
+                    BlackPepper.into(),
+                    SwapAmount::WithDesiredInput {
+                        desired_amount_in: balance!(33000),
+                        min_amount_out: 0,
+                    }
+                ),
+                crate::Error::<Runtime>::PoolIsNotEnabledForTradingPair
+            );
+        },
+    )]);
+}
+
 #[test]
 fn swap_pair_outcome_should_match_actual_desired_amount_in_with_basic_asset() {
     crate::Module::<Runtime>::preset_deposited_pool(vec![Rc::new(
@@ -1211,3 +1245,135 @@ fn variants_of_deposit_liquidity_twice() {
         );
     }
 }
+
+#[test]
+fn pools_of_different_dexes_should_keep_own_reserves() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        let gt: AssetId = GoldenTicket.into();
+        let bp: AssetId = BlackPepper.into();
+        assert_ok!(assets::Pallet::<Runtime>::register_asset_id(
+            ALICE(),
+            gt,
+            AssetSymbol(b"GT".to_vec()),
+            AssetName(b"Golden Ticket".to_vec()),
+            18,
+            Balance::from(0u32),
+            true,
+        ));
+        assert_ok!(assets::Pallet::<Runtime>::register_asset_id(
+            ALICE(),
+            bp,
+            AssetSymbol(b"BP".to_vec()),
+            AssetName(b"Black Pepper".to_vec()),
+            18,
+            Balance::from(0u32),
+            true,
+        ));
+        assert_ok!(assets::Pallet::<Runtime>::mint_to(
+            &gt,
+            &ALICE(),
+            &ALICE(),
+            balance!(900000)
+        ));
+        for (dex_id, base_amount, target_amount) in [
+            (DEX_A_ID, balance!(360000), balance!(144000)),
+            (DEX_B_ID, balance!(36000), balance!(14400)),
+        ]
+        .iter()
+        {
+            assert_ok!(trading_pair::Pallet::<Runtime>::register(
+                Origin::signed(BOB()),
+                *dex_id,
+                gt,
+                bp
+            ));
+            assert_ok!(crate::Module::<Runtime>::initialize_pool(
+                Origin::signed(BOB()),
+                *dex_id,
+                gt,
+                bp,
+            ));
+            assert_ok!(crate::Module::<Runtime>::deposit_liquidity(
+                Origin::signed(ALICE()),
+                *dex_id,
+                gt,
+                bp,
+                *base_amount,
+                *target_amount,
+                *base_amount,
+                *target_amount,
+            ));
+        }
+        assert_ne!(
+            crate::Module::<Runtime>::properties(DEX_A_ID, (gt, bp)),
+            crate::Module::<Runtime>::properties(DEX_B_ID, (gt, bp))
+        );
+        assert_eq!(
+            crate::Module::<Runtime>::reserves(DEX_A_ID, (gt, bp)),
+            (balance!(360000), balance!(144000))
+        );
+        assert_eq!(
+            crate::Module::<Runtime>::reserves(DEX_B_ID, (gt, bp)),
+            (balance!(36000), balance!(14400))
+        );
+
+        assert_ok!(crate::Module::<Runtime>::swap_pair(
+            Origin::signed(ALICE()),
+            ALICE(),
+            DEX_B_ID,
+            gt,
+            bp,
+            SwapAmount::with_desired_input(balance!(1000), 0),
+        ));
+        assert_eq!(
+            <crate::Module<Runtime> as GetPoolReserves<_, _>>::reserves(&DEX_A_ID, &gt, &bp),
+            (balance!(360000), balance!(144000))
+        );
+        let (base_reserve, target_reserve) =
+            <crate::Module<Runtime> as GetPoolReserves<_, _>>::reserves(&DEX_B_ID, &gt, &bp);
+        assert!(base_reserve > balance!(36000));
+        assert!(target_reserve < balance!(14400));
+    });
+}
+
+#[test]
+fn migrate_pools_should_key_pools_by_dex() {
+    crate::Module::<Runtime>::preset_deposited_pool(vec![Rc::new(
+        |dex_id, gt, bp, _, _, _, _repr: AccountId, _fee_repr: AccountId| {
+            let properties = crate::Module::<Runtime>::properties(dex_id, (gt, bp))
+                .expect("Pool has no properties");
+            let reserves = crate::Module::<Runtime>::reserves(dex_id, (gt, bp));
+            let v1_key = |final_prefix: [u8; 32]| {
+                let mut key = final_prefix.to_vec();
+                key.extend(Blake2_128Concat::hash(&gt.encode()));
+                key.extend(Blake2_128Concat::hash(&bp.encode()));
+                key
+            };
+            let properties_key = v1_key(crate::Properties::<Runtime>::final_prefix());
+            let reserves_key = v1_key(crate::Reserves::<Runtime>::final_prefix());
+            crate::Properties::<Runtime>::remove(dex_id, (gt, bp));
+            crate::Reserves::<Runtime>::remove(dex_id, (gt, bp));
+            unhashed::put(&properties_key, &properties);
+            unhashed::put(&reserves_key, &reserves);
+            crate::PalletStorageVersion::<Runtime>::put(crate::StorageVersion::V1);
+
+            crate::migrations::migrate_pools::<Runtime>();
+
+            assert_eq!(
+                crate::Module::<Runtime>::pallet_storage_version(),
+                crate::StorageVersion::V2
+            );
+            assert_eq!(
+                crate::Module::<Runtime>::properties(dex_id, (gt, bp)),
+                Some(properties)
+            );
+            assert_eq!(
+                crate::Module::<Runtime>::reserves(dex_id, (gt, bp)),
+                reserves
+            );
+            assert!(unhashed::get_raw(&properties_key).is_none());
+            assert!(unhashed::get_raw(&reserves_key).is_none());
+        },
+    )]);
+}
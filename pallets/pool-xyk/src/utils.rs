@@ -33,10 +33,9 @@
 use core::convert::TryInto;
 use frame_support::dispatch::{DispatchError, DispatchResult};
 use frame_support::ensure;
-use frame_support::traits::Get;
 
 use common::prelude::{Balance, SwapAmount};
-use common::{ToFeeAccount, ToTechUnitFromDEXAndTradingPair};
+use common::{TechAccountId, TechPurpose, ToFeeAccount, ToTechUnitFromDEXAndTradingPair};
 
 use crate::aliases::{AssetIdOf, DEXManager, ExtraAccountIdOf, TechAccountIdOf, TechAssetIdOf};
 use crate::bounds::*;
@@ -76,13 +75,13 @@ impl<T: Config> Pallet<T> {
     }
 
     pub fn decide_is_fee_from_destination(
+        base_asset_id: &AssetIdOf<T>,
         asset_a: &AssetIdOf<T>,
         asset_b: &AssetIdOf<T>,
     ) -> Result<bool, DispatchError> {
-        let base_asset_id: T::AssetId = T::GetBaseAssetId::get();
-        if &base_asset_id == asset_a {
+        if base_asset_id == asset_a {
             Ok(false)
-        } else if &base_asset_id == asset_b {
+        } else if base_asset_id == asset_b {
             Ok(true)
         } else {
             Ok(false)
@@ -142,6 +141,20 @@ impl<T: Config> Pallet<T> {
         Ok(())
     }
 
+    /// DEX and base asset of the pool, the pool account is derived from them.
+    pub fn get_dex_and_base_asset_of_pool(
+        pool_account: &TechAccountIdOf<T>,
+    ) -> Result<(T::DEXId, AssetIdOf<T>), DispatchError> {
+        let pool_account: TechAccountId<T::AccountId, TechAssetIdOf<T>, T::DEXId> =
+            pool_account.clone().into();
+        match pool_account {
+            TechAccountId::Pure(dex_id, TechPurpose::LiquidityKeeper(trading_pair)) => {
+                Ok((dex_id, trading_pair.base_asset_id.into()))
+            }
+            _ => Err(Error::<T>::PoolIsInvalid.into()),
+        }
+    }
+
     pub fn tech_account_from_dex_and_asset_pair(
         dex_id: T::DEXId,
         asset_a: T::AssetId,
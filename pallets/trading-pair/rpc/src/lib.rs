@@ -40,11 +40,25 @@ use sp_std::vec::Vec;
 use std::sync::Arc;
 
 pub use trading_pair_runtime_api::TradingPairAPI as TradingPairRuntimeAPI;
+use trading_pair_runtime_api::{EnabledPairInfo, PairStatsInfo};
 
 #[rpc]
-pub trait TradingPairAPI<BlockHash, DEXId, TradingPair, AssetId, LiquiditySourceType> {
+pub trait TradingPairAPI<
+    BlockHash,
+    DEXId,
+    TradingPair,
+    AssetId,
+    LiquiditySourceType,
+    PairListing,
+    PairStats,
+>
+{
     #[rpc(name = "tradingPair_listEnabledPairs")]
-    fn list_enabled_pairs(&self, dex_id: DEXId, at: Option<BlockHash>) -> Result<Vec<TradingPair>>;
+    fn list_enabled_pairs(
+        &self,
+        dex_id: DEXId,
+        at: Option<BlockHash>,
+    ) -> Result<Vec<EnabledPairInfo<TradingPair, PairListing>>>;
 
     #[rpc(name = "tradingPair_isPairEnabled")]
     fn is_pair_enabled(
@@ -73,6 +87,15 @@ pub trait TradingPairAPI<BlockHash, DEXId, TradingPair, AssetId, LiquiditySource
         source_type: LiquiditySourceType,
         at: Option<BlockHash>,
     ) -> Result<bool>;
+
+    #[rpc(name = "tradingPair_listPairStats")]
+    fn list_pair_stats(
+        &self,
+        dex_id: DEXId,
+        base_asset_id: AssetId,
+        target_asset_id: AssetId,
+        at: Option<BlockHash>,
+    ) -> Result<Vec<PairStatsInfo<LiquiditySourceType, PairStats>>>;
 }
 
 pub struct TradingPairClient<C, B> {
@@ -90,24 +113,41 @@ impl<C, B> TradingPairClient<C, B> {
     }
 }
 
-impl<C, Block, DEXId, TradingPair, AssetId, LiquiditySourceType>
-    TradingPairAPI<<Block as BlockT>::Hash, DEXId, TradingPair, AssetId, LiquiditySourceType>
-    for TradingPairClient<C, Block>
+impl<C, Block, DEXId, TradingPair, AssetId, LiquiditySourceType, PairListing, PairStats>
+    TradingPairAPI<
+        <Block as BlockT>::Hash,
+        DEXId,
+        TradingPair,
+        AssetId,
+        LiquiditySourceType,
+        PairListing,
+        PairStats,
+    > for TradingPairClient<C, Block>
 where
     Block: BlockT,
     C: Send + Sync + 'static,
     C: ProvideRuntimeApi<Block> + HeaderBackend<Block>,
-    C::Api: TradingPairRuntimeAPI<Block, DEXId, TradingPair, AssetId, LiquiditySourceType>,
+    C::Api: TradingPairRuntimeAPI<
+        Block,
+        DEXId,
+        TradingPair,
+        AssetId,
+        LiquiditySourceType,
+        PairListing,
+        PairStats,
+    >,
     DEXId: Codec,
     TradingPair: Codec,
     AssetId: Codec,
     LiquiditySourceType: Codec,
+    PairListing: Codec,
+    PairStats: Codec,
 {
     fn list_enabled_pairs(
         &self,
         dex_id: DEXId,
         at: Option<<Block as BlockT>::Hash>,
-    ) -> Result<Vec<TradingPair>> {
+    ) -> Result<Vec<EnabledPairInfo<TradingPair, PairListing>>> {
         let api = self.client.runtime_api();
         let at = BlockId::hash(at.unwrap_or(
             // If the block hash is not supplied assume the best block.
@@ -180,4 +220,24 @@ where
                 data: Some(format!("{:?}", e).into()),
             })
     }
+
+    fn list_pair_stats(
+        &self,
+        dex_id: DEXId,
+        base_asset_id: AssetId,
+        target_asset_id: AssetId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Vec<PairStatsInfo<LiquiditySourceType, PairStats>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or(
+            // If the block hash is not supplied assume the best block.
+            self.client.info().best_hash,
+        ));
+        api.list_pair_stats(&at, dex_id, base_asset_id, target_asset_id)
+            .map_err(|e| RpcError {
+                code: ErrorCode::ServerError(InvokeRPCError::RuntimeError.into()),
+                message: "Unable to list pair stats.".into(),
+                data: Some(format!("{:?}", e).into()),
+            })
+    }
 }
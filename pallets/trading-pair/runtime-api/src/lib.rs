@@ -32,17 +32,39 @@
 #![allow(clippy::too_many_arguments)]
 #![allow(clippy::unnecessary_mut_passed)]
 
-use codec::Codec;
+use codec::{Codec, Decode, Encode};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
 use sp_std::prelude::*;
 
+/// Trading pair along with the listing info if it has been listed with a bond.
+#[derive(Eq, PartialEq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct EnabledPairInfo<TradingPair, PairListing> {
+    pub pair: TradingPair,
+    pub listing: Option<PairListing>,
+}
+
+/// Trading statistics of a pair on the liquidity source.
+#[derive(Eq, PartialEq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct PairStatsInfo<LiquiditySourceType, PairStats> {
+    pub source_type: LiquiditySourceType,
+    pub stats: PairStats,
+}
+
 sp_api::decl_runtime_apis! {
-    pub trait TradingPairAPI<DEXId, TradingPair, AssetId, LiquiditySourceType> where
+    pub trait TradingPairAPI<DEXId, TradingPair, AssetId, LiquiditySourceType, PairListing, PairStats> where
         DEXId: Codec,
         TradingPair: Codec,
         AssetId: Codec,
         LiquiditySourceType: Codec,
+        PairListing: Codec,
+        PairStats: Codec,
     {
-        fn list_enabled_pairs(dex_id: DEXId) -> Vec<TradingPair>;
+        fn list_enabled_pairs(dex_id: DEXId) -> Vec<EnabledPairInfo<TradingPair, PairListing>>;
 
         fn is_pair_enabled(dex_id: DEXId, base_asset_id: AssetId, target_asset_id: AssetId) -> bool;
 
@@ -58,5 +80,11 @@ sp_api::decl_runtime_apis! {
             target_asset_id: AssetId,
             source_type: LiquiditySourceType,
         ) -> bool;
+
+        fn list_pair_stats(
+            dex_id: DEXId,
+            base_asset_id: AssetId,
+            target_asset_id: AssetId,
+        ) -> Vec<PairStatsInfo<LiquiditySourceType, PairStats>>;
     }
 }
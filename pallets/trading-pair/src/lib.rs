@@ -34,17 +34,30 @@
 #[macro_use]
 extern crate alloc;
 
-use common::{EnsureDEXManager, EnsureTradingPairExists, LiquiditySourceType, ManagementMode};
+use codec::{Decode, Encode};
+use common::prelude::FixedWrapper;
+#[cfg(feature = "std")]
+use common::utils::string_serialization;
+use common::{
+    Balance, EnableSourceForTradingPair, EnsureDEXManager, EnsureTradingPairExists, IsAssetInUse,
+    LiquiditySourceType, ManagementMode,
+};
 use frame_support::dispatch::{DispatchError, DispatchResult};
 use frame_support::ensure;
+use frame_support::sp_runtime::traits::Zero;
 use frame_support::traits::{Get, IsType};
 use frame_support::weights::Weight;
+use frame_support::RuntimeDebug;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
 use sp_std::collections::btree_set::BTreeSet;
 use sp_std::vec::Vec;
+use traits::MultiReservableCurrency;
 
 mod weights;
 
 mod benchmarking;
+pub mod migrations;
 
 #[cfg(test)]
 mod mock;
@@ -55,9 +68,65 @@ mod tests;
 type TradingPair<T> = common::prelude::TradingPair<<T as assets::Config>::AssetId>;
 type Assets<T> = assets::Pallet<T>;
 type DEXManager<T> = dex_manager::Pallet<T>;
+type PairListingOf<T> =
+    PairListing<<T as frame_system::Config>::AccountId, <T as frame_system::Config>::BlockNumber>;
+type PairStatsOf<T> = PairStats<<T as frame_system::Config>::BlockNumber>;
+
+/// Storage layout version, used to run the migrations only once.
+#[derive(Debug, Encode, Decode, Clone, Copy, PartialEq, Eq)]
+pub enum StorageVersion {
+    /// Trading pairs are only kept in `EnabledSources`.
+    V1,
+    /// Trading pairs are also indexed by their assets in `AssetTradingPairs`.
+    V2,
+}
 
 pub trait WeightInfo {
     fn register() -> Weight;
+    fn deregister() -> Weight;
+    fn disable_source_for_trading_pair() -> Weight;
+    fn set_listing_bond() -> Weight;
+    fn list() -> Weight;
+    fn slash_listing() -> Weight;
+    fn set_stats_era_length() -> Weight;
+}
+
+/// Trading pair registered by an arbitrary account in exchange for a bond.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct PairListing<AccountId, BlockNumber> {
+    /// Account which has listed the pair and reserved the bond
+    pub lister: AccountId,
+    /// Block the pair has been listed in
+    pub listed_at: BlockNumber,
+    /// Amount of the base asset reserved as the bond
+    #[cfg_attr(feature = "std", serde(with = "string_serialization"))]
+    pub bond: Balance,
+}
+
+/// Trading statistics of a trading pair on a single liquidity source.
+///
+/// Volumes and trade count are accumulated within an era, see `StatsEraLength`.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct PairStats<BlockNumber> {
+    /// Amount of the base asset exchanged within the era
+    #[cfg_attr(feature = "std", serde(with = "string_serialization"))]
+    pub base_volume: Balance,
+    /// Amount of the target asset exchanged within the era
+    #[cfg_attr(feature = "std", serde(with = "string_serialization"))]
+    pub target_volume: Balance,
+    /// Number of exchanges within the era
+    pub trade_count: u64,
+    /// Price of the target asset in the base asset at the last exchange
+    #[cfg_attr(feature = "std", serde(with = "string_serialization"))]
+    pub last_price: Balance,
+    /// Block of the last exchange
+    pub last_trade_block: BlockNumber,
+    /// Block the era the statistics are accumulated within has started at
+    pub era_start: BlockNumber,
 }
 
 impl<T: Config> EnsureTradingPairExists<T::DEXId, T::AssetId, DispatchError> for Pallet<T> {
@@ -74,6 +143,27 @@ impl<T: Config> EnsureTradingPairExists<T::DEXId, T::AssetId, DispatchError> for
     }
 }
 
+impl<T: Config> EnableSourceForTradingPair<T::DEXId, T::AssetId, DispatchError> for Pallet<T> {
+    fn enable_source_for_trading_pair(
+        dex_id: &T::DEXId,
+        base_asset_id: &T::AssetId,
+        target_asset_id: &T::AssetId,
+        source_type: LiquiditySourceType,
+    ) -> DispatchResult {
+        Self::enable_source_for_trading_pair(dex_id, base_asset_id, target_asset_id, source_type)
+    }
+}
+
+impl<T: Config> IsAssetInUse<T::AssetId> for Pallet<T> {
+    fn is_asset_in_use(asset_id: &T::AssetId) -> bool {
+        DEXManager::<T>::list_dex_ids().into_iter().any(|dex_id| {
+            AssetTradingPairs::<T>::iter_prefix((dex_id, *asset_id))
+                .next()
+                .is_some()
+        })
+    }
+}
+
 impl<T: Config> Pallet<T> {
     pub fn list_trading_pairs(dex_id: &T::DEXId) -> Result<Vec<TradingPair<T>>, DispatchError> {
         DEXManager::<T>::ensure_dex_exists(dex_id)?;
@@ -82,6 +172,19 @@ impl<T: Config> Pallet<T> {
             .collect())
     }
 
+    /// Lists trading pairs of the DEX along with the listing info of permissionlessly listed ones.
+    pub fn list_trading_pairs_with_listings(
+        dex_id: &T::DEXId,
+    ) -> Result<Vec<(TradingPair<T>, Option<PairListingOf<T>>)>, DispatchError> {
+        Ok(Self::list_trading_pairs(dex_id)?
+            .into_iter()
+            .map(|pair| {
+                let listing = Self::pair_listing(dex_id, &pair);
+                (pair, listing)
+            })
+            .collect())
+    }
+
     pub fn is_trading_pair_enabled(
         dex_id: &T::DEXId,
         &base_asset_id: &T::AssetId,
@@ -120,6 +223,188 @@ impl<T: Config> Pallet<T> {
         )
     }
 
+    fn ensure_can_register(
+        dex_id: &T::DEXId,
+        base_asset_id: T::AssetId,
+        target_asset_id: T::AssetId,
+    ) -> Result<TradingPair<T>, DispatchError> {
+        Assets::<T>::ensure_asset_exists(&base_asset_id)?;
+        Assets::<T>::ensure_asset_exists(&target_asset_id)?;
+        ensure!(
+            base_asset_id != target_asset_id,
+            Error::<T>::IdenticalAssetIds
+        );
+        ensure!(
+            base_asset_id == DEXManager::<T>::get_dex_info(dex_id)?.base_asset_id,
+            Error::<T>::ForbiddenBaseAssetId
+        );
+        let trading_pair = TradingPair::<T> {
+            base_asset_id,
+            target_asset_id,
+        };
+        ensure!(
+            Self::enabled_sources(dex_id, &trading_pair).is_none(),
+            Error::<T>::TradingPairExists
+        );
+        Ok(trading_pair)
+    }
+
+    /// Lists up to `limit` trading pairs of the asset on the DEX. Every listed pair costs
+    /// a single read, so the routing can bound its reads, unlike with `list_trading_pairs`.
+    pub fn list_trading_pairs_of_asset(
+        dex_id: &T::DEXId,
+        asset_id: &T::AssetId,
+        limit: usize,
+    ) -> Vec<TradingPair<T>> {
+        AssetTradingPairs::<T>::iter_prefix((*dex_id, *asset_id))
+            .take(limit)
+            .map(|(pair, _)| pair)
+            .collect()
+    }
+
+    /// Adds the trading pair without enabled sources and indexes it by its assets.
+    fn insert_trading_pair(dex_id: &T::DEXId, trading_pair: &TradingPair<T>) {
+        EnabledSources::<T>::insert(dex_id, trading_pair, BTreeSet::<LiquiditySourceType>::new());
+        for asset_id in &[trading_pair.base_asset_id, trading_pair.target_asset_id] {
+            AssetTradingPairs::<T>::insert((*dex_id, *asset_id), trading_pair, ());
+        }
+    }
+
+    /// Removes the trading pair along with its entries in the asset index and its statistics,
+    /// the liquidity sources still enabled for the pair are disabled.
+    fn remove_trading_pair(dex_id: &T::DEXId, trading_pair: &TradingPair<T>) {
+        let sources = EnabledSources::<T>::take(dex_id, trading_pair).unwrap_or_default();
+        for asset_id in &[trading_pair.base_asset_id, trading_pair.target_asset_id] {
+            AssetTradingPairs::<T>::remove((*dex_id, *asset_id), trading_pair);
+        }
+        PairStatistics::<T>::remove_prefix((*dex_id, trading_pair.clone()));
+        for source_type in sources {
+            Self::deposit_event(Event::SourceDisabledForTradingPair(
+                *dex_id,
+                trading_pair.clone(),
+                source_type,
+            ));
+        }
+    }
+
+    fn store_trading_pair(dex_id: T::DEXId, trading_pair: TradingPair<T>) {
+        Self::insert_trading_pair(&dex_id, &trading_pair);
+        Self::deposit_event(Event::TradingPairStored(dex_id, trading_pair));
+    }
+
+    /// Block the current era has started at. Start blocks, unlike era indices, stay comparable
+    /// when the era length is changed.
+    fn current_stats_era_start() -> T::BlockNumber {
+        let era_length = Self::stats_era_length();
+        if era_length.is_zero() {
+            Zero::zero()
+        } else {
+            let now = frame_system::Pallet::<T>::block_number();
+            now - now % era_length
+        }
+    }
+
+    /// Statistics with volumes and trade count starting over if they belong to the past era.
+    fn stats_for_current_era(stats: PairStatsOf<T>) -> PairStatsOf<T> {
+        let era_start = Self::current_stats_era_start();
+        if stats.era_start == era_start {
+            stats
+        } else {
+            PairStats {
+                base_volume: 0,
+                target_volume: 0,
+                trade_count: 0,
+                era_start,
+                ..stats
+            }
+        }
+    }
+
+    /// Returns statistics of the trading pair on the liquidity source for the current era.
+    pub fn get_pair_stats(
+        dex_id: &T::DEXId,
+        &base_asset_id: &T::AssetId,
+        &target_asset_id: &T::AssetId,
+        source_type: LiquiditySourceType,
+    ) -> PairStatsOf<T> {
+        let pair = TradingPair::<T> {
+            base_asset_id,
+            target_asset_id,
+        };
+        Self::stats_for_current_era(Self::pair_stats((*dex_id, pair), source_type))
+    }
+
+    /// Returns statistics of the trading pair for every liquidity source enabled for it.
+    pub fn list_pair_stats(
+        dex_id: &T::DEXId,
+        base_asset_id: &T::AssetId,
+        target_asset_id: &T::AssetId,
+    ) -> Result<Vec<(LiquiditySourceType, PairStatsOf<T>)>, DispatchError> {
+        Ok(
+            Self::list_enabled_sources_for_trading_pair(dex_id, base_asset_id, target_asset_id)?
+                .into_iter()
+                .map(|source_type| {
+                    let stats =
+                        Self::get_pair_stats(dex_id, base_asset_id, target_asset_id, source_type);
+                    (source_type, stats)
+                })
+                .collect(),
+        )
+    }
+
+    /// Weight of `record_trade`: reads of the DEX info, the trading pair, the era length and
+    /// the statistics, and a write of the statistics.
+    pub fn record_trade_weight() -> Weight {
+        T::DbWeight::get().reads_writes(4, 1)
+    }
+
+    /// Accounts exchange performed on the liquidity source in the statistics of the trading pair.
+    /// Exchanges between assets which don't form a registered trading pair are ignored.
+    pub fn record_trade(
+        dex_id: &T::DEXId,
+        source_type: LiquiditySourceType,
+        &input_asset_id: &T::AssetId,
+        &output_asset_id: &T::AssetId,
+        input_amount: Balance,
+        output_amount: Balance,
+    ) {
+        let base_asset_id = match DEXManager::<T>::get_dex_info(dex_id) {
+            Ok(dex_info) => dex_info.base_asset_id,
+            Err(_) => return,
+        };
+        let (pair, base_amount, target_amount) = if input_asset_id == base_asset_id {
+            let pair = TradingPair::<T> {
+                base_asset_id: input_asset_id,
+                target_asset_id: output_asset_id,
+            };
+            (pair, input_amount, output_amount)
+        } else if output_asset_id == base_asset_id {
+            let pair = TradingPair::<T> {
+                base_asset_id: output_asset_id,
+                target_asset_id: input_asset_id,
+            };
+            (pair, output_amount, input_amount)
+        } else {
+            return;
+        };
+        if !EnabledSources::<T>::contains_key(dex_id, &pair) {
+            return;
+        }
+        let now = frame_system::Pallet::<T>::block_number();
+        PairStatistics::<T>::mutate((*dex_id, pair), source_type, |stats| {
+            *stats = Self::stats_for_current_era(stats.clone());
+            stats.base_volume = stats.base_volume.saturating_add(base_amount);
+            stats.target_volume = stats.target_volume.saturating_add(target_amount);
+            stats.trade_count = stats.trade_count.saturating_add(1);
+            if let Ok(price) = (FixedWrapper::from(base_amount) / FixedWrapper::from(target_amount))
+                .try_into_balance()
+            {
+                stats.last_price = price;
+            }
+            stats.last_trade_block = now;
+        });
+    }
+
     pub fn enable_source_for_trading_pair(
         dex_id: &T::DEXId,
         &base_asset_id: &T::AssetId,
@@ -145,7 +430,7 @@ pub use pallet::*;
 #[frame_support::pallet]
 pub mod pallet {
     use super::*;
-    use common::DexIdOf;
+    use common::{AccountIdOf, DexIdOf};
     use frame_support::pallet_prelude::*;
     use frame_system::pallet_prelude::*;
 
@@ -164,7 +449,11 @@ pub mod pallet {
     pub struct Pallet<T>(PhantomData<T>);
 
     #[pallet::hooks]
-    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_runtime_upgrade() -> Weight {
+            migrations::migrate_asset_trading_pairs::<T>()
+        }
+    }
 
     #[pallet::call]
     impl<T: Config> Pallet<T> {
@@ -183,40 +472,214 @@ pub mod pallet {
         ) -> DispatchResultWithPostInfo {
             let _author =
                 T::EnsureDEXManager::ensure_can_manage(&dex_id, origin, ManagementMode::Public)?;
-            Assets::<T>::ensure_asset_exists(&base_asset_id)?;
-            Assets::<T>::ensure_asset_exists(&target_asset_id)?;
-            ensure!(
-                base_asset_id != target_asset_id,
-                Error::<T>::IdenticalAssetIds
-            );
-            ensure!(
-                base_asset_id == T::GetBaseAssetId::get(),
-                Error::<T>::ForbiddenBaseAssetId
-            );
+            let trading_pair = Self::ensure_can_register(&dex_id, base_asset_id, target_asset_id)?;
+            Self::store_trading_pair(dex_id, trading_pair);
+            Ok(().into())
+        }
+
+        /// Deregister trading pair on the given DEX.
+        /// Can be only called by the DEX owner or the lister of the pair, liquidity sources enabled
+        /// for the pair are disabled.
+        ///
+        /// - `dex_id`: ID of the exchange.
+        /// - `base_asset_id`: base asset ID.
+        /// - `target_asset_id`: target asset ID.
+        #[pallet::weight(<T as Config>::WeightInfo::deregister())]
+        pub fn deregister(
+            origin: OriginFor<T>,
+            dex_id: T::DEXId,
+            base_asset_id: T::AssetId,
+            target_asset_id: T::AssetId,
+        ) -> DispatchResultWithPostInfo {
             let trading_pair = TradingPair::<T> {
                 base_asset_id,
                 target_asset_id,
             };
+            let listing = Self::pair_listing(&dex_id, &trading_pair);
+            // Lister is allowed to delist own pair to get the bond back.
+            let is_lister = match (&listing, ensure_signed(origin.clone())) {
+                (Some(listing), Ok(who)) => listing.lister == who,
+                _ => false,
+            };
+            if !is_lister {
+                T::EnsureDEXManager::ensure_can_manage(&dex_id, origin, ManagementMode::Private)?;
+            }
             ensure!(
-                Self::enabled_sources(&dex_id, &trading_pair).is_none(),
-                Error::<T>::TradingPairExists
+                EnabledSources::<T>::contains_key(&dex_id, &trading_pair),
+                Error::<T>::TradingPairDoesntExist
             );
-            EnabledSources::<T>::insert(
-                &dex_id,
-                &trading_pair,
-                BTreeSet::<LiquiditySourceType>::new(),
+            Self::remove_trading_pair(&dex_id, &trading_pair);
+            if let Some(listing) = listing {
+                PairListings::<T>::remove(&dex_id, &trading_pair);
+                Assets::<T>::unreserve(T::GetBaseAssetId::get(), &listing.lister, listing.bond)?;
+                Self::deposit_event(Event::ListingBondReturned(
+                    dex_id,
+                    trading_pair.clone(),
+                    listing.lister,
+                    listing.bond,
+                ));
+            }
+            Self::deposit_event(Event::TradingPairRemoved(dex_id, trading_pair));
+            Ok(().into())
+        }
+
+        /// Disable liquidity source for trading pair on the given DEX.
+        /// Can be only called by the DEX owner.
+        ///
+        /// - `dex_id`: ID of the exchange.
+        /// - `base_asset_id`: base asset ID.
+        /// - `target_asset_id`: target asset ID.
+        /// - `source_type`: liquidity source to be disabled.
+        #[pallet::weight(<T as Config>::WeightInfo::disable_source_for_trading_pair())]
+        pub fn disable_source_for_trading_pair(
+            origin: OriginFor<T>,
+            dex_id: T::DEXId,
+            base_asset_id: T::AssetId,
+            target_asset_id: T::AssetId,
+            source_type: LiquiditySourceType,
+        ) -> DispatchResultWithPostInfo {
+            let _author =
+                T::EnsureDEXManager::ensure_can_manage(&dex_id, origin, ManagementMode::Private)?;
+            let trading_pair = TradingPair::<T> {
+                base_asset_id,
+                target_asset_id,
+            };
+            EnabledSources::<T>::try_mutate(&dex_id, &trading_pair, |opt_set| {
+                let set = opt_set.as_mut().ok_or(Error::<T>::TradingPairDoesntExist)?;
+                ensure!(set.remove(&source_type), Error::<T>::SourceIsNotEnabled);
+                Ok::<_, Error<T>>(())
+            })?;
+            Self::deposit_event(Event::SourceDisabledForTradingPair(
+                dex_id,
+                trading_pair,
+                source_type,
+            ));
+            Ok(().into())
+        }
+
+        /// Set the bond required to list trading pairs on the given DEX without being its owner.
+        /// Can be only called by the DEX owner, `None` disables permissionless listing.
+        ///
+        /// - `dex_id`: ID of the exchange.
+        /// - `bond`: amount of the base asset to be reserved from the lister.
+        #[pallet::weight(<T as Config>::WeightInfo::set_listing_bond())]
+        pub fn set_listing_bond(
+            origin: OriginFor<T>,
+            dex_id: T::DEXId,
+            bond: Option<Balance>,
+        ) -> DispatchResultWithPostInfo {
+            let _author =
+                T::EnsureDEXManager::ensure_can_manage(&dex_id, origin, ManagementMode::Private)?;
+            ListingBonds::<T>::mutate(&dex_id, |value| *value = bond);
+            Self::deposit_event(Event::ListingBondChanged(dex_id, bond));
+            Ok(().into())
+        }
+
+        /// List trading pair on the given DEX by reserving the listing bond.
+        /// Can be called by any account if permissionless listing is enabled for the DEX.
+        ///
+        /// - `dex_id`: ID of the exchange.
+        /// - `base_asset_id`: base asset ID.
+        /// - `target_asset_id`: target asset ID.
+        #[pallet::weight(<T as Config>::WeightInfo::list())]
+        pub fn list(
+            origin: OriginFor<T>,
+            dex_id: T::DEXId,
+            base_asset_id: T::AssetId,
+            target_asset_id: T::AssetId,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let bond = Self::listing_bond(&dex_id).ok_or(Error::<T>::ListingIsNotAllowed)?;
+            let trading_pair = Self::ensure_can_register(&dex_id, base_asset_id, target_asset_id)?;
+            Assets::<T>::reserve(T::GetBaseAssetId::get(), &who, bond)?;
+            let listing = PairListing {
+                lister: who.clone(),
+                listed_at: frame_system::Pallet::<T>::block_number(),
+                bond,
+            };
+            PairListings::<T>::insert(&dex_id, &trading_pair, listing);
+            Self::store_trading_pair(dex_id, trading_pair.clone());
+            Self::deposit_event(Event::TradingPairListed(dex_id, trading_pair, who, bond));
+            Ok(().into())
+        }
+
+        /// Slash the bond of permissionlessly listed trading pair and remove the pair from the DEX.
+        /// Can be only called by root, liquidity sources enabled for the pair are disabled.
+        ///
+        /// - `dex_id`: ID of the exchange.
+        /// - `base_asset_id`: base asset ID.
+        /// - `target_asset_id`: target asset ID.
+        #[pallet::weight(<T as Config>::WeightInfo::slash_listing())]
+        pub fn slash_listing(
+            origin: OriginFor<T>,
+            dex_id: T::DEXId,
+            base_asset_id: T::AssetId,
+            target_asset_id: T::AssetId,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            let trading_pair = TradingPair::<T> {
+                base_asset_id,
+                target_asset_id,
+            };
+            let listing = PairListings::<T>::take(&dex_id, &trading_pair)
+                .ok_or(Error::<T>::PairListingDoesntExist)?;
+            let not_slashed = <T as assets::Config>::Currency::slash_reserved(
+                T::GetBaseAssetId::get(),
+                &listing.lister,
+                listing.bond,
             );
-            Self::deposit_event(Event::TradingPairStored(dex_id, trading_pair));
+            Self::remove_trading_pair(&dex_id, &trading_pair);
+            Self::deposit_event(Event::ListingBondSlashed(
+                dex_id,
+                trading_pair.clone(),
+                listing.lister,
+                listing.bond.saturating_sub(not_slashed),
+            ));
+            Self::deposit_event(Event::TradingPairRemoved(dex_id, trading_pair));
+            Ok(().into())
+        }
+
+        /// Set the length of the era trading statistics are accumulated within.
+        /// Can be only called by root, zero length makes the statistics never reset.
+        ///
+        /// - `era_length`: length of the era in blocks.
+        #[pallet::weight(<T as Config>::WeightInfo::set_stats_era_length())]
+        pub fn set_stats_era_length(
+            origin: OriginFor<T>,
+            era_length: T::BlockNumber,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            StatsEraLength::<T>::put(era_length);
+            Self::deposit_event(Event::StatsEraLengthChanged(era_length));
             Ok(().into())
         }
     }
 
     #[pallet::event]
-    #[pallet::metadata(DexIdOf<T> = "DEXId", TradingPair<T> = "TradingPair")]
+    #[pallet::metadata(
+        DexIdOf<T> = "DEXId",
+        TradingPair<T> = "TradingPair",
+        AccountIdOf<T> = "AccountId",
+        BlockNumberFor<T> = "BlockNumber"
+    )]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
         /// Trading pair has been redistered on a DEX. [DEX Id, Trading Pair]
         TradingPairStored(DexIdOf<T>, TradingPair<T>),
+        /// Trading pair has been removed from a DEX. [DEX Id, Trading Pair]
+        TradingPairRemoved(DexIdOf<T>, TradingPair<T>),
+        /// Liquidity source has been disabled for a trading pair. [DEX Id, Trading Pair, Liquidity Source Type]
+        SourceDisabledForTradingPair(DexIdOf<T>, TradingPair<T>, LiquiditySourceType),
+        /// Listing bond has been changed for a DEX. [DEX Id, Bond]
+        ListingBondChanged(DexIdOf<T>, Option<Balance>),
+        /// Trading pair has been listed on a DEX with a bond. [DEX Id, Trading Pair, Lister, Bond]
+        TradingPairListed(DexIdOf<T>, TradingPair<T>, AccountIdOf<T>, Balance),
+        /// Listing bond has been returned to the lister. [DEX Id, Trading Pair, Lister, Bond]
+        ListingBondReturned(DexIdOf<T>, TradingPair<T>, AccountIdOf<T>, Balance),
+        /// Listing bond has been slashed. [DEX Id, Trading Pair, Lister, Slashed Amount]
+        ListingBondSlashed(DexIdOf<T>, TradingPair<T>, AccountIdOf<T>, Balance),
+        /// Length of the trading statistics era has been changed. [Era Length]
+        StatsEraLengthChanged(BlockNumberFor<T>),
     }
 
     #[pallet::error]
@@ -229,6 +692,12 @@ pub mod pallet {
         IdenticalAssetIds,
         /// Trading pair is not registered for given DEXId.
         TradingPairDoesntExist,
+        /// Liquidity source is not enabled for given trading pair.
+        SourceIsNotEnabled,
+        /// Permissionless listing is not enabled for given DEXId.
+        ListingIsNotAllowed,
+        /// Trading pair has not been listed with a bond.
+        PairListingDoesntExist,
     }
 
     #[pallet::storage]
@@ -242,6 +711,64 @@ pub mod pallet {
         BTreeSet<LiquiditySourceType>,
     >;
 
+    /// Trading pairs of every asset, keyed by the DEX and the asset.
+    #[pallet::storage]
+    pub(super) type AssetTradingPairs<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        (T::DEXId, T::AssetId),
+        Blake2_128Concat,
+        TradingPair<T>,
+        (),
+        ValueQuery,
+    >;
+
+    /// Bond required to list a trading pair without being the DEX owner.
+    #[pallet::storage]
+    #[pallet::getter(fn listing_bond)]
+    pub(super) type ListingBonds<T: Config> = StorageMap<_, Twox64Concat, T::DEXId, Balance>;
+
+    /// Listing info of trading pairs registered with a bond.
+    #[pallet::storage]
+    #[pallet::getter(fn pair_listing)]
+    pub(super) type PairListings<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        T::DEXId,
+        Blake2_128Concat,
+        TradingPair<T>,
+        PairListingOf<T>,
+    >;
+
+    /// Trading statistics per trading pair and liquidity source.
+    #[pallet::storage]
+    #[pallet::getter(fn pair_stats)]
+    pub(super) type PairStatistics<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        (T::DEXId, TradingPair<T>),
+        Twox64Concat,
+        LiquiditySourceType,
+        PairStatsOf<T>,
+        ValueQuery,
+    >;
+
+    /// Length of the era in blocks trading statistics are accumulated within, zero if never reset.
+    #[pallet::storage]
+    #[pallet::getter(fn stats_era_length)]
+    pub(super) type StatsEraLength<T: Config> = StorageValue<_, T::BlockNumber, ValueQuery>;
+
+    #[pallet::type_value]
+    pub(super) fn DefaultForPalletStorageVersion() -> StorageVersion {
+        StorageVersion::V1
+    }
+
+    /// Storage layout version, chains started before the versioning have `V1`.
+    #[pallet::storage]
+    #[pallet::getter(fn pallet_storage_version)]
+    pub(super) type PalletStorageVersion<T: Config> =
+        StorageValue<_, StorageVersion, ValueQuery, DefaultForPalletStorageVersion>;
+
     #[pallet::genesis_config]
     pub struct GenesisConfig<T: Config> {
         pub trading_pairs: Vec<(T::DEXId, TradingPair<T>)>,
@@ -260,8 +787,9 @@ pub mod pallet {
     impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
         fn build(&self) {
             self.trading_pairs.iter().for_each(|(dex_id, pair)| {
-                EnabledSources::<T>::insert(&dex_id, &pair, BTreeSet::<LiquiditySourceType>::new());
-            })
+                Pallet::<T>::insert_trading_pair(dex_id, pair);
+            });
+            PalletStorageVersion::<T>::put(StorageVersion::V2);
         }
     }
 }
@@ -0,0 +1,51 @@
+// This file is part of the SORA network and Polkaswap app.
+
+// Copyright (c) 2020, 2021, Polka Biome Ltd. All rights reserved.
+// SPDX-License-Identifier: BSD-4-Clause
+
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+
+// Redistributions of source code must retain the above copyright notice, this list
+// of conditions and the following disclaimer.
+// Redistributions in binary form must reproduce the above copyright notice, this
+// list of conditions and the following disclaimer in the documentation and/or other
+// materials provided with the distribution.
+//
+// All advertising materials mentioning features or use of this software must display
+// the following acknowledgement: This product includes software developed by Polka Biome
+// Ltd., SORA, and Polkaswap.
+//
+// Neither the name of the Polka Biome Ltd. nor the names of its contributors may be used
+// to endorse or promote products derived from this software without specific prior written permission.
+
+// THIS SOFTWARE IS PROVIDED BY Polka Biome Ltd. AS IS AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL Polka Biome Ltd. BE LIABLE FOR ANY
+// DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS;
+// OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT,
+// STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+use crate::{AssetTradingPairs, Config, EnabledSources, PalletStorageVersion, StorageVersion};
+use frame_support::traits::Get;
+use frame_support::weights::Weight;
+
+/// Indexes the registered trading pairs by their assets in `AssetTradingPairs`. Runs only for
+/// `StorageVersion::V1`.
+pub fn migrate_asset_trading_pairs<T: Config>() -> Weight {
+    if PalletStorageVersion::<T>::get() != StorageVersion::V1 {
+        return T::DbWeight::get().reads(1);
+    }
+    PalletStorageVersion::<T>::put(StorageVersion::V2);
+    let mut reads: Weight = 1;
+    let mut writes: Weight = 1;
+    for (dex_id, pair, _) in EnabledSources::<T>::iter() {
+        reads += 1;
+        writes += 2;
+        for asset_id in &[pair.base_asset_id, pair.target_asset_id] {
+            AssetTradingPairs::<T>::insert((dex_id, *asset_id), &pair, ());
+        }
+    }
+    T::DbWeight::get().reads_writes(reads, writes)
+}
@@ -31,7 +31,9 @@
 use crate::{self as trading_pair, Config};
 use common::mock::ExistentialDeposits;
 use common::prelude::{Balance, DEXInfo};
-use common::{hash, AssetId32, AssetName, AssetSymbol, BalancePrecision, DOT, KSM, XOR};
+use common::{
+    balance, hash, AssetId32, AssetName, AssetSymbol, BalancePrecision, BasisPoints, DOT, KSM, XOR,
+};
 use currencies::BasicCurrencyAdapter;
 use frame_support::traits::GenesisBuild;
 use frame_support::weights::Weight;
@@ -59,7 +61,7 @@ construct_runtime! {
         Assets: assets::{Pallet, Call, Config<T>, Storage, Event<T>},
         Balances: pallet_balances::{Pallet, Call, Storage, Event<T>},
         Permissions: permissions::{Pallet, Call, Config<T>, Storage, Event<T>},
-        DexManager: dex_manager::{Pallet, Call, Config<T>, Storage},
+        DexManager: dex_manager::{Pallet, Call, Config<T>, Storage, Event<T>},
         ParachainSystem: cumulus_pallet_parachain_system::{Pallet, Call, Storage, Inherent, Event<T>},
         ParachainInfo: parachain_info::{Pallet, Storage, Config},
     }
@@ -72,6 +74,7 @@ pub type BlockNumber = u64;
 pub type Amount = i128;
 
 pub const ALICE: AccountId = 1;
+pub const BOB: AccountId = 2;
 pub const DEX_ID: DEXId = 0;
 type AssetId = AssetId32<common::PredefinedAssetId>;
 
@@ -80,6 +83,7 @@ parameter_types! {
     pub const MaximumBlockWeight: Weight = 1024;
     pub const MaximumBlockLength: u32 = 2 * 1024;
     pub const AvailableBlockRatio: Perbill = Perbill::from_percent(75);
+    pub const MaxDexFee: BasisPoints = 100;
 }
 
 impl frame_system::Config for Runtime {
@@ -158,6 +162,7 @@ impl assets::Config for Runtime {
     type AssetId = AssetId;
     type GetBaseAssetId = GetBaseAssetId;
     type Currency = currencies::Module<Runtime>;
+    type IsAssetInUse = trading_pair::Pallet<Runtime>;
     type WeightInfo = ();
 }
 
@@ -182,7 +187,11 @@ impl permissions::Config for Runtime {
     type Event = Event;
 }
 
-impl dex_manager::Config for Runtime {}
+impl dex_manager::Config for Runtime {
+    type Event = Event;
+    type MaxFee = MaxDexFee;
+    type WeightInfo = ();
+}
 
 pub struct ExtBuilder {
     endowed_assets: Vec<(
@@ -278,6 +287,7 @@ impl Default for ExtBuilder {
                 DEXInfo {
                     base_asset_id: XOR,
                     is_public: true,
+                    default_fee: 30,
                 },
             )],
             initial_permission_owners: vec![
@@ -299,7 +309,7 @@ impl ExtBuilder {
             .unwrap();
 
         pallet_balances::GenesisConfig::<Runtime> {
-            balances: vec![(ALICE, 0)],
+            balances: vec![(ALICE, 0), (BOB, balance!(100))],
         }
         .assimilate_storage(&mut t)
         .unwrap();
@@ -329,6 +339,12 @@ impl ExtBuilder {
         .assimilate_storage(&mut t)
         .unwrap();
 
+        crate::GenesisConfig::<Runtime> {
+            trading_pairs: Vec::new(),
+        }
+        .assimilate_storage(&mut t)
+        .unwrap();
+
         t.into()
     }
 }
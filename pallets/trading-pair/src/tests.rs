@@ -29,9 +29,15 @@
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 use crate::mock::*;
-use crate::{Error, Module};
-use common::{EnsureTradingPairExists, LiquiditySourceType, TradingPair, DOT, KSM, XOR};
+use crate::{
+    migrations, AssetTradingPairs, Error, Module, PairListing, PairStatistics, PairStats,
+    PalletStorageVersion, StorageVersion,
+};
+use common::{
+    balance, DEXInfo, EnsureTradingPairExists, LiquiditySourceType, TradingPair, DOT, KSM, XOR,
+};
 use frame_support::{assert_noop, assert_ok};
+use sp_runtime::DispatchError;
 
 type TradingPairModule = Module<Runtime>;
 
@@ -76,6 +82,34 @@ fn should_not_register_trading_pair_with_wrong_base_asset() {
     });
 }
 
+#[test]
+fn should_register_trading_pair_with_base_asset_of_dex() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        let dex_id = DEX_ID + 1;
+        dex_manager::DEXInfos::<Runtime>::insert(
+            dex_id,
+            DEXInfo {
+                base_asset_id: DOT,
+                is_public: true,
+                default_fee: 30,
+            },
+        );
+        assert_noop!(
+            TradingPairModule::register(Origin::signed(ALICE), dex_id, XOR, DOT),
+            Error::<Runtime>::ForbiddenBaseAssetId
+        );
+        assert_ok!(TradingPairModule::register(
+            Origin::signed(ALICE),
+            dex_id,
+            DOT,
+            XOR
+        ));
+        assert!(TradingPairModule::ensure_trading_pair_exists(&dex_id, &DOT, &XOR).is_ok());
+        assert!(TradingPairModule::ensure_trading_pair_exists(&DEX_ID, &XOR, &DOT).is_err());
+    });
+}
+
 #[test]
 fn should_not_register_trading_pair_with_same_assets() {
     let mut ext = ExtBuilder::default().build();
@@ -467,3 +501,488 @@ fn should_fail_with_nonexistent_dex() {
         );
     });
 }
+
+#[test]
+fn should_disable_source_for_pair() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        TradingPairModule::register(Origin::signed(ALICE), DEX_ID, XOR, DOT)
+            .expect("Failed to register pair.");
+        TradingPairModule::enable_source_for_trading_pair(
+            &DEX_ID,
+            &XOR,
+            &DOT,
+            LiquiditySourceType::XYKPool,
+        )
+        .expect("Failed to enable source.");
+        assert_noop!(
+            TradingPairModule::disable_source_for_trading_pair(
+                Origin::signed(BOB),
+                DEX_ID,
+                XOR,
+                DOT,
+                LiquiditySourceType::XYKPool,
+            ),
+            permissions::Error::<Runtime>::Forbidden
+        );
+        assert_ok!(TradingPairModule::disable_source_for_trading_pair(
+            Origin::signed(ALICE),
+            DEX_ID,
+            XOR,
+            DOT,
+            LiquiditySourceType::XYKPool,
+        ));
+        assert!(!TradingPairModule::is_source_enabled_for_trading_pair(
+            &DEX_ID,
+            &XOR,
+            &DOT,
+            LiquiditySourceType::XYKPool,
+        )
+        .expect("Failed to query pair."));
+        assert_noop!(
+            TradingPairModule::disable_source_for_trading_pair(
+                Origin::signed(ALICE),
+                DEX_ID,
+                XOR,
+                DOT,
+                LiquiditySourceType::XYKPool,
+            ),
+            Error::<Runtime>::SourceIsNotEnabled
+        );
+        assert_noop!(
+            TradingPairModule::disable_source_for_trading_pair(
+                Origin::signed(ALICE),
+                DEX_ID,
+                XOR,
+                KSM,
+                LiquiditySourceType::XYKPool,
+            ),
+            Error::<Runtime>::TradingPairDoesntExist
+        );
+    });
+}
+
+#[test]
+fn should_deregister_pair_and_disable_its_sources() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        System::set_block_number(1);
+        TradingPairModule::register(Origin::signed(ALICE), DEX_ID, XOR, DOT)
+            .expect("Failed to register pair.");
+        TradingPairModule::enable_source_for_trading_pair(
+            &DEX_ID,
+            &XOR,
+            &DOT,
+            LiquiditySourceType::XYKPool,
+        )
+        .expect("Failed to enable source.");
+        TradingPairModule::record_trade(
+            &DEX_ID,
+            LiquiditySourceType::XYKPool,
+            &XOR,
+            &DOT,
+            balance!(10),
+            balance!(5),
+        );
+        assert_noop!(
+            TradingPairModule::deregister(Origin::signed(BOB), DEX_ID, XOR, DOT),
+            permissions::Error::<Runtime>::Forbidden
+        );
+        assert_ok!(TradingPairModule::deregister(
+            Origin::signed(ALICE),
+            DEX_ID,
+            XOR,
+            DOT
+        ));
+        assert!(
+            !TradingPairModule::is_trading_pair_enabled(&DEX_ID, &XOR, &DOT)
+                .expect("Failed to query pair.")
+        );
+        assert!(TradingPairModule::list_trading_pairs(&DEX_ID)
+            .expect("Failed to list pairs.")
+            .is_empty());
+        let pair = TradingPair {
+            base_asset_id: XOR,
+            target_asset_id: DOT,
+        };
+        assert!(System::events().iter().any(|record| record.event
+            == crate::Event::SourceDisabledForTradingPair(
+                DEX_ID,
+                pair.clone(),
+                LiquiditySourceType::XYKPool
+            )
+            .into()));
+        assert_noop!(
+            TradingPairModule::deregister(Origin::signed(ALICE), DEX_ID, XOR, DOT),
+            Error::<Runtime>::TradingPairDoesntExist
+        );
+        // Pair can be registered again after removal, without the statistics of the removed one.
+        assert_ok!(TradingPairModule::register(
+            Origin::signed(ALICE),
+            DEX_ID,
+            XOR,
+            DOT
+        ));
+        assert_eq!(
+            TradingPairModule::get_pair_stats(&DEX_ID, &XOR, &DOT, LiquiditySourceType::XYKPool),
+            Default::default()
+        );
+    });
+}
+
+#[test]
+fn should_index_pairs_by_assets() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        let xor_dot = TradingPair {
+            base_asset_id: XOR,
+            target_asset_id: DOT,
+        };
+        let xor_ksm = TradingPair {
+            base_asset_id: XOR,
+            target_asset_id: KSM,
+        };
+        TradingPairModule::register(Origin::signed(ALICE), DEX_ID, XOR, DOT)
+            .expect("Failed to register pair.");
+        TradingPairModule::register(Origin::signed(ALICE), DEX_ID, XOR, KSM)
+            .expect("Failed to register pair.");
+        let xor_pairs = TradingPairModule::list_trading_pairs_of_asset(&DEX_ID, &XOR, 10);
+        assert_eq!(xor_pairs.len(), 2);
+        assert!(xor_pairs.contains(&xor_dot));
+        assert!(xor_pairs.contains(&xor_ksm));
+        assert_eq!(
+            TradingPairModule::list_trading_pairs_of_asset(&DEX_ID, &XOR, 1).len(),
+            1
+        );
+        assert_eq!(
+            TradingPairModule::list_trading_pairs_of_asset(&DEX_ID, &DOT, 10),
+            vec![xor_dot.clone()]
+        );
+        assert_ok!(TradingPairModule::deregister(
+            Origin::signed(ALICE),
+            DEX_ID,
+            XOR,
+            DOT
+        ));
+        assert_eq!(
+            TradingPairModule::list_trading_pairs_of_asset(&DEX_ID, &XOR, 10),
+            vec![xor_ksm]
+        );
+        assert!(TradingPairModule::list_trading_pairs_of_asset(&DEX_ID, &DOT, 10).is_empty());
+    });
+}
+
+#[test]
+fn should_keep_assets_of_registered_pairs_from_being_destroyed() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        TradingPairModule::register(Origin::signed(ALICE), DEX_ID, XOR, DOT)
+            .expect("Failed to register pair.");
+        assert_noop!(
+            assets::Pallet::<Runtime>::destroy(Origin::signed(ALICE), DOT),
+            assets::Error::<Runtime>::AssetIsInUse
+        );
+        assert_ok!(TradingPairModule::deregister(
+            Origin::signed(ALICE),
+            DEX_ID,
+            XOR,
+            DOT
+        ));
+        assert_ok!(assets::Pallet::<Runtime>::destroy(
+            Origin::signed(ALICE),
+            DOT
+        ));
+        assert!(!assets::Pallet::<Runtime>::asset_exists(&DOT));
+    });
+}
+
+#[test]
+fn should_migrate_asset_trading_pairs_once() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        let xor_dot = TradingPair {
+            base_asset_id: XOR,
+            target_asset_id: DOT,
+        };
+        assert_eq!(
+            TradingPairModule::pallet_storage_version(),
+            StorageVersion::V2
+        );
+        TradingPairModule::register(Origin::signed(ALICE), DEX_ID, XOR, DOT)
+            .expect("Failed to register pair.");
+        AssetTradingPairs::<Runtime>::remove_all();
+        PalletStorageVersion::<Runtime>::put(StorageVersion::V1);
+
+        migrations::migrate_asset_trading_pairs::<Runtime>();
+
+        assert_eq!(
+            TradingPairModule::pallet_storage_version(),
+            StorageVersion::V2
+        );
+        assert_eq!(
+            TradingPairModule::list_trading_pairs_of_asset(&DEX_ID, &XOR, 10),
+            vec![xor_dot.clone()]
+        );
+        assert_eq!(
+            TradingPairModule::list_trading_pairs_of_asset(&DEX_ID, &DOT, 10),
+            vec![xor_dot]
+        );
+
+        // The migration runs only once.
+        AssetTradingPairs::<Runtime>::remove_all();
+        migrations::migrate_asset_trading_pairs::<Runtime>();
+        assert!(TradingPairModule::list_trading_pairs_of_asset(&DEX_ID, &XOR, 10).is_empty());
+    });
+}
+
+#[test]
+fn should_list_pair_with_bond() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        System::set_block_number(1);
+        assert_noop!(
+            TradingPairModule::list(Origin::signed(BOB), DEX_ID, XOR, DOT),
+            Error::<Runtime>::ListingIsNotAllowed
+        );
+        assert_noop!(
+            TradingPairModule::set_listing_bond(Origin::signed(BOB), DEX_ID, Some(balance!(10))),
+            permissions::Error::<Runtime>::Forbidden
+        );
+        assert_ok!(TradingPairModule::set_listing_bond(
+            Origin::signed(ALICE),
+            DEX_ID,
+            Some(balance!(10))
+        ));
+        assert_ok!(TradingPairModule::list(
+            Origin::signed(BOB),
+            DEX_ID,
+            XOR,
+            DOT
+        ));
+        assert_eq!(Balances::reserved_balance(BOB), balance!(10));
+        assert_eq!(Balances::free_balance(BOB), balance!(90));
+        let pair = TradingPair {
+            base_asset_id: XOR,
+            target_asset_id: DOT,
+        };
+        let listing = PairListing {
+            lister: BOB,
+            listed_at: 1,
+            bond: balance!(10),
+        };
+        assert_eq!(
+            TradingPairModule::list_trading_pairs_with_listings(&DEX_ID)
+                .expect("Failed to list pairs."),
+            vec![(pair.clone(), Some(listing))]
+        );
+        assert!(System::events().iter().any(|record| record.event
+            == crate::Event::TradingPairListed(DEX_ID, pair.clone(), BOB, balance!(10)).into()));
+        assert_noop!(
+            TradingPairModule::list(Origin::signed(BOB), DEX_ID, XOR, DOT),
+            Error::<Runtime>::TradingPairExists
+        );
+        assert_noop!(
+            TradingPairModule::list(Origin::signed(ALICE), DEX_ID, XOR, KSM),
+            pallet_balances::Error::<Runtime>::InsufficientBalance
+        );
+
+        // Lister gets the bond back on delisting, even if the pair has enabled sources.
+        TradingPairModule::enable_source_for_trading_pair(
+            &DEX_ID,
+            &XOR,
+            &DOT,
+            LiquiditySourceType::XYKPool,
+        )
+        .expect("Failed to enable source.");
+        assert_ok!(TradingPairModule::deregister(
+            Origin::signed(BOB),
+            DEX_ID,
+            XOR,
+            DOT
+        ));
+        assert_eq!(Balances::reserved_balance(BOB), 0);
+        assert_eq!(Balances::free_balance(BOB), balance!(100));
+        assert_eq!(TradingPairModule::pair_listing(&DEX_ID, &pair), None);
+
+        assert_ok!(TradingPairModule::set_listing_bond(
+            Origin::signed(ALICE),
+            DEX_ID,
+            None
+        ));
+        assert_noop!(
+            TradingPairModule::list(Origin::signed(BOB), DEX_ID, XOR, DOT),
+            Error::<Runtime>::ListingIsNotAllowed
+        );
+    });
+}
+
+#[test]
+fn should_slash_listing_bond() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        assert_ok!(TradingPairModule::set_listing_bond(
+            Origin::signed(ALICE),
+            DEX_ID,
+            Some(balance!(10))
+        ));
+        assert_ok!(TradingPairModule::list(
+            Origin::signed(BOB),
+            DEX_ID,
+            XOR,
+            DOT
+        ));
+        TradingPairModule::enable_source_for_trading_pair(
+            &DEX_ID,
+            &XOR,
+            &DOT,
+            LiquiditySourceType::XYKPool,
+        )
+        .expect("Failed to enable source.");
+        TradingPairModule::record_trade(
+            &DEX_ID,
+            LiquiditySourceType::XYKPool,
+            &XOR,
+            &DOT,
+            balance!(10),
+            balance!(5),
+        );
+        assert_eq!(
+            TradingPairModule::get_pair_stats(&DEX_ID, &XOR, &DOT, LiquiditySourceType::XYKPool)
+                .trade_count,
+            1
+        );
+        assert_noop!(
+            TradingPairModule::slash_listing(Origin::signed(ALICE), DEX_ID, XOR, DOT),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            TradingPairModule::slash_listing(Origin::root(), DEX_ID, XOR, KSM),
+            Error::<Runtime>::PairListingDoesntExist
+        );
+        assert_ok!(TradingPairModule::slash_listing(
+            Origin::root(),
+            DEX_ID,
+            XOR,
+            DOT
+        ));
+        assert_eq!(Balances::reserved_balance(BOB), 0);
+        assert_eq!(Balances::free_balance(BOB), balance!(90));
+        assert!(
+            !TradingPairModule::is_trading_pair_enabled(&DEX_ID, &XOR, &DOT)
+                .expect("Failed to query pair.")
+        );
+        assert!(!TradingPairModule::is_source_enabled_for_trading_pair(
+            &DEX_ID,
+            &XOR,
+            &DOT,
+            LiquiditySourceType::XYKPool
+        )
+        .unwrap_or(false));
+        let pair = TradingPair {
+            base_asset_id: XOR,
+            target_asset_id: DOT,
+        };
+        assert!(PairStatistics::<Runtime>::iter_prefix((DEX_ID, pair))
+            .next()
+            .is_none());
+    });
+}
+
+#[test]
+fn should_reset_pair_stats_by_era() {
+    let mut ext = ExtBuilder::default().build();
+    ext.execute_with(|| {
+        System::set_block_number(5);
+        TradingPairModule::register(Origin::signed(ALICE), DEX_ID, XOR, DOT)
+            .expect("Failed to register pair.");
+        assert_noop!(
+            TradingPairModule::set_stats_era_length(Origin::signed(ALICE), 10),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(TradingPairModule::set_stats_era_length(Origin::root(), 10));
+        let source_type = LiquiditySourceType::XYKPool;
+        TradingPairModule::record_trade(
+            &DEX_ID,
+            source_type,
+            &XOR,
+            &DOT,
+            balance!(10),
+            balance!(5),
+        );
+        TradingPairModule::record_trade(
+            &DEX_ID,
+            source_type,
+            &DOT,
+            &XOR,
+            balance!(4),
+            balance!(12),
+        );
+        // Exchanges between assets not forming a registered pair are ignored.
+        TradingPairModule::record_trade(&DEX_ID, source_type, &DOT, &KSM, balance!(1), balance!(1));
+        TradingPairModule::record_trade(&DEX_ID, source_type, &XOR, &KSM, balance!(1), balance!(1));
+        assert_eq!(
+            TradingPairModule::get_pair_stats(&DEX_ID, &XOR, &DOT, source_type),
+            PairStats {
+                base_volume: balance!(22),
+                target_volume: balance!(9),
+                trade_count: 2,
+                last_price: balance!(3),
+                last_trade_block: 5,
+                era_start: 0,
+            }
+        );
+        assert_eq!(
+            TradingPairModule::get_pair_stats(&DEX_ID, &XOR, &KSM, source_type),
+            Default::default()
+        );
+
+        System::set_block_number(23);
+        assert_eq!(
+            TradingPairModule::get_pair_stats(&DEX_ID, &XOR, &DOT, source_type),
+            PairStats {
+                base_volume: 0,
+                target_volume: 0,
+                trade_count: 0,
+                last_price: balance!(3),
+                last_trade_block: 5,
+                era_start: 20,
+            }
+        );
+        TradingPairModule::record_trade(&DEX_ID, source_type, &XOR, &DOT, balance!(1), balance!(2));
+        assert_eq!(
+            TradingPairModule::get_pair_stats(&DEX_ID, &XOR, &DOT, source_type),
+            PairStats {
+                base_volume: balance!(1),
+                target_volume: balance!(2),
+                trade_count: 1,
+                last_price: balance!(0.5),
+                last_trade_block: 23,
+                era_start: 20,
+            }
+        );
+
+        // Statistics of the past era don't come back when the era length is changed.
+        System::set_block_number(250);
+        assert_ok!(TradingPairModule::set_stats_era_length(Origin::root(), 100));
+        assert_eq!(
+            TradingPairModule::get_pair_stats(&DEX_ID, &XOR, &DOT, source_type),
+            PairStats {
+                base_volume: 0,
+                target_volume: 0,
+                trade_count: 0,
+                last_price: balance!(0.5),
+                last_trade_block: 23,
+                era_start: 200,
+            }
+        );
+        TradingPairModule::enable_source_for_trading_pair(&DEX_ID, &XOR, &DOT, source_type)
+            .expect("Failed to enable source.");
+        assert_eq!(
+            TradingPairModule::list_pair_stats(&DEX_ID, &XOR, &DOT).expect("Failed to list stats."),
+            vec![(
+                source_type,
+                TradingPairModule::get_pair_stats(&DEX_ID, &XOR, &DOT, source_type)
+            )]
+        );
+    });
+}
@@ -38,12 +38,58 @@ impl<T: frame_system::Config> crate::WeightInfo for WeightInfo<T> {
     fn register() -> Weight {
         (240_519_000 as Weight)
             .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+    fn deregister() -> Weight {
+        (150_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(4 as Weight))
+    }
+    fn disable_source_for_trading_pair() -> Weight {
+        (150_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
             .saturating_add(T::DbWeight::get().writes(1 as Weight))
     }
+    fn set_listing_bond() -> Weight {
+        (100_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn list() -> Weight {
+        (300_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(6 as Weight))
+            .saturating_add(T::DbWeight::get().writes(5 as Weight))
+    }
+    fn slash_listing() -> Weight {
+        (200_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(6 as Weight))
+    }
+    fn set_stats_era_length() -> Weight {
+        (50_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
 }
 
 impl crate::WeightInfo for () {
     fn register() -> Weight {
         EXTRINSIC_FIXED_WEIGHT
     }
+    fn deregister() -> Weight {
+        EXTRINSIC_FIXED_WEIGHT
+    }
+    fn disable_source_for_trading_pair() -> Weight {
+        EXTRINSIC_FIXED_WEIGHT
+    }
+    fn set_listing_bond() -> Weight {
+        EXTRINSIC_FIXED_WEIGHT
+    }
+    fn list() -> Weight {
+        EXTRINSIC_FIXED_WEIGHT
+    }
+    fn slash_listing() -> Weight {
+        EXTRINSIC_FIXED_WEIGHT
+    }
+    fn set_stats_era_length() -> Weight {
+        EXTRINSIC_FIXED_WEIGHT
+    }
 }
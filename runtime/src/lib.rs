@@ -25,6 +25,7 @@ use sp_version::RuntimeVersion;
 use traits::parameter_type_with_key;
 use hex_literal::hex;
 use currencies::BasicCurrencyAdapter;
+use common::{BasisPoints, FromGenericPair};
 
 use frame_system::{
     limits::{BlockLength, BlockWeights},
@@ -107,10 +108,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	spec_name: create_runtime_str!("cumulus-test-parachain"),
 	impl_name: create_runtime_str!("cumulus-test-parachain"),
 	authoring_version: 1,
-    spec_version: 1,
+    spec_version: 2,
     impl_version: 1,
     apis: RUNTIME_API_VERSIONS,
-    transaction_version: 1,
+    transaction_version: 2,
 };
 
 /// This determines the average expected block time that we are targetting.
@@ -179,13 +180,25 @@ parameter_types! {
 		.avg_block_initialization(AVERAGE_ON_INITIALIZE_RATIO)
 		.build_or_panic();
 	pub const SS58Prefix: u8 = 0;
+    pub const MaxDexFee: BasisPoints = 100;
 }
 
 // Configure FRAME pallets to include in runtime.
 
+/// Filters out the calls of the balances pallet. Balances of the native currency, like those of
+/// any other asset, are only transferred through the assets pallet which enforces the freezes,
+/// the currencies pallet doesn't expose its calls for the same reason.
+pub struct BaseCallFilter;
+
+impl frame_support::traits::Filter<Call> for BaseCallFilter {
+    fn filter(call: &Call) -> bool {
+        !matches!(call, Call::Balances(_))
+    }
+}
+
 impl frame_system::Config for Runtime {
     /// The basic call filter to use in dispatchable.
-    type BaseCallFilter = ();
+    type BaseCallFilter = BaseCallFilter;
     /// Block & extrinsics weights: base values and limits.
     type BlockWeights = RuntimeBlockWeights;
     /// The maximum length of a block (in bytes).
@@ -318,6 +331,7 @@ impl assets::Config for Runtime {
     type AssetId = AssetId;
     type GetBaseAssetId = GetBaseAssetId;
     type Currency = currencies::Pallet<Runtime>;
+    type IsAssetInUse = (trading_pair::Pallet<Runtime>, pool_xyk::Pallet<Runtime>);
     type WeightInfo = assets::weights::WeightInfo<Runtime>;
 }
 
@@ -327,10 +341,23 @@ impl trading_pair::Config for Runtime {
     type WeightInfo = ();
 }
 
-impl dex_manager::Config for Runtime {}
+impl dex_manager::Config for Runtime {
+    type Event = Event;
+    type MaxFee = MaxDexFee;
+    type WeightInfo = dex_manager::weights::WeightInfo<Runtime>;
+}
+
+parameter_types! {
+    pub const MaxDistributionAccounts: u32 = 16;
+    pub const MaxBuyBackRetriesPerBlock: u32 = 4;
+}
 
 impl bonding_curve_pool::Config for Runtime {
-    type DEXApi = ();
+    type Event = Event;
+    type LiquidityProxy = liquidity_proxy::Pallet<Runtime>;
+    type MaxDistributionAccounts = MaxDistributionAccounts;
+    type MaxBuyBackRetriesPerBlock = MaxBuyBackRetriesPerBlock;
+    type WeightInfo = bonding_curve_pool::weights::WeightInfo<Runtime>;
 }
 
 impl technical::Config for Runtime {
@@ -350,9 +377,84 @@ impl pool_xyk::Config for Runtime {
     type WithdrawLiquidityAction = pool_xyk::WithdrawLiquidityAction<AssetId, TechAssetId, Balance, AccountId, TechAccountId>;
     type PolySwapAction = pool_xyk::PolySwapAction<AssetId, TechAssetId, Balance, AccountId, TechAccountId>;
     type EnsureDEXManager = dex_manager::Pallet<Runtime>;
+    type EnsureTradingNotPaused = dex_api::Pallet<Runtime>;
     type WeightInfo = pool_xyk::weights::WeightInfo<Runtime>;
 }
 
+parameter_types! {
+    pub const MaxOrdersPerSide: u32 = 100;
+    pub const MaxExpiringOrdersPerBlock: u32 = 50;
+    pub const MaxFillsPerExchange: u32 = 10;
+    pub const MinOrderAmount: Balance = common::balance!(1);
+}
+
+impl order_book::Config for Runtime {
+    type Event = Event;
+    type EnsureTradingPairExists = trading_pair::Pallet<Runtime>;
+    type EnableSourceForTradingPair = trading_pair::Pallet<Runtime>;
+    type MaxOrdersPerSide = MaxOrdersPerSide;
+    type MaxExpiringOrdersPerBlock = MaxExpiringOrdersPerBlock;
+    type MaxFillsPerExchange = MaxFillsPerExchange;
+    type MinOrderAmount = MinOrderAmount;
+    type WeightInfo = order_book::weights::WeightInfo<Runtime>;
+}
+
+impl dex_api::Config for Runtime {
+    type Event = Event;
+    type LiquiditySources = (
+        pool_xyk::Pallet<Runtime>,
+        bonding_curve_pool::Pallet<Runtime>,
+        order_book::Pallet<Runtime>,
+    );
+    type WeightInfo = dex_api::weights::WeightInfo<Runtime>;
+}
+
+parameter_types! {
+    pub const GetNumSamples: usize = 40;
+    pub const GetMaxRouteHops: usize = 3;
+    pub const GetMaxRoutingPairs: usize = 64;
+    pub const GetMaxCandidatePaths: usize = 8;
+    pub const GetMaxSplitRoutes: usize = 3;
+    pub const MaxBatchSwapLegs: u32 = 16;
+    pub const CommitRevealWindow: BlockNumber = 20;
+    // Fits a single DCA swap routed across the maximum number of paths along with deferring
+    // the rest of the orders scheduled for the block.
+    pub DcaExecutionWeightBudget: Weight = Perbill::from_percent(40) * MAXIMUM_BLOCK_WEIGHT;
+    pub const MaxDcaOrdersPerBlock: u32 = 32;
+    pub GetLiquidityProxyTechAccountId: TechAccountId = {
+        let tech_account_id = TechAccountId::from_generic_pair(
+            liquidity_proxy::TECH_ACCOUNT_PREFIX.to_vec(),
+            liquidity_proxy::TECH_ACCOUNT_MAIN.to_vec(),
+        );
+        tech_account_id
+    };
+    pub GetLiquidityProxyAccountId: AccountId = {
+        let tech_account_id = GetLiquidityProxyTechAccountId::get();
+        let account_id =
+            technical::Pallet::<Runtime>::tech_account_id_to_account_id(&tech_account_id)
+                .expect("Failed to get ordinary account id for technical account id.");
+        account_id
+    };
+}
+
+impl liquidity_proxy::Config for Runtime {
+    type Event = Event;
+    type LiquidityRegistry = dex_api::Pallet<Runtime>;
+    type GetNumSamples = GetNumSamples;
+    type GetMaxRouteHops = GetMaxRouteHops;
+    type GetMaxRoutingPairs = GetMaxRoutingPairs;
+    type GetMaxCandidatePaths = GetMaxCandidatePaths;
+    type GetMaxSplitRoutes = GetMaxSplitRoutes;
+    type MaxBatchSwapLegs = MaxBatchSwapLegs;
+    type GetTechnicalAccountId = GetLiquidityProxyAccountId;
+    type PrimaryMarket = ();
+    type SecondaryMarket = pool_xyk::Pallet<Runtime>;
+    type SwapCurves = dex_api::Pallet<Runtime>;
+    type DcaExecutionWeightBudget = DcaExecutionWeightBudget;
+    type MaxDcaOrdersPerBlock = MaxDcaOrdersPerBlock;
+    type CommitRevealWindow = CommitRevealWindow;
+    type WeightInfo = liquidity_proxy::weights::WeightInfo<Runtime>;
+}
 
 parameter_types! {
 	pub const TransactionByteFee: Balance = 1 ;
@@ -536,12 +638,17 @@ construct_runtime!(
 
         Permissions: permissions::{Pallet, Call, Storage, Config<T>, Event<T>},
         Tokens: tokens::{Pallet, Storage, Config<T>, Event<T>},
-        Currencies: currencies::{Pallet, Call, Event<T>},
+        // Assets are transferred through the `Assets` calls only, see `BaseCallFilter`.
+        Currencies: currencies::{Pallet, Event<T>},
         Assets: assets::{Pallet, Call, Storage, Config<T>, Event<T>},
         TradingPair: trading_pair::{Pallet, Call, Storage, Config<T>, Event<T>},
-        DEXManager: dex_manager::{Pallet, Storage, Config<T>},
+        DEXManager: dex_manager::{Pallet, Call, Storage, Config<T>, Event<T>},
         Technical: technical::{Pallet, Call, Config<T>, Event<T>},
-        PoolXYK: pool_xyk::{Pallet, Call, Storage, Event<T>},
+        PoolXYK: pool_xyk::{Pallet, Call, Storage, Config, Event<T>},
+        BondingCurvePool: bonding_curve_pool::{Pallet, Call, Config<T>, Storage, Event<T>},
+        OrderBook: order_book::{Pallet, Call, Storage, Event<T>},
+        DEXAPI: dex_api::{Pallet, Call, Config, Storage, Event<T>},
+        LiquidityProxy: liquidity_proxy::{Pallet, Call, Storage, Event<T>},
 	}
 );
 
@@ -709,3 +816,26 @@ impl_runtime_apis! {
 }
 
 cumulus_pallet_parachain_system::register_validate_block!(Runtime, Executive);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use frame_support::traits::Filter;
+
+    #[test]
+    fn base_call_filter_should_refuse_balances_calls() {
+        let account_id = AccountId::default();
+        let address = Address::Id(account_id.clone());
+        assert!(!BaseCallFilter::filter(&Call::Balances(
+            BalancesCall::transfer(address.clone(), 1)
+        )));
+        assert!(!BaseCallFilter::filter(&Call::Balances(
+            BalancesCall::transfer_keep_alive(address, 1)
+        )));
+        assert!(BaseCallFilter::filter(&Call::Assets(assets::Call::transfer(
+            common::XOR,
+            account_id,
+            1,
+        ))));
+    }
+}